@@ -4,7 +4,7 @@ use syn::parse::{Parse, ParseStream};
 
 mod derive_into_plot;
 
-/// Input for icon_name! macro: EnumName, "path", [optional derives]
+/// Input for icon_name! macro: EnumName, "path", [optional derives], [optional `custom`]
 struct IconNameInput {
     enum_name: syn::Ident,
     _comma: syn::Token![,],
@@ -13,6 +13,7 @@ struct IconNameInput {
         syn::Token![,],
         syn::punctuated::Punctuated<syn::Path, syn::Token![,]>,
     )>,
+    custom: bool,
 }
 
 impl Parse for IconNameInput {
@@ -22,7 +23,7 @@ impl Parse for IconNameInput {
         let path = input.parse()?;
 
         // Check if there's an optional derives list
-        let derives = if input.peek(syn::Token![,]) {
+        let derives = if input.peek(syn::Token![,]) && input.peek2(syn::token::Bracket) {
             let comma = input.parse()?;
             let content;
             syn::bracketed!(content in input);
@@ -32,11 +33,24 @@ impl Parse for IconNameInput {
             None
         };
 
+        // Check if there's the optional `custom` flag
+        let custom = if input.peek(syn::Token![,]) {
+            input.parse::<syn::Token![,]>()?;
+            let flag: syn::Ident = input.parse()?;
+            if flag != "custom" {
+                return Err(syn::Error::new(flag.span(), "expected `custom`"));
+            }
+            true
+        } else {
+            false
+        };
+
         Ok(IconNameInput {
             enum_name,
             _comma,
             path,
             derives,
+            custom,
         })
     }
 }
@@ -108,6 +122,10 @@ fn pascal_case(filename: &str) -> String {
 ///
 /// // With custom derives
 /// icon_named!(IconName, "icons", [Debug, Copy, PartialEq, Eq]);
+///
+/// // With a `Custom(SharedString)` variant for the icons registered at runtime by
+/// // `gpui_component::icons::register`, this can't be combined with `Copy`.
+/// icon_named!(IconName, "icons", custom);
 /// ```
 #[proc_macro]
 pub fn icon_named(input: TokenStream) -> TokenStream {
@@ -115,6 +133,7 @@ pub fn icon_named(input: TokenStream) -> TokenStream {
         enum_name,
         path,
         derives,
+        custom,
         ..
     } = syn::parse_macro_input!(input as IconNameInput);
 
@@ -180,19 +199,35 @@ pub fn icon_named(input: TokenStream) -> TokenStream {
         }
     };
 
+    // The `custom:` prefix is resolved by the `Icon` with the registered icons on rendering.
+    let (custom_variant, custom_path) = if custom {
+        (
+            quote! {
+                /// An icon registered at runtime, see `gpui_component::icons::register`.
+                Custom(SharedString),
+            },
+            quote! {
+                Self::Custom(name) => format!("custom:{}", name).into(),
+            },
+        )
+    } else {
+        (quote! {}, quote! {})
+    };
+
     let expanded = quote! {
         #derive_attrs
 
         pub enum #enum_name {
             #(#variants,)*
+            #custom_variant
         }
 
         impl IconNamed for #enum_name {
             fn path(self) -> SharedString {
                 match self {
-                    #(Self::#variants => #paths,)*
+                    #(Self::#variants => #paths.into(),)*
+                    #custom_path
                 }
-                .into()
             }
         }
     };
@@ -8,9 +8,14 @@ fn main() {
     let name = std::env::args().nth(1);
 
     app.run(move |cx| {
+        // Before the `init` of the story, which initializes the components again as a no-op.
+        gpui_component::init_with(
+            gpui_component::InitOptions::default()
+                .app_name("story")
+                .enable_mcp(cfg!(feature = "mcp")),
+            cx,
+        );
         init(cx);
-        #[cfg(feature = "mcp")]
-        gpui_component::mcp::init_mcp(cx, "story");
         cx.activate(true);
 
         create_new_window(
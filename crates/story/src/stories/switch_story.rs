@@ -1,6 +1,6 @@
 use gpui::{
     App, AppContext, Context, Div, Entity, FocusHandle, Focusable, IntoElement, ParentElement,
-    Render, SharedString, Styled, Window, px,
+    Render, SharedString, Styled, Subscription, Window, px,
 };
 
 use gpui_component::{
@@ -9,6 +9,12 @@ use gpui_component::{
 
 use crate::section;
 
+struct NotificationSettings {
+    email: bool,
+    push: bool,
+    sms: bool,
+}
+
 pub struct SwitchStory {
     focus_handle: FocusHandle,
     switch1: bool,
@@ -16,6 +22,8 @@ pub struct SwitchStory {
     switch3: bool,
     switch4: bool,
     switch5: bool,
+    settings: Entity<NotificationSettings>,
+    _subscriptions: Vec<Subscription>,
 }
 
 impl super::Story for SwitchStory {
@@ -38,6 +46,13 @@ impl SwitchStory {
     }
 
     fn new(_: &mut Window, cx: &mut Context<Self>) -> Self {
+        let settings = cx.new(|_| NotificationSettings {
+            email: true,
+            push: false,
+            sms: false,
+        });
+        let _subscriptions = vec![cx.observe(&settings, |_, _, cx| cx.notify())];
+
         Self {
             focus_handle: cx.focus_handle(),
             switch1: true,
@@ -45,6 +60,8 @@ impl SwitchStory {
             switch3: true,
             switch4: true,
             switch5: false,
+            settings,
+            _subscriptions,
         }
     }
 }
@@ -177,5 +194,41 @@ impl Render for SwitchStory {
                         })),
                 ),
             )
+            .child(
+                section("Bind").child(
+                    v_flex()
+                        .gap_2()
+                        .child(Switch::new("bind-email").label("Email").bind_with(
+                            &self.settings,
+                            |settings| settings.email,
+                            |settings, checked| settings.email = checked,
+                        ))
+                        .child(Switch::new("bind-push").label("Push").bind_with(
+                            &self.settings,
+                            |settings| settings.push,
+                            |settings, checked| settings.push = checked,
+                        ))
+                        .child(
+                            Switch::new("bind-sms")
+                                .label("SMS")
+                                .bind_with(
+                                    &self.settings,
+                                    |settings| settings.sms,
+                                    |settings, checked| settings.sms = checked,
+                                )
+                                .on_click(|checked, _, _| {
+                                    println!("SMS notifications: {}", checked);
+                                }),
+                        )
+                        .child({
+                            let settings = self.settings.read(cx);
+                            Label::new(format!(
+                                "Email: {}, Push: {}, SMS: {}",
+                                settings.email, settings.push, settings.sms
+                            ))
+                            .text_color(theme.muted_foreground)
+                        }),
+                ),
+            )
     }
 }
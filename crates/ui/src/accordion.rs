@@ -76,7 +76,7 @@ impl Accordion {
 
 impl Sizable for Accordion {
     fn with_size(mut self, size: impl Into<Size>) -> Self {
-        self.size = size.into();
+        self.size = size.into().clamp("Accordion");
         self
     }
 }
@@ -213,7 +213,7 @@ impl ParentElement for AccordionItem {
 
 impl Sizable for AccordionItem {
     fn with_size(mut self, size: impl Into<Size>) -> Self {
-        self.size = size.into();
+        self.size = size.into().clamp("AccordionItem");
         self
     }
 }
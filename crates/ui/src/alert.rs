@@ -159,7 +159,7 @@ impl Alert {
 
 impl Sizable for Alert {
     fn with_size(mut self, size: impl Into<Size>) -> Self {
-        self.size = size.into();
+        self.size = size.into().clamp("Alert");
         self
     }
 }
@@ -1,11 +1,19 @@
 use std::{rc::Rc, time::Duration};
 
 use gpui::{
-    Animation, AnimationExt, ElementId, IntoElement, Pixels, Point, Styled, point,
+    Animation, AnimationExt, ElementId, IntoElement, Pixels, Point, Styled, Window, point,
     prelude::FluentBuilder, px,
 };
 use smallvec::SmallVec;
 
+/// Request the next frame of an animation, like [`Window::request_animation_frame`], reported
+/// as `animation_pending` by the MCP `get_app_state`.
+pub(crate) fn request_animation_frame(window: &mut Window) {
+    #[cfg(feature = "mcp")]
+    crate::mcp::mcp_animation_frame_requested(window);
+    window.request_animation_frame();
+}
+
 /// A cubic bezier function like CSS `cubic-bezier`.
 ///
 /// Builder:
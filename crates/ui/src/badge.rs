@@ -93,7 +93,7 @@ impl ParentElement for Badge {
 
 impl Sizable for Badge {
     fn with_size(mut self, size: impl Into<Size>) -> Self {
-        self.size = size.into();
+        self.size = size.into().clamp("Badge");
         self
     }
 }
@@ -164,7 +164,8 @@ impl RenderOnce for Toggle {
                 Size::XSmall => this.min_w_5().h_5().px_0p5().text_xs(),
                 Size::Small => this.min_w_6().h_6().px_1().text_sm(),
                 Size::Large => this.min_w_9().h_9().px_3().text_lg(),
-                _ => this.min_w_8().h_8().px_2(),
+                Size::Size(size) => this.min_w(size).h(size).px(size * 0.25),
+                Size::Medium => this.min_w_8().h_8().px_2(),
             })
             .when(self.border_corners.top_left, |this| {
                 this.rounded_tl(rounding)
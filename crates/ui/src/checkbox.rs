@@ -164,7 +164,7 @@ pub(crate) fn checkbox_check_icon(
             Size::Small => this.size_2p5(),
             Size::Medium => this.size_3(),
             Size::Large => this.size_3p5(),
-            _ => this.size_3(),
+            Size::Size(size) => this.size(size * 0.75),
         })
         .text_color(color)
         .map(|this| match checked {
@@ -219,6 +219,24 @@ impl RenderOnce for Checkbox {
         };
         let radius = cx.theme().radius.min(px(4.));
 
+        #[cfg(feature = "mcp")]
+        crate::mcp::mcp_set_element_properties(
+            window,
+            &self.id,
+            serde_json::json!({
+                "role": "checkbox",
+                "label": self
+                    .label
+                    .as_ref()
+                    .and_then(Text::as_plain_str)
+                    .map(|label| label.to_string()),
+                "checked": checked,
+                "disabled": self.disabled,
+            }),
+        );
+        #[cfg(feature = "mcp")]
+        crate::mcp::mcp_set_element_focus(window, &self.id, &focus_handle);
+
         div().child(
             self.base
                 .id(self.id.clone())
@@ -255,7 +273,7 @@ impl RenderOnce for Checkbox {
                             Size::Small => this.size_3p5(),
                             Size::Medium => this.size_4(),
                             Size::Large => this.size(rems(1.125)),
-                            _ => this.size_4(),
+                            Size::Size(size) => this.size(size),
                         })
                         .flex_shrink_0()
                         .border_1()
@@ -725,7 +725,7 @@ impl ColorPicker {
 
 impl Sizable for ColorPicker {
     fn with_size(mut self, size: impl Into<Size>) -> Self {
-        self.size = size.into();
+        self.size = size.into().clamp("ColorPicker");
         self
     }
 }
@@ -811,7 +811,7 @@ impl Selectable for ColorPickerButton {
 
 impl Sizable for ColorPickerButton {
     fn with_size(mut self, size: impl Into<Size>) -> Self {
-        self.size = size.into();
+        self.size = size.into().clamp("ColorPicker");
         self
     }
 }
@@ -242,7 +242,7 @@ impl DescriptionList {
 
 impl Sizable for DescriptionList {
     fn with_size(mut self, size: impl Into<Size>) -> Self {
-        self.size = size.into();
+        self.size = size.into().clamp("DescriptionList");
         self
     }
 }
@@ -0,0 +1,124 @@
+use gpui::{
+    AnyElement, App, Bounds, Div, Element, ElementId, Global, GlobalElementId, InspectorElementId,
+    IntoElement, LayoutId, ParentElement, Pixels, StyleRefinement, Styled, Window, div,
+};
+
+/// The number of the disabled scopes being rendered.
+#[derive(Default)]
+struct DisabledScopes(usize);
+
+impl Global for DisabledScopes {}
+
+/// Returns a [`DisabledScope`], disabling the descendants when `disabled` is true.
+pub fn disabled_scope(disabled: bool) -> DisabledScope {
+    DisabledScope {
+        disabled,
+        div: div(),
+    }
+}
+
+/// Returns true if the element being rendered is inside a disabled [`DisabledScope`].
+///
+/// The [`crate::Disableable`] components are disabled if their own `disabled` is set, or if this
+/// returns true.
+pub fn inherited_disabled(cx: &App) -> bool {
+    cx.try_global::<DisabledScopes>()
+        .is_some_and(|scopes| scopes.0 > 0)
+}
+
+/// A container disabling the [`crate::Disableable`] descendants, e.g. a whole settings section,
+/// without setting `disabled` on each of them.
+///
+/// The descendants are disabled in addition to their own `disabled`, a nested scope can't enable
+/// them again. The descendants are disabled while rendered, so a cached view isn't updated
+/// until it's rendered again.
+///
+/// ```ignore
+/// disabled_scope(!self.sync_enabled)
+///     .gap_2()
+///     .child(Switch::new("sync-files").label("Files"))
+///     .child(Switch::new("sync-settings").label("Settings"))
+/// ```
+pub struct DisabledScope {
+    disabled: bool,
+    div: Div,
+}
+
+impl Styled for DisabledScope {
+    fn style(&mut self) -> &mut StyleRefinement {
+        self.div.style()
+    }
+}
+
+impl ParentElement for DisabledScope {
+    fn extend(&mut self, elements: impl IntoIterator<Item = AnyElement>) {
+        self.div.extend(elements);
+    }
+}
+
+impl IntoElement for DisabledScope {
+    type Element = Self;
+
+    fn into_element(self) -> Self::Element {
+        self
+    }
+}
+
+impl Element for DisabledScope {
+    type RequestLayoutState = AnyElement;
+    type PrepaintState = ();
+
+    fn id(&self) -> Option<ElementId> {
+        None
+    }
+
+    fn source_location(&self) -> Option<&'static std::panic::Location<'static>> {
+        None
+    }
+
+    fn request_layout(
+        &mut self,
+        _: Option<&GlobalElementId>,
+        _: Option<&InspectorElementId>,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> (LayoutId, Self::RequestLayoutState) {
+        let mut element = std::mem::replace(&mut self.div, div()).into_any_element();
+
+        // The components are rendered in the layout of the children.
+        if self.disabled {
+            cx.default_global::<DisabledScopes>().0 += 1;
+        }
+        let layout_id = element.request_layout(window, cx);
+        if self.disabled {
+            cx.default_global::<DisabledScopes>().0 -= 1;
+        }
+
+        (layout_id, element)
+    }
+
+    fn prepaint(
+        &mut self,
+        _: Option<&GlobalElementId>,
+        _: Option<&InspectorElementId>,
+        _: Bounds<Pixels>,
+        element: &mut Self::RequestLayoutState,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> Self::PrepaintState {
+        element.prepaint(window, cx);
+    }
+
+    fn paint(
+        &mut self,
+        _: Option<&GlobalElementId>,
+        _: Option<&InspectorElementId>,
+        _: Bounds<Pixels>,
+        element: &mut Self::RequestLayoutState,
+        _: &mut Self::PrepaintState,
+        window: &mut Window,
+        cx: &mut App,
+    ) {
+        element.paint(window, cx);
+    }
+}
@@ -1,4 +1,6 @@
-use gpui::{App, ClickEvent, InteractiveElement, Stateful, Window};
+use gpui::{App, ClickEvent, InteractiveElement, ParentElement, Stateful, Window};
+
+use crate::ElementExt as _;
 
 pub trait InteractiveElementExt: InteractiveElement {
     /// Set the listener for a double click event.
@@ -16,6 +18,20 @@ pub trait InteractiveElementExt: InteractiveElement {
         });
         self
     }
+
+    /// Exclude the element from the [`crate::TitleBar`] drag region.
+    ///
+    /// Use this for interactive children of the title bar, especially ones that render
+    /// overlays (e.g. dropdowns). The element occludes the drag area, so Windows hit testing
+    /// treats it as client area, and a mouse down on it never starts a window move.
+    fn exclude_from_drag(self) -> Self
+    where
+        Self: ParentElement + Sized,
+    {
+        self.occlude().on_prepaint(|bounds, window, cx| {
+            crate::title_bar::exclude_from_drag(bounds, window, cx);
+        })
+    }
 }
 
 impl<E: InteractiveElement> InteractiveElementExt for Stateful<E> {}
@@ -0,0 +1,57 @@
+use std::rc::Rc;
+
+use gpui::App;
+
+use crate::GlobalState;
+
+/// The kind of an interaction, passed to the handler of [`set_feedback_handler`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum FeedbackKind {
+    /// A toggle changed, e.g. a [`crate::switch::Switch`], `on` is the new state.
+    Toggle { on: bool },
+}
+
+/// Set the handler called by the interactive components on the interactions, e.g. to play a
+/// click sound when a switch toggles.
+///
+/// The components call it once per committed change, whether by the mouse, the keyboard or
+/// the drag.
+pub fn set_feedback_handler(handler: impl Fn(FeedbackKind, &mut App) + 'static, cx: &mut App) {
+    GlobalState::global_mut(cx).feedback_handler = Some(Rc::new(handler));
+}
+
+/// Call the feedback handler, if any.
+pub(crate) fn feedback(kind: FeedbackKind, cx: &mut App) {
+    let Some(handler) = cx
+        .try_global::<GlobalState>()
+        .and_then(|state| state.feedback_handler.clone())
+    else {
+        return;
+    };
+    handler(kind, cx);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gpui::TestAppContext;
+    use std::cell::RefCell;
+
+    #[gpui::test]
+    fn test_feedback_handler(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            // Without the handler, nothing happens.
+            feedback(FeedbackKind::Toggle { on: true }, cx);
+
+            cx.set_global(GlobalState::new());
+            feedback(FeedbackKind::Toggle { on: true }, cx);
+
+            let kinds = Rc::new(RefCell::new(Vec::new()));
+            let handler_kinds = kinds.clone();
+            set_feedback_handler(move |kind, _| handler_kinds.borrow_mut().push(kind), cx);
+            feedback(FeedbackKind::Toggle { on: false }, cx);
+            assert_eq!(*kinds.borrow(), vec![FeedbackKind::Toggle { on: false }]);
+        });
+    }
+}
@@ -0,0 +1,359 @@
+use gpui::{
+    App, AppContext as _, Axis, ElementId, Entity, FocusHandle, InteractiveElement, KeyDownEvent,
+    Window,
+};
+
+use crate::Direction;
+
+/// The state of a [`FocusGroup`], kept across renders.
+struct FocusGroupState {
+    /// The focus handles of the items in order, registered by the current render.
+    items: Vec<FocusHandle>,
+    /// The item being the tab stop, `None` for the first item.
+    active: Option<FocusHandle>,
+}
+
+/// A group of focusable items navigated with the arrow keys, e.g. a cluster of switches or the
+/// buttons of a toolbar.
+///
+/// Only one item of the group is a tab stop (roving tabindex), so `tab` enters and leaves the
+/// group at once. The tab stop is the item focused last, or the first item. When an item has
+/// the focus:
+///
+/// - The arrow keys move the focus to the previous or the next item, `left` and `right` are
+///   swapped in the right to left [`Direction`].
+/// - `home` and `end` move the focus to the first or the last item.
+///
+/// Create the group on each render, register the items in order with [`FocusGroup::item`], and
+/// handle the keys on the container with [`FocusGroupElement::focus_group`]:
+///
+/// ```ignore
+/// let group = FocusGroup::new("toolbar", window, cx).orientation(Axis::Horizontal);
+/// h_flex()
+///     .focus_group(&group)
+///     .children(self.tools.iter().map(|tool| {
+///         let tab_stop = group.item(&tool.focus_handle, window, cx);
+///         div()
+///             .id(tool.id.clone())
+///             .track_focus(&tool.focus_handle.clone().tab_stop(tab_stop))
+///             .child(tool.label.clone())
+///     }))
+/// ```
+#[derive(Clone)]
+pub struct FocusGroup {
+    state: Entity<FocusGroupState>,
+    orientation: Option<Axis>,
+    wrap: bool,
+    direction: Direction,
+}
+
+impl FocusGroup {
+    /// Returns the focus group of the `id` in the window, to register the items of this render.
+    pub fn new(id: impl Into<ElementId>, window: &mut Window, cx: &mut App) -> Self {
+        let state = window.use_keyed_state(id, cx, |_, _| FocusGroupState {
+            items: vec![],
+            active: None,
+        });
+        state.update(cx, |state, _| {
+            // Fall back to the first item when the tab stop is gone.
+            if state
+                .active
+                .as_ref()
+                .is_some_and(|active| !state.items.contains(active))
+            {
+                state.active = None;
+            }
+            state.items.clear();
+        });
+
+        Self {
+            state,
+            orientation: None,
+            wrap: true,
+            direction: crate::direction(cx),
+        }
+    }
+
+    /// Set the orientation of the items, only the arrow keys of the axis move the focus.
+    ///
+    /// Default is `None`, all the arrow keys move the focus.
+    pub fn orientation(mut self, orientation: impl Into<Option<Axis>>) -> Self {
+        self.orientation = orientation.into();
+        self
+    }
+
+    /// Set to wrap around at the ends of the group, default is true.
+    pub fn wrap(mut self, wrap: bool) -> Self {
+        self.wrap = wrap;
+        self
+    }
+
+    /// Set the layout direction for the `left` and `right` keys, default is the direction of the
+    /// application, see [`crate::set_direction`].
+    pub fn direction(mut self, direction: Direction) -> Self {
+        self.direction = direction;
+        self
+    }
+
+    /// Register the focus handle of the next item, returns true if the item is the tab stop.
+    ///
+    /// Set the returned value to the `tab_stop` of the item, the disabled items should not be
+    /// registered.
+    pub fn item(&self, focus_handle: &FocusHandle, window: &Window, cx: &mut App) -> bool {
+        self.state.update(cx, |state, _| {
+            if focus_handle.is_focused(window) {
+                state.active = Some(focus_handle.clone());
+            }
+            state.items.push(focus_handle.clone());
+
+            match &state.active {
+                Some(active) => active == focus_handle,
+                None => state.items.len() == 1,
+            }
+        })
+    }
+
+    /// Returns the index of the item to focus by the `key`, from the focused item at `ix`.
+    fn target(&self, key: &str, ix: usize, len: usize) -> Option<usize> {
+        let is_rtl = self.direction.is_rtl();
+        let horizontal = self.orientation != Some(Axis::Vertical);
+        let vertical = self.orientation != Some(Axis::Horizontal);
+
+        let step: isize = match key {
+            "home" => return Some(0),
+            "end" => return len.checked_sub(1),
+            "up" if vertical => -1,
+            "down" if vertical => 1,
+            "left" if horizontal => {
+                if is_rtl {
+                    1
+                } else {
+                    -1
+                }
+            }
+            "right" if horizontal => {
+                if is_rtl {
+                    -1
+                } else {
+                    1
+                }
+            }
+            _ => return None,
+        };
+
+        let next_ix = ix as isize + step;
+        if self.wrap {
+            Some(next_ix.rem_euclid(len as isize) as usize)
+        } else {
+            Some(next_ix.clamp(0, len as isize - 1) as usize)
+        }
+    }
+
+    /// Move the focus by the key of the `event`, returns true if the key is handled.
+    fn on_key_down(&self, event: &KeyDownEvent, window: &mut Window, cx: &mut App) -> bool {
+        if event.keystroke.modifiers.modified() {
+            return false;
+        }
+
+        let items = &self.state.read(cx).items;
+        let Some(ix) = items.iter().position(|handle| handle.is_focused(window)) else {
+            return false;
+        };
+        let Some(next_ix) = self.target(&event.keystroke.key, ix, items.len()) else {
+            return false;
+        };
+
+        let next = items[next_ix].clone();
+        next.focus(window, cx);
+        self.state.update(cx, |state, cx| {
+            state.active = Some(next);
+            cx.notify();
+        });
+        true
+    }
+}
+
+/// An extension trait to handle the keys of a [`FocusGroup`] on the container.
+pub trait FocusGroupElement: InteractiveElement + Sized {
+    /// Move the focus between the items of the `group` with the arrow keys, `home` and `end`.
+    fn focus_group(self, group: &FocusGroup) -> Self {
+        let group = group.clone();
+        self.on_key_down(move |event, window, cx| {
+            if group.on_key_down(event, window, cx) {
+                cx.stop_propagation();
+            }
+        })
+    }
+}
+
+impl<T: InteractiveElement + Sized> FocusGroupElement for T {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gpui::{
+        Context, FocusHandle, InteractiveElement as _, IntoElement, ParentElement as _, Render,
+        Styled as _, TestAppContext, VisualTestContext, div,
+    };
+
+    struct GroupView {
+        before: FocusHandle,
+        items: Vec<FocusHandle>,
+        after: FocusHandle,
+        orientation: Option<Axis>,
+        wrap: bool,
+        direction: Direction,
+    }
+
+    impl Render for GroupView {
+        fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+            let group = FocusGroup::new("group", window, cx)
+                .orientation(self.orientation)
+                .wrap(self.wrap)
+                .direction(self.direction);
+
+            div()
+                .size_full()
+                .child(
+                    div()
+                        .id("before")
+                        .track_focus(&self.before.clone().tab_stop(true)),
+                )
+                .child(div().id("items").focus_group(&group).children(
+                    self.items.iter().enumerate().map(|(ix, handle)| {
+                        let tab_stop = group.item(handle, window, cx);
+                        div().id(ix).track_focus(&handle.clone().tab_stop(tab_stop))
+                    }),
+                ))
+                .child(
+                    div()
+                        .id("after")
+                        .track_focus(&self.after.clone().tab_stop(true)),
+                )
+        }
+    }
+
+    fn build(
+        orientation: Option<Axis>,
+        wrap: bool,
+        cx: &mut TestAppContext,
+    ) -> (Entity<GroupView>, &mut VisualTestContext) {
+        let (view, cx) = cx.add_window_view(|_, cx| GroupView {
+            before: cx.focus_handle(),
+            items: (0..4).map(|_| cx.focus_handle()).collect(),
+            after: cx.focus_handle(),
+            orientation,
+            wrap,
+            direction: Direction::Ltr,
+        });
+        cx.run_until_parked();
+        (view, cx)
+    }
+
+    /// Returns the index of the focused item, `None` if no item is focused.
+    fn focused(view: &Entity<GroupView>, cx: &mut VisualTestContext) -> Option<usize> {
+        cx.update(|window, cx| {
+            view.read(cx)
+                .items
+                .iter()
+                .position(|handle| handle.is_focused(window))
+        })
+    }
+
+    fn simulate(keystrokes: &str, cx: &mut VisualTestContext) {
+        cx.simulate_keystrokes(keystrokes);
+        cx.run_until_parked();
+    }
+
+    #[gpui::test]
+    fn test_focus_group_arrow_keys(cx: &mut TestAppContext) {
+        let (view, cx) = build(None, true, cx);
+        cx.update(|window, cx| view.read(cx).items[0].focus(window, cx));
+        cx.run_until_parked();
+
+        simulate("down", cx);
+        assert_eq!(focused(&view, cx), Some(1));
+        simulate("right right", cx);
+        assert_eq!(focused(&view, cx), Some(3));
+        simulate("up left", cx);
+        assert_eq!(focused(&view, cx), Some(1));
+
+        // Wrap around at the ends.
+        simulate("left left", cx);
+        assert_eq!(focused(&view, cx), Some(3));
+        simulate("down", cx);
+        assert_eq!(focused(&view, cx), Some(0));
+
+        simulate("end", cx);
+        assert_eq!(focused(&view, cx), Some(3));
+        simulate("home", cx);
+        assert_eq!(focused(&view, cx), Some(0));
+    }
+
+    #[gpui::test]
+    fn test_focus_group_orientation(cx: &mut TestAppContext) {
+        let (view, cx) = build(Some(Axis::Horizontal), false, cx);
+        cx.update(|window, cx| view.read(cx).items[0].focus(window, cx));
+        cx.run_until_parked();
+
+        // The vertical keys are ignored.
+        simulate("down", cx);
+        assert_eq!(focused(&view, cx), Some(0));
+        simulate("right", cx);
+        assert_eq!(focused(&view, cx), Some(1));
+
+        // Stop at the ends without wrapping.
+        simulate("left left", cx);
+        assert_eq!(focused(&view, cx), Some(0));
+        simulate("end right", cx);
+        assert_eq!(focused(&view, cx), Some(3));
+    }
+
+    #[gpui::test]
+    fn test_focus_group_roving_tab_stop(cx: &mut TestAppContext) {
+        let (view, cx) = build(None, true, cx);
+        let focus_next = |cx: &mut VisualTestContext| {
+            cx.update(|window, cx| window.focus_next(cx));
+            cx.run_until_parked();
+        };
+        let focus_prev = |cx: &mut VisualTestContext| {
+            cx.update(|window, cx| window.focus_prev(cx));
+            cx.run_until_parked();
+        };
+        cx.update(|window, cx| view.read(cx).before.focus(window, cx));
+        cx.run_until_parked();
+
+        // Tab enters the group at the first item, and leaves it at once.
+        focus_next(cx);
+        assert_eq!(focused(&view, cx), Some(0));
+        simulate("down down", cx);
+        focus_next(cx);
+        assert_eq!(focused(&view, cx), None);
+        assert!(cx.update(|window, cx| view.read(cx).after.is_focused(window)));
+
+        // Tab back to the item focused last.
+        focus_prev(cx);
+        assert_eq!(focused(&view, cx), Some(2));
+        focus_prev(cx);
+        assert!(cx.update(|window, cx| view.read(cx).before.is_focused(window)));
+    }
+
+    #[gpui::test]
+    fn test_focus_group_rtl(cx: &mut TestAppContext) {
+        let (view, cx) = build(None, true, cx);
+        view.update(cx, |view, cx| {
+            view.direction = Direction::Rtl;
+            cx.notify();
+        });
+        cx.update(|window, cx| view.read(cx).items[1].focus(window, cx));
+        cx.run_until_parked();
+
+        // The `left` and `right` keys are swapped, the vertical keys are not.
+        simulate("left", cx);
+        assert_eq!(focused(&view, cx), Some(2));
+        simulate("right right", cx);
+        assert_eq!(focused(&view, cx), Some(0));
+        simulate("down", cx);
+        assert_eq!(focused(&view, cx), Some(1));
+    }
+}
@@ -83,7 +83,7 @@ impl Styled for Form {
 
 impl Sizable for Form {
     fn with_size(mut self, size: impl Into<Size>) -> Self {
-        self.props.size = size.into();
+        self.props.size = size.into().clamp("Form");
         self
     }
 }
@@ -0,0 +1,199 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    rc::Rc,
+};
+
+use gpui::{Context, EventEmitter, SharedString};
+use serde_json::{Map, Value};
+
+type Validator = Rc<dyn Fn(&Value) -> Option<SharedString>>;
+
+/// Events emitted by the [`FormState`].
+#[derive(Clone, Debug, PartialEq)]
+pub enum FormEvent {
+    /// The user changed the value of the named control.
+    Change(SharedString),
+    /// The values are reset to the initial values.
+    Reset,
+}
+
+struct FormValue {
+    initial: Value,
+    value: Value,
+    error: Option<SharedString>,
+}
+
+/// The values of the named controls in a form, e.g. a [`crate::switch::Switch`] with `.name()`
+/// and `.form()`.
+///
+/// The controls register their initial value on the first render, report the changes, and
+/// unregister when they are no longer rendered.
+#[derive(Default)]
+pub struct FormState {
+    values: BTreeMap<SharedString, FormValue>,
+    validators: HashMap<SharedString, Validator>,
+}
+
+impl EventEmitter<FormEvent> for FormState {}
+
+impl FormState {
+    /// Create a new empty form state.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a validator for the named control, returns the error message of an invalid value.
+    ///
+    /// The control shows the error after the value changed, or after [`FormState::validate`].
+    pub fn validator(
+        mut self,
+        name: impl Into<SharedString>,
+        validator: impl Fn(&Value) -> Option<SharedString> + 'static,
+    ) -> Self {
+        self.validators.insert(name.into(), Rc::new(validator));
+        self
+    }
+
+    /// Returns the current values of the registered controls, as a map from the name to the value.
+    pub fn values(&self) -> Value {
+        Value::Object(
+            self.values
+                .iter()
+                .map(|(name, value)| (name.to_string(), value.value.clone()))
+                .collect::<Map<_, _>>(),
+        )
+    }
+
+    /// Returns the current value of the named control.
+    pub fn value(&self, name: &str) -> Option<&Value> {
+        self.values.get(name).map(|value| &value.value)
+    }
+
+    /// Returns the error of the named control.
+    pub fn error(&self, name: &str) -> Option<&SharedString> {
+        self.values.get(name).and_then(|value| value.error.as_ref())
+    }
+
+    /// Returns true if any value differs from the initial value.
+    pub fn is_dirty(&self) -> bool {
+        self.values
+            .values()
+            .any(|value| value.value != value.initial)
+    }
+
+    /// Set the value of the named control, and validate it.
+    pub fn set_value(
+        &mut self,
+        name: impl Into<SharedString>,
+        value: impl Into<Value>,
+        cx: &mut Context<Self>,
+    ) {
+        let name = name.into();
+        let value = value.into();
+        let error = self.check(&name, &value);
+        let entry = self
+            .values
+            .entry(name.clone())
+            .or_insert_with(|| FormValue {
+                initial: value.clone(),
+                value: Value::Null,
+                error: None,
+            });
+        entry.value = value;
+        entry.error = error;
+        cx.emit(FormEvent::Change(name));
+        cx.notify();
+    }
+
+    /// Reset all values to the initial values, and clear the errors.
+    pub fn reset(&mut self, cx: &mut Context<Self>) {
+        for value in self.values.values_mut() {
+            value.value = value.initial.clone();
+            value.error = None;
+        }
+        cx.emit(FormEvent::Reset);
+        cx.notify();
+    }
+
+    /// Validate all values, returns true if all are valid.
+    pub fn validate(&mut self, cx: &mut Context<Self>) -> bool {
+        let errors = self
+            .values
+            .iter()
+            .map(|(name, value)| (name.clone(), self.check(name, &value.value)))
+            .collect::<Vec<_>>();
+        for (name, error) in errors {
+            if let Some(value) = self.values.get_mut(&name) {
+                value.error = error;
+            }
+        }
+        cx.notify();
+        self.values.values().all(|value| value.error.is_none())
+    }
+
+    fn check(&self, name: &str, value: &Value) -> Option<SharedString> {
+        self.validators
+            .get(name)
+            .and_then(|validator| validator(value))
+    }
+
+    /// Register the named control with its initial value, keeps the value if already registered.
+    pub(crate) fn register(&mut self, name: SharedString, initial: Value) {
+        self.values.entry(name).or_insert_with(|| FormValue {
+            value: initial.clone(),
+            initial,
+            error: None,
+        });
+    }
+
+    /// Update the value of a controlled control without the [`FormEvent::Change`].
+    pub(crate) fn sync(&mut self, name: &str, value: Value) {
+        if let Some(entry) = self.values.get_mut(name) {
+            entry.value = value;
+        }
+    }
+
+    pub(crate) fn unregister(&mut self, name: &str) {
+        self.values.remove(name);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gpui::{AppContext as _, TestAppContext};
+
+    #[gpui::test]
+    fn test_form_state(cx: &mut TestAppContext) {
+        let form = cx.new(|_| {
+            FormState::new().validator("push", |value| {
+                (value != &Value::Bool(true)).then(|| "Required".into())
+            })
+        });
+
+        form.update(cx, |form, cx| {
+            form.register("email".into(), true.into());
+            form.register("push".into(), true.into());
+            // The registered value is kept.
+            form.register("email".into(), false.into());
+            assert_eq!(
+                form.values(),
+                serde_json::json!({ "email": true, "push": true })
+            );
+            assert!(!form.is_dirty());
+
+            form.set_value("push", false, cx);
+            assert_eq!(form.error("push"), Some(&SharedString::from("Required")));
+            assert!(form.is_dirty());
+            assert!(!form.validate(cx));
+
+            form.reset(cx);
+            assert_eq!(form.value("push"), Some(&Value::Bool(true)));
+            assert!(form.error("push").is_none());
+            assert!(form.validate(cx));
+
+            form.unregister("email");
+            assert_eq!(form.values(), serde_json::json!({ "push": true }));
+        });
+    }
+}
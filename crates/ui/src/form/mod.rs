@@ -1,8 +1,10 @@
 mod field;
 mod form;
+mod form_state;
 
 pub use field::*;
 pub use form::*;
+pub use form_state::*;
 
 /// Create a new [`Form`] with a vertical layout.
 pub fn v_form() -> Form {
@@ -1,7 +1,7 @@
 // crates/ui/src/geometry.rs
 use std::fmt::{self, Debug, Display, Formatter};
 
-use gpui::{AbsoluteLength, Axis, Length, Pixels};
+use gpui::{AbsoluteLength, Axis, Length, Pixels, Radians, radians};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
@@ -84,6 +84,35 @@ impl Side {
     }
 }
 
+impl From<Side> for Placement {
+    fn from(side: Side) -> Self {
+        match side {
+            Side::Left => Placement::Left,
+            Side::Right => Placement::Right,
+        }
+    }
+}
+
+/// The layout direction of the components, see [`crate::set_direction`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Direction {
+    /// Left to right.
+    #[default]
+    #[serde(rename = "ltr")]
+    Ltr,
+    /// Right to left, e.g. for Arabic and Hebrew.
+    #[serde(rename = "rtl")]
+    Rtl,
+}
+
+impl Direction {
+    /// Returns true if the direction is right to left.
+    #[inline]
+    pub fn is_rtl(&self) -> bool {
+        matches!(self, Self::Rtl)
+    }
+}
+
 /// A trait to extend the [`Axis`] enum with utility methods.
 pub trait AxisExt {
     fn is_horizontal(self) -> bool;
@@ -119,6 +148,16 @@ impl LengthExt for Length {
     }
 }
 
+/// An angle in degrees, e.g. for [`crate::Icon::rotate`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, PartialOrd)]
+pub struct Degrees(pub f32);
+
+impl From<Degrees> for Radians {
+    fn from(degrees: Degrees) -> Self {
+        radians(degrees.0.to_radians())
+    }
+}
+
 /// A struct for defining the edges of an element.
 ///
 /// A extend version of [`gpui::Edges`] to serialize/deserialize.
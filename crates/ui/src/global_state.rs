@@ -1,7 +1,10 @@
-use gpui::{App, ElementId, Entity, FocusHandle, Global, OwnedMenu};
-use std::collections::HashSet;
+use gpui::{App, ElementId, Entity, FocusHandle, Global, OwnedMenu, WindowId};
+use std::{
+    collections::{HashMap, HashSet},
+    rc::Rc,
+};
 
-use crate::text::TextViewState;
+use crate::{ComponentDensity, Direction, FeedbackKind, text::TextViewState};
 
 pub(crate) fn init(cx: &mut App) {
     cx.set_global(GlobalState::new());
@@ -22,6 +25,16 @@ pub struct GlobalState {
     /// interaction (e.g. `Input`, `Button`); reset by the selection
     /// controller in the capture phase of every left mouse down.
     pub(crate) suppress_text_selection: bool,
+    /// When true, the components skip their animations, see [`crate::reduce_motion`].
+    pub(crate) reduce_motion: bool,
+    /// The layout direction of the components, see [`crate::direction`].
+    pub(crate) direction: Direction,
+    /// The density of the components, see [`crate::density`].
+    pub(crate) density: ComponentDensity,
+    /// The density of the windows, over the [`GlobalState::density`].
+    pub(crate) window_densities: HashMap<WindowId, ComponentDensity>,
+    /// The handler of the interactions, see [`crate::set_feedback_handler`].
+    pub(crate) feedback_handler: Option<Rc<dyn Fn(FeedbackKind, &mut App)>>,
 }
 
 impl GlobalState {
@@ -31,6 +44,11 @@ impl GlobalState {
             open_deferred_popovers: HashSet::new(),
             app_menus: Vec::new(),
             suppress_text_selection: false,
+            reduce_motion: false,
+            direction: Direction::Ltr,
+            density: ComponentDensity::Comfortable,
+            window_densities: HashMap::new(),
+            feedback_handler: None,
         }
     }
 
@@ -1,10 +1,15 @@
-use crate::{ActiveTheme, Sizable, Size};
+use crate::{
+    ActiveTheme, Sizable, Size,
+    icons::{self, ResolvedIcon},
+};
 use gpui::{
-    AnyElement, App, AppContext, Context, Entity, Hsla, IntoElement, Radians, Render, RenderOnce,
-    SharedString, StyleRefinement, Styled, Svg, Transformation, Window,
-    prelude::FluentBuilder as _, svg,
+    Animation, AnimationExt as _, AnyElement, App, AppContext, Context, Entity, Hsla, IntoElement,
+    Pixels, Radians, Render, RenderOnce, SharedString, StyleRefinement, Styled, Svg,
+    Transformation, Window, prelude::FluentBuilder as _, radians, size, svg,
 };
 use gpui_component_macros::icon_named;
+use instant::Duration;
+use std::f32::consts::TAU;
 
 /// Types implementing this trait can automatically be converted to [`Icon`].
 ///
@@ -26,7 +31,9 @@ impl<T: IconNamed> From<T> for Icon {
 // crate's `build.rs` (via cargo's `links` mechanism) and re-exported by
 // our own `build.rs`. See `gpui_component_macros::icon_named!`'s doc
 // comment for the full mechanism.
-icon_named!(IconName, "$GPUI_COMPONENT_DEFAULT_ICONS_DIR");
+//
+// The `IconName::Custom` icons are registered at runtime, see `crate::icons`.
+icon_named!(IconName, "$GPUI_COMPONENT_DEFAULT_ICONS_DIR", custom);
 
 impl IconName {
     /// Return the icon as a Entity<Icon>
@@ -54,7 +61,31 @@ pub struct Icon {
     path: SharedString,
     text_color: Option<Hsla>,
     size: Option<Size>,
+    transform: IconTransform,
+    spin: Option<Duration>,
+}
+
+/// The rotation and the flips of the [`Icon`], applied on painting without affecting the layout.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+struct IconTransform {
     rotation: Option<Radians>,
+    flip_x: bool,
+    flip_y: bool,
+}
+
+impl IconTransform {
+    fn is_identity(&self) -> bool {
+        self.rotation.is_none() && !self.flip_x && !self.flip_y
+    }
+
+    /// Returns the transformation, rotated further by the `turn` (0.0 to 1.0) of the spin.
+    fn transformation(&self, turn: f32) -> Transformation {
+        let rotation = self.rotation.map_or(0., |rotation| rotation.0);
+        let scale = |flip: bool| if flip { -1. } else { 1. };
+
+        Transformation::rotate(radians(rotation + turn * TAU))
+            .with_scaling(size(scale(self.flip_x), scale(self.flip_y)))
+    }
 }
 
 impl Default for Icon {
@@ -65,7 +96,8 @@ impl Default for Icon {
             path: "".into(),
             text_color: None,
             size: None,
-            rotation: None,
+            transform: IconTransform::default(),
+            spin: None,
         }
     }
 }
@@ -74,7 +106,8 @@ impl Clone for Icon {
     fn clone(&self) -> Self {
         let mut this = Self::default().path(self.path.clone());
         this.style = self.style.clone();
-        this.rotation = self.rotation;
+        this.transform = self.transform;
+        this.spin = self.spin;
         this.size = self.size;
         this.text_color = self.text_color;
         this
@@ -92,7 +125,8 @@ impl Icon {
 
     /// Set the icon path of the Assets bundle
     ///
-    /// For example: `icons/foo.svg`
+    /// For example: `icons/foo.svg`, or `custom:foo` for the icon registered by
+    /// [`crate::icons::register`].
     pub fn path(mut self, path: impl Into<SharedString>) -> Self {
         self.path = path.into();
         self
@@ -103,6 +137,8 @@ impl Icon {
         cx.new(|_| self)
     }
 
+    /// Set the transformation of the icon, replaced by the [`Icon::rotate`], [`Icon::flip_x`],
+    /// [`Icon::flip_y`] and [`Icon::spin`] if any.
     pub fn transform(mut self, transformation: gpui::Transformation) -> Self {
         self.base = self.base.with_transformation(transformation);
         self
@@ -112,13 +148,49 @@ impl Icon {
         Self::default()
     }
 
-    /// Rotate the icon by the given angle
-    pub fn rotate(mut self, radians: impl Into<Radians>) -> Self {
-        self.base = self
-            .base
-            .with_transformation(Transformation::rotate(radians));
+    /// Rotate the icon by the given angle, e.g. `Degrees(90.)` or `percentage(0.25)`.
+    pub fn rotate(mut self, angle: impl Into<Radians>) -> Self {
+        self.transform.rotation = Some(angle.into());
         self
     }
+
+    /// Flip the icon horizontally.
+    pub fn flip_x(mut self) -> Self {
+        self.transform.flip_x = true;
+        self
+    }
+
+    /// Flip the icon vertically.
+    pub fn flip_y(mut self) -> Self {
+        self.transform.flip_y = true;
+        self
+    }
+
+    /// Spin the icon by a full turn per `duration`, e.g. for a loading indicator.
+    ///
+    /// The icon is static when the [`crate::reduce_motion`] is enabled.
+    pub fn spin(mut self, duration: Duration) -> Self {
+        self.spin = Some(duration);
+        self
+    }
+
+    /// Apply the rotation, the flips and the spin to the `svg`.
+    fn animate(svg: Svg, transform: IconTransform, spin: Option<Duration>, cx: &App) -> AnyElement {
+        let svg = svg.when(!transform.is_identity(), |this| {
+            this.with_transformation(transform.transformation(0.))
+        });
+
+        match spin.filter(|_| !crate::reduce_motion(cx)) {
+            Some(duration) => svg
+                .with_animation(
+                    "icon-spin",
+                    Animation::new(duration).repeat(),
+                    move |this, delta| this.with_transformation(transform.transformation(delta)),
+                )
+                .into_any_element(),
+            None => svg.into_any_element(),
+        }
+    }
 }
 
 impl Styled for Icon {
@@ -139,16 +211,28 @@ impl Sizable for Icon {
     }
 }
 
+/// Returns the size of the icon without an explicit or a base size, the text size, scaled to the
+/// [`Size::Small`] metrics in the [`crate::ComponentDensity::Compact`].
+fn icon_text_size(size: Option<Size>, window: &Window, cx: &App) -> Pixels {
+    let text_size = window.text_style().font_size.to_pixels(window.rem_size());
+    if size.is_none() && crate::window_density(window, cx).is_compact() {
+        text_size * 0.875
+    } else {
+        text_size
+    }
+}
+
 impl RenderOnce for Icon {
-    fn render(self, window: &mut Window, _cx: &mut App) -> impl IntoElement {
+    fn render(self, window: &mut Window, cx: &mut App) -> impl IntoElement {
         let text_color = self.text_color.unwrap_or_else(|| window.text_style().color);
-        let text_size = window.text_style().font_size.to_pixels(window.rem_size());
+        let text_size = icon_text_size(self.size, window, cx);
         let has_base_size = self.style.size.width.is_some() || self.style.size.height.is_some();
 
         let mut base = self.base;
         *base.style() = self.style;
 
-        base.flex_shrink_0()
+        let base = base
+            .flex_shrink_0()
             .text_color(text_color)
             .when(!has_base_size, |this| this.size(text_size))
             .when_some(self.size, |this, size| match size {
@@ -158,7 +242,12 @@ impl RenderOnce for Icon {
                 Size::Medium => this.size_4(),
                 Size::Large => this.size_6(),
             })
-            .path(self.path)
+            .map(|this| match icons::resolve(&self.path, cx) {
+                ResolvedIcon::Asset(path) => this.path(path),
+                ResolvedIcon::External(path) => this.external_path(path),
+            });
+
+        Self::animate(base, self.transform, self.spin, cx)
     }
 }
 
@@ -171,13 +260,14 @@ impl From<Icon> for AnyElement {
 impl Render for Icon {
     fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
         let text_color = self.text_color.unwrap_or_else(|| cx.theme().foreground);
-        let text_size = window.text_style().font_size.to_pixels(window.rem_size());
+        let text_size = icon_text_size(self.size, window, cx);
         let has_base_size = self.style.size.width.is_some() || self.style.size.height.is_some();
 
         let mut base = svg().flex_none();
         *base.style() = self.style.clone();
 
-        base.flex_shrink_0()
+        let base = base
+            .flex_shrink_0()
             .text_color(text_color)
             .when(!has_base_size, |this| this.size(text_size))
             .when_some(self.size, |this, size| match size {
@@ -187,9 +277,38 @@ impl Render for Icon {
                 Size::Medium => this.size_4(),
                 Size::Large => this.size_6(),
             })
-            .path(self.path.clone())
-            .when_some(self.rotation, |this, rotation| {
-                this.with_transformation(Transformation::rotate(rotation))
-            })
+            .map(|this| match icons::resolve(&self.path, cx) {
+                ResolvedIcon::Asset(path) => this.path(path),
+                ResolvedIcon::External(path) => this.external_path(path),
+            });
+
+        Self::animate(base, self.transform, self.spin, cx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Degrees;
+
+    #[test]
+    fn test_icon_transform() {
+        let icon = Icon::new(IconName::ChevronRight);
+        assert!(icon.transform.is_identity());
+        assert_eq!(icon.spin, None);
+
+        let icon = icon
+            .rotate(Degrees(90.))
+            .flip_x()
+            .spin(Duration::from_secs(1));
+        assert!((icon.transform.rotation.unwrap().0 - TAU / 4.).abs() < f32::EPSILON);
+        assert!(icon.transform.flip_x);
+        assert!(!icon.transform.flip_y);
+        assert_eq!(icon.spin, Some(Duration::from_secs(1)));
+
+        // The transformations are kept on cloning.
+        let cloned = icon.clone();
+        assert_eq!(cloned.transform, icon.transform);
+        assert_eq!(cloned.spin, icon.spin);
     }
 }
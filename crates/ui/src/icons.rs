@@ -0,0 +1,225 @@
+//! The registry of the custom icons, rendered by the [`IconName::Custom`] variant.
+//!
+//! ```ignore
+//! use gpui_component::{Icon, IconName, icons};
+//!
+//! icons::register("brand-logo", include_bytes!("../assets/brand-logo.svg"), cx)?;
+//! icons::register("report", std::path::Path::new("/usr/share/my-app/report.svg"), cx)?;
+//!
+//! Icon::new(IconName::Custom("brand-logo".into())).small()
+//! ```
+use std::{
+    borrow::Cow,
+    collections::{HashMap, HashSet},
+    hash::{DefaultHasher, Hash as _, Hasher as _},
+    path::{Path, PathBuf},
+};
+
+use anyhow::{Context as _, Result, anyhow};
+use gpui::{App, Global, SharedString};
+
+use crate::{IconName, IconNamed as _};
+
+/// The path prefix of the [`IconName::Custom`] icons, resolved by the [`crate::Icon`] on
+/// rendering.
+pub(crate) const CUSTOM_ICON_PREFIX: &str = "custom:";
+
+/// The source of a custom icon, see [`register`].
+#[derive(Debug, Clone)]
+pub enum IconSource {
+    /// The SVG data, e.g. from `include_bytes!`.
+    Bytes(Cow<'static, [u8]>),
+    /// The path of a SVG file.
+    Path(PathBuf),
+}
+
+impl From<&'static [u8]> for IconSource {
+    fn from(bytes: &'static [u8]) -> Self {
+        Self::Bytes(Cow::Borrowed(bytes))
+    }
+}
+
+impl<const N: usize> From<&'static [u8; N]> for IconSource {
+    fn from(bytes: &'static [u8; N]) -> Self {
+        Self::Bytes(Cow::Borrowed(bytes))
+    }
+}
+
+impl From<Vec<u8>> for IconSource {
+    fn from(bytes: Vec<u8>) -> Self {
+        Self::Bytes(Cow::Owned(bytes))
+    }
+}
+
+impl From<PathBuf> for IconSource {
+    fn from(path: PathBuf) -> Self {
+        Self::Path(path)
+    }
+}
+
+impl From<&Path> for IconSource {
+    fn from(path: &Path) -> Self {
+        Self::Path(path.to_path_buf())
+    }
+}
+
+#[derive(Default)]
+struct IconRegistry {
+    /// The SVG files of the registered icons.
+    icons: HashMap<SharedString, SharedString>,
+    /// The missing icons already warned about.
+    warned: HashSet<SharedString>,
+}
+
+impl Global for IconRegistry {}
+
+/// Register a custom icon under the `name`, replacing the previous icon of the name.
+///
+/// The SVG data is cached to a file of the temporary directory, shared by the icons with the
+/// same data, and the SVG files are rendered from the disk as the paths of the [`crate::Icon`].
+pub fn register(
+    name: impl Into<SharedString>,
+    source: impl Into<IconSource>,
+    cx: &mut App,
+) -> Result<()> {
+    let name = name.into();
+    if name.is_empty() {
+        return Err(anyhow!("The icon name must not be empty"));
+    }
+
+    let path = match source.into() {
+        IconSource::Bytes(bytes) => cache_svg(&bytes)?,
+        IconSource::Path(path) => {
+            let path = path
+                .canonicalize()
+                .with_context(|| format!("Failed to read icon {}", path.display()))?;
+            if !path.is_file() {
+                return Err(anyhow!("The icon {} is not a file", path.display()));
+            }
+            path
+        }
+    };
+
+    let registry = cx.default_global::<IconRegistry>();
+    registry.warned.remove(&name);
+    registry
+        .icons
+        .insert(name, path.to_string_lossy().to_string().into());
+    Ok(())
+}
+
+/// Unregister the custom icon of the `name`, returns true if it was registered.
+pub fn unregister(name: &str, cx: &mut App) -> bool {
+    cx.default_global::<IconRegistry>()
+        .icons
+        .remove(name)
+        .is_some()
+}
+
+/// Returns true if a custom icon is registered under the `name`.
+pub fn is_registered(name: &str, cx: &App) -> bool {
+    cx.try_global::<IconRegistry>()
+        .is_some_and(|registry| registry.icons.contains_key(name))
+}
+
+/// Write the SVG data to the cache directory, named by the hash of the data.
+fn cache_svg(bytes: &[u8]) -> Result<PathBuf> {
+    let mut hasher = DefaultHasher::new();
+    bytes.hash(&mut hasher);
+
+    let dir = std::env::temp_dir().join("gpui-component-icons");
+    let path = dir.join(format!("{:016x}.svg", hasher.finish()));
+    if !path.is_file() {
+        std::fs::create_dir_all(&dir)
+            .with_context(|| format!("Failed to create {}", dir.display()))?;
+        std::fs::write(&path, bytes)
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+    }
+    Ok(path)
+}
+
+/// The resolved path of an icon to render.
+pub(crate) enum ResolvedIcon {
+    /// The path of the Assets bundle.
+    Asset(SharedString),
+    /// The path of a SVG file on the disk.
+    External(SharedString),
+}
+
+/// Resolve the icon `path` of the [`crate::Icon`], the missing custom icons are resolved to a
+/// placeholder with a warning logged once.
+pub(crate) fn resolve(path: &SharedString, cx: &mut App) -> ResolvedIcon {
+    let Some(name) = path.strip_prefix(CUSTOM_ICON_PREFIX) else {
+        return ResolvedIcon::Asset(path.clone());
+    };
+
+    let registry = cx.default_global::<IconRegistry>();
+    if let Some(path) = registry.icons.get(name) {
+        return ResolvedIcon::External(path.clone());
+    }
+
+    if registry.warned.insert(SharedString::from(name.to_string())) {
+        tracing::warn!(
+            "The icon \"{}\" is not registered, use icons::register.",
+            name
+        );
+    }
+    ResolvedIcon::Asset(IconName::SquareDashed.path())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gpui::TestAppContext;
+
+    const SVG: &[u8] = br#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 24 24"/>"#;
+
+    #[test]
+    fn test_custom_icon_path() {
+        assert_eq!(
+            IconName::Custom("brand-logo".into()).path(),
+            SharedString::from("custom:brand-logo")
+        );
+        assert_eq!(
+            IconName::Check.path(),
+            SharedString::from("icons/check.svg")
+        );
+    }
+
+    #[gpui::test]
+    fn test_register(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            let path = SharedString::from("custom:brand-logo");
+            assert!(matches!(
+                resolve(&path, cx),
+                ResolvedIcon::Asset(path) if path == IconName::SquareDashed.path()
+            ));
+
+            register("brand-logo", SVG, cx).unwrap();
+            assert!(is_registered("brand-logo", cx));
+            let ResolvedIcon::External(file) = resolve(&path, cx) else {
+                panic!("expected the registered icon");
+            };
+            assert_eq!(std::fs::read(file.as_str()).unwrap(), SVG);
+
+            // The same data is cached once.
+            register("brand-logo-2", SVG.to_vec(), cx).unwrap();
+            let ResolvedIcon::External(file_2) =
+                resolve(&SharedString::from("custom:brand-logo-2"), cx)
+            else {
+                panic!("expected the registered icon");
+            };
+            assert_eq!(file, file_2);
+
+            // Register a file path.
+            register("from-file", PathBuf::from(file.as_str()), cx).unwrap();
+            assert!(is_registered("from-file", cx));
+            assert!(register("missing", Path::new("/no/such/icon.svg"), cx).is_err());
+            assert!(!is_registered("missing", cx));
+
+            assert!(unregister("brand-logo", cx));
+            assert!(!is_registered("brand-logo", cx));
+            assert!(matches!(resolve(&path, cx), ResolvedIcon::Asset(_)));
+        });
+    }
+}
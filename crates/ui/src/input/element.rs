@@ -15,7 +15,7 @@ use smallvec::SmallVec;
 use std::{ops::Range, rc::Rc};
 
 use crate::{
-    ActiveTheme as _, Colorize, IconName, Root, Selectable, Sizable as _,
+    ActiveTheme as _, Colorize, IconName, Root, Selectable, Sizable as _, WindowExt as _,
     button::{Button, ButtonVariants as _},
     input::{RopeExt as _, blink_cursor::CURSOR_WIDTH, display_map::LineLayout},
     scroll::Scrollbar,
@@ -2081,7 +2081,7 @@ impl Element for TextElement {
         }
 
         // Paint selections
-        if window.is_window_active() {
+        if window.window_active(cx) {
             let secondary_selection = cx.theme().selection.saturation(0.1);
             for (path, is_active) in prepaint.search_match_paths.iter() {
                 window.paint_path(path.clone(), secondary_selection);
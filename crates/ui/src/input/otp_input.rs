@@ -6,8 +6,9 @@ use gpui::{
 };
 
 use super::{InputEvent, blink_cursor::BlinkCursor, input::input_style, state::InputState};
-use crate::Root;
+use crate::window_ext::observe_window_active;
 use crate::{ActiveTheme, Disableable, Icon, IconName, Sizable, Size, h_flex, v_flex};
+use crate::{Root, WindowExt as _};
 
 pub struct OtpState {
     focus_handle: FocusHandle,
@@ -33,8 +34,8 @@ impl OtpState {
             // Observe the blink cursor to repaint the view when it changes.
             cx.observe(&blink_cursor, |_, _, cx| cx.notify()),
             // Blink the cursor when the window is active, pause when it's not.
-            cx.observe_window_activation(window, |this, window, cx| {
-                if window.is_window_active() {
+            observe_window_active(window, cx, |this, window, cx| {
+                if window.window_active(cx) {
                     let focus_handle = this.focus_handle.clone();
                     if focus_handle.is_focused(window) {
                         this.blink_cursor.update(cx, |blink_cursor, cx| {
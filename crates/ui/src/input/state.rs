@@ -32,6 +32,7 @@ use super::{
     number_input::{NumberStep, StepAction},
 };
 use crate::Size;
+use crate::WindowExt as _;
 use crate::actions::{SelectDown, SelectLeft, SelectRight, SelectUp};
 use crate::highlighter::DiagnosticSet;
 #[cfg(not(target_family = "wasm"))]
@@ -47,6 +48,7 @@ use crate::input::{
 };
 use crate::native_menu::NativeMenu;
 use crate::scroll::AutoScroll;
+use crate::window_ext::observe_window_active;
 use crate::{Root, history::History};
 
 #[derive(Action, Clone, PartialEq, Eq, Deserialize)]
@@ -480,8 +482,8 @@ impl InputState {
             // Observe the blink cursor to repaint the view when it changes.
             cx.observe(&blink_cursor, |_, _, cx| cx.notify()),
             // Blink the cursor when the window is active, pause when it's not.
-            cx.observe_window_activation(window, |input, window, cx| {
-                if window.is_window_active() {
+            observe_window_active(window, cx, |input, window, cx| {
+                if window.window_active(cx) {
                     let focus_handle = input.focus_handle.clone();
                     if focus_handle.is_focused(window) {
                         input.blink_cursor.update(cx, |blink_cursor, cx| {
@@ -2341,7 +2343,7 @@ impl InputState {
         (self.focus_handle.is_focused(window) || self.is_context_menu_open(cx))
             && !self.disabled
             && self.blink_cursor.read(cx).visible()
-            && window.is_window_active()
+            && window.window_active(cx)
     }
 
     fn on_focus(&mut self, _: &mut Window, cx: &mut Context<Self>) {
@@ -1,9 +1,12 @@
-use gpui::{App, SharedString};
+use gpui::{App, Global, SharedString, Window};
 use std::ops::Deref;
 
 mod async_util;
+mod disabled_scope;
 mod element_ext;
 mod event;
+mod feedback;
+mod focus_group;
 mod focus_trap;
 mod geometry;
 pub mod global_state;
@@ -17,8 +20,10 @@ mod root;
 mod styled;
 mod time;
 mod title_bar;
+mod title_bar_tabs;
 mod virtual_list;
 mod window_border;
+mod window_controls;
 mod window_ext;
 
 pub(crate) mod actions;
@@ -44,6 +49,7 @@ pub mod group_box;
 pub mod highlighter;
 pub mod history;
 pub mod hover_card;
+pub mod icons;
 pub mod input;
 pub mod kbd;
 pub mod label;
@@ -81,8 +87,11 @@ pub mod tooltip;
 pub mod tree;
 
 pub use crate::Disableable;
+pub use disabled_scope::{DisabledScope, disabled_scope, inherited_disabled};
 pub use element_ext::*;
 pub use event::InteractiveElementExt;
+pub use feedback::{FeedbackKind, set_feedback_handler};
+pub use focus_group::{FocusGroup, FocusGroupElement};
 pub use focus_trap::FocusTrapElement;
 pub use geometry::*;
 pub use global_state::GlobalState;
@@ -97,16 +106,148 @@ pub use styled::*;
 pub use theme::*;
 pub use time::{calendar, date_picker};
 pub use title_bar::*;
+pub use title_bar_tabs::*;
 pub use virtual_list::{VirtualList, VirtualListScrollHandle, h_virtual_list, v_virtual_list};
-pub use window_border::{WindowBorder, window_border, window_paddings};
+pub use window_border::{
+    CornerBackdrop, StartKeyboardResize, WindowBorder, WindowShadow, WindowSizeConstraints,
+    content_to_window_point, window_border, window_content_bounds, window_paddings,
+    window_to_content_point,
+};
+pub use window_controls::*;
 pub use window_ext::WindowExt;
 
 rust_i18n::i18n!("locales", fallback = "en");
 
-/// Initialize the components.
+/// The environment variable enabling the MCP server in [`init`], e.g. in the instrumented builds.
+pub const MCP_ENV: &str = "GPUI_MCP_SOCKET";
+
+/// The options of [`init_with`].
+///
+/// ```ignore
+/// gpui_component::init_with(
+///     InitOptions::default()
+///         .theme_mode(ThemeMode::Dark)
+///         .direction(Direction::Rtl)
+///         .icon("brand-logo", include_bytes!("../assets/brand-logo.svg"))
+///         .app_name("my-app"),
+///     cx,
+/// );
+/// ```
+pub struct InitOptions {
+    theme_mode: ThemeMode,
+    reduce_motion: bool,
+    high_contrast: Option<bool>,
+    direction: Direction,
+    density: ComponentDensity,
+    icons: Vec<(SharedString, icons::IconSource)>,
+    enable_mcp: bool,
+    #[cfg_attr(not(feature = "mcp"), allow(dead_code))]
+    app_name: SharedString,
+}
+
+impl Default for InitOptions {
+    fn default() -> Self {
+        Self {
+            theme_mode: ThemeMode::default(),
+            reduce_motion: false,
+            high_contrast: None,
+            direction: Direction::default(),
+            density: ComponentDensity::default(),
+            icons: Vec::new(),
+            enable_mcp: false,
+            app_name: "gpui-app".into(),
+        }
+    }
+}
+
+impl InitOptions {
+    /// Set the initial theme mode, default is [`ThemeMode::Light`].
+    pub fn theme_mode(mut self, mode: ThemeMode) -> Self {
+        self.theme_mode = mode;
+        self
+    }
+
+    /// Set to true to disable the animations, see [`set_reduce_motion`].
+    pub fn reduce_motion(mut self, reduce_motion: bool) -> Self {
+        self.reduce_motion = reduce_motion;
+        self
+    }
+
+    /// Set to true to use the high-contrast styles, default follows the system, see
+    /// [`set_high_contrast`].
+    pub fn high_contrast(mut self, high_contrast: bool) -> Self {
+        self.high_contrast = Some(high_contrast);
+        self
+    }
+
+    /// Set the layout direction, see [`set_direction`].
+    pub fn direction(mut self, direction: Direction) -> Self {
+        self.direction = direction;
+        self
+    }
+
+    /// Set the density of the components, see [`set_density`].
+    pub fn density(mut self, density: ComponentDensity) -> Self {
+        self.density = density;
+        self
+    }
+
+    /// Register a custom icon, see [`icons::register`].
+    pub fn icon(
+        mut self,
+        name: impl Into<SharedString>,
+        source: impl Into<icons::IconSource>,
+    ) -> Self {
+        self.icons.push((name.into(), source.into()));
+        self
+    }
+
+    /// Set to true to start the MCP server, also started if the [`MCP_ENV`] environment
+    /// variable is set.
+    ///
+    /// Requires the `mcp` feature, see `mcp::init_mcp`.
+    pub fn enable_mcp(mut self, enable_mcp: bool) -> Self {
+        self.enable_mcp = enable_mcp;
+        self
+    }
+
+    /// Set the name of the application for the MCP server, default is `gpui-app`.
+    pub fn app_name(mut self, app_name: impl Into<SharedString>) -> Self {
+        self.app_name = app_name.into();
+        self
+    }
+}
+
+/// Marks the components as initialized, see [`init_with`].
+struct Initialized;
+
+impl Global for Initialized {}
+
+/// Initialize the components with the default [`InitOptions`].
 ///
 /// You must initialize the components at your application's entry point.
 pub fn init(cx: &mut App) {
+    init_with(InitOptions::default(), cx);
+}
+
+/// Initialize the components with the `options`.
+///
+/// This sets the globals of the components: the [`Theme`], the [`ThemeRegistry`], the
+/// [`GlobalState`], the focus traps and the [`dock::PanelRegistry`], the registry of the
+/// custom [`icons`] is set by the first [`icons::register`]. It also binds the keys of the components, e.g. `escape` to
+/// close a popover, and handles the window actions, e.g. [`StartKeyboardResize`].
+///
+/// Only the first call initializes the components, the later calls do nothing even with other
+/// options, so a library or a test can call it again safely. Use [`Theme::change`],
+/// [`set_reduce_motion`], [`set_high_contrast`], [`set_direction`] and [`set_density`] to change
+/// the options afterwards.
+pub fn init_with(options: InitOptions, cx: &mut App) {
+    if cx.has_global::<Initialized>() {
+        tracing::debug!("gpui_component is already initialized, skip init.");
+        return;
+    }
+    cx.set_global(Initialized);
+
     theme::init(cx);
     global_state::init(cx);
     #[cfg(any(feature = "inspector", debug_assertions))]
@@ -128,6 +269,31 @@ pub fn init(cx: &mut App) {
     text::init(cx);
     tree::init(cx);
     tooltip::init(cx);
+    title_bar_tabs::init(cx);
+    window_border::init(cx);
+
+    Theme::change(options.theme_mode, None, cx);
+    set_reduce_motion(options.reduce_motion, cx);
+    set_high_contrast(
+        options
+            .high_contrast
+            .unwrap_or_else(theme::system_high_contrast),
+        cx,
+    );
+    set_direction(options.direction, cx);
+    set_density(options.density, cx);
+    for (name, source) in options.icons {
+        if let Err(err) = icons::register(name.clone(), source, cx) {
+            tracing::warn!("failed to register icon {}: {:?}", name, err);
+        }
+    }
+
+    if options.enable_mcp || std::env::var_os(MCP_ENV).is_some() {
+        #[cfg(feature = "mcp")]
+        mcp::init_mcp(cx, &options.app_name);
+        #[cfg(not(feature = "mcp"))]
+        tracing::warn!("the MCP server requires the `mcp` feature.");
+    }
 }
 
 #[inline]
@@ -140,6 +306,81 @@ pub fn set_locale(locale: &str) {
     rust_i18n::set_locale(locale)
 }
 
+/// Returns true if the components should skip their animations, e.g. the toggle of a
+/// [`switch::Switch`].
+#[inline]
+pub fn reduce_motion(cx: &App) -> bool {
+    cx.try_global::<GlobalState>()
+        .is_some_and(|state| state.reduce_motion)
+}
+
+/// Set to true to disable the animations of the components for accessibility, default is false.
+#[inline]
+pub fn set_reduce_motion(reduce_motion: bool, cx: &mut App) {
+    GlobalState::global_mut(cx).reduce_motion = reduce_motion;
+}
+
+/// Returns the layout direction of the components, e.g. a [`switch::Switch`] is on at the left
+/// in [`Direction::Rtl`].
+#[inline]
+pub fn direction(cx: &App) -> Direction {
+    cx.try_global::<GlobalState>()
+        .map_or(Direction::Ltr, |state| state.direction)
+}
+
+/// Set the layout direction of the components, default is [`Direction::Ltr`].
+#[inline]
+pub fn set_direction(direction: Direction, cx: &mut App) {
+    GlobalState::global_mut(cx).direction = direction;
+}
+
+/// Returns the density of the components in all windows, see [`window_density`].
+#[inline]
+pub fn density(cx: &App) -> ComponentDensity {
+    cx.try_global::<GlobalState>()
+        .map_or(ComponentDensity::Comfortable, |state| state.density)
+}
+
+/// Set the density of the components in all windows, default is
+/// [`ComponentDensity::Comfortable`].
+///
+/// The windows are refreshed to lay out the components with the density.
+pub fn set_density(density: ComponentDensity, cx: &mut App) {
+    GlobalState::global_mut(cx).density = density;
+    cx.refresh_windows();
+}
+
+/// Returns the density of the components in the `window`, the window density if set, or the
+/// global [`density`].
+pub fn window_density(window: &Window, cx: &App) -> ComponentDensity {
+    let Some(state) = cx.try_global::<GlobalState>() else {
+        return ComponentDensity::Comfortable;
+    };
+    state
+        .window_densities
+        .get(&window.window_handle().window_id())
+        .copied()
+        .unwrap_or(state.density)
+}
+
+/// Set the density of the components in the `window` over the global [`density`], or `None` to
+/// follow the global density again.
+pub fn set_window_density(density: Option<ComponentDensity>, window: &mut Window, cx: &mut App) {
+    let window_id = window.window_handle().window_id();
+    let open_windows = cx
+        .windows()
+        .iter()
+        .map(|window| window.window_id())
+        .collect::<Vec<_>>();
+    let window_densities = &mut GlobalState::global_mut(cx).window_densities;
+    window_densities.retain(|window_id, _| open_windows.contains(window_id));
+    match density {
+        Some(density) => window_densities.insert(window_id, density),
+        None => window_densities.remove(&window_id),
+    };
+    window.refresh();
+}
+
 #[inline]
 pub(crate) fn measure_enable() -> bool {
     std::env::var("ZED_MEASUREMENTS").is_ok() || std::env::var("GPUI_MEASUREMENTS").is_ok()
@@ -187,3 +428,68 @@ impl Measure {
         tracing::trace!("{} in {:?}", self.name, duration);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gpui::TestAppContext;
+
+    #[test]
+    fn test_init_options_default() {
+        let options = InitOptions::default();
+        assert_eq!(options.app_name, "gpui-app");
+        assert_eq!(options.high_contrast, None);
+        assert!(!options.enable_mcp);
+    }
+
+    #[gpui::test]
+    fn test_init_with(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            init_with(
+                InitOptions::default()
+                    .theme_mode(ThemeMode::Dark)
+                    .reduce_motion(true)
+                    .direction(Direction::Rtl)
+                    .density(ComponentDensity::Compact),
+                cx,
+            );
+            assert!(cx.theme().mode.is_dark());
+            assert!(reduce_motion(cx));
+            assert_eq!(direction(cx), Direction::Rtl);
+            assert_eq!(density(cx), ComponentDensity::Compact);
+
+            // The second call is a no-op, the globals are kept.
+            set_direction(Direction::Ltr, cx);
+            init(cx);
+            assert!(cx.theme().mode.is_dark());
+            assert!(reduce_motion(cx));
+            assert_eq!(direction(cx), Direction::Ltr);
+        });
+    }
+
+    #[gpui::test]
+    fn test_window_density(cx: &mut TestAppContext) {
+        cx.update(init);
+        let cx = cx.add_empty_window();
+
+        cx.update(|window, cx| {
+            assert_eq!(window_density(window, cx), ComponentDensity::Comfortable);
+            assert_eq!(ComponentDensity::resolve(None, window, cx), Size::Medium);
+
+            set_window_density(Some(ComponentDensity::Compact), window, cx);
+            assert_eq!(window_density(window, cx), ComponentDensity::Compact);
+            assert_eq!(density(cx), ComponentDensity::Comfortable);
+            assert_eq!(ComponentDensity::resolve(None, window, cx), Size::Small);
+            // The explicit size always wins.
+            assert_eq!(
+                ComponentDensity::resolve(Some(Size::Large), window, cx),
+                Size::Large
+            );
+
+            // The window follows the global density again.
+            set_window_density(None, window, cx);
+            set_density(ComponentDensity::Compact, cx);
+            assert_eq!(window_density(window, cx), ComponentDensity::Compact);
+        });
+    }
+}
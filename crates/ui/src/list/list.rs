@@ -731,7 +731,7 @@ where
     D: ListDelegate + 'static,
 {
     fn with_size(mut self, size: impl Into<Size>) -> Self {
-        self.options.size = size.into();
+        self.options.size = size.into().clamp("List");
         self
     }
 }
@@ -9,16 +9,23 @@
 //! fn main() {
 //!     let app = Application::new();
 //!     app.run(|cx| {
-//!         gpui_component::init(cx);
 //!         // Pick a stable identifier for your app — the gpui-mcp-server
 //!         // uses it to target this app specifically when multiple GPUI
 //!         // apps are running.
-//!         gpui_component::mcp::init_mcp(cx, "my-app");
+//!         gpui_component::init_with(
+//!             InitOptions::default().app_name("my-app").enable_mcp(true),
+//!             cx,
+//!         );
 //!         // ... app code ...
 //!     });
 //! }
 //! ```
 //!
+//! Without `enable_mcp`, the server is started when the `GPUI_MCP_SOCKET`
+//! environment variable is set, see [`crate::MCP_ENV`]. Call [`init_mcp`]
+//! directly to start the server after [`crate::init`], or [`init_mcp_with`] to pass a
+//! [`McpConfig`], e.g. to trace the requests.
+//!
 //! ## Socket naming
 //!
 //! The socket is created at `{temp_dir}/gpui-mcp-{app_name}-{pid}.sock`.
@@ -40,9 +47,143 @@ use gpui::{point, px, App, Keystroke, MouseButton as GpuiMouseButton, Pixels};
 use gpui_mcp_protocol::protocol::*;
 use serde_json::json;
 
+use crate::WindowExt as _;
+
 /// Maximum number of stored log entries
 const MAX_LOG_ENTRIES: usize = 500;
 
+/// Get the active theme, handled here until it's added to the protocol `methods`.
+const GET_THEME: &str = "get_theme";
+
+/// Set the density of the components, globally or in a window, handled here until it's added to
+/// the protocol `methods`.
+const SET_DENSITY: &str = "set_density";
+
+/// Set a toggle, e.g. a switch or a checkbox, to the `checked` state, handled here until it's
+/// added to the protocol `methods`.
+const SET_TOGGLE: &str = "set_toggle";
+
+/// Compare the UI tree with a baseline stored in [`SNAPSHOT_DIR_ENV`], handled here until it's
+/// added to the protocol `methods`.
+const ASSERT_TREE_SNAPSHOT: &str = "assert_tree_snapshot";
+
+/// The environment variable with the directory of the UI tree baselines, see
+/// `assert_tree_snapshot`.
+pub const SNAPSHOT_DIR_ENV: &str = "GPUI_MCP_SNAPSHOT_DIR";
+
+/// Activate a window, also ending a simulated deactivation, handled here until it's added to the
+/// protocol `methods`.
+const SET_WINDOW_ACTIVE: &str = "set_window_active";
+
+/// Simulate a window losing the activation, or regaining it with `reactivate: true`, without
+/// changing the OS focus, only handled in the test mode, see [`TEST_MODE_ENV`].
+const SIMULATE_DEACTIVATE: &str = "simulate_deactivate";
+
+/// The environment variable enabling the methods for tests only, e.g. `simulate_deactivate`.
+pub const TEST_MODE_ENV: &str = "GPUI_MCP_TEST_MODE";
+
+/// Collect the logs, windows, UI trees, theme and screenshots in one call, handled here until
+/// it's added to the protocol `methods`.
+const DIAGNOSTIC_BUNDLE: &str = "diagnostic_bundle";
+
+/// The sections of the diagnostic bundle, all included by default.
+const DIAGNOSTIC_SECTIONS: [&str; 6] = ["logs", "windows", "tree", "theme", "perf", "screenshots"];
+
+/// The default size limit of the diagnostic bundle, see `max_bytes`.
+const DIAGNOSTIC_MAX_BYTES: usize = 20 * 1024 * 1024;
+
+/// The max width and height of the screenshots in the diagnostic bundle.
+const DIAGNOSTIC_SCREENSHOT_SIZE: u32 = 800;
+
+/// The interval to resolve the element again before retrying a missed click, about a frame.
+const CLICK_RETRY_INTERVAL: Duration = Duration::from_millis(16);
+
+/// The max time to wait for the toggle to change after the click.
+const SET_TOGGLE_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// The interval to check the state of the toggle after the click.
+const SET_TOGGLE_INTERVAL: Duration = Duration::from_millis(16);
+
+/// Enable or disable the tracing of the requests and responses at runtime, handled here until
+/// it's added to the protocol `methods`.
+const SET_TRACING: &str = "set_tracing";
+
+/// The environment variable with the trace file, see [`McpConfig::trace_path`].
+pub const TRACE_ENV: &str = "GPUI_MCP_TRACE";
+
+/// The default max length of the traced strings, see [`McpConfig::trace_payload_limit`].
+const TRACE_PAYLOAD_LIMIT: usize = 1024;
+
+/// The default max size of the trace file, see [`McpConfig::trace_max_file_size`].
+const TRACE_MAX_FILE_SIZE: u64 = 10 * 1024 * 1024;
+
+/// Record the bounds of an element on each frame for a duration, handled here until it's added
+/// to the protocol `methods`.
+const TRACK_ELEMENT: &str = "track_element";
+
+/// The default duration of `track_element`.
+const TRACK_ELEMENT_DURATION: Duration = Duration::from_secs(1);
+
+/// The max duration of `track_element`, under the request timeout.
+const TRACK_ELEMENT_MAX_DURATION: Duration = Duration::from_secs(5);
+
+/// The max samples of a tracked element, about 10s at 60 FPS.
+const TRACK_ELEMENT_MAX_SAMPLES: usize = 600;
+
+/// The max elements tracked at the same time.
+const MAX_TRACKED_ELEMENTS: usize = 8;
+
+/// The interval to check whether the tracking is done.
+const TRACK_ELEMENT_INTERVAL: Duration = Duration::from_millis(16);
+
+/// Select the profile of the connection with a `token`, see [`McpConfig::token`], handled here
+/// until it's added to the protocol `methods`.
+const AUTHENTICATE: &str = "authenticate";
+
+/// List the methods allowed to the connection, handled here until it's added to the protocol
+/// `methods`.
+const GET_CAPABILITIES: &str = "get_capabilities";
+
+/// The methods handled by the server, in the order reported by `get_capabilities`.
+const METHODS: [&str; 21] = [
+    methods::GET_WINDOWS,
+    methods::GET_APP_STATE,
+    methods::GET_LOGS,
+    methods::INSPECT_UI_TREE,
+    methods::GET_ELEMENT,
+    methods::TAKE_SCREENSHOT,
+    methods::LIST_ACTIONS,
+    methods::GET_FOCUS_INFO,
+    GET_THEME,
+    TRACK_ELEMENT,
+    methods::CLICK_ELEMENT,
+    methods::SEND_KEY,
+    methods::TYPE_TEXT,
+    methods::EXECUTE_ACTION,
+    SET_TOGGLE,
+    SET_DENSITY,
+    SET_WINDOW_ACTIVE,
+    SIMULATE_DEACTIVATE,
+    ASSERT_TREE_SNAPSHOT,
+    DIAGNOSTIC_BUNDLE,
+    SET_TRACING,
+];
+
+/// The methods of [`McpProfile::ReadOnly`], only reading the UI state, without input or writing
+/// files.
+const READ_ONLY_METHODS: [&str; 10] = [
+    methods::GET_WINDOWS,
+    methods::GET_APP_STATE,
+    methods::GET_LOGS,
+    methods::INSPECT_UI_TREE,
+    methods::GET_ELEMENT,
+    methods::TAKE_SCREENSHOT,
+    methods::LIST_ACTIONS,
+    methods::GET_FOCUS_INFO,
+    GET_THEME,
+    TRACK_ELEMENT,
+];
+
 /// Type for request messages from IPC thread to main thread
 type RequestMsg = (IpcRequest, mpsc::Sender<IpcResponse>);
 
@@ -56,6 +197,335 @@ static APP_STATE_PROVIDER: std::sync::LazyLock<
     Mutex<Option<Box<dyn Fn(&App) -> serde_json::Value + Send>>>,
 > = std::sync::LazyLock::new(|| Mutex::new(None));
 
+/// Semantic properties reported by components, keyed by `(window_id, element_id)`.
+static ELEMENT_PROPERTIES: std::sync::LazyLock<
+    Mutex<std::collections::HashMap<(String, String), serde_json::Map<String, serde_json::Value>>>,
+> = std::sync::LazyLock::new(|| Mutex::new(std::collections::HashMap::new()));
+
+/// Properties reported by components for a window, keyed by `window_id`.
+static WINDOW_PROPERTIES: std::sync::LazyLock<
+    Mutex<std::collections::HashMap<String, serde_json::Map<String, serde_json::Value>>>,
+> = std::sync::LazyLock::new(|| Mutex::new(std::collections::HashMap::new()));
+
+/// The `window_id` of the windows reported hidden, see [`mcp_set_window_visible`].
+static HIDDEN_WINDOWS: std::sync::LazyLock<Mutex<std::collections::HashSet<String>>> =
+    std::sync::LazyLock::new(|| Mutex::new(std::collections::HashSet::new()));
+
+thread_local! {
+    /// The focus handles reported by components, keyed by `(window_id, element_id)`.
+    static ELEMENT_FOCUS: std::cell::RefCell<
+        std::collections::HashMap<(String, String), gpui::WeakFocusHandle>,
+    > = std::cell::RefCell::new(std::collections::HashMap::new());
+
+    /// The `window_id` of the windows with an animation frame requested by the components, see
+    /// [`crate::animation::request_animation_frame`].
+    static PENDING_ANIMATION_FRAMES: std::cell::RefCell<std::collections::HashSet<String>> =
+        std::cell::RefCell::new(std::collections::HashSet::new());
+
+    /// The number of elements tracked by `track_element`, up to [`MAX_TRACKED_ELEMENTS`].
+    static TRACKED_ELEMENTS: std::cell::Cell<usize> = const { std::cell::Cell::new(0) };
+}
+
+/// The trace of the requests and responses, see [`McpConfig::trace_path`].
+static TRACER: std::sync::LazyLock<Mutex<Tracer>> =
+    std::sync::LazyLock::new(|| Mutex::new(Tracer::default()));
+
+/// The options of [`init_mcp_with`].
+///
+/// ```ignore
+/// gpui_component::mcp::init_mcp_with(
+///     cx,
+///     McpConfig::new("my-app").trace_path("/tmp/my-app-mcp.log"),
+/// );
+/// ```
+#[derive(Clone)]
+pub struct McpConfig {
+    app_name: String,
+    trace_path: Option<std::path::PathBuf>,
+    trace_payload_limit: usize,
+    trace_max_file_size: u64,
+    permissions: Permissions,
+}
+
+impl Default for McpConfig {
+    fn default() -> Self {
+        Self {
+            app_name: String::new(),
+            trace_path: None,
+            trace_payload_limit: TRACE_PAYLOAD_LIMIT,
+            trace_max_file_size: TRACE_MAX_FILE_SIZE,
+            permissions: Permissions::default(),
+        }
+    }
+}
+
+impl McpConfig {
+    /// Create the config for the app, see [`init_mcp`] for the `app_name`.
+    pub fn new(app_name: impl Into<String>) -> Self {
+        Self {
+            app_name: app_name.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Append every request and response line to the file, with the timestamp and the
+    /// connection id, default is the [`TRACE_ENV`] environment variable.
+    ///
+    /// The secrets, e.g. a `token`, are redacted, and the tracing can be toggled at runtime with
+    /// the `set_tracing` method.
+    pub fn trace_path(mut self, path: impl Into<std::path::PathBuf>) -> Self {
+        self.trace_path = Some(path.into());
+        self
+    }
+
+    /// Set the max length of the traced strings, e.g. a screenshot, default is 1024.
+    pub fn trace_payload_limit(mut self, limit: usize) -> Self {
+        self.trace_payload_limit = limit;
+        self
+    }
+
+    /// Set the size of the trace file to rotate it at, to `{path}.1`, default is 10MB.
+    pub fn trace_max_file_size(mut self, size: u64) -> Self {
+        self.trace_max_file_size = size;
+        self
+    }
+
+    /// Add a named profile, to select with [`McpConfig::token`] or
+    /// [`McpConfig::default_profile`], besides the built-in `read_only` and `full`.
+    pub fn profile(mut self, name: impl Into<String>, profile: McpProfile) -> Self {
+        self.permissions.profiles.insert(name.into(), profile);
+        self
+    }
+
+    /// Map a token to the named profile, selected by the `authenticate` method of a connection.
+    pub fn token(mut self, token: impl Into<String>, profile: impl Into<String>) -> Self {
+        self.permissions.tokens.insert(token.into(), profile.into());
+        self
+    }
+
+    /// Set the named profile of a connection before `authenticate`, default is `full` without
+    /// any [`McpConfig::token`], or `read_only` otherwise.
+    pub fn default_profile(mut self, profile: impl Into<String>) -> Self {
+        self.permissions.default_profile = Some(profile.into());
+        self
+    }
+}
+
+/// The methods allowed to a connection, see [`McpConfig::profile`].
+///
+/// A method not allowed returns a `forbidden` error, and `get_capabilities` only lists the allowed
+/// methods. The `authenticate` and `get_capabilities` methods are always allowed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum McpProfile {
+    /// Only the methods reading the UI state, e.g. `get_windows` or `take_screenshot`, without
+    /// the input methods or the methods writing files.
+    ReadOnly,
+    /// All the methods.
+    Full,
+    /// Only the listed methods.
+    Methods(Vec<String>),
+}
+
+impl McpProfile {
+    /// Create a profile allowing only the listed `methods`.
+    pub fn methods(methods: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        Self::Methods(methods.into_iter().map(Into::into).collect())
+    }
+
+    /// Returns true if the profile allows the `method`.
+    pub fn allows(&self, method: &str) -> bool {
+        if method == AUTHENTICATE || method == GET_CAPABILITIES {
+            return true;
+        }
+
+        match self {
+            Self::ReadOnly => READ_ONLY_METHODS.contains(&method),
+            Self::Full => true,
+            Self::Methods(methods) => methods.iter().any(|m| m == method),
+        }
+    }
+}
+
+/// The profiles and the tokens of [`McpConfig`].
+#[derive(Clone, Default)]
+struct Permissions {
+    profiles: std::collections::HashMap<String, McpProfile>,
+    tokens: std::collections::HashMap<String, String>,
+    default_profile: Option<String>,
+}
+
+impl Permissions {
+    /// Returns the named profile, the custom ones win over the built-in ones.
+    fn profile(&self, name: &str) -> Option<ConnectionProfile> {
+        let profile = match self.profiles.get(name) {
+            Some(profile) => profile.clone(),
+            None if name == "read_only" => McpProfile::ReadOnly,
+            None if name == "full" => McpProfile::Full,
+            None => return None,
+        };
+        Some(ConnectionProfile {
+            name: name.to_string(),
+            profile,
+        })
+    }
+
+    /// Returns the profile of a connection before `authenticate`.
+    ///
+    /// An unknown default profile allows no method rather than all of them.
+    fn default_profile(&self) -> ConnectionProfile {
+        let name = match &self.default_profile {
+            Some(name) => name.as_str(),
+            None if self.tokens.is_empty() => "full",
+            None => "read_only",
+        };
+        self.profile(name).unwrap_or_else(|| ConnectionProfile {
+            name: name.to_string(),
+            profile: McpProfile::Methods(vec![]),
+        })
+    }
+
+    /// Returns the profile mapped to the `token`.
+    fn authenticate(&self, token: &str) -> Result<ConnectionProfile, String> {
+        let name = self.tokens.get(token).ok_or("forbidden: invalid token")?;
+        self.profile(name)
+            .ok_or_else(|| format!("forbidden: unknown profile `{}`", name))
+    }
+}
+
+/// The profile selected by a connection.
+#[derive(Clone, Debug)]
+struct ConnectionProfile {
+    name: String,
+    profile: McpProfile,
+}
+
+impl ConnectionProfile {
+    /// Returns the `forbidden` error if the profile doesn't allow the `method`.
+    fn check(&self, method: &str) -> Result<(), String> {
+        if self.profile.allows(method) {
+            Ok(())
+        } else {
+            Err(format!(
+                "forbidden: `{}` is not allowed by the `{}` profile",
+                method, self.name
+            ))
+        }
+    }
+
+    /// Returns the `get_capabilities` response, with only the allowed methods.
+    fn capabilities(&self) -> serde_json::Value {
+        let methods: Vec<&str> = METHODS
+            .iter()
+            .copied()
+            .filter(|method| self.profile.allows(method))
+            .collect();
+        json!({
+            "profile": self.name,
+            "methods": methods,
+        })
+    }
+}
+
+/// Appends the request and response lines to the trace file.
+struct Tracer {
+    path: Option<std::path::PathBuf>,
+    enabled: bool,
+    payload_limit: usize,
+    max_file_size: u64,
+}
+
+impl Default for Tracer {
+    fn default() -> Self {
+        Self {
+            path: None,
+            enabled: false,
+            payload_limit: TRACE_PAYLOAD_LIMIT,
+            max_file_size: TRACE_MAX_FILE_SIZE,
+        }
+    }
+}
+
+impl Tracer {
+    fn trace(&self, connection_id: u64, direction: &str, line: &str) -> std::io::Result<()> {
+        let Some(path) = self.path.as_ref().filter(|_| self.enabled) else {
+            return Ok(());
+        };
+
+        let payload = match serde_json::from_str::<serde_json::Value>(line) {
+            Ok(mut value) => {
+                redact_payload(&mut value, self.payload_limit);
+                value
+            }
+            Err(_) => json!(truncate_payload(line, self.payload_limit)),
+        };
+        let entry = json!({
+            "timestamp": chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Millis, true),
+            "connection": connection_id,
+            "direction": direction,
+            "payload": payload,
+        });
+
+        if std::fs::metadata(path).is_ok_and(|m| m.len() >= self.max_file_size) {
+            let mut rotated = path.clone().into_os_string();
+            rotated.push(".1");
+            std::fs::rename(path, rotated)?;
+        }
+        let mut file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)?;
+        writeln!(file, "{}", entry)
+    }
+}
+
+/// Trace a request or response line of the connection, see [`McpConfig::trace_path`].
+fn trace_line(connection_id: u64, direction: &str, line: &str) {
+    if let Ok(tracer) = TRACER.lock() {
+        if let Err(e) = tracer.trace(connection_id, direction, line) {
+            eprintln!("[MCP] Trace error: {}", e);
+        }
+    }
+}
+
+/// Redact the secrets and truncate the long strings of a traced payload.
+fn redact_payload(value: &mut serde_json::Value, limit: usize) {
+    match value {
+        serde_json::Value::Object(map) => {
+            for (key, value) in map.iter_mut() {
+                let key = key.to_lowercase();
+                let is_secret = ["token", "secret", "password", "authorization"]
+                    .iter()
+                    .any(|secret| key.contains(secret));
+                if is_secret && !value.is_null() {
+                    *value = json!("<redacted>");
+                } else {
+                    redact_payload(value, limit);
+                }
+            }
+        }
+        serde_json::Value::Array(values) => {
+            for value in values {
+                redact_payload(value, limit);
+            }
+        }
+        serde_json::Value::String(text) if text.len() > limit => {
+            *text = truncate_payload(text, limit);
+        }
+        _ => {}
+    }
+}
+
+fn truncate_payload(text: &str, limit: usize) -> String {
+    if text.len() <= limit {
+        return text.to_string();
+    }
+    let mut end = limit;
+    while !text.is_char_boundary(end) {
+        end -= 1;
+    }
+    format!("{}...<{} bytes truncated>", &text[..end], text.len() - end)
+}
+
 fn px_to_f32(p: Pixels) -> f32 {
     f32::from(p)
 }
@@ -83,6 +553,128 @@ pub fn mcp_set_app_state_provider(
     }
 }
 
+/// Report semantic properties of an element (e.g. `role`, `selected`) for the MCP UI tree.
+///
+/// Components call this while rendering. The properties are merged into the
+/// properties of the element whose short name (the leaf segment of its
+/// `global_id`) matches `id`, in `inspect_ui_tree` and `get_element`.
+/// Calling this again for the same element replaces the previous properties.
+pub fn mcp_set_element_properties(
+    window: &gpui::Window,
+    id: &gpui::ElementId,
+    properties: serde_json::Value,
+) {
+    let serde_json::Value::Object(properties) = properties else {
+        return;
+    };
+    let key = (
+        format!("{:?}", window.window_handle().window_id()),
+        id.to_string(),
+    );
+    if let Ok(mut guard) = ELEMENT_PROPERTIES.lock() {
+        guard.insert(key, properties);
+    }
+}
+
+/// Report the focus handle of an element, to report the element as `focused_element` in
+/// `get_app_state` while it's focused.
+///
+/// Components call this while rendering, like [`mcp_set_element_properties`].
+pub fn mcp_set_element_focus(
+    window: &gpui::Window,
+    id: &gpui::ElementId,
+    focus_handle: &gpui::FocusHandle,
+) {
+    let key = (
+        format!("{:?}", window.window_handle().window_id()),
+        id.to_string(),
+    );
+    ELEMENT_FOCUS.with_borrow_mut(|focus| focus.insert(key, focus_handle.downgrade()));
+}
+
+/// The ID of the focused element of the window, if reported by [`mcp_set_element_focus`].
+fn focused_element(window_id: &str, window: &gpui::Window) -> Option<String> {
+    ELEMENT_FOCUS.with_borrow_mut(|focus| {
+        // Drop the handles of the released elements.
+        focus.retain(|_, handle| handle.upgrade().is_some());
+        focus
+            .iter()
+            .filter(|((id, _), _)| id == window_id)
+            .find(|(_, handle)| handle.upgrade().is_some_and(|h| h.is_focused(window)))
+            .map(|((_, element_id), _)| element_id.clone())
+    })
+}
+
+/// Mark an animation frame as pending until the next frame of the window is drawn.
+pub(crate) fn mcp_animation_frame_requested(window: &gpui::Window) {
+    let window_id = format!("{:?}", window.window_handle().window_id());
+    PENDING_ANIMATION_FRAMES.with_borrow_mut(|pending| pending.insert(window_id.clone()));
+    window.on_next_frame(move |_, _| {
+        PENDING_ANIMATION_FRAMES.with_borrow_mut(|pending| pending.remove(&window_id));
+    });
+}
+
+/// Report properties of a window (e.g. `accent`) for the MCP `get_windows` response.
+///
+/// Components call this while rendering, the properties are merged into the window info.
+/// Calling this again for the same window merges into the previous properties.
+pub fn mcp_set_window_properties(window: &gpui::Window, properties: serde_json::Value) {
+    let serde_json::Value::Object(properties) = properties else {
+        return;
+    };
+    let key = format!("{:?}", window.window_handle().window_id());
+    if let Ok(mut guard) = WINDOW_PROPERTIES.lock() {
+        guard.entry(key).or_default().extend(properties);
+    }
+}
+
+/// Report whether the window is shown, e.g. `false` for a preferences window kept alive while
+/// hidden. The windows are visible by default.
+///
+/// The hidden windows are reported with `visible: false` by `get_windows`, and the input
+/// methods only dispatch into them by the explicit `window_id`, or with `activate: true`.
+pub fn mcp_set_window_visible(window: &gpui::Window, visible: bool) {
+    set_window_visible(
+        &format!("{:?}", window.window_handle().window_id()),
+        visible,
+    );
+}
+
+fn set_window_visible(window_id: &str, visible: bool) {
+    if let Ok(mut guard) = HIDDEN_WINDOWS.lock() {
+        if visible {
+            guard.remove(window_id);
+        } else {
+            guard.insert(window_id.to_string());
+        }
+    }
+}
+
+fn is_window_visible(window_id: &str) -> bool {
+    HIDDEN_WINDOWS
+        .lock()
+        .map_or(true, |guard| !guard.contains(window_id))
+}
+
+/// Returns the windows, the visible windows first.
+fn windows_by_visibility(cx: &App) -> Vec<gpui::AnyWindowHandle> {
+    let mut windows = cx.windows();
+    windows.sort_by_key(|handle| !is_window_visible(&format!("{:?}", handle.window_id())));
+    windows
+}
+
+/// Returns the semantic properties reported by components for the element.
+fn element_properties(
+    window_id: &str,
+    global_id: &str,
+) -> Option<serde_json::Map<String, serde_json::Value>> {
+    let key = (window_id.to_string(), short_name_of(global_id).to_string());
+    ELEMENT_PROPERTIES
+        .lock()
+        .ok()
+        .and_then(|guard| guard.get(&key).cloned())
+}
+
 /// Add a log entry (can be called from anywhere)
 pub fn mcp_log(message: impl Into<String>) {
     if let Ok(mut buffer) = LOG_BUFFER.lock() {
@@ -137,14 +729,28 @@ fn socket_path_for(app_name: &str) -> String {
 /// Starts a Unix Domain Socket listener on a background thread and
 /// polls incoming requests on the GPUI main thread.
 pub fn init_mcp(cx: &mut App, app_name: &str) {
-    let socket_path = socket_path_for(app_name);
+    init_mcp_with(cx, McpConfig::new(app_name));
+}
 
+/// Start the MCP server with the `config`, see [`init_mcp`].
+pub fn init_mcp_with(cx: &mut App, config: McpConfig) {
+    let socket_path = socket_path_for(&config.app_name);
+    if let Ok(mut tracer) = TRACER.lock() {
+        tracer.path = config
+            .trace_path
+            .or_else(|| std::env::var_os(TRACE_ENV).map(std::path::PathBuf::from));
+        tracer.enabled = tracer.path.is_some();
+        tracer.payload_limit = config.trace_payload_limit;
+        tracer.max_file_size = config.trace_max_file_size;
+    }
+
+    let permissions = Arc::new(config.permissions);
     let (req_tx, req_rx) = mpsc::channel::<RequestMsg>();
 
     // Start IPC server on background thread
     let path = socket_path.clone();
     std::thread::spawn(move || {
-        if let Err(e) = run_ipc_listener(&path, req_tx) {
+        if let Err(e) = run_ipc_listener(&path, permissions, req_tx) {
             eprintln!("[MCP] IPC Server error: {}", e);
         }
     });
@@ -161,6 +767,24 @@ pub fn init_mcp(cx: &mut App, app_name: &str) {
 
             // Process all pending requests
             while let Ok((request, resp_tx)) = req_rx.try_recv() {
+                let method = request.method.as_str();
+                if [SET_TOGGLE, TRACK_ELEMENT, methods::CLICK_ELEMENT].contains(&method) {
+                    // Wait for the UI without blocking the other requests.
+                    cx.spawn(async move |cx| {
+                        let result = match request.method.as_str() {
+                            SET_TOGGLE => handle_set_toggle(&request.params, cx).await,
+                            TRACK_ELEMENT => handle_track_element(&request.params, cx).await,
+                            _ => handle_click_element(&request.params, cx).await,
+                        };
+                        let _ = resp_tx.send(IpcResponse {
+                            id: request.id.clone(),
+                            result,
+                        });
+                    })
+                    .detach();
+                    continue;
+                }
+
                 let ipc_response = cx.update(|cx| handle_request(&request, cx));
                 let _ = resp_tx.send(ipc_response);
             }
@@ -172,6 +796,7 @@ pub fn init_mcp(cx: &mut App, app_name: &str) {
 /// Unix Socket listener loop (runs on background thread)
 fn run_ipc_listener(
     socket_path: &str,
+    permissions: Arc<Permissions>,
     req_tx: mpsc::Sender<RequestMsg>,
 ) -> anyhow::Result<()> {
     // Remove old socket
@@ -179,12 +804,15 @@ fn run_ipc_listener(
 
     let listener = UnixListener::bind(socket_path)?;
 
-    for stream in listener.incoming() {
+    for (connection_id, stream) in (1..).zip(listener.incoming()) {
         match stream {
             Ok(stream) => {
                 let tx = req_tx.clone();
+                let permissions = permissions.clone();
                 std::thread::spawn(move || {
-                    if let Err(e) = handle_ipc_connection(stream, tx) {
+                    if let Err(e) =
+                        handle_ipc_connection(stream, connection_id, &permissions, tx)
+                    {
                         eprintln!("[MCP] Connection error: {}", e);
                     }
                 });
@@ -199,31 +827,57 @@ fn run_ipc_listener(
 }
 
 /// Handle a single IPC connection (runs on connection thread)
+///
+/// The methods are checked against the profile of the connection before reaching the main
+/// thread, so every method, including the ones added later, respects the profile.
 fn handle_ipc_connection(
     stream: UnixStream,
+    connection_id: u64,
+    permissions: &Permissions,
     req_tx: mpsc::Sender<RequestMsg>,
 ) -> anyhow::Result<()> {
     let reader = BufReader::new(&stream);
     let mut writer = &stream;
+    let mut profile = permissions.default_profile();
 
     for line in reader.lines() {
         let line = line?;
+        trace_line(connection_id, "request", &line);
         let request: IpcRequest = serde_json::from_str(&line)?;
 
-        let (resp_tx, resp_rx) = mpsc::channel();
-
-        req_tx.send((request, resp_tx)).map_err(|e| {
-            anyhow::anyhow!("Failed to send request to main thread: {}", e)
-        })?;
-
-        let response = resp_rx
-            .recv_timeout(Duration::from_secs(10))
-            .unwrap_or_else(|_| IpcResponse {
-                id: String::new(),
-                result: Err("Request timeout (10s)".into()),
-            });
+        let response = match request.method.as_str() {
+            AUTHENTICATE => IpcResponse {
+                id: request.id.clone(),
+                result: handle_authenticate(&request.params, permissions, &mut profile),
+            },
+            GET_CAPABILITIES => IpcResponse {
+                id: request.id.clone(),
+                result: Ok(profile.capabilities()),
+            },
+            method => match profile.check(method) {
+                Ok(()) => {
+                    let (resp_tx, resp_rx) = mpsc::channel();
+
+                    req_tx.send((request, resp_tx)).map_err(|e| {
+                        anyhow::anyhow!("Failed to send request to main thread: {}", e)
+                    })?;
+
+                    resp_rx
+                        .recv_timeout(Duration::from_secs(10))
+                        .unwrap_or_else(|_| IpcResponse {
+                            id: String::new(),
+                            result: Err("Request timeout (10s)".into()),
+                        })
+                }
+                Err(err) => IpcResponse {
+                    id: request.id.clone(),
+                    result: Err(err),
+                },
+            },
+        };
 
         let response_json = serde_json::to_string(&response)?;
+        trace_line(connection_id, "response", &response_json);
         writer.write_all(response_json.as_bytes())?;
         writer.write_all(b"\n")?;
         writer.flush()?;
@@ -232,11 +886,28 @@ fn handle_ipc_connection(
     Ok(())
 }
 
+/// Select the profile mapped to the `token` for the next requests of the connection.
+///
+/// An invalid token keeps the current profile.
+fn handle_authenticate(
+    params: &serde_json::Value,
+    permissions: &Permissions,
+    profile: &mut ConnectionProfile,
+) -> Result<serde_json::Value, String> {
+    let token = params
+        .get("token")
+        .and_then(|v| v.as_str())
+        .ok_or("Missing `token`")?;
+    *profile = permissions.authenticate(token)?;
+    mcp_log(format!("Authenticated with the `{}` profile", profile.name));
+
+    Ok(profile.capabilities())
+}
+
 /// Handle an IPC request on the GPUI main thread
 fn handle_request(request: &IpcRequest, cx: &mut App) -> IpcResponse {
     let result = match request.method.as_str() {
         methods::GET_WINDOWS => handle_get_windows(cx),
-        methods::CLICK_ELEMENT => handle_click_element(&request.params, cx),
         methods::SEND_KEY => handle_send_key(&request.params, cx),
         methods::GET_APP_STATE => handle_get_app_state(cx),
         methods::GET_LOGS => handle_get_logs(),
@@ -247,6 +918,13 @@ fn handle_request(request: &IpcRequest, cx: &mut App) -> IpcResponse {
         methods::LIST_ACTIONS => handle_list_actions(&request.params, cx),
         methods::GET_FOCUS_INFO => handle_get_focus_info(&request.params, cx),
         methods::TYPE_TEXT => handle_type_text(&request.params, cx),
+        GET_THEME => handle_get_theme(&request.params, cx),
+        SET_DENSITY => handle_set_density(&request.params, cx),
+        ASSERT_TREE_SNAPSHOT => handle_assert_tree_snapshot(&request.params, cx),
+        SET_WINDOW_ACTIVE => handle_set_window_active(&request.params, cx),
+        DIAGNOSTIC_BUNDLE => handle_diagnostic_bundle(&request.params, cx),
+        SET_TRACING => handle_set_tracing(&request.params),
+        SIMULATE_DEACTIVATE => handle_simulate_deactivate(&request.params, cx),
         _ => Err(format!("Unknown method: {}", request.method)),
     };
 
@@ -278,12 +956,55 @@ fn resolve_window(
         return Ok(handle);
     }
 
-    cx.windows()
+    windows_by_visibility(cx)
         .into_iter()
         .next()
         .ok_or_else(|| "No windows available".to_string())
 }
 
+/// Resolve the window an input method dispatches into, the window of the element found by ID,
+/// or the `window_id` with the fallback of [`resolve_window`].
+///
+/// A hidden window is only targeted by its explicit `window_id`, dispatching without activating
+/// it, or with `activate` raising it first. With `activate`, an inactive window is raised too.
+fn resolve_input_window(
+    window_id: Option<&str>,
+    element_window: Option<gpui::AnyWindowHandle>,
+    activate: bool,
+    cx: &mut App,
+) -> Result<gpui::AnyWindowHandle, String> {
+    let handle = match element_window {
+        Some(handle) => handle,
+        None => resolve_window(window_id, cx)?,
+    };
+    let id = format!("{:?}", handle.window_id());
+    let is_visible = is_window_visible(&id);
+    if !is_visible && !activate && window_id.is_none() {
+        return Err(format!(
+            "Window {} exists but is hidden, and activation was not requested: pass \
+            `window_id: \"{}\"` to dispatch into it, or `activate: true` to show it first",
+            id, id
+        ));
+    }
+
+    if activate && (!is_visible || cx.active_window() != Some(handle)) {
+        handle
+            .update(cx, |_, window, _| window.activate_window())
+            .map_err(|e| e.to_string())?;
+        set_window_visible(&id, true);
+        mcp_log(format!("Activated window {}", id));
+    }
+    Ok(handle)
+}
+
+/// Returns the `activate` param of an input method, default is false.
+fn activate_param(params: &serde_json::Value) -> bool {
+    params
+        .get("activate")
+        .and_then(|activate| activate.as_bool())
+        .unwrap_or(false)
+}
+
 /// Returns the id of the window that driver tools will target when no
 /// explicit window_id is provided. Priority mirrors `resolve_window(None, _)`:
 /// OS-focused window → first window → None.
@@ -298,7 +1019,10 @@ fn default_target_window_id(cx: &mut App) -> Option<gpui::WindowId> {
     if let Some(handle) = cx.active_window() {
         return Some(handle.window_id());
     }
-    cx.windows().into_iter().next().map(|h| h.window_id())
+    windows_by_visibility(cx)
+        .into_iter()
+        .next()
+        .map(|h| h.window_id())
 }
 
 /// Extract the last `.`-separated segment of a `global_id` — the actual
@@ -450,31 +1174,121 @@ fn handle_get_windows(cx: &mut App) -> Result<serde_json::Value, String> {
     // not "this window has OS focus". See `default_target_window_id`.
     let active_window_id = default_target_window_id(cx);
 
-    let windows: Vec<WindowInfo> = cx
+    let (windows, content_bounds): (Vec<WindowInfo>, Vec<Bounds>) = cx
         .windows()
         .iter()
         .filter_map(|handle| {
             handle
-                .update(cx, |_, window, _cx| {
+                .update(cx, |_, window, cx| {
                     let bounds = window.bounds();
-                    WindowInfo {
+                    let info = WindowInfo {
                         id: format!("{:?}", handle.window_id()),
                         title: window.window_title(),
                         bounds: convert_bounds(bounds),
                         is_active: active_window_id == Some(handle.window_id()),
                         display_id: None,
-                    }
+                    };
+                    let content_bounds = crate::window_content_bounds(window, cx);
+                    (info, convert_bounds(content_bounds))
                 })
                 .ok()
         })
-        .collect();
+        .unzip();
+
+    let mut value = serde_json::to_value(&windows).map_err(|e| e.to_string())?;
+    // The content bounds are in window coordinates, as the click and element positions.
+    if let Some(windows) = value.as_array_mut() {
+        for (window, content_bounds) in windows.iter_mut().zip(content_bounds) {
+            if let (Some(window), Ok(content_bounds)) =
+                (window.as_object_mut(), serde_json::to_value(content_bounds))
+            {
+                let visible = window
+                    .get("id")
+                    .and_then(|id| id.as_str())
+                    .map_or(true, is_window_visible);
+                window.insert("content_bounds".into(), content_bounds);
+                window.insert("visible".into(), json!(visible));
+            }
+        }
+    }
+    // Merge the properties reported by components, e.g. the TitleBar `accent`.
+    if let (Some(windows), Ok(properties)) = (value.as_array_mut(), WINDOW_PROPERTIES.lock()) {
+        for window in windows.iter_mut().filter_map(|window| window.as_object_mut()) {
+            let id = window.get("id").and_then(|id| id.as_str()).map(String::from);
+            if let Some(properties) = id.and_then(|id| properties.get(&id).cloned()) {
+                window.extend(properties);
+            }
+        }
+    }
+    Ok(value)
+}
 
-    serde_json::to_value(&windows).map_err(|e| e.to_string())
+fn handle_set_window_active(
+    params: &serde_json::Value,
+    cx: &mut App,
+) -> Result<serde_json::Value, String> {
+    let window_id = params.get("window_id").and_then(|v| v.as_str());
+    let handle = resolve_window(window_id, cx)?;
+    let id = format!("{:?}", handle.window_id());
+    handle
+        .update(cx, |_, window, cx| {
+            window.simulate_window_active(true, cx);
+            window.activate_window();
+        })
+        .map_err(|e| e.to_string())?;
+    set_window_visible(&id, true);
+    mcp_log(format!("Activated window {}", id));
+
+    let response = json!({ "success": true, "window_id": id });
+    Ok(attach_post_state(response, Some(&id), cx))
+}
+
+fn handle_simulate_deactivate(
+    params: &serde_json::Value,
+    cx: &mut App,
+) -> Result<serde_json::Value, String> {
+    if std::env::var_os(TEST_MODE_ENV).is_none() {
+        return Err(format!(
+            "{} is only available in the test mode, set {}",
+            SIMULATE_DEACTIVATE, TEST_MODE_ENV
+        ));
+    }
+    simulate_deactivate(params, cx)
 }
 
-fn handle_click_element(
+/// Deactivate the window, or reactivate it with `reactivate: true`, through the same path as the
+/// OS activation: blurring the focus and notifying the activation observers.
+fn simulate_deactivate(
     params: &serde_json::Value,
     cx: &mut App,
+) -> Result<serde_json::Value, String> {
+    let window_id = params.get("window_id").and_then(|v| v.as_str());
+    let reactivate = params
+        .get("reactivate")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false);
+    let handle = resolve_window(window_id, cx)?;
+    let id = format!("{:?}", handle.window_id());
+    let active = handle
+        .update(cx, |_, window, cx| {
+            window.simulate_window_active(reactivate, cx);
+            window.window_active(cx)
+        })
+        .map_err(|e| e.to_string())?;
+    let change = if reactivate {
+        "activation"
+    } else {
+        "deactivation"
+    };
+    mcp_log(format!("Simulated the {} of window {}", change, id));
+
+    let response = json!({ "success": true, "window_id": id, "active": active });
+    Ok(attach_post_state(response, Some(&id), cx))
+}
+
+async fn handle_click_element(
+    params: &serde_json::Value,
+    cx: &mut gpui::AsyncApp,
 ) -> Result<serde_json::Value, String> {
     let event: ClickEvent = serde_json::from_value(params.clone()).map_err(|e| e.to_string())?;
 
@@ -483,41 +1297,142 @@ fn handle_click_element(
         MouseButton::Right => GpuiMouseButton::Right,
         MouseButton::Middle => GpuiMouseButton::Middle,
     };
+    let retries = params
+        .get("retries")
+        .and_then(|retries| retries.as_u64())
+        .unwrap_or(0);
+    let activate = activate_param(params);
+    let window_id = event.window_id.as_deref();
+
+    let Some(element_id) = event.element_id.as_deref() else {
+        let position = point(px(event.x), px(event.y));
+        return cx.update(|cx| {
+            let handle = resolve_input_window(window_id, None, activate, cx)?;
+            handle
+                .update(cx, |_, window, cx| {
+                    window.dispatch_click(position, button, cx);
+                })
+                .map_err(|e| e.to_string())?;
+            mcp_log(format!(
+                "Click at ({}, {}) button={:?}",
+                event.x, event.y, event.button
+            ));
 
-    // If element_id is provided, resolve its bounds center
-    let (position, resolved_id) = if let Some(ref element_id) = event.element_id {
-        let (pos, id) = resolve_element_center(element_id, event.window_id.as_deref(), cx)?;
-        (pos, Some(id))
-    } else {
-        (point(px(event.x), px(event.y)), None)
+            let result = json!({ "success": true, "x": event.x, "y": event.y });
+            let window_id = format!("{:?}", handle.window_id());
+            Ok(attach_post_state(result, Some(&window_id), cx))
+        });
+    };
+
+    // Dispatch into the window of the element, and retry while the click misses the element,
+    // e.g. when it moved after the last frame.
+    let mut attempts = 0;
+    let (element, click) = loop {
+        attempts += 1;
+        let (element, click) = cx.update(|cx| {
+            let element = resolve_element(element_id, window_id, cx)?;
+            resolve_input_window(window_id, Some(element.window), activate, cx)?;
+            let click = dispatch_element_click(&element, button, cx)?;
+            Ok::<_, String>((element, click))
+        })?;
+        if click.hit || attempts > retries {
+            break (element, click);
+        }
+        cx.background_executor().timer(CLICK_RETRY_INTERVAL).await;
     };
 
-    let handle = resolve_window(event.window_id.as_deref(), cx)?;
+    let x = f32::from(click.position.x);
+    let y = f32::from(click.position.y);
+    mcp_log(format!(
+        "Click element '{}' at ({}, {}) button={:?} attempts={} hit={}",
+        element.full_id, x, y, event.button, attempts, click.hit
+    ));
 
-    handle
+    let result = json!({
+        "success": true,
+        "x": x,
+        "y": y,
+        "resolved_element": element.full_id,
+        "received_element": click.received,
+        "hit": click.hit,
+        "attempts": attempts,
+    });
+    Ok(cx.update(|cx| attach_post_state(result, Some(&element.window_id), cx)))
+}
+
+/// A click dispatched on an element, see [`dispatch_element_click`].
+struct ElementClick {
+    position: gpui::Point<Pixels>,
+    /// The full ID of the topmost element at the position, which received the click.
+    received: Option<String>,
+    /// Whether the element, or one of its children, received the click.
+    hit: bool,
+}
+
+/// Click the center of the element, with its bounds resolved again in the same update as the
+/// dispatch, as the element can move after [`resolve_element`], e.g. while animating.
+fn dispatch_element_click(
+    element: &ResolvedElement,
+    button: GpuiMouseButton,
+    cx: &mut App,
+) -> Result<ElementClick, String> {
+    element
+        .window
         .update(cx, |_, window, cx| {
+            let elements = window.inspector_elements();
+            let position = elements
+                .iter()
+                .find(|info| {
+                    format!(
+                        "{}/{}[{}]",
+                        element.window_id, info.global_id, info.instance_id
+                    ) == element.full_id
+                })
+                .map_or(element.center, |info| info.bounds.center());
+            let received = hit_test(&element.window_id, &elements, position);
             window.dispatch_click(position, button, cx);
+
+            let hit = received.as_ref().is_some_and(|(global_id, _)| {
+                global_id == &element.global_id
+                    || global_id.starts_with(&format!("{}.", element.global_id))
+            });
+            ElementClick {
+                position,
+                received: received.map(|(_, full_id)| full_id),
+                hit,
+            }
         })
-        .map_err(|e| e.to_string())?;
+        .map_err(|e| e.to_string())
+}
 
-    let x = f32::from(position.x);
-    let y = f32::from(position.y);
-    if let Some(id) = &resolved_id {
-        mcp_log(format!(
-            "Click element '{}' at ({}, {}) button={:?}",
-            id, x, y, event.button
-        ));
-    } else {
-        mcp_log(format!("Click at ({}, {}) button={:?}", x, y, event.button));
-    }
+/// Returns the `global_id` and the full ID of the topmost element at the position, the deepest
+/// of the elements containing it.
+fn hit_test(
+    window_id: &str,
+    elements: &[gpui::InspectorElementInfo],
+    position: gpui::Point<Pixels>,
+) -> Option<(String, String)> {
+    elements
+        .iter()
+        .filter(|info| info.bounds.contains(&position))
+        .max_by_key(|info| info.global_id.matches('.').count())
+        .map(|info| {
+            (
+                info.global_id.to_string(),
+                format!("{}/{}[{}]", window_id, info.global_id, info.instance_id),
+            )
+        })
+}
 
-    let mut result = json!({ "success": true, "x": x, "y": y });
-    if let Some(id) = resolved_id {
-        result
-            .as_object_mut()
-            .map(|o| o.insert("resolved_element".into(), json!(id)));
-    }
-    Ok(attach_post_state(result, event.window_id.as_deref(), cx))
+/// An element resolved by ID, see [`resolve_element`].
+struct ResolvedElement {
+    window: gpui::AnyWindowHandle,
+    window_id: String,
+    global_id: String,
+    /// The `{window_id}/{global_id}[{instance_id}]` of the element.
+    full_id: String,
+    center: gpui::Point<Pixels>,
+    bounds: gpui::Bounds<Pixels>,
 }
 
 /// Resolve the center point of an element by ID.
@@ -527,37 +1442,27 @@ fn resolve_element_center(
     window_id: Option<&str>,
     cx: &mut App,
 ) -> Result<(gpui::Point<Pixels>, String), String> {
+    resolve_element(query, window_id, cx).map(|element| (element.center, element.full_id))
+}
+
+/// Resolve an element by ID, in all windows or in the `window_id`.
+fn resolve_element(
+    query: &str,
+    window_id: Option<&str>,
+    cx: &mut App,
+) -> Result<ResolvedElement, String> {
+    // The elements of the visible windows win over the hidden ones.
     let windows: Vec<gpui::AnyWindowHandle> = if let Some(wid) = window_id {
-        cx.windows()
-            .into_iter()
-            .filter(|h| format!("{:?}", h.window_id()) == wid)
-            .collect()
+        vec![resolve_window(Some(wid), cx)?]
     } else {
-        cx.windows()
+        windows_by_visibility(cx)
     };
 
     for handle in &windows {
-        let result = handle.update(cx, |_, window, _cx| {
-            let window_id_str = format!("{:?}", handle.window_id());
-            for info in window.inspector_elements() {
-                let full_id =
-                    format!("{}/{}[{}]", window_id_str, info.global_id, info.instance_id);
-
-                let matches = full_id == query
-                    || info.global_id == query
-                    || info.global_id.ends_with(query);
-
-                if matches {
-                    let center_x = info.bounds.origin.x + info.bounds.size.width / 2.0;
-                    let center_y = info.bounds.origin.y + info.bounds.size.height / 2.0;
-                    return Some((point(center_x, center_y), full_id));
-                }
-            }
-            None
-        });
+        let result = handle.update(cx, |_, window, _cx| find_element(query, window));
 
-        if let Ok(Some((pos, id))) = result {
-            return Ok((pos, id));
+        if let Ok(Some(element)) = result {
+            return Ok(element);
         }
     }
 
@@ -565,6 +1470,243 @@ fn resolve_element_center(
     Err(not_found_error(query, candidates))
 }
 
+/// Find an element by ID in the elements of the last frame of the window.
+fn find_element(query: &str, window: &mut gpui::Window) -> Option<ResolvedElement> {
+    let handle = window.window_handle();
+    let window_id_str = format!("{:?}", handle.window_id());
+    for info in window.inspector_elements() {
+        let full_id = format!("{}/{}[{}]", window_id_str, info.global_id, info.instance_id);
+
+        let matches =
+            full_id == query || info.global_id == query || info.global_id.ends_with(query);
+
+        if matches {
+            let center_x = info.bounds.origin.x + info.bounds.size.width / 2.0;
+            let center_y = info.bounds.origin.y + info.bounds.size.height / 2.0;
+            return Some(ResolvedElement {
+                window: handle,
+                window_id: window_id_str,
+                global_id: info.global_id.to_string(),
+                full_id,
+                center: point(center_x, center_y),
+                bounds: info.bounds,
+            });
+        }
+    }
+    None
+}
+
+/// Returns the `checked` state reported by the toggle, an error if the element isn't a toggle
+/// or can't be changed.
+fn toggle_state(element: &ResolvedElement) -> Result<bool, String> {
+    let properties = element_properties(&element.window_id, &element.global_id)
+        .ok_or_else(|| format!("Element '{}' is not a toggle", element.full_id))?;
+    let checked = properties
+        .get("checked")
+        .and_then(|checked| checked.as_bool())
+        .ok_or_else(|| format!("Element '{}' is not a toggle", element.full_id))?;
+    for property in ["disabled", "readonly"] {
+        if properties.get(property) == Some(&json!(true)) {
+            return Err(format!("Element '{}' is {}", element.full_id, property));
+        }
+    }
+    Ok(checked)
+}
+
+/// Handle the `set_toggle` request, `{ "element_id", "checked", "window_id"? }`.
+///
+/// Clicks the toggle only if its `checked` state differs, and waits for the state to change,
+/// until the [`SET_TOGGLE_TIMEOUT`].
+async fn handle_set_toggle(
+    params: &serde_json::Value,
+    cx: &mut gpui::AsyncApp,
+) -> Result<serde_json::Value, String> {
+    let query = params
+        .get("element_id")
+        .and_then(|id| id.as_str())
+        .ok_or("Missing `element_id`")?;
+    let checked = params
+        .get("checked")
+        .and_then(|checked| checked.as_bool())
+        .ok_or("Missing `checked`")?;
+    let window_id = params.get("window_id").and_then(|id| id.as_str());
+
+    let activate = activate_param(params);
+    let element = cx.update(|cx| {
+        let element = resolve_element(query, window_id, cx)?;
+        resolve_input_window(window_id, Some(element.window), activate, cx)?;
+        Ok::<_, String>(element)
+    })?;
+    let before = toggle_state(&element)?;
+    let clicked = before != checked;
+    let mut after = before;
+    if clicked {
+        cx.update(|cx| dispatch_element_click(&element, GpuiMouseButton::Left, cx))?;
+
+        // The state is reported on the next render of the toggle.
+        let mut waited = Duration::ZERO;
+        while after != checked && waited < SET_TOGGLE_TIMEOUT {
+            cx.background_executor().timer(SET_TOGGLE_INTERVAL).await;
+            waited += SET_TOGGLE_INTERVAL;
+            after = toggle_state(&element).unwrap_or(after);
+        }
+    }
+
+    mcp_log(format!(
+        "Set toggle '{}' to {}: before={} after={} clicked={}",
+        element.full_id, checked, before, after, clicked
+    ));
+
+    let result = json!({
+        "success": after == checked,
+        "resolved_element": element.full_id,
+        "before": before,
+        "after": after,
+        "clicked": clicked,
+        "timed_out": after != checked,
+    });
+    Ok(cx.update(|cx| attach_post_state(result, Some(&element.window_id), cx)))
+}
+
+/// The samples of an element recorded by `track_element`.
+struct ElementTrack {
+    query: String,
+    started: std::time::Instant,
+    max_samples: usize,
+    force_frames: bool,
+    stopped: bool,
+    samples: Vec<serde_json::Value>,
+}
+
+impl ElementTrack {
+    fn is_full(&self) -> bool {
+        self.samples.len() >= self.max_samples
+    }
+
+    /// Record a sample of the element, with null `bounds` while it's not rendered.
+    ///
+    /// Returns false once the tracking is done.
+    fn record(
+        &mut self,
+        bounds: Option<gpui::Bounds<Pixels>>,
+        opacity: Option<serde_json::Value>,
+    ) -> bool {
+        if self.stopped || self.is_full() {
+            return false;
+        }
+
+        let mut sample = json!({
+            "frame": self.samples.len(),
+            "t_ms": self.started.elapsed().as_secs_f64() * 1000.,
+            "bounds": bounds.map(convert_bounds),
+        });
+        if let Some(opacity) = opacity {
+            sample["opacity"] = opacity;
+        }
+        self.samples.push(sample);
+        !self.is_full()
+    }
+
+    /// Record a sample on the next frame of the window, and on each following frame until done.
+    ///
+    /// Only the frames drawn anyway are sampled, unless `force_frames` refreshes the window.
+    fn schedule(track: std::rc::Rc<std::cell::RefCell<Self>>, window: &gpui::Window) {
+        window.on_next_frame(move |window, _| {
+            let mut this = track.borrow_mut();
+            let element = find_element(&this.query, window);
+            let opacity = element.as_ref().and_then(|element| {
+                element_properties(&element.window_id, &element.global_id)
+                    .and_then(|properties| properties.get("opacity").cloned())
+            });
+            if !this.record(element.map(|element| element.bounds), opacity) {
+                return;
+            }
+            if this.force_frames {
+                window.refresh();
+            }
+            drop(this);
+            Self::schedule(track, window);
+        });
+    }
+}
+
+/// Record the bounds of an element, and its `opacity` when reported, on each frame for
+/// `duration_ms` or up to `frames`, e.g. to check that an animation is smooth.
+async fn handle_track_element(
+    params: &serde_json::Value,
+    cx: &mut gpui::AsyncApp,
+) -> Result<serde_json::Value, String> {
+    let query = params
+        .get("element_id")
+        .and_then(|id| id.as_str())
+        .ok_or("Missing `element_id`")?;
+    let window_id = params.get("window_id").and_then(|id| id.as_str());
+    let duration = params
+        .get("duration_ms")
+        .and_then(|duration| duration.as_u64())
+        .map_or(TRACK_ELEMENT_DURATION, Duration::from_millis)
+        .min(TRACK_ELEMENT_MAX_DURATION);
+    let max_samples = params
+        .get("frames")
+        .and_then(|frames| frames.as_u64())
+        .map_or(TRACK_ELEMENT_MAX_SAMPLES, |frames| {
+            (frames as usize).min(TRACK_ELEMENT_MAX_SAMPLES)
+        });
+    let force_frames = params
+        .get("force_frames")
+        .and_then(|force| force.as_bool())
+        .unwrap_or(false);
+
+    if TRACKED_ELEMENTS.get() >= MAX_TRACKED_ELEMENTS {
+        return Err(format!(
+            "Already tracking {} elements, retry when one is done",
+            MAX_TRACKED_ELEMENTS
+        ));
+    }
+    let element = cx.update(|cx| resolve_element(query, window_id, cx))?;
+
+    let track = std::rc::Rc::new(std::cell::RefCell::new(ElementTrack {
+        query: element.global_id.clone(),
+        started: std::time::Instant::now(),
+        max_samples,
+        force_frames,
+        stopped: false,
+        samples: vec![],
+    }));
+    TRACKED_ELEMENTS.set(TRACKED_ELEMENTS.get() + 1);
+    let scheduled = cx.update(|cx| {
+        let track = track.clone();
+        element
+            .window
+            .update(cx, |_, window, _| ElementTrack::schedule(track, window))
+    });
+    if scheduled.is_ok() {
+        while track.borrow().started.elapsed() < duration && !track.borrow().is_full() {
+            cx.background_executor().timer(TRACK_ELEMENT_INTERVAL).await;
+        }
+    }
+    track.borrow_mut().stopped = true;
+    TRACKED_ELEMENTS.set(TRACKED_ELEMENTS.get() - 1);
+    scheduled.map_err(|e| e.to_string())?;
+
+    let samples = std::mem::take(&mut track.borrow_mut().samples);
+    let elapsed = track.borrow().started.elapsed();
+    mcp_log(format!(
+        "Tracked element '{}' for {} frames",
+        element.full_id,
+        samples.len()
+    ));
+
+    Ok(json!({
+        "success": true,
+        "resolved_element": element.full_id,
+        "duration_ms": elapsed.as_millis() as u64,
+        "frames": samples.len(),
+        "capped": samples.len() >= TRACK_ELEMENT_MAX_SAMPLES,
+        "samples": samples,
+    }))
+}
+
 fn handle_send_key(
     params: &serde_json::Value,
     cx: &mut App,
@@ -592,7 +1734,8 @@ fn handle_send_key(
     // driver handlers (click/type/screenshot/execute_action). This lets the
     // LLM drive the app even when it's OS-backgrounded — the app's own
     // window is still a valid dispatch target.
-    let handle = resolve_window(event.window_id.as_deref(), cx)?;
+    let handle =
+        resolve_input_window(event.window_id.as_deref(), None, activate_param(params), cx)?;
 
     let dispatched = handle
         .update(cx, |_, window, cx| {
@@ -616,7 +1759,7 @@ fn handle_type_text(
     let opts: TypeTextParams =
         serde_json::from_value(params.clone()).map_err(|e| e.to_string())?;
 
-    let handle = resolve_window(opts.window_id.as_deref(), cx)?;
+    let handle = resolve_input_window(opts.window_id.as_deref(), None, activate_param(params), cx)?;
 
     let mut dispatched_count = 0usize;
     for ch in opts.text.chars() {
@@ -663,40 +1806,185 @@ fn handle_get_app_state(cx: &mut App) -> Result<serde_json::Value, String> {
     let active_window_id = default_target_window_id(cx).map(|id| format!("{:?}", id));
     let window_count = cx.windows().len();
 
+    let popover_open = cx
+        .try_global::<crate::GlobalState>()
+        .is_some_and(|state| state.is_in_deferred_context());
     let windows: Vec<serde_json::Value> = cx
         .windows()
         .iter()
         .filter_map(|handle| {
             handle
-                .update(cx, |_, window, _cx| {
+                .update(cx, |_, window, cx| {
+                    let window_id = format!("{:?}", handle.window_id());
                     let bounds = convert_bounds(window.bounds());
+                    // Whether the input is swallowed by the layers above the content.
+                    let modal_active = window.root::<crate::Root>().flatten().is_some_and(|root| {
+                        let root = root.read(cx);
+                        !root.active_dialogs.is_empty() || root.active_sheet.is_some()
+                    });
+                    let is_active = cx.active_window() == Some(*handle);
                     json!({
-                        "id": format!("{:?}", handle.window_id()),
+                        "id": window_id,
                         "title": window.window_title(),
                         "bounds": bounds,
+                        "focused": window.focused(cx).map(|focus| format!("{:?}", focus)),
+                        "focused_element": focused_element(&window_id, window),
+                        "modal_active": modal_active,
+                        "overlay_active": modal_active || (is_active && popover_open),
+                        "dragging": is_active && cx.has_active_drag(),
+                        "animation_pending": PENDING_ANIMATION_FRAMES
+                            .with_borrow(|pending| pending.contains(&window_id)),
                     })
                 })
                 .ok()
         })
         .collect();
 
-    let mut result = json!({
-        "window_count": window_count,
-        "active_window": active_window_id,
-        "windows": windows,
+    let mut result = json!({
+        "window_count": window_count,
+        "active_window": active_window_id,
+        "windows": windows,
+    });
+
+    // Merge app-specific semantic state if a provider is registered
+    if let Ok(guard) = APP_STATE_PROVIDER.lock() {
+        if let Some(provider) = guard.as_ref() {
+            let app_state = provider(cx);
+            if let Some(obj) = result.as_object_mut() {
+                obj.insert("app".into(), app_state);
+            }
+        }
+    }
+
+    Ok(result)
+}
+
+fn handle_get_theme(
+    params: &serde_json::Value,
+    cx: &mut App,
+) -> Result<serde_json::Value, String> {
+    use crate::Colorize as _;
+
+    // The theme is global, so only an explicit `window_id` must exist.
+    let window_id = params.get("window_id").and_then(|id| id.as_str());
+    let window_id = match window_id {
+        Some(_) => Some(resolve_window(window_id, cx)?.window_id()),
+        None => resolve_window(None, cx).ok().map(|handle| handle.window_id()),
+    };
+
+    let theme = crate::Theme::global(cx);
+    let colors = match window_id {
+        Some(window_id) => theme.colors_for_window(window_id),
+        None => &theme.colors,
+    };
+    let colors = serde_json::to_value(crate::ThemeConfigColors::from_colors(colors))
+        .map_err(|err| err.to_string())?;
+
+    // Mark the overridden tokens, the window overrides win over the global ones.
+    let (global_overrides, window_overrides) = theme.overrides_for_window(window_id);
+    let mut overrides = serde_json::Map::new();
+    let scopes = [("global", Some(global_overrides)), ("window", window_overrides)];
+    for (scope, scope_overrides) in scopes {
+        let Some(scope_overrides) = scope_overrides else {
+            continue;
+        };
+        for (token, color) in scope_overrides.iter() {
+            overrides.insert(
+                token.to_string(),
+                json!({ "color": color.to_hex(), "scope": scope }),
+            );
+        }
+    }
+
+    let density = window_id
+        .and_then(|window_id| {
+            cx.try_global::<crate::GlobalState>()
+                .and_then(|state| state.window_densities.get(&window_id).copied())
+        })
+        .unwrap_or(crate::density(cx));
+
+    Ok(json!({
+        "name": theme.theme_name(),
+        "mode": theme.mode.name(),
+        "appearance": theme.appearance.name(),
+        "density": density.as_str(),
+        "colors": colors,
+        "overrides": overrides,
+    }))
+}
+
+/// Set the `density` of the components, in the window of the `window_id` if given, a null
+/// `density` makes the window follow the global density again.
+fn handle_set_density(
+    params: &serde_json::Value,
+    cx: &mut App,
+) -> Result<serde_json::Value, String> {
+    let density = match params.get("density") {
+        None | Some(serde_json::Value::Null) => None,
+        Some(density) => Some(
+            serde_json::from_value::<crate::ComponentDensity>(density.clone()).map_err(|_| {
+                format!(
+                    "Invalid density: {}, expected comfortable or compact",
+                    density
+                )
+            })?,
+        ),
+    };
+
+    let Some(window_id) = params.get("window_id").and_then(|id| id.as_str()) else {
+        let density = density.ok_or("Missing density")?;
+        crate::set_density(density, cx);
+        mcp_log(format!("Set the density to {}", density.as_str()));
+        return Ok(json!({ "success": true, "density": density.as_str(), "scope": "global" }));
+    };
+
+    let handle = resolve_window(Some(window_id), cx)?;
+    let id = format!("{:?}", handle.window_id());
+    let density = handle
+        .update(cx, |_, window, cx| {
+            crate::set_window_density(density, window, cx);
+            crate::window_density(window, cx)
+        })
+        .map_err(|e| e.to_string())?;
+    mcp_log(format!(
+        "Set the density of window {} to {}",
+        id,
+        density.as_str()
+    ));
+
+    let response = json!({
+        "success": true,
+        "density": density.as_str(),
+        "scope": "window",
+        "window_id": id,
     });
+    Ok(attach_post_state(response, Some(&id), cx))
+}
 
-    // Merge app-specific semantic state if a provider is registered
-    if let Ok(guard) = APP_STATE_PROVIDER.lock() {
-        if let Some(provider) = guard.as_ref() {
-            let app_state = provider(cx);
-            if let Some(obj) = result.as_object_mut() {
-                obj.insert("app".into(), app_state);
-            }
-        }
+/// Enable or disable the tracing, to the `path` or the configured trace file.
+fn handle_set_tracing(params: &serde_json::Value) -> Result<serde_json::Value, String> {
+    let enabled = params
+        .get("enabled")
+        .and_then(|v| v.as_bool())
+        .ok_or("Missing `enabled`")?;
+    let mut tracer = TRACER.lock().map_err(|e| e.to_string())?;
+    if let Some(path) = params.get("path").and_then(|v| v.as_str()) {
+        tracer.path = Some(path.into());
+    }
+    if enabled && tracer.path.is_none() {
+        return Err(format!(
+            "No trace file, pass `path` or set {} to enable the tracing",
+            TRACE_ENV
+        ));
     }
+    tracer.enabled = enabled;
+    mcp_log(format!("Tracing enabled={}", enabled));
 
-    Ok(result)
+    Ok(json!({
+        "success": true,
+        "enabled": tracer.enabled,
+        "path": tracer.path.as_ref().map(|path| path.to_string_lossy()),
+    }))
 }
 
 fn handle_get_logs() -> Result<serde_json::Value, String> {
@@ -997,6 +2285,257 @@ fn filter_tree_by_text(elements: &mut Vec<UiElement>, filter_lower: &str) {
     });
 }
 
+fn handle_assert_tree_snapshot(
+    params: &serde_json::Value,
+    cx: &mut App,
+) -> Result<serde_json::Value, String> {
+    let dir = std::env::var_os(SNAPSHOT_DIR_ENV)
+        .ok_or_else(|| format!("{} is not set", SNAPSHOT_DIR_ENV))?;
+    assert_tree_snapshot(params, std::path::Path::new(&dir), cx)
+}
+
+/// Compare the flat rows of a window, or of the `root_element_id` subtree, with the baseline
+/// `{dir}/{name}.json`, rewriting the baseline with `update: true`.
+///
+/// The fields matched by the `ignore` patterns are not compared, see [`ignore_matches`].
+fn assert_tree_snapshot(
+    params: &serde_json::Value,
+    dir: &std::path::Path,
+    cx: &mut App,
+) -> Result<serde_json::Value, String> {
+    let name = params
+        .get("name")
+        .and_then(|v| v.as_str())
+        .ok_or("Missing `name`")?;
+    if name.is_empty() || name.contains(['/', '\\']) || name.contains("..") {
+        return Err(format!("Invalid snapshot name: {}", name));
+    }
+    let update = params
+        .get("update")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false);
+    let ignore: Vec<String> = params
+        .get("ignore")
+        .and_then(|v| v.as_array())
+        .map(|patterns| {
+            patterns
+                .iter()
+                .filter_map(|p| p.as_str().map(String::from))
+                .collect()
+        })
+        .unwrap_or_default();
+    let window_id = params.get("window_id").and_then(|v| v.as_str());
+    let root_id = params.get("root_element_id").and_then(|v| v.as_str());
+
+    let handle = resolve_window(window_id, cx)?;
+    let rows = window_rows(handle, root_id, cx)?;
+
+    let path = dir.join(format!("{}.json", name));
+    let baseline = match std::fs::read_to_string(&path) {
+        Ok(content) => {
+            let baseline: serde_json::Value =
+                serde_json::from_str(&content).map_err(|e| format!("{}: {}", path.display(), e))?;
+            Some(baseline["rows"].as_array().cloned().unwrap_or_default())
+        }
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => None,
+        Err(e) => return Err(format!("{}: {}", path.display(), e)),
+    };
+
+    if baseline.is_none() && !update {
+        return Err(format!(
+            "No baseline at {}, pass `update: true` to record it",
+            path.display()
+        ));
+    }
+
+    let (added, removed, changed) =
+        diff_rows(baseline.as_deref().unwrap_or_default(), &rows, &ignore);
+    let passed = added.is_empty() && removed.is_empty() && changed.is_empty();
+
+    if update {
+        std::fs::create_dir_all(dir).map_err(|e| format!("{}: {}", dir.display(), e))?;
+        let content = serde_json::to_string_pretty(&json!({ "name": name, "rows": rows }))
+            .map_err(|e| e.to_string())?;
+        std::fs::write(&path, content).map_err(|e| format!("{}: {}", path.display(), e))?;
+    }
+
+    Ok(json!({
+        "success": update || passed,
+        "passed": passed,
+        "updated": update,
+        "baseline": path.display().to_string(),
+        "rows": rows.len(),
+        "added": added,
+        "removed": removed,
+        "changed": changed,
+    }))
+}
+
+/// The flat rows of the window, or of the `root_id` subtree, see [`flatten_rows`].
+fn window_rows(
+    handle: gpui::AnyWindowHandle,
+    root_id: Option<&str>,
+    cx: &mut App,
+) -> Result<Vec<serde_json::Value>, String> {
+    handle
+        .update(cx, |_, window, _| {
+            let window_id = format!("{:?}", handle.window_id());
+            let mut elements = build_element_tree(&window_id, window.inspector_elements());
+            if let Some(root_id) = root_id {
+                elements = find_subtree(&elements, root_id)
+                    .map(|e| vec![e])
+                    .ok_or_else(|| format!("Element not found: {}", root_id))?;
+            }
+            let mut rows = vec![];
+            flatten_rows(&elements, 0, &mut rows);
+            Ok(rows)
+        })
+        .map_err(|e| e.to_string())?
+}
+
+/// Flatten the tree into rows in the depth-first order, with the IDs relative to the window
+/// so the baselines don't depend on the window that was opened first.
+fn flatten_rows(elements: &[UiElement], depth: usize, rows: &mut Vec<serde_json::Value>) {
+    for elem in elements {
+        let id = shorten_element_id(&elem.id);
+        let id = id.find('/').map(|i| &id[i + 1..]).unwrap_or(&id);
+        let properties: std::collections::BTreeMap<_, _> = elem
+            .properties
+            .iter()
+            .filter(|(key, _)| key.as_str() != "instance_id")
+            .collect();
+        rows.push(json!({
+            "id": id,
+            "type": elem.element_type,
+            "depth": depth,
+            "bounds": {
+                "x": elem.bounds.x,
+                "y": elem.bounds.y,
+                "width": elem.bounds.width,
+                "height": elem.bounds.height,
+            },
+            "text": elem.text_content,
+            "properties": properties,
+        }));
+        flatten_rows(&elem.children, depth + 1, rows);
+    }
+}
+
+/// Diff the rows by `id`, returning the added rows, the removed rows and the changed fields of
+/// the rows in both.
+fn diff_rows(
+    baseline: &[serde_json::Value],
+    rows: &[serde_json::Value],
+    ignore: &[String],
+) -> (
+    Vec<serde_json::Value>,
+    Vec<serde_json::Value>,
+    Vec<serde_json::Value>,
+) {
+    let baseline_by_id: std::collections::HashMap<_, _> = baseline
+        .iter()
+        .map(|row| (row["id"].as_str(), row))
+        .collect();
+    let row_ids: std::collections::HashSet<_> = rows.iter().map(|row| row["id"].as_str()).collect();
+
+    let mut added = vec![];
+    let mut changed = vec![];
+    for row in rows {
+        let Some(baseline_row) = baseline_by_id.get(&row["id"].as_str()) else {
+            added.push(row.clone());
+            continue;
+        };
+        let mut changes = vec![];
+        diff_values(&mut vec![], baseline_row, row, ignore, &mut changes);
+        if !changes.is_empty() {
+            changed.push(json!({ "id": row["id"], "changes": changes }));
+        }
+    }
+    let removed = baseline
+        .iter()
+        .filter(|row| !row_ids.contains(&row["id"].as_str()))
+        .cloned()
+        .collect();
+
+    (added, removed, changed)
+}
+
+fn diff_values(
+    path: &mut Vec<String>,
+    baseline: &serde_json::Value,
+    current: &serde_json::Value,
+    ignore: &[String],
+    changes: &mut Vec<serde_json::Value>,
+) {
+    let segments: Vec<&str> = path.iter().map(String::as_str).collect();
+    if ignore
+        .iter()
+        .any(|pattern| ignore_matches(pattern, &segments))
+    {
+        return;
+    }
+
+    match (baseline, current) {
+        (serde_json::Value::Object(a), serde_json::Value::Object(b)) => {
+            let keys: std::collections::BTreeSet<_> = a.keys().chain(b.keys()).collect();
+            for key in keys {
+                path.push(key.clone());
+                diff_values(
+                    path,
+                    a.get(key).unwrap_or(&serde_json::Value::Null),
+                    b.get(key).unwrap_or(&serde_json::Value::Null),
+                    ignore,
+                    changes,
+                );
+                path.pop();
+            }
+        }
+        _ if baseline != current => changes.push(json!({
+            "path": path.join("."),
+            "baseline": baseline,
+            "current": current,
+        })),
+        _ => {}
+    }
+}
+
+/// Whether the ignore `pattern` matches the field at `path` or one of its parents.
+///
+/// A pattern without `.` matches the field of that name at any level, e.g. `content_mask`,
+/// otherwise it's matched from the row, e.g. `bounds.x`. `*` matches any characters within a
+/// name, and a `**` segment matches any number of names, e.g. `properties.*_at`, `**.width`.
+fn ignore_matches(pattern: &str, path: &[&str]) -> bool {
+    let pattern: Vec<&str> = pattern.split('.').collect();
+    if let [name] = pattern.as_slice() {
+        return path.iter().any(|segment| wildcard_match(name, segment));
+    }
+    (1..=path.len()).any(|len| segments_match(&pattern, &path[..len]))
+}
+
+fn segments_match(pattern: &[&str], path: &[&str]) -> bool {
+    match pattern.split_first() {
+        None => path.is_empty(),
+        Some((&"**", rest)) => (0..=path.len()).any(|i| segments_match(rest, &path[i..])),
+        Some((name, rest)) => path.split_first().is_some_and(|(segment, path)| {
+            wildcard_match(name, segment) && segments_match(rest, path)
+        }),
+    }
+}
+
+fn wildcard_match(pattern: &str, text: &str) -> bool {
+    match pattern.split_once('*') {
+        None => pattern == text,
+        Some((prefix, rest)) => {
+            let Some(text) = text.strip_prefix(prefix) else {
+                return false;
+            };
+            (0..=text.len())
+                .filter(|i| text.is_char_boundary(*i))
+                .any(|i| wildcard_match(rest, &text[i..]))
+        }
+    }
+}
+
 /// Build a hierarchical tree from GPUI's flat inspector element list.
 /// Uses dot-separated global_id as hierarchy key.
 /// Optimized: builds parent lookup via sorted prefix matching instead of O(n²) scan.
@@ -1039,6 +2578,9 @@ fn build_element_tree(
                     "height": px_to_f32(cm.size.height),
                 }),
             );
+            if let Some(semantic) = element_properties(window_id, &info.global_id) {
+                properties.extend(semantic);
+            }
 
             FlatEntry {
                 full_id: full_id.clone(),
@@ -1195,7 +2737,7 @@ fn handle_take_screenshot(
         .map_err(|e| format!("Failed to render screenshot: {}", e))?;
 
     // If element_id is set, resolve bounds and crop
-    let (final_image, element_info) = if let Some(ref element_id) = opts.element_id {
+    let (final_image, element_info, crop_origin) = if let Some(ref element_id) = opts.element_id {
         // Resolve element bounds (in logical pixels)
         let bounds_result = handle
             .update(cx, |_, window, _cx| {
@@ -1239,11 +2781,58 @@ fn handle_take_screenshot(
 
         use image::GenericImageView;
         let cropped = image.view(x, y, w, h).to_image();
-        (cropped, Some(resolved_id))
+        (cropped, Some(resolved_id), (x, y))
     } else {
-        (image, None)
+        (image, None, (0, 0))
     };
 
+    // Highlight the elements, numbered by the order of the queries. An element that can't be
+    // resolved is reported in its entry, without failing the screenshot.
+    let queries: Vec<String> = match params.get("highlight").and_then(|v| v.as_array()) {
+        Some(queries) => queries
+            .iter()
+            .filter_map(|query| query.as_str().map(String::from))
+            .collect(),
+        None => opts
+            .highlight_elements
+            .iter()
+            .map(|query| query.to_string())
+            .collect(),
+    };
+    let mut final_image = final_image;
+    let window_id = format!("{:?}", handle.window_id());
+    let highlighted_elements: Vec<serde_json::Value> = queries
+        .iter()
+        .enumerate()
+        .map(|(ix, query)| {
+            let index = ix + 1;
+            let element = match resolve_element(query, Some(&window_id), cx) {
+                Ok(element) => element,
+                Err(error) => return json!({ "index": index, "query": query, "error": error }),
+            };
+            let to_image = |v: Pixels| (f32::from(v) * scale_factor).round() as i64;
+            let x = to_image(element.bounds.origin.x) - crop_origin.0 as i64;
+            let y = to_image(element.bounds.origin.y) - crop_origin.1 as i64;
+            let width = to_image(element.bounds.size.width);
+            let height = to_image(element.bounds.size.height);
+            let [r, g, b] = HIGHLIGHT_COLORS[ix % HIGHLIGHT_COLORS.len()];
+            draw_highlight(
+                &mut final_image,
+                (x, y, width, height),
+                image::Rgba([r, g, b, 255]),
+                index,
+                (scale_factor.round() as i64).max(1),
+            );
+            json!({
+                "index": index,
+                "query": query,
+                "element_id": element.full_id,
+                "bounds": { "x": x, "y": y, "width": width, "height": height },
+                "color": format!("#{:02X}{:02X}{:02X}", r, g, b),
+            })
+        })
+        .collect();
+
     let (width, height) = final_image.dimensions();
 
     // Save as PNG to a temp file
@@ -1269,6 +2858,7 @@ fn handle_take_screenshot(
         "height": height,
         "format": "png",
         "path": temp_path.to_string_lossy(),
+        "highlighted_elements": highlighted_elements,
     });
     if let Some(id) = element_info {
         result
@@ -1278,6 +2868,229 @@ fn handle_take_screenshot(
     Ok(result)
 }
 
+/// Collect the `include` sections, [`DIAGNOSTIC_SECTIONS`] by default, into a bundle returned
+/// inline, or written with the screenshots to a new directory in `write_to`.
+///
+/// A failing section gets an `error` marker instead of failing the bundle, and a section over the
+/// `max_bytes` limit is skipped with a `skipped` marker.
+fn handle_diagnostic_bundle(
+    params: &serde_json::Value,
+    cx: &mut App,
+) -> Result<serde_json::Value, String> {
+    let write_to = params
+        .get("write_to")
+        .and_then(|v| v.as_str())
+        .map(std::path::PathBuf::from);
+    let include: Vec<String> = match params.get("include").and_then(|v| v.as_array()) {
+        Some(include) => include
+            .iter()
+            .filter_map(|section| section.as_str().map(String::from))
+            .collect(),
+        None => DIAGNOSTIC_SECTIONS.iter().map(|s| s.to_string()).collect(),
+    };
+    let max_bytes = params
+        .get("max_bytes")
+        .and_then(|v| v.as_u64())
+        .map_or(DIAGNOSTIC_MAX_BYTES, |max_bytes| max_bytes as usize);
+
+    let timestamp = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis();
+    let dir = write_to.map(|dir| dir.join(format!("gpui-diagnostics-{}", timestamp)));
+    if let Some(dir) = &dir {
+        std::fs::create_dir_all(dir).map_err(|e| format!("{}: {}", dir.display(), e))?;
+    }
+
+    let mut sections = serde_json::Map::new();
+    let mut total_bytes = 0;
+    for section in &include {
+        let value = match section.as_str() {
+            "logs" => handle_get_logs(),
+            "windows" => handle_get_windows(cx),
+            "tree" => diagnostic_trees(cx),
+            "theme" => handle_get_theme(&json!({}), cx).map(|theme| {
+                json!({
+                    "name": theme["name"],
+                    "mode": theme["mode"],
+                    "appearance": theme["appearance"],
+                })
+            }),
+            "perf" => Err("The performance stats are not collected".to_string()),
+            "screenshots" => match &dir {
+                Some(dir) => diagnostic_screenshots(dir, max_bytes.saturating_sub(total_bytes), cx),
+                None => Err("The screenshots require `write_to`".to_string()),
+            },
+            _ => Err(format!("Unknown section: {}", section)),
+        };
+        let value = value.unwrap_or_else(|error| json!({ "error": error }));
+
+        let mut bytes = serde_json::to_vec(&value).map_or(0, |json| json.len());
+        if section == "screenshots" {
+            bytes += value["bytes"].as_u64().unwrap_or(0) as usize;
+        }
+        if total_bytes + bytes > max_bytes {
+            sections.insert(
+                section.clone(),
+                json!({ "skipped": format!("Over the size limit of {} bytes", max_bytes) }),
+            );
+            continue;
+        }
+        total_bytes += bytes;
+        sections.insert(section.clone(), value);
+    }
+
+    let bundle = json!({
+        "timestamp": timestamp,
+        "pid": std::process::id(),
+        "sections": sections,
+    });
+    let Some(dir) = dir else {
+        return Ok(json!({ "success": true, "bytes": total_bytes, "bundle": bundle }));
+    };
+
+    let path = dir.join("bundle.json");
+    let content = serde_json::to_string_pretty(&bundle).map_err(|e| e.to_string())?;
+    std::fs::write(&path, content).map_err(|e| format!("{}: {}", path.display(), e))?;
+    mcp_log(format!("Diagnostic bundle written to {}", dir.display()));
+
+    Ok(json!({
+        "success": true,
+        "bytes": total_bytes,
+        "path": dir.to_string_lossy(),
+        "sections": include,
+    }))
+}
+
+/// The flat rows of every window, keyed by `window_id`.
+fn diagnostic_trees(cx: &mut App) -> Result<serde_json::Value, String> {
+    let mut trees = serde_json::Map::new();
+    for handle in cx.windows() {
+        let rows =
+            window_rows(handle, None, cx).unwrap_or_else(|error| vec![json!({ "error": error })]);
+        trees.insert(format!("{:?}", handle.window_id()), json!(rows));
+    }
+    Ok(serde_json::Value::Object(trees))
+}
+
+/// Save a downscaled screenshot of every window in `dir`, until the `max_bytes` are used.
+fn diagnostic_screenshots(
+    dir: &std::path::Path,
+    max_bytes: usize,
+    cx: &mut App,
+) -> Result<serde_json::Value, String> {
+    let mut screenshots = serde_json::Map::new();
+    let mut total_bytes = 0;
+    for handle in cx.windows() {
+        let window_id = format!("{:?}", handle.window_id());
+        let path = dir.join(format!("screenshot-{}.png", sanitize_app_name(&window_id)));
+        let screenshot = handle
+            .update(cx, |_, window, _| window.render_to_image())
+            .map_err(|e| e.to_string())
+            .and_then(|image| image.map_err(|e| e.to_string()))
+            .and_then(|image| {
+                let (width, height) = image.dimensions();
+                let scale = (DIAGNOSTIC_SCREENSHOT_SIZE as f32 / width.max(height) as f32).min(1.);
+                let width = ((width as f32 * scale) as u32).max(1);
+                let height = ((height as f32 * scale) as u32).max(1);
+                image::imageops::thumbnail(&image, width, height)
+                    .save(&path)
+                    .map_err(|e| e.to_string())?;
+                let bytes = std::fs::metadata(&path).map_or(0, |m| m.len() as usize);
+                if total_bytes + bytes > max_bytes {
+                    let _ = std::fs::remove_file(&path);
+                    return Err("Over the size limit".to_string());
+                }
+                total_bytes += bytes;
+                Ok(json!({
+                    "path": path.to_string_lossy(),
+                    "width": width,
+                    "height": height,
+                }))
+            });
+        let screenshot = screenshot.unwrap_or_else(|error| json!({ "error": error }));
+        screenshots.insert(window_id, screenshot);
+    }
+    Ok(json!({ "windows": screenshots, "bytes": total_bytes }))
+}
+
+/// The colors of the highlight rectangles of the screenshot, cycled by the index.
+const HIGHLIGHT_COLORS: [[u8; 3]; 6] = [
+    [0xE5, 0x39, 0x35],
+    [0x1E, 0x88, 0xE5],
+    [0x43, 0xA0, 0x47],
+    [0xFB, 0x8C, 0x00],
+    [0x8E, 0x24, 0xAA],
+    [0x00, 0xAC, 0xC1],
+];
+
+/// The 3x5 bitmaps of the digits of the highlight labels, the low 3 bits of each row.
+const DIGIT_BITMAPS: [[u8; 5]; 10] = [
+    [0b111, 0b101, 0b101, 0b101, 0b111],
+    [0b010, 0b110, 0b010, 0b010, 0b111],
+    [0b111, 0b001, 0b111, 0b100, 0b111],
+    [0b111, 0b001, 0b111, 0b001, 0b111],
+    [0b101, 0b101, 0b111, 0b001, 0b001],
+    [0b111, 0b100, 0b111, 0b001, 0b111],
+    [0b111, 0b100, 0b111, 0b101, 0b111],
+    [0b111, 0b001, 0b001, 0b001, 0b001],
+    [0b111, 0b101, 0b111, 0b101, 0b111],
+    [0b111, 0b101, 0b111, 0b001, 0b111],
+];
+
+/// Draw the outline of the `(x, y, width, height)` rect in image pixels, with the index label
+/// at its top left corner, clipped to the image.
+fn draw_highlight(
+    image: &mut image::RgbaImage,
+    (x, y, width, height): (i64, i64, i64, i64),
+    color: image::Rgba<u8>,
+    index: usize,
+    scale: i64,
+) {
+    let thickness = 2 * scale;
+    fill_rect(image, (x, y, width, thickness), color);
+    fill_rect(image, (x, y + height - thickness, width, thickness), color);
+    fill_rect(image, (x, y, thickness, height), color);
+    fill_rect(image, (x + width - thickness, y, thickness, height), color);
+
+    // A digit is 3x5 dots, with a dot of spacing and padding.
+    let dot = 2 * scale;
+    let digits = index.to_string();
+    let label_width = (digits.len() as i64 * 4 + 1) * dot;
+    fill_rect(image, (x, y, label_width, 7 * dot), color);
+    let white = image::Rgba([255, 255, 255, 255]);
+    for (i, digit) in digits.bytes().enumerate() {
+        let bitmap = DIGIT_BITMAPS[(digit - b'0') as usize];
+        for (row, bits) in bitmap.iter().enumerate() {
+            for col in 0..3 {
+                if bits & (0b100 >> col) != 0 {
+                    let dot_x = x + (1 + i as i64 * 4 + col) * dot;
+                    let dot_y = y + (1 + row as i64) * dot;
+                    fill_rect(image, (dot_x, dot_y, dot, dot), white);
+                }
+            }
+        }
+    }
+}
+
+/// Fill the `(x, y, width, height)` rect in image pixels, clipped to the image.
+fn fill_rect(
+    image: &mut image::RgbaImage,
+    (x, y, width, height): (i64, i64, i64, i64),
+    color: image::Rgba<u8>,
+) {
+    let (image_width, image_height) = image.dimensions();
+    let x0 = x.clamp(0, image_width as i64) as u32;
+    let y0 = y.clamp(0, image_height as i64) as u32;
+    let x1 = (x + width).clamp(0, image_width as i64) as u32;
+    let y1 = (y + height).clamp(0, image_height as i64) as u32;
+    for py in y0..y1 {
+        for px in x0..x1 {
+            image.put_pixel(px, py, color);
+        }
+    }
+}
+
 fn handle_execute_action(
     params: &serde_json::Value,
     cx: &mut App,
@@ -1296,7 +3109,7 @@ fn handle_execute_action(
         .build_action(&opts.action, action_data)
         .map_err(|e| format!("Failed to build action '{}': {:?}", opts.action, e))?;
 
-    let handle = resolve_window(opts.window_id.as_deref(), cx)?;
+    let handle = resolve_input_window(opts.window_id.as_deref(), None, activate_param(params), cx)?;
 
     // Use FocusHandle::dispatch_action (synchronous) when the window has
     // a focused element — Window::dispatch_action uses cx.defer() which
@@ -1517,6 +3330,7 @@ fn handle_get_focus_info(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use gpui::ParentElement as _;
 
     #[test]
     fn sanitize_passes_valid_chars_through() {
@@ -1544,4 +3358,572 @@ mod tests {
         let pid = std::process::id();
         assert!(path.ends_with(&format!("gpui-mcp-elane-{}.sock", pid)));
     }
+
+    struct SwitchView;
+
+    impl gpui::Render for SwitchView {
+        fn render(
+            &mut self,
+            _: &mut gpui::Window,
+            _: &mut gpui::Context<Self>,
+        ) -> impl gpui::IntoElement {
+            gpui::div()
+                .child(
+                    crate::switch::Switch::new("wifi")
+                        .label("Wi-Fi")
+                        .checked(true),
+                )
+                .child(
+                    crate::switch::Switch::new("bluetooth")
+                        .description("Allow nearby devices to connect.")
+                        .disabled(true)
+                        .disabled_reason("Requires the admin permission."),
+                )
+        }
+    }
+
+    #[gpui::test]
+    fn get_element_returns_switch_properties(cx: &mut gpui::TestAppContext) {
+        cx.update(|cx| cx.set_global(crate::Theme::default()));
+        let (_, cx) = cx.add_window_view(|_, _| SwitchView);
+        cx.run_until_parked();
+
+        let element = cx
+            .update(|_, cx| handle_get_element(&json!({ "element_id": "wifi" }), cx))
+            .unwrap();
+        let properties = &element["properties"];
+        assert_eq!(properties["role"], "switch");
+        assert_eq!(properties["checked"], true);
+        assert_eq!(properties["disabled"], false);
+        assert_eq!(properties["label"], "Wi-Fi");
+
+        let element = cx
+            .update(|_, cx| handle_get_element(&json!({ "element_id": "bluetooth" }), cx))
+            .unwrap();
+        let properties = &element["properties"];
+        assert_eq!(properties["disabled"], true);
+        assert_eq!(
+            properties["description"],
+            "Allow nearby devices to connect. Requires the admin permission."
+        );
+    }
+
+    struct ToggleView;
+
+    impl gpui::Render for ToggleView {
+        fn render(
+            &mut self,
+            _: &mut gpui::Window,
+            _: &mut gpui::Context<Self>,
+        ) -> impl gpui::IntoElement {
+            gpui::div()
+                .child(crate::switch::Switch::new("sync").default_checked(false))
+                .child(
+                    crate::switch::Switch::new("locked")
+                        .checked(false)
+                        .disabled(true),
+                )
+        }
+    }
+
+    #[gpui::test]
+    fn set_toggle_clicks_only_when_changed(cx: &mut gpui::TestAppContext) {
+        use futures::FutureExt as _;
+
+        cx.update(|cx| cx.set_global(crate::Theme::default()));
+        let (_, cx) = cx.add_window_view(|_, _| ToggleView);
+        cx.run_until_parked();
+
+        let set_toggle = |element_id: &str, checked: bool, cx: &mut gpui::VisualTestContext| {
+            let params = json!({ "element_id": element_id, "checked": checked });
+            let task =
+                cx.update(|_, cx| cx.spawn(async move |cx| handle_set_toggle(&params, cx).await));
+            for _ in 0..4 {
+                cx.executor().advance_clock(SET_TOGGLE_INTERVAL);
+                cx.run_until_parked();
+            }
+            task.now_or_never().expect("set_toggle is done")
+        };
+
+        let result = set_toggle("sync", true, cx).unwrap();
+        assert_eq!(result["before"], false);
+        assert_eq!(result["after"], true);
+        assert_eq!(result["clicked"], true);
+        assert_eq!(result["success"], true);
+
+        // Already checked, no click is needed.
+        let result = set_toggle("sync", true, cx).unwrap();
+        assert_eq!(result["before"], true);
+        assert_eq!(result["clicked"], false);
+
+        assert!(set_toggle("locked", true, cx).is_err());
+    }
+
+    #[gpui::test]
+    fn click_element_reports_attempts_and_receiver(cx: &mut gpui::TestAppContext) {
+        use futures::FutureExt as _;
+
+        cx.update(|cx| cx.set_global(crate::Theme::default()));
+        let (_, cx) = cx.add_window_view(|_, _| ToggleView);
+        cx.run_until_parked();
+
+        let params = json!({ "element_id": "sync", "retries": 2 });
+        let task =
+            cx.update(|_, cx| cx.spawn(async move |cx| handle_click_element(&params, cx).await));
+        cx.run_until_parked();
+        let result = task.now_or_never().expect("click is done").unwrap();
+        assert_eq!(result["hit"], true);
+        assert_eq!(result["attempts"], 1);
+        let received = result["received_element"].as_str().unwrap();
+        assert!(received.contains("sync"), "{received}");
+
+        cx.run_until_parked();
+        let element = cx
+            .update(|_, cx| resolve_element("sync", None, cx))
+            .ok()
+            .unwrap();
+        assert_eq!(toggle_state(&element), Ok(true));
+    }
+
+    #[gpui::test]
+    fn hidden_window_requires_window_id(cx: &mut gpui::TestAppContext) {
+        cx.update(|cx| cx.set_global(crate::Theme::default()));
+        // The hidden window isn't the first window, the ids are shared with the other tests.
+        cx.add_window_view(|_, _| SwitchView);
+        let (_, hidden) = cx.add_window_view(|_, _| ToggleView);
+        hidden.run_until_parked();
+        let hidden_id = hidden.update(|window, _| {
+            mcp_set_window_visible(window, false);
+            format!("{:?}", window.window_handle().window_id())
+        });
+
+        hidden.update(|_, cx| {
+            let windows = handle_get_windows(cx).unwrap();
+            let window = windows
+                .as_array()
+                .unwrap()
+                .iter()
+                .find(|window| window["id"] == hidden_id.as_str())
+                .unwrap();
+            assert_eq!(window["visible"], false);
+
+            // Found in the hidden window, but not dispatched into it implicitly.
+            let element = resolve_element("sync", None, cx).unwrap();
+            assert_eq!(element.window_id, hidden_id);
+            let err = resolve_input_window(None, Some(element.window), false, cx).unwrap_err();
+            assert!(err.contains("is hidden"), "{err}");
+            assert!(
+                resolve_input_window(Some(&hidden_id), Some(element.window), false, cx).is_ok()
+            );
+
+            let err = resolve_element("sync", Some("missing"), cx).err().unwrap();
+            assert!(err.starts_with("Window not found"), "{err}");
+        });
+
+        set_window_visible(&hidden_id, true);
+    }
+
+    struct ActivationView {
+        focus_handle: gpui::FocusHandle,
+        activations: usize,
+        _subscription: gpui::Subscription,
+    }
+
+    impl gpui::Render for ActivationView {
+        fn render(
+            &mut self,
+            _: &mut gpui::Window,
+            _: &mut gpui::Context<Self>,
+        ) -> impl gpui::IntoElement {
+            use gpui::InteractiveElement as _;
+
+            gpui::div().track_focus(&self.focus_handle)
+        }
+    }
+
+    #[gpui::test]
+    fn simulate_deactivate_blurs_and_restores_focus(cx: &mut gpui::TestAppContext) {
+        let (view, cx) = cx.add_window_view(|window, cx| ActivationView {
+            focus_handle: cx.focus_handle(),
+            activations: 0,
+            _subscription: crate::window_ext::observe_window_active(window, cx, |this, _, _| {
+                this.activations += 1;
+            }),
+        });
+        cx.update(|window, cx| view.read(cx).focus_handle.clone().focus(window, cx));
+        cx.run_until_parked();
+        let window_id = cx.update(|window, _| format!("{:?}", window.window_handle().window_id()));
+
+        let result = cx
+            .update(|_, cx| simulate_deactivate(&json!({ "window_id": window_id }), cx))
+            .unwrap();
+        assert_eq!(result["active"], false);
+        cx.run_until_parked();
+        cx.update(|window, cx| {
+            assert!(!window.window_active(cx));
+            assert!(!view.read(cx).focus_handle.is_focused(window));
+            assert_eq!(view.read(cx).activations, 1);
+        });
+
+        cx.update(|_, cx| {
+            simulate_deactivate(&json!({ "window_id": window_id, "reactivate": true }), cx)
+        })
+        .unwrap();
+        cx.run_until_parked();
+        cx.update(|window, cx| {
+            assert_eq!(window.window_active(cx), window.is_window_active());
+            assert!(view.read(cx).focus_handle.is_focused(window));
+            assert_eq!(view.read(cx).activations, 2);
+        });
+    }
+
+    #[test]
+    fn trace_redacts_and_truncates_payloads() {
+        let path = std::env::temp_dir().join(format!("gpui-mcp-trace-{}.log", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let tracer = Tracer {
+            path: Some(path.clone()),
+            enabled: true,
+            payload_limit: 8,
+            max_file_size: TRACE_MAX_FILE_SIZE,
+        };
+
+        let request =
+            json!({ "id": "1", "params": { "auth_token": "s3cr3t", "data": "0123456789" } });
+        tracer.trace(3, "request", &request.to_string()).unwrap();
+        tracer.trace(3, "response", "not json, but long").unwrap();
+
+        let content = std::fs::read_to_string(&path).unwrap();
+        let lines: Vec<serde_json::Value> = content
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0]["connection"], 3);
+        assert_eq!(lines[0]["direction"], "request");
+        let params = &lines[0]["payload"]["params"];
+        assert_eq!(params["auth_token"], "<redacted>");
+        assert_eq!(params["data"], "01234567...<2 bytes truncated>");
+        assert_eq!(lines[1]["payload"], "not json...<10 bytes truncated>");
+        assert!(!content.contains("s3cr3t"));
+
+        // Rotated when the file is over the max size.
+        let tracer = Tracer {
+            max_file_size: 1,
+            ..tracer
+        };
+        tracer.trace(4, "request", "{}").unwrap();
+        let mut rotated = path.clone().into_os_string();
+        rotated.push(".1");
+        assert_eq!(std::fs::read_to_string(&rotated).unwrap(), content);
+        assert_eq!(std::fs::read_to_string(&path).unwrap().lines().count(), 1);
+
+        let _ = std::fs::remove_file(&path);
+        let _ = std::fs::remove_file(&rotated);
+    }
+
+    #[test]
+    fn draw_highlight_outlines_and_labels() {
+        let background = image::Rgba([0, 0, 0, 255]);
+        let color = image::Rgba([255, 0, 0, 255]);
+        let mut image = image::RgbaImage::from_pixel(40, 40, background);
+        draw_highlight(&mut image, (10, 10, 20, 20), color, 1, 1);
+
+        assert_eq!(*image.get_pixel(29, 20), color);
+        assert_eq!(*image.get_pixel(20, 29), color);
+        assert_eq!(*image.get_pixel(20, 20), background);
+        assert_eq!(*image.get_pixel(9, 9), background);
+        // The top dot of the `1` in the label, in the middle column.
+        let white = image::Rgba([255, 255, 255, 255]);
+        assert_eq!(*image.get_pixel(10 + 2 * 2, 10 + 2), white);
+
+        // Clipped to the image.
+        draw_highlight(&mut image, (30, 30, 20, 20), color, 12, 1);
+        assert_eq!(*image.get_pixel(30, 39), color);
+    }
+
+    #[test]
+    fn ignore_patterns_match_wildcards() {
+        assert!(ignore_matches("bounds", &["bounds", "x"]));
+        assert!(ignore_matches(
+            "content_mask",
+            &["properties", "content_mask"]
+        ));
+        assert!(ignore_matches("bounds.x", &["bounds", "x"]));
+        assert!(!ignore_matches("bounds.x", &["bounds", "y"]));
+        assert!(ignore_matches(
+            "properties.*_at",
+            &["properties", "updated_at"]
+        ));
+        assert!(!ignore_matches("properties.*_at", &["properties", "label"]));
+        assert!(ignore_matches("**.width", &["bounds", "width"]));
+        assert!(ignore_matches("*time*", &["properties", "timestamp"]));
+    }
+
+    #[test]
+    fn element_track_caps_samples() {
+        let mut track = ElementTrack {
+            query: "switch".into(),
+            started: std::time::Instant::now(),
+            max_samples: 2,
+            force_frames: false,
+            stopped: false,
+            samples: vec![],
+        };
+        let bounds = gpui::Bounds::new(point(px(10.), px(20.)), gpui::size(px(30.), px(40.)));
+
+        assert!(track.record(Some(bounds), Some(json!(0.5))));
+        assert!(!track.record(None, None));
+        assert!(!track.record(Some(bounds), None));
+        assert_eq!(track.samples.len(), 2);
+        assert_eq!(track.samples[0]["frame"], 0);
+        assert_eq!(track.samples[0]["opacity"], 0.5);
+        assert_eq!(track.samples[0]["bounds"]["x"], 10.);
+        assert!(track.samples[1]["bounds"].is_null());
+        assert!(track.samples[1].get("opacity").is_none());
+
+        let mut track = ElementTrack {
+            max_samples: 10,
+            stopped: true,
+            samples: vec![],
+            ..track
+        };
+        assert!(!track.record(Some(bounds), None));
+        assert!(track.samples.is_empty());
+    }
+
+    #[test]
+    fn profiles_restrict_methods_by_token() {
+        let config = McpConfig::new("app")
+            .profile("ci", McpProfile::methods([methods::GET_WINDOWS, SET_TOGGLE]))
+            .token("agent", "read_only")
+            .token("ci-token", "ci")
+            .token("broken", "missing");
+        let permissions = &config.permissions;
+
+        // Read-only until authenticated once a token is configured.
+        let profile = permissions.default_profile();
+        assert_eq!(profile.name, "read_only");
+        assert!(profile.check(methods::TAKE_SCREENSHOT).is_ok());
+        let err = profile.check(methods::CLICK_ELEMENT).unwrap_err();
+        assert!(err.starts_with("forbidden:"));
+        assert!(profile.check(AUTHENTICATE).is_ok());
+        assert!(profile.check(GET_CAPABILITIES).is_ok());
+        let capabilities = profile.capabilities();
+        assert_eq!(
+            capabilities["methods"].as_array().unwrap().len(),
+            READ_ONLY_METHODS.len()
+        );
+
+        let profile = permissions.authenticate("ci-token").unwrap();
+        assert_eq!(profile.name, "ci");
+        assert!(profile.check(SET_TOGGLE).is_ok());
+        assert!(profile.check(methods::GET_ELEMENT).is_err());
+        assert!(profile.check("my_app_method").is_err());
+        assert_eq!(
+            profile.capabilities()["methods"],
+            json!([methods::GET_WINDOWS, SET_TOGGLE])
+        );
+
+        assert_eq!(
+            permissions.authenticate("wrong").unwrap_err(),
+            "forbidden: invalid token"
+        );
+        assert!(permissions.authenticate("broken").is_err());
+
+        // All the methods without tokens.
+        let profile = Permissions::default().default_profile();
+        assert_eq!(profile.name, "full");
+        assert!(profile.check(SET_TRACING).is_ok());
+        assert_eq!(
+            profile.capabilities()["methods"].as_array().unwrap().len(),
+            METHODS.len()
+        );
+    }
+
+    struct SnapshotView {
+        checked: bool,
+    }
+
+    impl gpui::Render for SnapshotView {
+        fn render(
+            &mut self,
+            _: &mut gpui::Window,
+            _: &mut gpui::Context<Self>,
+        ) -> impl gpui::IntoElement {
+            gpui::div().child(crate::switch::Switch::new("wifi").checked(self.checked))
+        }
+    }
+
+    #[gpui::test]
+    fn assert_tree_snapshot_diffs_baseline(cx: &mut gpui::TestAppContext) {
+        cx.update(|cx| cx.set_global(crate::Theme::default()));
+        let (view, cx) = cx.add_window_view(|_, _| SnapshotView { checked: true });
+        cx.run_until_parked();
+
+        let dir = std::env::temp_dir().join(format!("gpui-mcp-snapshot-{}", std::process::id()));
+        let window_id = cx.update(|window, _| format!("{:?}", window.window_handle().window_id()));
+        let assert_snapshot = |params: serde_json::Value, cx: &mut gpui::VisualTestContext| {
+            let mut params = params;
+            params["name"] = json!("switch");
+            params["window_id"] = json!(window_id);
+            cx.update(|_, cx| assert_tree_snapshot(&params, &dir, cx))
+        };
+
+        let err = assert_snapshot(json!({}), cx).unwrap_err();
+        assert!(err.starts_with("No baseline"), "{err}");
+
+        let result = assert_snapshot(json!({ "update": true }), cx).unwrap();
+        assert_eq!(result["updated"], true);
+        let result = assert_snapshot(json!({}), cx).unwrap();
+        assert_eq!(result["passed"], true);
+
+        cx.update(|_, cx| {
+            view.update(cx, |view, cx| {
+                view.checked = false;
+                cx.notify();
+            })
+        });
+        cx.run_until_parked();
+
+        let result = assert_snapshot(json!({}), cx).unwrap();
+        assert_eq!(result["success"], false);
+        let changes = result["changed"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .flat_map(|row| row["changes"].as_array().unwrap().clone())
+            .collect::<Vec<_>>();
+        assert!(
+            changes
+                .iter()
+                .any(|change| change["path"] == "properties.checked" && change["current"] == false)
+        );
+
+        let result = assert_snapshot(
+            json!({ "ignore": ["bounds", "content_mask", "properties.check*"] }),
+            cx,
+        )
+        .unwrap();
+        assert_eq!(result["passed"], true, "{result}");
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[gpui::test]
+    fn diagnostic_bundle_marks_failed_sections(cx: &mut gpui::TestAppContext) {
+        cx.update(|cx| cx.set_global(crate::Theme::default()));
+        let (_, cx) = cx.add_window_view(|_, _| SwitchView);
+        cx.run_until_parked();
+
+        let params = json!({ "include": ["windows", "tree", "screenshots", "unknown"] });
+        let result = cx
+            .update(|_, cx| handle_diagnostic_bundle(&params, cx))
+            .unwrap();
+        let sections = &result["bundle"]["sections"];
+        assert!(sections["windows"].is_array());
+        let trees = sections["tree"].as_object().unwrap();
+        assert!(trees.values().any(|rows| {
+            rows.as_array()
+                .unwrap()
+                .iter()
+                .any(|row| row["properties"]["label"] == "Wi-Fi")
+        }));
+        assert!(sections["screenshots"]["error"].is_string());
+        assert!(sections["unknown"]["error"].is_string());
+
+        let params = json!({ "include": ["logs", "tree"], "max_bytes": 64 });
+        let result = cx
+            .update(|_, cx| handle_diagnostic_bundle(&params, cx))
+            .unwrap();
+        let sections = &result["bundle"]["sections"];
+        assert!(sections["tree"]["skipped"].is_string());
+        assert!(result["bytes"].as_u64().unwrap() <= 64);
+    }
+
+    #[gpui::test]
+    fn get_app_state_reports_focus_and_modal(cx: &mut gpui::TestAppContext) {
+        use gpui::AppContext as _;
+
+        cx.update(crate::init);
+        let (_, cx) =
+            cx.add_window_view(|window, cx| crate::Root::new(cx.new(|_| SwitchView), window, cx));
+        cx.run_until_parked();
+        let window_id = cx.update(|window, _| format!("{:?}", window.window_handle().window_id()));
+        let window_state = |cx: &mut gpui::VisualTestContext| {
+            let state = cx.update(|_, cx| handle_get_app_state(cx)).unwrap();
+            state["windows"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .find(|window| window["id"] == window_id.as_str())
+                .unwrap()
+                .clone()
+        };
+
+        let state = window_state(cx);
+        assert_eq!(state["focused_element"], serde_json::Value::Null);
+        assert_eq!(state["modal_active"], false);
+        assert_eq!(state["dragging"], false);
+
+        cx.update(|window, cx| window.focus_next(cx));
+        cx.run_until_parked();
+        assert_eq!(window_state(cx)["focused_element"], "wifi");
+
+        cx.update(|window, cx| window.open_dialog(cx, |dialog, _, _| dialog));
+        cx.run_until_parked();
+        let state = window_state(cx);
+        assert_eq!(state["modal_active"], true);
+        assert_eq!(state["overlay_active"], true);
+    }
+
+    #[gpui::test]
+    fn get_theme_returns_mode_and_overrides(cx: &mut gpui::TestAppContext) {
+        cx.update(|cx| {
+            crate::theme::init(cx);
+            crate::set_appearance(crate::Appearance::Dark, cx);
+            crate::set_overrides(
+                crate::ThemeOverrides::new().color("switch.background", gpui::red()),
+                cx,
+            );
+
+            let theme = handle_get_theme(&json!({}), cx).unwrap();
+            assert_eq!(theme["mode"], "dark");
+            assert_eq!(theme["appearance"], "dark");
+            assert_eq!(theme["colors"]["switch.background"], "#FF0000");
+            assert_eq!(theme["overrides"]["switch.background"]["scope"], "global");
+            assert!(theme["overrides"].get("title_bar.background").is_none());
+            let name = crate::Theme::global(cx).theme_name().to_string();
+            assert_eq!(theme["name"], name);
+        });
+    }
+    #[gpui::test]
+    fn set_density_updates_global_and_window(cx: &mut gpui::TestAppContext) {
+        cx.update(crate::init);
+        let cx = cx.add_empty_window();
+        let window_id = cx.update(|window, _| format!("{:?}", window.window_handle().window_id()));
+
+        cx.update(|_, cx| {
+            assert_eq!(
+                handle_get_theme(&json!({}), cx).unwrap()["density"],
+                "comfortable"
+            );
+            assert!(handle_set_density(&json!({ "density": "dense" }), cx).is_err());
+
+            let result = handle_set_density(&json!({ "density": "compact" }), cx).unwrap();
+            assert_eq!(result["scope"], "global");
+            assert_eq!(crate::density(cx), crate::ComponentDensity::Compact);
+
+            let params = json!({ "density": "comfortable", "window_id": window_id });
+            let result = handle_set_density(&params, cx).unwrap();
+            assert_eq!(result["scope"], "window");
+            let theme = handle_get_theme(&json!({ "window_id": window_id }), cx).unwrap();
+            assert_eq!(theme["density"], "comfortable");
+
+            // A null density follows the global density again.
+            let params = json!({ "density": null, "window_id": window_id });
+            let result = handle_set_density(&params, cx).unwrap();
+            assert_eq!(result["density"], "compact");
+        });
+    }
 }
@@ -1,16 +1,21 @@
+use std::{collections::HashMap, rc::Rc};
+
 use crate::{
-    Selectable, Sizable,
-    actions::{Cancel, SelectLeft, SelectRight},
+    ActiveTheme as _, FocusRingStyle, FocusableExt as _, InteractiveElementExt as _, Selectable,
+    Sizable,
+    actions::{Cancel, Confirm, SelectDown, SelectLeft, SelectRight},
     button::{Button, ButtonVariants},
     global_state::GlobalState,
     h_flex,
-    menu::PopupMenu,
+    kbd::Kbd,
+    menu::{PopupMenu, PopupMenuItem},
 };
 use gpui::{
     App, AppContext as _, ClickEvent, Context, DismissEvent, Entity, FocusHandle, Focusable,
-    InteractiveElement as _, IntoElement, KeyBinding, MouseButton, OwnedMenu, ParentElement,
-    Render, SharedString, StatefulInteractiveElement, Styled, Subscription, Window, anchored,
-    deferred, div, prelude::FluentBuilder, px,
+    Global, InteractiveElement as _, IntoElement, KeyBinding, Keystroke, MouseButton, OwnedMenu,
+    OwnedMenuItem, ParentElement, Render, SharedString, StatefulInteractiveElement, Styled,
+    Subscription, WeakEntity, Window, WindowId, anchored, deferred, div, prelude::FluentBuilder,
+    px,
 };
 
 const CONTEXT: &str = "AppMenuBar";
@@ -19,13 +24,127 @@ pub fn init(cx: &mut App) {
         KeyBinding::new("escape", Cancel, Some(CONTEXT)),
         KeyBinding::new("left", SelectLeft, Some(CONTEXT)),
         KeyBinding::new("right", SelectRight, Some(CONTEXT)),
+        KeyBinding::new("down", SelectDown, Some(CONTEXT)),
+        KeyBinding::new("enter", Confirm { secondary: false }, Some(CONTEXT)),
+        KeyBinding::new("space", Confirm { secondary: false }, Some(CONTEXT)),
     ]);
 }
 
+/// A menu of the [`crate::TitleBar::menu_bar`].
+#[derive(Clone)]
+pub struct TitleBarMenu {
+    label: SharedString,
+    items: Vec<TitleBarMenuItem>,
+}
+
+impl TitleBarMenu {
+    /// Create a new menu with the label of the root button.
+    pub fn new(label: impl Into<SharedString>) -> Self {
+        Self {
+            label: label.into(),
+            items: Vec::new(),
+        }
+    }
+
+    /// Add a menu item.
+    pub fn item(mut self, item: TitleBarMenuItem) -> Self {
+        self.items.push(item);
+        self
+    }
+
+    /// Add menu items.
+    pub fn items(mut self, items: impl IntoIterator<Item = TitleBarMenuItem>) -> Self {
+        self.items.extend(items);
+        self
+    }
+}
+
+/// An item of the [`TitleBarMenu`], it can be nested by [`TitleBarMenuItem::submenu`].
+#[derive(Clone)]
+pub struct TitleBarMenuItem {
+    label: SharedString,
+    keystroke: Option<SharedString>,
+    disabled: bool,
+    separator: bool,
+    on_click: Option<Rc<dyn Fn(&ClickEvent, &mut Window, &mut App)>>,
+    children: Vec<TitleBarMenuItem>,
+}
+
+impl TitleBarMenuItem {
+    /// Create a new menu item with the label.
+    pub fn new(label: impl Into<SharedString>) -> Self {
+        Self {
+            label: label.into(),
+            keystroke: None,
+            disabled: false,
+            separator: false,
+            on_click: None,
+            children: Vec::new(),
+        }
+    }
+
+    /// Create a separator item.
+    pub fn separator() -> Self {
+        Self {
+            separator: true,
+            ..Self::new("")
+        }
+    }
+
+    /// Set the keystroke hint shown on the right side, e.g. `"ctrl-s"`.
+    ///
+    /// This is only a hint, the key binding must be registered by the app.
+    pub fn keystroke(mut self, keystroke: impl Into<SharedString>) -> Self {
+        self.keystroke = Some(keystroke.into());
+        self
+    }
+
+    /// Set the disabled state, default is false.
+    pub fn disabled(mut self, disabled: bool) -> Self {
+        self.disabled = disabled;
+        self
+    }
+
+    /// Set the callback when the item is activated.
+    pub fn on_click(mut self, f: impl Fn(&ClickEvent, &mut Window, &mut App) + 'static) -> Self {
+        self.on_click = Some(Rc::new(f));
+        self
+    }
+
+    /// Set the items of the submenu.
+    pub fn submenu(mut self, items: impl IntoIterator<Item = TitleBarMenuItem>) -> Self {
+        self.children = items.into_iter().collect();
+        self
+    }
+}
+
+/// The menu bars rendered by the TitleBar, per window, for the Alt key to focus.
+#[derive(Default)]
+struct TitleBarMenuBars(HashMap<WindowId, WeakEntity<AppMenuBar>>);
+
+impl Global for TitleBarMenuBars {}
+
+/// Toggle the keyboard focus of the TitleBar menu bar in the window, used when Alt is released.
+pub(crate) fn toggle_title_bar_menu_focus(window: &mut Window, cx: &mut App) {
+    let window_id = window.window_handle().window_id();
+    let Some(menu_bar) = cx
+        .try_global::<TitleBarMenuBars>()
+        .and_then(|bars| bars.0.get(&window_id))
+        .and_then(|bar| bar.upgrade())
+    else {
+        return;
+    };
+
+    menu_bar.update(cx, |menu_bar, cx| menu_bar.toggle_focus(window, cx));
+}
+
 /// The application menu bar, for Windows and Linux.
 pub struct AppMenuBar {
+    focus_handle: FocusHandle,
     menus: Vec<Entity<AppMenu>>,
     selected_index: Option<usize>,
+    /// The root focused by keyboard (Alt) while no menu is open.
+    focused_index: Option<usize>,
     action_context: Option<FocusHandle>,
 }
 
@@ -33,16 +152,53 @@ impl AppMenuBar {
     /// Create a new app menu bar.
     pub fn new(cx: &mut App) -> Entity<Self> {
         cx.new(|cx| {
-            let mut this = Self {
-                selected_index: None,
-                action_context: None,
-                menus: Vec::new(),
-            };
+            let mut this = Self::empty(cx);
             this.reload(cx);
             this
         })
     }
 
+    fn empty(cx: &mut Context<Self>) -> Self {
+        Self {
+            focus_handle: cx.focus_handle(),
+            selected_index: None,
+            focused_index: None,
+            action_context: None,
+            menus: Vec::new(),
+        }
+    }
+
+    /// Returns the menu bar of the TitleBar in this window, updated with the menus.
+    pub(crate) fn title_bar(
+        menus: Vec<TitleBarMenu>,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> Entity<Self> {
+        let menu_bar = window.use_keyed_state("title-bar-menu-bar", cx, |_, cx| Self::empty(cx));
+        menu_bar.update(cx, |menu_bar, cx| menu_bar.set_menus(menus, cx));
+
+        let window_id = window.window_handle().window_id();
+        if !cx
+            .default_global::<TitleBarMenuBars>()
+            .0
+            .contains_key(&window_id)
+        {
+            // Drop the menu bars of the closed windows when a new window is rendered.
+            let open_windows = cx
+                .windows()
+                .iter()
+                .map(|window| window.window_id())
+                .collect::<Vec<_>>();
+            cx.global_mut::<TitleBarMenuBars>()
+                .0
+                .retain(|window_id, _| open_windows.contains(window_id));
+        }
+        cx.global_mut::<TitleBarMenuBars>()
+            .0
+            .insert(window_id, menu_bar.downgrade());
+        menu_bar
+    }
+
     /// Reload the menus from the app.
     pub fn reload(&mut self, cx: &mut Context<Self>) {
         let menu_bar = cx.entity();
@@ -54,37 +210,111 @@ impl AppMenuBar {
         self.menus = menus
             .iter()
             .enumerate()
-            .map(|(ix, menu)| AppMenu::new(ix, menu, menu_bar.clone(), cx))
+            .map(|(ix, menu)| {
+                let items = AppMenuItems::App(menu.items.clone());
+                AppMenu::new(ix, menu.name.clone(), items, menu_bar.clone(), cx)
+            })
             .collect();
         self.selected_index = None;
+        self.focused_index = None;
         self.action_context = None;
         cx.notify();
     }
 
-    fn on_move_left(&mut self, _: &SelectLeft, window: &mut Window, cx: &mut Context<Self>) {
-        let Some(selected_index) = self.selected_index else {
+    /// Set the menus of the TitleBar, the open menu is kept if the roots are unchanged.
+    fn set_menus(&mut self, menus: Vec<TitleBarMenu>, cx: &mut Context<Self>) {
+        let unchanged = self.menus.len() == menus.len()
+            && self
+                .menus
+                .iter()
+                .zip(&menus)
+                .all(|(app_menu, menu)| app_menu.read(cx).name == menu.label);
+        if unchanged {
+            for (app_menu, menu) in self.menus.iter().zip(menus) {
+                app_menu.update(cx, |app_menu, _| {
+                    app_menu.items = AppMenuItems::TitleBar(menu.items);
+                });
+            }
             return;
-        };
+        }
+
+        let menu_bar = cx.entity();
+        self.menus = menus
+            .into_iter()
+            .enumerate()
+            .map(|(ix, menu)| {
+                let items = AppMenuItems::TitleBar(menu.items);
+                AppMenu::new(ix, menu.label, items, menu_bar.clone(), cx)
+            })
+            .collect();
+        self.selected_index = None;
+        self.focused_index = None;
+        cx.notify();
+    }
+
+    /// Focus the first root for keyboard navigation, or give the focus back if already focused.
+    fn toggle_focus(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        if self.menus.is_empty() {
+            return;
+        }
+
+        if self.selected_index.is_some() || self.focused_index.is_some() {
+            self.on_cancel(&Cancel, window, cx);
+            return;
+        }
 
-        let new_ix = if selected_index == 0 {
+        self.action_context = window.focused(cx);
+        self.focused_index = Some(0);
+        self.focus_handle.focus(window, cx);
+        cx.notify();
+    }
+
+    fn step_index(&self, ix: usize, forward: bool) -> usize {
+        if forward {
+            if ix + 1 >= self.menus.len() {
+                0
+            } else {
+                ix + 1
+            }
+        } else if ix == 0 {
             self.menus.len().saturating_sub(1)
         } else {
-            selected_index.saturating_sub(1)
-        };
-        self.set_selected_index(Some(new_ix), window, cx);
+            ix - 1
+        }
+    }
+
+    fn on_move_left(&mut self, _: &SelectLeft, window: &mut Window, cx: &mut Context<Self>) {
+        if let Some(selected_index) = self.selected_index {
+            let new_ix = self.step_index(selected_index, false);
+            self.set_selected_index(Some(new_ix), window, cx);
+        } else if let Some(focused_index) = self.focused_index {
+            self.focused_index = Some(self.step_index(focused_index, false));
+            cx.notify();
+        }
     }
 
     fn on_move_right(&mut self, _: &SelectRight, window: &mut Window, cx: &mut Context<Self>) {
-        let Some(selected_index) = self.selected_index else {
-            return;
-        };
+        if let Some(selected_index) = self.selected_index {
+            let new_ix = self.step_index(selected_index, true);
+            self.set_selected_index(Some(new_ix), window, cx);
+        } else if let Some(focused_index) = self.focused_index {
+            self.focused_index = Some(self.step_index(focused_index, true));
+            cx.notify();
+        }
+    }
 
-        let new_ix = if selected_index + 1 >= self.menus.len() {
-            0
-        } else {
-            selected_index + 1
-        };
-        self.set_selected_index(Some(new_ix), window, cx);
+    fn on_open(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        if let Some(focused_index) = self.focused_index {
+            self.set_selected_index(Some(focused_index), window, cx);
+        }
+    }
+
+    fn on_confirm(&mut self, _: &Confirm, window: &mut Window, cx: &mut Context<Self>) {
+        self.on_open(window, cx);
+    }
+
+    fn on_select_down(&mut self, _: &SelectDown, window: &mut Window, cx: &mut Context<Self>) {
+        self.on_open(window, cx);
     }
 
     fn on_cancel(&mut self, _: &Cancel, window: &mut Window, cx: &mut Context<Self>) {
@@ -98,7 +328,10 @@ impl AppMenuBar {
         cx: &mut Context<Self>,
     ) {
         if self.selected_index.is_none() && ix.is_some() {
-            self.action_context = window.focused(cx);
+            // Keep the context captured when the bar was focused by keyboard.
+            if self.focused_index.is_none() {
+                self.action_context = window.focused(cx);
+            }
         } else if ix.is_none() {
             if let Some(action_context) = self.action_context.as_ref() {
                 action_context.focus(window, cx);
@@ -106,6 +339,7 @@ impl AppMenuBar {
             self.action_context = None;
         }
 
+        self.focused_index = None;
         self.selected_index = ix;
         cx.notify();
     }
@@ -116,13 +350,22 @@ impl AppMenuBar {
     }
 }
 
+impl Focusable for AppMenuBar {
+    fn focus_handle(&self, _: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
 impl Render for AppMenuBar {
     fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
         h_flex()
             .id("app-menu-bar")
             .key_context(CONTEXT)
+            .track_focus(&self.focus_handle)
             .on_action(cx.listener(Self::on_move_left))
             .on_action(cx.listener(Self::on_move_right))
+            .on_action(cx.listener(Self::on_select_down))
+            .on_action(cx.listener(Self::on_confirm))
             .on_action(cx.listener(Self::on_cancel))
             .size_full()
             .gap_x_1()
@@ -131,30 +374,94 @@ impl Render for AppMenuBar {
     }
 }
 
+/// The items of an [`AppMenu`].
+#[derive(Clone)]
+enum AppMenuItems {
+    /// The menus set by `cx.set_menus`.
+    App(Vec<OwnedMenuItem>),
+    /// The menus of the [`crate::TitleBar::menu_bar`].
+    TitleBar(Vec<TitleBarMenuItem>),
+}
+
+/// Add the [`TitleBarMenuItem`]s to the popup menu.
+fn with_title_bar_items(
+    mut menu: PopupMenu,
+    items: &[TitleBarMenuItem],
+    window: &mut Window,
+    cx: &mut Context<PopupMenu>,
+) -> PopupMenu {
+    for item in items {
+        if item.separator {
+            menu = menu.separator();
+            continue;
+        }
+
+        if !item.children.is_empty() {
+            let children = item.children.clone();
+            menu = menu.submenu(item.label.clone(), window, cx, move |menu, window, cx| {
+                with_title_bar_items(menu, &children, window, cx)
+            });
+            if let Some(submenu) = menu.menu_items.pop() {
+                menu.menu_items.push(submenu.disabled(item.disabled));
+            }
+            continue;
+        }
+
+        let label = item.label.clone();
+        let keystroke = item
+            .keystroke
+            .as_ref()
+            .and_then(|keystroke| Keystroke::parse(keystroke).ok());
+        menu = menu.item(
+            PopupMenuItem::element(move |_, cx| {
+                h_flex()
+                    .w_full()
+                    .gap_3()
+                    .items_center()
+                    .justify_between()
+                    .child(label.clone())
+                    .when_some(keystroke.clone(), |this, keystroke| {
+                        this.child(
+                            Kbd::new(keystroke)
+                                .appearance(false)
+                                .text_color(cx.theme().muted_foreground),
+                        )
+                    })
+            })
+            .disabled(item.disabled)
+            .when_some(item.on_click.clone(), |this, on_click| {
+                this.on_click(move |event, window, cx| on_click(event, window, cx))
+            }),
+        );
+    }
+
+    menu
+}
+
 /// A menu in the menu bar.
 pub(super) struct AppMenu {
     menu_bar: Entity<AppMenuBar>,
     ix: usize,
     name: SharedString,
-    menu: OwnedMenu,
+    items: AppMenuItems,
     popup_menu: Option<Entity<PopupMenu>>,
 
     _subscription: Option<Subscription>,
 }
 
 impl AppMenu {
-    pub(super) fn new(
+    fn new(
         ix: usize,
-        menu: &OwnedMenu,
+        name: SharedString,
+        items: AppMenuItems,
         menu_bar: Entity<AppMenuBar>,
         cx: &mut App,
     ) -> Entity<Self> {
-        let name = menu.name.clone();
         cx.new(|_| Self {
             ix,
             menu_bar,
             name,
-            menu: menu.clone(),
+            items,
             popup_menu: None,
             _subscription: None,
         })
@@ -168,9 +475,10 @@ impl AppMenu {
         let action_context = self.menu_bar.read(cx).action_context.clone();
         let popup_menu = match self.popup_menu.as_ref() {
             None => {
-                let items = self.menu.items.clone();
-                let popup_menu = PopupMenu::build(window, cx, |menu, window, cx| {
-                    menu.with_menu_items(items, window, cx)
+                let items = self.items.clone();
+                let popup_menu = PopupMenu::build(window, cx, |menu, window, cx| match items {
+                    AppMenuItems::App(items) => menu.with_menu_items(items, window, cx),
+                    AppMenuItems::TitleBar(items) => with_title_bar_items(menu, &items, window, cx),
                 });
                 popup_menu.update(cx, |menu, cx| {
                     menu.set_action_context(action_context.clone(), cx);
@@ -245,10 +553,23 @@ impl Render for AppMenu {
     fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
         let menu_bar = self.menu_bar.read(cx);
         let is_selected = menu_bar.selected_index == Some(self.ix);
+        let is_focused = menu_bar.focused_index == Some(self.ix);
 
         div()
             .id(self.ix)
+            .exclude_from_drag()
             .relative()
+            .rounded(cx.theme().radius)
+            // Inside the bounds, the menu bar clips the overflow.
+            .focus_ring_with(
+                is_focused && !is_selected,
+                FocusRingStyle::new()
+                    .color(cx.theme().ring)
+                    .thickness(px(1.))
+                    .offset(px(-1.)),
+                window,
+                cx,
+            )
             .child(
                 Button::new("menu")
                     .small()
@@ -256,7 +577,7 @@ impl Render for AppMenu {
                     .compact()
                     .ghost()
                     .label(self.name.clone())
-                    .selected(is_selected)
+                    .selected(is_selected || is_focused)
                     .on_mouse_down(MouseButton::Left, |_, window, cx| {
                         // Stop propagation to avoid dragging the window.
                         window.prevent_default();
@@ -311,11 +632,7 @@ mod tests {
             first_focus.focus(window, cx);
 
             TestRoot {
-                menu_bar: cx.new(|_| AppMenuBar {
-                    menus: Vec::new(),
-                    selected_index: None,
-                    action_context: None,
-                }),
+                menu_bar: cx.new(|cx| AppMenuBar::empty(cx)),
                 first_focus,
                 second_focus,
             }
@@ -346,4 +663,70 @@ mod tests {
             assert_eq!(menu_bar.action_context.as_ref(), Some(&second_focus));
         });
     }
+
+    #[gpui::test]
+    fn test_title_bar_menu_builder(_cx: &mut TestAppContext) {
+        let menu = TitleBarMenu::new("File")
+            .item(
+                TitleBarMenuItem::new("Open")
+                    .keystroke("ctrl-o")
+                    .on_click(|_, _, _| {}),
+            )
+            .item(TitleBarMenuItem::separator())
+            .items([TitleBarMenuItem::new("Recent")
+                .disabled(true)
+                .submenu([TitleBarMenuItem::new("a.txt")])]);
+
+        assert_eq!(menu.label, SharedString::from("File"));
+        assert_eq!(menu.items.len(), 3);
+        assert_eq!(menu.items[0].keystroke, Some("ctrl-o".into()));
+        assert!(menu.items[0].on_click.is_some());
+        assert!(menu.items[1].separator);
+        assert!(menu.items[2].disabled);
+        assert_eq!(menu.items[2].children.len(), 1);
+    }
+
+    #[gpui::test]
+    fn toggles_keyboard_focus_of_title_bar_menus(cx: &mut TestAppContext) {
+        let (root, cx) = cx.add_window_view(|window, cx| {
+            let first_focus = cx.focus_handle();
+            first_focus.focus(window, cx);
+
+            TestRoot {
+                menu_bar: cx.new(|cx| AppMenuBar::empty(cx)),
+                first_focus,
+                second_focus: cx.focus_handle(),
+            }
+        });
+
+        let (menu_bar, first_focus) = root.read_with(cx, |root, _| {
+            (root.menu_bar.clone(), root.first_focus.clone())
+        });
+
+        menu_bar.update_in(cx, |menu_bar, window, cx| {
+            menu_bar.set_menus(
+                vec![TitleBarMenu::new("File"), TitleBarMenu::new("Edit")],
+                cx,
+            );
+            assert_eq!(menu_bar.menus.len(), 2);
+
+            menu_bar.toggle_focus(window, cx);
+            assert_eq!(menu_bar.focused_index, Some(0));
+            assert!(menu_bar.focus_handle.is_focused(window));
+
+            menu_bar.on_move_right(&SelectRight, window, cx);
+            assert_eq!(menu_bar.focused_index, Some(1));
+            menu_bar.on_move_right(&SelectRight, window, cx);
+            assert_eq!(menu_bar.focused_index, Some(0));
+
+            menu_bar.on_select_down(&SelectDown, window, cx);
+            assert_eq!(menu_bar.selected_index, Some(0));
+            assert_eq!(menu_bar.focused_index, None);
+            assert_eq!(menu_bar.action_context.as_ref(), Some(&first_focus));
+
+            menu_bar.toggle_focus(window, cx);
+            assert_eq!(menu_bar.selected_index, None);
+            assert_eq!(window.focused(cx).as_ref(), Some(&first_focus));
+        });
+    }
 }
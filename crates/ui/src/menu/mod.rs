@@ -6,7 +6,8 @@ mod dropdown_menu;
 mod menu_item;
 mod popup_menu;
 
-pub use app_menu_bar::AppMenuBar;
+pub use app_menu_bar::{AppMenuBar, TitleBarMenu, TitleBarMenuItem};
+pub(crate) use app_menu_bar::toggle_title_bar_menu_focus;
 pub use context_menu::{ContextMenu, ContextMenuExt, ContextMenuState};
 pub use dropdown_menu::DropdownMenu;
 pub use popup_menu::{PopupMenu, PopupMenuItem};
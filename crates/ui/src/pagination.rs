@@ -158,7 +158,7 @@ impl Disableable for Pagination {
 
 impl Sizable for Pagination {
     fn with_size(mut self, size: impl Into<Size>) -> Self {
-        self.size = size.into();
+        self.size = size.into().clamp("Pagination");
         self
     }
 }
@@ -187,7 +187,7 @@ impl RenderOnce for Radio {
                             Size::Small => this.size_3p5(),
                             Size::Medium => this.size_4(),
                             Size::Large => this.size(rems(1.125)),
-                            _ => this.size_4(),
+                            Size::Size(size) => this.size(size),
                         })
                         .flex_shrink_0()
                         .rounded_full()
@@ -12,9 +12,9 @@ use crate::{
 };
 use gpui::{
     Anchor, AnyView, App, AppContext, Bounds, ClipboardItem, Context, DefiniteLength, ElementId,
-    Entity, EntityId, FocusHandle, Hitbox, InteractiveElement, IntoElement, KeyBinding,
-    ParentElement as _, Pixels, Render, StyleRefinement, Styled, WeakEntity, WeakFocusHandle,
-    Window, actions, div, prelude::FluentBuilder as _,
+    Entity, EntityId, FocusHandle, Hitbox, InteractiveElement, IntoElement, KeyBinding, Modifiers,
+    ModifiersChangedEvent, ParentElement as _, Pixels, Render, StyleRefinement, Styled,
+    Subscription, WeakEntity, WeakFocusHandle, Window, actions, div, prelude::FluentBuilder as _,
 };
 use std::{any::TypeId, collections::HashMap, rc::Rc};
 
@@ -46,6 +46,7 @@ pub struct Root {
     pub(crate) native_menu_overlay: Entity<FallbackMenuOverlay>,
     sheet_size: Option<DefiniteLength>,
     window_shadow_size: Pixels,
+    window_resize_handle_size: Option<Pixels>,
     /// Corner radius for the Linux CSD window border / content rounding.
     border_radius: Pixels,
     /// Render the Linux CSD `window_border` wrapper.
@@ -59,6 +60,9 @@ pub struct Root {
     pub(crate) selectable_text_views: HashMap<EntityId, (WeakEntity<TextViewState>, Hitbox)>,
     /// Inline text bounds for selectable TextViews, keyed by parent TextView id.
     pub(crate) selectable_text_inlines: HashMap<EntityId, Vec<Bounds<Pixels>>>,
+    /// Alt is pressed alone, releasing it toggles the focus of the TitleBar menu bar.
+    alt_pending: bool,
+    _subscriptions: Vec<Subscription>,
 }
 
 #[derive(Clone)]
@@ -106,12 +110,17 @@ impl Root {
             native_menu_overlay: cx.new(|_| FallbackMenuOverlay::new()),
             sheet_size: None,
             window_shadow_size: window_border::SHADOW_SIZE,
+            window_resize_handle_size: None,
             border_radius: window_border::BORDER_RADIUS,
             bordered: true,
             pending_focus_restore: None,
             text_selection: WindowTextSelection::default(),
             selectable_text_views: HashMap::new(),
             selectable_text_inlines: HashMap::new(),
+            alt_pending: false,
+            _subscriptions: vec![cx.observe_window_appearance(window, |_, window, cx| {
+                crate::theme::window_appearance_changed(window, cx)
+            })],
         }
     }
 
@@ -132,6 +141,15 @@ impl Root {
         self
     }
 
+    /// Set the resize handle size of the window border for Linux client-side decorations,
+    /// independent of the [`Root::window_shadow_size`].
+    ///
+    /// See [`crate::WindowBorder::resize_handle_size`].
+    pub fn window_resize_handle_size(mut self, size: impl Into<Pixels>) -> Self {
+        self.window_resize_handle_size = Some(size.into());
+        self
+    }
+
     /// Set the corner radius for the window border and content rounding (Linux CSD).
     ///
     /// Default: [`window_border::BORDER_RADIUS`]
@@ -531,6 +549,27 @@ impl Root {
         window.focus_prev(cx);
     }
 
+    fn on_modifiers_changed(
+        &mut self,
+        event: &ModifiersChangedEvent,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if cfg!(target_os = "macos") {
+            return;
+        }
+
+        let modifiers = event.modifiers;
+        if modifiers == Modifiers::alt() {
+            self.alt_pending = true;
+        } else if self.alt_pending && !modifiers.modified() {
+            self.alt_pending = false;
+            crate::menu::toggle_title_bar_menu_focus(window, cx);
+        } else {
+            self.alt_pending = false;
+        }
+    }
+
     fn on_action_copy(&mut self, _: &Copy, _: &mut Window, cx: &mut Context<Self>) {
         let text = self.window_selected_text(cx).trim().to_string();
         if text.is_empty() {
@@ -549,6 +588,9 @@ impl Styled for Root {
 
 impl Render for Root {
     fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        // The views of the window are rendered after the Root, with the window overrides until
+        // the end of the draw.
+        crate::theme::set_current_window(window, cx);
         window.set_rem_size(cx.theme().font_size);
         let border_radius = self.border_radius;
 
@@ -558,6 +600,9 @@ impl Render for Root {
             .on_action(cx.listener(Self::on_action_tab))
             .on_action(cx.listener(Self::on_action_tab_prev))
             .on_action(cx.listener(Self::on_action_copy))
+            .on_modifiers_changed(cx.listener(Self::on_modifiers_changed))
+            // Alt used in a shortcut (e.g. `alt-f`) doesn't toggle the menu bar.
+            .capture_key_down(cx.listener(|this, _, _, _| this.alt_pending = false))
             .relative()
             .size_full()
             .overflow_hidden()
@@ -565,7 +610,7 @@ impl Render for Root {
             .bg(cx.theme().background)
             .text_color(cx.theme().foreground)
             .refine_style(&self.style)
-            .map(|div| match window.window_decorations() {
+            .map(|div| match window_border::window_decorations(window) {
                 gpui::Decorations::Server => div,
                 gpui::Decorations::Client { tiling } => div
                     .when(!(tiling.top || tiling.left), |d| d.rounded_tl(border_radius))
@@ -581,6 +626,9 @@ impl Render for Root {
         if self.bordered {
             window_border()
                 .shadow_size(self.window_shadow_size)
+                .when_some(self.window_resize_handle_size, |this, size| {
+                    this.resize_handle_size(size)
+                })
                 .border_radius(border_radius)
                 .child(inner)
                 .into_any_element()
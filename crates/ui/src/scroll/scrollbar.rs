@@ -656,7 +656,7 @@ impl Element for Scrollbar {
                             let opacity = 1.0 - (elapsed - FADE_OUT_DELAY).powi(10);
                             idle_state.0 = cx.theme().scrollbar_thumb.opacity(opacity);
 
-                            window.request_animation_frame();
+                            crate::animation::request_animation_frame(window);
                         }
                     }
 
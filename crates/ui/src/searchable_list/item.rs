@@ -82,7 +82,7 @@ impl Selectable for SearchableListItemElement {
 
 impl Sizable for SearchableListItemElement {
     fn with_size(mut self, size: impl Into<Size>) -> Self {
-        self.size = size.into();
+        self.size = size.into().clamp("SearchableListItemElement");
         self
     }
 }
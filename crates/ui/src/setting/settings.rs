@@ -284,7 +284,7 @@ impl Settings {
 
 impl Sizable for Settings {
     fn with_size(mut self, size: impl Into<Size>) -> Self {
-        self.size = size.into();
+        self.size = size.into().clamp("Settings");
         self
     }
 }
@@ -100,7 +100,7 @@ impl ParentElement for StepperItem {
 
 impl Sizable for StepperItem {
     fn with_size(mut self, size: impl Into<Size>) -> Self {
-        self.size = size.into();
+        self.size = size.into().clamp("StepperItem");
         self
     }
 }
@@ -95,7 +95,7 @@ impl Stepper {
 
 impl Sizable for Stepper {
     fn with_size(mut self, size: impl Into<Size>) -> Self {
-        self.size = size.into();
+        self.size = size.into().clamp("Stepper");
         self
     }
 }
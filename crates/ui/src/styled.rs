@@ -17,6 +17,31 @@ pub fn v_flex() -> Div {
     div().v_flex()
 }
 
+/// Returns a `Div` as horizontal flex layout, with the children centered on both axes.
+#[inline(always)]
+pub fn h_flex_centered() -> Div {
+    h_flex().justify_center()
+}
+
+/// Returns a `Div` as horizontal flex layout, with the free space between the children.
+#[inline(always)]
+pub fn h_flex_between() -> Div {
+    h_flex().justify_between()
+}
+
+/// Returns a `Div` as vertical flex layout, with the children centered on both axes.
+#[inline(always)]
+pub fn v_flex_centered() -> Div {
+    v_flex().items_center().justify_center()
+}
+
+/// Returns an empty element filling the free space of a flex layout, e.g. to push the next
+/// children to the end.
+#[inline(always)]
+pub fn spacer() -> Div {
+    div().flex_1()
+}
+
 // ---------------------------------------------------------------------------
 // Platform-aware control sizing
 // ---------------------------------------------------------------------------
@@ -93,6 +118,10 @@ macro_rules! font_weight {
 #[cfg_attr(any(feature = "inspector", debug_assertions), gpui_macros::derive_inspector_reflection)]
 pub trait StyledExt: Styled + Sized {
     /// Refine the style of this element, applying the given style refinement.
+    ///
+    /// The properties set in the `style` override the ones set before. Components apply the
+    /// style of the user after their own default styles, so any default can be overridden,
+    /// and apply the protected properties, e.g. the height of the [`crate::TitleBar`], after it.
     fn refine_style(mut self, style: &StyleRefinement) -> Self {
         self.style().refine(style);
         self
@@ -199,6 +228,12 @@ pub trait StyledExt: Styled + Sized {
             .rounded(cx.theme().radius)
     }
 
+    /// Set the gap between the children to a named gap of the theme, see [`GapSize`].
+    #[inline]
+    fn gap_size(self, size: GapSize, cx: &App) -> Self {
+        self.gap(cx.theme().gap(size))
+    }
+
     /// Set corner radii for the element.
     fn corner_radii(self, radius: Corners<Pixels>) -> Self {
         self.rounded_tl(radius.top_left)
@@ -213,6 +248,10 @@ impl<E: Styled> StyledExt for E {}
 /// A size for elements.
 #[derive(Clone, Default, Copy, PartialEq, Eq, Debug, Deserialize, Serialize)]
 pub enum Size {
+    /// A custom size in pixels, e.g. the track height of the [`crate::switch::Switch`], the edge
+    /// length of the [`crate::Icon`], see the `with_size` of the components.
+    ///
+    /// The components not supporting the custom size use the [`Size::nearest`] named size.
     Size(Pixels),
     XSmall,
     Small,
@@ -261,6 +300,45 @@ impl Size {
         }
     }
 
+    /// Returns the named size nearest to the custom pixels, by the control heights: 20px for
+    /// `XSmall`, 24px for `Small`, 32px for `Medium` and 40px for `Large`.
+    ///
+    /// The named sizes are returned as is.
+    pub fn nearest(&self) -> Self {
+        let Size::Size(size) = self else {
+            return *self;
+        };
+
+        let size = size.as_f32();
+        [
+            (Size::XSmall, 20.),
+            (Size::Small, 24.),
+            (Size::Medium, 32.),
+            (Size::Large, 40.),
+        ]
+        .into_iter()
+        .min_by(|(_, a), (_, b)| (a - size).abs().total_cmp(&(b - size).abs()))
+        .map(|(named, _)| named)
+        .unwrap_or_default()
+    }
+
+    /// Returns the [`Size::nearest`] named size for the `component` not supporting the custom
+    /// size, with a debug log.
+    pub(crate) fn clamp(self, component: &str) -> Self {
+        let Size::Size(size) = self else {
+            return self;
+        };
+
+        let nearest = self.nearest();
+        tracing::debug!(
+            "{} doesn't support the custom size {:?}, clamped to {}.",
+            component,
+            size,
+            nearest.as_str()
+        );
+        nearest
+    }
+
     /// Returns the height for table row.
     #[inline]
     pub fn table_row_height(&self) -> Pixels {
@@ -386,6 +464,83 @@ impl From<Pixels> for Size {
     }
 }
 
+/// The density of the components, see [`crate::set_density`].
+///
+/// The components consult the density only when no explicit size is set, the `with_size` of
+/// [`Sizable`] always wins.
+#[derive(Clone, Default, Copy, PartialEq, Eq, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ComponentDensity {
+    /// The default metrics, [`Size::Medium`].
+    #[default]
+    Comfortable,
+    /// The [`Size::Small`] metrics, e.g. for the [`crate::switch::Switch`], the [`crate::Icon`]
+    /// and the tooltip paddings, and a lower [`crate::TitleBar`].
+    Compact,
+}
+
+impl ComponentDensity {
+    /// Returns the density as a static string.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Comfortable => "comfortable",
+            Self::Compact => "compact",
+        }
+    }
+
+    /// Returns true if the density is [`ComponentDensity::Compact`].
+    #[inline]
+    pub fn is_compact(&self) -> bool {
+        matches!(self, Self::Compact)
+    }
+
+    /// Returns the size of the components without an explicit size.
+    pub fn default_size(&self) -> Size {
+        match self {
+            Self::Comfortable => Size::Medium,
+            Self::Compact => Size::Small,
+        }
+    }
+
+    /// Returns the explicit `size`, or the default size of the density of the window.
+    #[inline]
+    pub(crate) fn resolve(size: Option<Size>, window: &Window, cx: &App) -> Size {
+        size.unwrap_or_else(|| crate::window_density(window, cx).default_size())
+    }
+}
+
+/// A named gap of the spacing scale of the theme, see [`crate::Theme::spacing`].
+///
+/// The gaps are multiples of the spacing unit, so a compact theme with a smaller unit tightens
+/// all of them at once.
+#[derive(Clone, Default, Copy, PartialEq, Eq, Debug, Deserialize, Serialize)]
+pub enum GapSize {
+    /// 1 spacing unit, 4px by default.
+    XSmall,
+    /// 2 spacing units, 8px by default.
+    Small,
+    /// 3 spacing units, 12px by default.
+    #[default]
+    Medium,
+    /// 4 spacing units, 16px by default.
+    Large,
+    /// 6 spacing units, 24px by default.
+    XLarge,
+}
+
+impl GapSize {
+    /// Returns the number of spacing units of the gap.
+    pub(crate) fn units(&self) -> f32 {
+        match self {
+            GapSize::XSmall => 1.,
+            GapSize::Small => 2.,
+            GapSize::Medium => 3.,
+            GapSize::Large => 4.,
+            GapSize::XLarge => 6.,
+        }
+    }
+}
+
 /// A trait for defining element that can be selected.
 #[allow(patterns_in_fns_without_body)]
 pub trait Selectable: Sized {
@@ -402,6 +557,9 @@ pub trait Selectable: Sized {
 }
 
 /// A trait for defining element that can be disabled.
+///
+/// The element is also disabled inside a disabled [`crate::DisabledScope`], see
+/// [`crate::inherited_disabled`].
 #[allow(patterns_in_fns_without_body)]
 pub trait Disableable {
     /// Set the disabled state of the element.
@@ -409,13 +567,14 @@ pub trait Disableable {
 }
 
 /// A trait for setting the size of an element.
-/// Size::Medium is use by default.
+/// Size::Medium is use by default, some components follow the [`ComponentDensity`] instead.
 #[allow(patterns_in_fns_without_body)]
 pub trait Sizable: Sized {
     /// Set the ui::Size of this element.
     ///
     /// Also can receive a `ButtonSize` to convert to `IconSize`,
-    /// Or a `Pixels` to set a custom size: `px(30.)`
+    /// Or a `Pixels` to set a custom size: `px(30.)`, the components not supporting the custom
+    /// size are clamped to the [`Size::nearest`] named size.
     fn with_size(mut self, size: impl Into<Size>) -> Self;
 
     /// Set to Size::XSmall
@@ -501,7 +660,7 @@ impl<T: Styled> StyleSized<T> for T {
             Size::Medium => self.h(px(32.) + b),
             Size::Small => self.h(px(24.) + b),
             Size::XSmall => self.h(px(20.) + b),
-            _ => self.h(px(24.) + b),
+            Size::Size(height) => self.h(height),
         }
     }
 
@@ -563,18 +722,104 @@ impl<T: Styled> StyleSized<T> for T {
     }
 }
 
-pub(crate) trait FocusableExt<T: ParentElement + Styled + Sized> {
-    /// Add focus ring to the element.
+/// The default width of the focus ring border.
+const RING_BORDER_WIDTH: Pixels = px(1.5);
+
+/// The style of the focus ring, see [`FocusableExt::focus_ring_with`].
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct FocusRingStyle {
+    /// The color, default is the `ring` color of the theme with 20% alpha.
+    pub color: Option<Hsla>,
+    /// The width of the ring border, default is 1.5px.
+    pub thickness: Option<Pixels>,
+    /// The gap between the border of the element and the ring, default is 0.
+    ///
+    /// A negative offset draws the ring inside the bounds, e.g. `-thickness` to cover the border
+    /// of the element, so it isn't clipped by an `overflow_hidden` parent.
+    pub offset: Pixels,
+    /// The corner radius, default follows the rounding of the element, grown by the offset and
+    /// the thickness.
+    pub radius: Option<Pixels>,
+}
+
+impl FocusRingStyle {
+    /// Create the default style.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the color of the ring.
+    pub fn color(mut self, color: impl Into<Hsla>) -> Self {
+        self.color = Some(color.into());
+        self
+    }
+
+    /// Set the width of the ring border, default is 1.5px.
+    pub fn thickness(mut self, thickness: impl Into<Pixels>) -> Self {
+        self.thickness = Some(thickness.into());
+        self
+    }
+
+    /// Set the gap between the border of the element and the ring, negative inside the bounds.
+    pub fn offset(mut self, offset: impl Into<Pixels>) -> Self {
+        self.offset = offset.into();
+        self
+    }
+
+    /// Set the corner radius of the ring.
+    pub fn radius(mut self, radius: impl Into<Pixels>) -> Self {
+        self.radius = Some(radius.into());
+        self
+    }
+
+    /// Returns the width of the ring border.
+    fn thickness_or_default(&self) -> Pixels {
+        self.thickness.unwrap_or(RING_BORDER_WIDTH)
+    }
+
+    /// Returns the corner radius of the ring around an element with the `radius`.
+    fn corner_radius(&self, radius: Pixels) -> Pixels {
+        let radius = self.radius.unwrap_or(radius + self.offset + self.thickness_or_default());
+        if radius < px(0.) { px(0.) } else { radius }
+    }
+}
+
+/// Extends the elements with a focus ring.
+pub trait FocusableExt<T: ParentElement + Styled + Sized> {
+    /// Add focus ring to the element, out of the element by `margins`.
+    ///
+    /// Same as the [`FocusableExt::focus_ring_with`] with the `margins` as the offset.
     fn focus_ring(self, is_focused: bool, margins: Pixels, window: &Window, cx: &App) -> Self;
+
+    /// Add focus ring in the [`FocusRingStyle`] to the element.
+    ///
+    /// Call this after the border and the rounding of the element are set, the ring follows them.
+    fn focus_ring_with(
+        self,
+        is_focused: bool,
+        style: FocusRingStyle,
+        window: &Window,
+        cx: &App,
+    ) -> Self;
 }
 
 impl<T: ParentElement + Styled + Sized> FocusableExt<T> for T {
-    fn focus_ring(mut self, is_focused: bool, margins: Pixels, window: &Window, cx: &App) -> Self {
+    fn focus_ring(self, is_focused: bool, margins: Pixels, window: &Window, cx: &App) -> Self {
+        self.focus_ring_with(is_focused, FocusRingStyle::new().offset(margins), window, cx)
+    }
+
+    fn focus_ring_with(
+        mut self,
+        is_focused: bool,
+        ring: FocusRingStyle,
+        window: &Window,
+        cx: &App,
+    ) -> Self {
         if !is_focused {
             return self;
         }
 
-        const RING_BORDER_WIDTH: Pixels = px(1.5);
+        let thickness = ring.thickness_or_default();
         let rem_size = window.rem_size();
         let style = self.style();
 
@@ -585,7 +830,7 @@ impl<T: ParentElement + Styled + Sized> FocusableExt<T> for T {
             right: style.border_widths.right.map(|v| v.to_pixels(rem_size)).unwrap_or_default(),
         };
 
-        // Update the radius based on element's corner radii and the ring border width.
+        // Update the radius based on element's corner radii and the ring offset and width.
         let radius = Corners::<Pixels> {
             top_left: style
                 .corner_radii
@@ -608,7 +853,7 @@ impl<T: ParentElement + Styled + Sized> FocusableExt<T> for T {
                 .map(|v| v.to_pixels(rem_size))
                 .unwrap_or_default(),
         }
-        .map(|v| *v + RING_BORDER_WIDTH);
+        .map(|v| ring.corner_radius(*v));
 
         let mut inner_style = StyleRefinement::default();
         inner_style.corner_radii.top_left = Some(radius.top_left.into());
@@ -616,7 +861,7 @@ impl<T: ParentElement + Styled + Sized> FocusableExt<T> for T {
         inner_style.corner_radii.bottom_left = Some(radius.bottom_left.into());
         inner_style.corner_radii.bottom_right = Some(radius.bottom_right.into());
 
-        let inset = RING_BORDER_WIDTH + margins;
+        let inset = thickness + ring.offset;
 
         self.child(
             div()
@@ -626,8 +871,8 @@ impl<T: ParentElement + Styled + Sized> FocusableExt<T> for T {
                 .left(-(inset + border_widths.left))
                 .right(-(inset + border_widths.right))
                 .bottom(-(inset + border_widths.bottom))
-                .border(RING_BORDER_WIDTH)
-                .border_color(cx.theme().ring.alpha(0.2))
+                .border(thickness)
+                .border_color(ring.color.unwrap_or(cx.theme().ring.alpha(0.2)))
                 .refine_style(&inner_style),
         )
     }
@@ -643,7 +888,7 @@ pub trait Collapsible {
 mod tests {
     use gpui::px;
 
-    use crate::Size;
+    use crate::{FocusRingStyle, GapSize, Size, Theme};
 
     #[test]
     fn test_size_max_min() {
@@ -674,6 +919,52 @@ mod tests {
         assert_eq!(Size::Size(px(15.)).as_str(), "custom");
     }
 
+    #[test]
+    fn test_size_nearest() {
+        assert_eq!(Size::Size(px(12.)).nearest(), Size::XSmall);
+        assert_eq!(Size::Size(px(18.)).nearest(), Size::XSmall);
+        assert_eq!(Size::Size(px(23.)).nearest(), Size::Small);
+        assert_eq!(Size::Size(px(30.)).nearest(), Size::Medium);
+        assert_eq!(Size::Size(px(37.)).nearest(), Size::Large);
+        assert_eq!(Size::Size(px(64.)).nearest(), Size::Large);
+        assert_eq!(Size::Small.nearest(), Size::Small);
+
+        assert_eq!(Size::Size(px(18.)).clamp("Tag"), Size::XSmall);
+        assert_eq!(Size::Large.clamp("Tag"), Size::Large);
+    }
+
+    #[test]
+    fn test_focus_ring_radius() {
+        // Grows by the offset and the thickness to hug the element.
+        let ring = FocusRingStyle::new().offset(px(2.));
+        assert_eq!(ring.corner_radius(px(6.)), px(9.5));
+
+        // Inside the bounds, covering the border.
+        let ring = FocusRingStyle::new().thickness(px(2.)).offset(px(-2.));
+        assert_eq!(ring.corner_radius(px(10.)), px(10.));
+        assert_eq!(ring.corner_radius(px(0.)), px(0.));
+
+        let ring = FocusRingStyle::new().offset(px(-4.));
+        assert_eq!(ring.corner_radius(px(1.)), px(0.));
+
+        let ring = FocusRingStyle::new().radius(px(4.));
+        assert_eq!(ring.corner_radius(px(10.)), px(4.));
+    }
+
+    #[test]
+    fn test_gap_size() {
+        let mut theme = Theme::default();
+        assert_eq!(theme.gap(GapSize::XSmall), px(4.));
+        assert_eq!(theme.gap(GapSize::Small), px(8.));
+        assert_eq!(theme.gap(GapSize::default()), px(12.));
+        assert_eq!(theme.gap(GapSize::XLarge), px(24.));
+
+        // A compact theme tightens all the gaps at once.
+        theme.spacing = px(3.);
+        assert_eq!(theme.gap(GapSize::Small), px(6.));
+        assert_eq!(theme.gap(GapSize::Large), px(12.));
+    }
+
     #[test]
     fn test_table_row_height() {
         assert_eq!(Size::XSmall.table_row_height(), px(26.));
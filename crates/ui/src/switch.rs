@@ -1,28 +1,155 @@
 use crate::{
-    ActiveTheme, Disableable, Side, Sizable, Size, StyledExt, h_flex, text::Text,
+    ActiveTheme, AxisExt, ColorRole, ColorSet, ComponentDensity, Direction, Disableable,
+    ElementExt, FeedbackKind, FocusGroup, FocusGroupElement as _, FocusRingStyle,
+    FocusableExt as _, GapSize, Icon, IconName, Placement, Sizable, Size, StyledExt,
+    form::FormState,
+    h_flex, inherited_disabled,
+    kbd::Kbd,
+    text::{Text, TruncateMode, TruncateState, text_width},
     tooltip::ComponentTooltip,
+    v_flex,
 };
 use gpui::{
-    Animation, AnimationExt as _, App, ElementId, Hsla, InteractiveElement, IntoElement,
-    ParentElement as _, RenderOnce, SharedString, StatefulInteractiveElement as _,
-    StyleRefinement, Styled, Window, div, prelude::FluentBuilder as _, px,
+    AnyElement, App, AppContext as _, Axis, Bounds, Context, DragMoveEvent, ElementId, Empty,
+    Entity, EntityId, FocusHandle, Global, Hsla, InteractiveElement, IntoElement, MouseButton,
+    MouseUpEvent, ParentElement as _, Pixels, Point, Render, RenderOnce, SharedString,
+    StatefulInteractiveElement as _, StyleRefinement, Styled, Subscription, Task, Window, div,
+    prelude::FluentBuilder as _, px, relative, rems,
 };
-use std::{rc::Rc, time::Duration};
+use rust_i18n::t;
+use std::{
+    collections::HashMap,
+    rc::Rc,
+    sync::Once,
+    time::{Duration, Instant},
+};
+
+/// An icon or a text rendered inside the track of the [`Switch`].
+#[derive(Clone)]
+enum TrackGlyph {
+    Icon(IconName),
+    Label(SharedString),
+}
+
+const THUMB_TRANSITION_DURATION: Duration = Duration::from_millis(150);
+const SPINNER_DURATION: Duration = Duration::from_millis(800);
+const TOUCH_TARGET: Pixels = px(32.);
+
+/// The metrics of the track and the thumb of the [`Switch`] in a size.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct SwitchMetrics {
+    width: Pixels,
+    height: Pixels,
+    thumb: Pixels,
+    inset: Pixels,
+    spinner: Pixels,
+    glyph: Pixels,
+}
+
+impl SwitchMetrics {
+    fn new(size: Size) -> Self {
+        let (width, height, inset, spinner, glyph) = match size {
+            Size::XSmall => (px(24.), px(14.), px(2.), px(6.), px(6.)),
+            Size::Small => (px(28.), px(16.), px(2.), px(8.), px(8.)),
+            Size::Medium => (px(36.), px(20.), px(2.), px(12.), px(10.)),
+            Size::Large => (px(44.), px(24.), px(2.), px(14.), px(12.)),
+            // The pixels are the track height, the rest scales from the medium size.
+            Size::Size(height) => (
+                height * 9. / 5.,
+                height,
+                height / 10.,
+                height * 3. / 5.,
+                height / 2.,
+            ),
+        };
+        Self {
+            width,
+            height,
+            thumb: height - inset * 2,
+            inset,
+            spinner,
+            glyph,
+        }
+    }
+}
+
+/// The visual variant of the [`Switch`].
+#[derive(Clone, Debug, PartialEq, Eq, Default)]
+pub enum SwitchVariant {
+    /// A track with a sliding thumb.
+    #[default]
+    Default,
+    /// Two labeled segments in a pill, the highlight slides to the active segment, e.g.
+    /// `Metric / Imperial`.
+    Segmented {
+        off_label: SharedString,
+        on_label: SharedString,
+    },
+}
+
+/// The layout of the [`Switch`].
+#[derive(Clone, Copy, PartialEq, Eq, Default, Debug)]
+pub enum SwitchLayout {
+    /// The track and the label are placed next to each other.
+    #[default]
+    Inline,
+    /// The label is on the left, the track is on the right edge, and the whole row fills the
+    /// container width and is clickable.
+    Row,
+}
+
+/// The binding of the checked state of a [`Switch`] to an entity.
+#[derive(Clone)]
+struct SwitchBinding {
+    get: Rc<dyn Fn(&App) -> bool>,
+    set: Rc<dyn Fn(bool, &mut App)>,
+}
 
 /// A Switch element that can be toggled on or off.
 #[derive(IntoElement)]
 pub struct Switch {
     id: ElementId,
     style: StyleRefinement,
-    checked: bool,
+    checked: Option<bool>,
+    default_checked: Option<bool>,
+    binding: Option<SwitchBinding>,
+    indeterminate: bool,
+    /// The focus handle given by the app or the [`SwitchGroup`].
+    focus_handle: Option<FocusHandle>,
     disabled: bool,
+    disabled_reason: Option<SharedString>,
+    read_only: bool,
+    loading: bool,
     label: Option<Text>,
-    label_side: Side,
+    description: Option<Text>,
+    prefix: Option<AnyElement>,
+    suffix: Option<AnyElement>,
+    label_truncate: bool,
+    error: bool,
+    error_message: Option<Text>,
+    name: Option<SharedString>,
+    form: Option<Entity<FormState>>,
+    label_side: Option<Placement>,
+    direction: Option<Direction>,
+    keybinding: Option<Kbd>,
+    animated: Option<bool>,
+    animation_duration: Duration,
+    touch_target: Pixels,
+    layout: SwitchLayout,
+    variant: SwitchVariant,
+    on_glyph: Option<TrackGlyph>,
+    off_glyph: Option<TrackGlyph>,
     on_click: Option<Rc<dyn Fn(&bool, &mut Window, &mut App)>>,
-    size: Size,
-    color: Option<Hsla>,
+    on_toggle_async: Option<Rc<dyn Fn(&bool, &mut Window, &mut App) -> Task<bool>>>,
+    on_hover: Option<Rc<dyn Fn(&bool, &mut Window, &mut App)>>,
+    hover_style: StyleRefinement,
+    /// The explicit size, or the default size of the [`crate::ComponentDensity`].
+    size: Option<Size>,
+    checked_bg: Option<Hsla>,
+    unchecked_bg: Option<Hsla>,
+    thumb_color: Option<Hsla>,
     tooltip: ComponentTooltip,
-    tab_stop: bool,
+    tab_stop: Option<bool>,
     tab_index: isize,
 }
 
@@ -33,22 +160,127 @@ impl Switch {
         Self {
             id: id.clone(),
             style: StyleRefinement::default(),
-            checked: false,
+            checked: None,
+            default_checked: None,
+            binding: None,
+            indeterminate: false,
+            focus_handle: None,
             disabled: false,
+            disabled_reason: None,
+            read_only: false,
+            loading: false,
             label: None,
+            description: None,
+            prefix: None,
+            suffix: None,
+            label_truncate: false,
+            error: false,
+            error_message: None,
+            name: None,
+            form: None,
             on_click: None,
-            label_side: Side::Right,
-            size: Size::Medium,
-            color: None,
+            on_toggle_async: None,
+            on_hover: None,
+            hover_style: StyleRefinement::default(),
+            label_side: None,
+            direction: None,
+            keybinding: None,
+            animated: None,
+            animation_duration: THUMB_TRANSITION_DURATION,
+            touch_target: TOUCH_TARGET,
+            layout: SwitchLayout::default(),
+            variant: SwitchVariant::default(),
+            on_glyph: None,
+            off_glyph: None,
+            size: None,
+            checked_bg: None,
+            unchecked_bg: None,
+            thumb_color: None,
             tooltip: ComponentTooltip::default(),
-            tab_stop: true,
+            tab_stop: None,
             tab_index: 0,
         }
     }
 
-    /// Set the checked state of the switch.
+    /// Set the checked state of the switch, the switch is controlled.
+    ///
+    /// A controlled switch only changes when it is rendered with a new `checked`, update it in
+    /// the `on_click`.
     pub fn checked(mut self, checked: bool) -> Self {
-        self.checked = checked;
+        self.checked = Some(checked);
+        self
+    }
+
+    /// Set the initial checked state of the switch, the switch is uncontrolled.
+    ///
+    /// An uncontrolled switch keeps the checked state in the element state and toggles it on
+    /// click, the `on_click` still receives the changes. The `checked` wins if both are set.
+    /// Without `checked` nor `default_checked`, the switch is unchecked and only reports the
+    /// clicks to `on_click`.
+    pub fn default_checked(mut self, checked: bool) -> Self {
+        self.default_checked = Some(checked);
+        self
+    }
+
+    /// Bind the checked state to an `Entity<bool>`.
+    ///
+    /// The switch reads the entity on render, and updates and notifies it on click. The binding
+    /// wins over `checked` and `default_checked`, the `on_click` is still called.
+    pub fn bind(self, entity: &Entity<bool>) -> Self {
+        self.bind_with(entity, |value| *value, |value, checked| *value = checked)
+    }
+
+    /// Bind the checked state to a field of an entity, with the `get` and `set` of the field.
+    ///
+    /// ```ignore
+    /// Switch::new("dark-mode").bind_with(
+    ///     &settings,
+    ///     |settings| settings.dark_mode,
+    ///     |settings, checked| settings.dark_mode = checked,
+    /// )
+    /// ```
+    pub fn bind_with<T: 'static>(
+        mut self,
+        entity: &Entity<T>,
+        get: impl Fn(&T) -> bool + 'static,
+        set: impl Fn(&mut T, bool) + 'static,
+    ) -> Self {
+        let (read_entity, update_entity) = (entity.clone(), entity.clone());
+        self.binding = Some(SwitchBinding {
+            get: Rc::new(move |cx| get(read_entity.read(cx))),
+            set: Rc::new(move |checked, cx| {
+                update_entity.update(cx, |value, cx| {
+                    set(value, checked);
+                    cx.notify();
+                })
+            }),
+        });
+        self
+    }
+
+    /// Set true to show the indeterminate state, the thumb is in the middle of the track.
+    ///
+    /// Clicking an indeterminate switch turns it on, default is false.
+    pub fn indeterminate(mut self, indeterminate: bool) -> Self {
+        self.indeterminate = indeterminate;
+        self
+    }
+
+    /// Use the focus handle of the app, e.g. to move the focus to the switch with
+    /// `focus_handle.focus(window, cx)`.
+    ///
+    /// If not set, the switch creates its own focus handle.
+    pub fn track_focus(mut self, focus_handle: &FocusHandle) -> Self {
+        self.focus_handle = Some(focus_handle.clone());
+        self
+    }
+
+    /// Set true to show a spinner in the thumb while the change is pending, default is false.
+    ///
+    /// A loading switch ignores clicks but keeps the track color, and animates to the `checked`
+    /// state when the loading ends.
+    pub fn loading(mut self, loading: bool) -> Self {
+        self.loading = loading;
         self
     }
 
@@ -58,6 +290,196 @@ impl Switch {
         self
     }
 
+    /// Set the description of the switch, rendered in muted color under the label.
+    ///
+    /// Clicking the description also toggles the switch.
+    pub fn description(mut self, description: impl Into<Text>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// Set the element before the label, e.g. an icon, centered to the first line of the label.
+    pub fn prefix(mut self, prefix: impl IntoElement) -> Self {
+        self.prefix = Some(prefix.into_any_element());
+        self
+    }
+
+    /// Set the element after the label, e.g. a badge, centered to the first line of the label.
+    pub fn suffix(mut self, suffix: impl IntoElement) -> Self {
+        self.suffix = Some(suffix.into_any_element());
+        self
+    }
+
+    /// Set true to keep the label in a single line with an ellipsis, default is false.
+    ///
+    /// When the label is truncated, the tooltip shows the full label, above the `tooltip` text if
+    /// any.
+    pub fn label_truncate(mut self, label_truncate: bool) -> Self {
+        self.label_truncate = label_truncate;
+        self
+    }
+
+    /// Set the keybinding hint, rendered in a muted badge after the label, e.g. `⌘⇧P` on macOS
+    /// and `Ctrl+Shift+P` elsewhere.
+    ///
+    /// This is only a hint, the app still needs to bind the key.
+    pub fn keybinding(mut self, keybinding: impl Into<Kbd>) -> Self {
+        self.keybinding = Some(keybinding.into());
+        self
+    }
+
+    /// Set the reason why the switch is disabled, e.g. a missing permission.
+    ///
+    /// Only while disabled, the reason replaces the tooltip, and is added to the description
+    /// reported to the accessibility tools.
+    pub fn disabled_reason(mut self, reason: impl Into<SharedString>) -> Self {
+        self.disabled_reason = Some(reason.into());
+        self
+    }
+
+    /// Set the switch as read-only, default is false.
+    ///
+    /// Unlike `disabled`, the switch keeps its colors, but ignores the clicks and keys, shows a
+    /// lock icon after the label and a read-only tooltip by default, and is removed from the tab
+    /// order unless [`Switch::tab_stop`] is set.
+    pub fn read_only(mut self, read_only: bool) -> Self {
+        self.read_only = read_only;
+        self
+    }
+
+    /// Set the switch as invalid, the track border and the focus ring use the danger color,
+    /// default is false.
+    ///
+    /// Unlike `disabled`, the switch keeps its colors and still toggles.
+    pub fn error(mut self, error: bool) -> Self {
+        self.error = error;
+        self
+    }
+
+    /// Set the error message of the switch, rendered in danger color under the description.
+    ///
+    /// This also sets the switch as invalid, see [`Switch::error`].
+    pub fn error_message(mut self, message: impl Into<Text>) -> Self {
+        self.error_message = Some(message.into());
+        self
+    }
+
+    /// Set the name of the switch in the form, e.g. `notifications.email`, see [`Switch::form`].
+    pub fn name(mut self, name: impl Into<SharedString>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// Register the named switch to the form, the switch reports the changes to the form, and
+    /// shows the error of the form validator.
+    ///
+    /// The uncontrolled switch keeps the checked state in the form, so `FormState::reset` resets
+    /// it. The switch unregisters once it is no longer rendered.
+    pub fn form(mut self, form: &Entity<FormState>) -> Self {
+        self.form = Some(form.clone());
+        self
+    }
+
+    /// Set the duration of the toggle animation, default is 150ms.
+    pub fn animation_duration(mut self, duration: Duration) -> Self {
+        self.animation_duration = duration;
+        self
+    }
+
+    /// Set false to move the thumb at once without animation.
+    ///
+    /// Default follows [`crate::reduce_motion`], animated unless the reduced motion is enabled.
+    pub fn animated(mut self, animated: bool) -> Self {
+        self.animated = Some(animated);
+        self
+    }
+
+    /// Set the minimum size of the clickable area around the track, default is 32px.
+    ///
+    /// The area is invisible and doesn't affect the layout. Where the areas of the adjacent
+    /// switches overlap, the nearest track toggles.
+    pub fn touch_target(mut self, size: impl Into<Pixels>) -> Self {
+        self.touch_target = size.into();
+        self
+    }
+
+    /// Set the side of the label, default is [`crate::Side::Right`], or [`crate::Side::Left`] in
+    /// [`Direction::Rtl`].
+    ///
+    /// Accepts a [`crate::Side`] or a [`Placement`], the `Top` and `Bottom` stack the label and the track
+    /// in a centered column.
+    pub fn label_side(mut self, side: impl Into<Placement>) -> Self {
+        self.label_side = Some(side.into());
+        self
+    }
+
+    /// Set the layout direction of the switch, default is [`crate::direction`].
+    ///
+    /// In [`Direction::Rtl`] the switch is on at the left, e.g. for a right-to-left label in a
+    /// left-to-right UI.
+    pub fn direction(mut self, direction: Direction) -> Self {
+        self.direction = Some(direction);
+        self
+    }
+
+    /// Set the layout of the switch, default is [`SwitchLayout::Inline`].
+    ///
+    /// The [`SwitchLayout::Row`] ignores the `label_side`, the label is always at the start, on the
+    /// left, or on the right in [`Direction::Rtl`].
+    pub fn layout(mut self, layout: SwitchLayout) -> Self {
+        self.layout = layout;
+        self
+    }
+
+    /// Set the visual variant of the switch, default is [`SwitchVariant::Default`].
+    ///
+    /// The variants share the rest of the API, e.g. the [`SwitchVariant::Segmented`] is on when
+    /// the `on_label` segment is active.
+    pub fn variant(mut self, variant: SwitchVariant) -> Self {
+        self.variant = variant;
+        self
+    }
+
+    /// Set the icon inside the track when checked, on the side opposite the thumb.
+    pub fn on_icon(mut self, icon: IconName) -> Self {
+        self.on_glyph = Some(TrackGlyph::Icon(icon));
+        self
+    }
+
+    /// Set the icon inside the track when unchecked, on the side opposite the thumb.
+    pub fn off_icon(mut self, icon: IconName) -> Self {
+        self.off_glyph = Some(TrackGlyph::Icon(icon));
+        self
+    }
+
+    /// Set the text inside the track when checked, e.g. `I`.
+    ///
+    /// The text is hidden when it doesn't fit in the track.
+    pub fn on_label(mut self, label: impl Into<SharedString>) -> Self {
+        self.on_glyph = Some(TrackGlyph::Label(label.into()));
+        self
+    }
+
+    /// Set the text inside the track when unchecked, e.g. `O`.
+    ///
+    /// The text is hidden when it doesn't fit in the track.
+    pub fn off_label(mut self, label: impl Into<SharedString>) -> Self {
+        self.off_glyph = Some(TrackGlyph::Label(label.into()));
+        self
+    }
+
+    /// Set an async handler to confirm the toggle, e.g. with a dialog.
+    ///
+    /// The switch shows the loading state while the task runs, and only toggles, and calls the
+    /// `on_click`, if the task resolves to true. Otherwise, the thumb stays and shakes.
+    pub fn on_toggle_async(
+        mut self,
+        handler: impl Fn(&bool, &mut Window, &mut App) -> Task<bool> + 'static,
+    ) -> Self {
+        self.on_toggle_async = Some(Rc::new(handler));
+        self
+    }
+
     /// Add a click handler for the switch.
     pub fn on_click<F>(mut self, handler: F) -> Self
     where
@@ -67,10 +489,47 @@ impl Switch {
         self
     }
 
+    /// Add hover handler for the whole switch, the bool parameter indicates whether the mouse is
+    /// hovering.
+    ///
+    /// The handler is not called while disabled.
+    pub fn on_hover(mut self, handler: impl Fn(&bool, &mut Window, &mut App) + 'static) -> Self {
+        self.on_hover = Some(Rc::new(handler));
+        self
+    }
+
+    /// Set the style of the track while the switch is hovered, refines the default hover style.
+    ///
+    /// Only a clickable switch has the hover style.
+    pub fn hover_style(mut self, f: impl FnOnce(StyleRefinement) -> StyleRefinement) -> Self {
+        self.hover_style = f(StyleRefinement::default());
+        self
+    }
+
     /// Set the background color of the switch when checked.
     /// Defaults to `cx.theme().primary`.
     pub fn color(mut self, color: impl Into<Hsla>) -> Self {
-        self.color = Some(color.into());
+        self.checked_bg = Some(color.into());
+        self
+    }
+
+    /// Set the track color when checked, same as [`Switch::color`].
+    ///
+    /// Default is `cx.theme().primary`.
+    pub fn checked_bg(mut self, color: impl Into<Hsla>) -> Self {
+        self.checked_bg = Some(color.into());
+        self
+    }
+
+    /// Set the track color when unchecked, default is `cx.theme().switch`.
+    pub fn unchecked_bg(mut self, color: impl Into<Hsla>) -> Self {
+        self.unchecked_bg = Some(color.into());
+        self
+    }
+
+    /// Set the thumb color, default is `cx.theme().switch_thumb`.
+    pub fn thumb_color(mut self, color: impl Into<Hsla>) -> Self {
+        self.thumb_color = Some(color.into());
         self
     }
 
@@ -80,9 +539,36 @@ impl Switch {
         self
     }
 
-    /// Set the tab stop for the switch, default is true.
+    /// Set the preferred side of the tooltip, default is [`Placement::Top`].
+    ///
+    /// The tooltip flips to the opposite side if it doesn't fit in the window.
+    pub fn tooltip_side(mut self, side: Placement) -> Self {
+        self.tooltip.options.side = Some(side);
+        self
+    }
+
+    /// Set the delay before showing the tooltip, default is 500ms.
+    pub fn tooltip_delay(mut self, delay: Duration) -> Self {
+        self.tooltip.options.delay = Some(delay);
+        self
+    }
+
+    /// Set the max width of the tooltip, the text is wrapped to multiple lines, default is
+    /// unlimited.
+    pub fn tooltip_max_width(mut self, max_width: impl Into<Pixels>) -> Self {
+        self.tooltip.options.max_width = Some(max_width.into());
+        self
+    }
+
+    /// Set the gap between the switch and the tooltip, default is 0.
+    pub fn tooltip_offset(mut self, offset: impl Into<Pixels>) -> Self {
+        self.tooltip.options.offset = Some(offset.into());
+        self
+    }
+
+    /// Set the tab stop for the switch, default is true, or false if read-only.
     pub fn tab_stop(mut self, tab_stop: bool) -> Self {
-        self.tab_stop = tab_stop;
+        self.tab_stop = Some(tab_stop);
         self
     }
 
@@ -91,8 +577,221 @@ impl Switch {
         self.tab_index = tab_index;
         self
     }
+
+    /// Returns true if the switch keeps the checked state itself, with `default_checked` or in
+    /// the form. A switch without `checked` nor `default_checked` is a static display.
+    fn is_uncontrolled(&self) -> bool {
+        self.checked.is_none() && (self.default_checked.is_some() || self.form.is_some())
+    }
+
+    fn clickable(&self) -> bool {
+        // A bound or uncontrolled switch toggles itself, even without `on_click`.
+        !(self.disabled || self.read_only || self.loading)
+            && (self.on_click.is_some()
+                || self.on_toggle_async.is_some()
+                || self.is_uncontrolled()
+                || self.binding.is_some())
+    }
+}
+
+#[derive(Clone)]
+struct DragThumb(EntityId);
+
+impl Render for DragThumb {
+    fn render(&mut self, _: &mut Window, _: &mut Context<Self>) -> impl IntoElement {
+        Empty
+    }
+}
+
+const SHAKE_DURATION: Duration = Duration::from_millis(300);
+const SHAKE_DISTANCE: Pixels = px(3.);
+
+/// The state of the `on_toggle_async` of a [`Switch`].
+#[derive(Default)]
+struct AsyncToggleState {
+    pending: bool,
+    rejected_at: Option<Instant>,
+    _task: Option<Task<()>>,
+}
+
+impl AsyncToggleState {
+    /// Returns the offset of the shake in `-1..=1` after a rejected toggle, or `None` when done.
+    fn shake(&mut self, now: Instant) -> Option<f32> {
+        let t = now.duration_since(self.rejected_at?).as_secs_f32() / SHAKE_DURATION.as_secs_f32();
+        if t >= 1. {
+            self.rejected_at = None;
+            return None;
+        }
+        Some((t * std::f32::consts::PI * 6.).sin() * (1. - t))
+    }
+}
+
+/// The registration of a named [`Switch`] to the [`FormState`].
+struct FormRegistration {
+    _subscriptions: [Subscription; 2],
+}
+
+/// The nearest touch target of the current click, the touch targets of the adjacent switches
+/// can overlap.
+#[derive(Default)]
+struct PendingTap(Option<(Pixels, Rc<dyn Fn(&mut Window, &mut App)>)>);
+
+impl Global for PendingTap {}
+
+/// Claim the click in a touch target, the nearest track toggles after the dispatch.
+fn claim_tap(
+    distance: Pixels,
+    toggle: Rc<dyn Fn(&mut Window, &mut App)>,
+    window: &mut Window,
+    cx: &mut App,
+) {
+    let pending = &mut cx.default_global::<PendingTap>().0;
+    let first = pending.is_none();
+    if pending
+        .as_ref()
+        .is_none_or(|(nearest, _)| distance < *nearest)
+    {
+        *pending = Some((distance, toggle));
+    }
+    if first {
+        window.defer(cx, |window, cx| {
+            if let Some((_, toggle)) = cx.default_global::<PendingTap>().0.take() {
+                toggle(window, cx);
+            }
+        });
+    }
+}
+
+/// Returns the offset of the thumb from the left of the free space, the `position` is `0..=1`
+/// from off to on.
+fn thumb_offset(position: f32, max_x: Pixels, is_rtl: bool) -> Pixels {
+    if is_rtl {
+        max_x * (1. - position)
+    } else {
+        max_x * position
+    }
+}
+
+/// Returns the font size of the label in the size of the [`Switch`].
+fn label_text_size(size: Size, window: &Window) -> Pixels {
+    match size {
+        Size::XSmall => rems(0.75).to_pixels(window.rem_size()),
+        Size::Small => rems(0.875).to_pixels(window.rem_size()),
+        Size::Medium => rems(1.).to_pixels(window.rem_size()),
+        Size::Large => rems(1.125).to_pixels(window.rem_size()),
+        Size::Size(size) => size * 0.8,
+    }
+}
+
+/// Returns the distance from the point to the bounds, zero inside.
+fn distance_to(bounds: Bounds<Pixels>, point: Point<Pixels>) -> Pixels {
+    let dx = (bounds.left() - point.x)
+        .max(point.x - bounds.right())
+        .max(px(0.));
+    let dy = (bounds.top() - point.y)
+        .max(point.y - bounds.bottom())
+        .max(px(0.));
+    px(f32::from(dx).hypot(f32::from(dy)))
+}
+
+/// The transition of the thumb, the position is 0 when unchecked, 1 when checked and 0.5 when
+/// indeterminate.
+struct ThumbTransition {
+    to: f32,
+    from: f32,
+    started_at: Option<Instant>,
+    duration: Duration,
+    /// The position of the thumb while it's dragged.
+    drag: Option<f32>,
+}
+
+impl ThumbTransition {
+    fn new(to: f32) -> Self {
+        Self {
+            to,
+            from: to,
+            started_at: None,
+            duration: Duration::ZERO,
+            drag: None,
+        }
+    }
+
+    /// Returns the thumb position to render, and true if the transition is still running.
+    ///
+    /// Without a `duration`, the thumb moves to the final position at once.
+    fn position(&mut self, to: f32, duration: Option<Duration>, now: Instant) -> (f32, bool) {
+        if let Some(drag) = self.drag {
+            return (drag, false);
+        }
+        if self.to != to {
+            // Start from the current position, so a change in the middle doesn't jump.
+            self.from = self.current(now);
+            self.to = to;
+            self.duration = duration.unwrap_or_default();
+            self.started_at = (!self.duration.is_zero()).then_some(now);
+        }
+
+        let position = self.current(now);
+        if self
+            .started_at
+            .is_some_and(|started_at| now.duration_since(started_at) >= self.duration)
+        {
+            self.started_at = None;
+        }
+        (position, self.started_at.is_some())
+    }
+
+    /// Ends the drag, returns true if the thumb is released past the midpoint.
+    fn release(&mut self, duration: Option<Duration>, now: Instant) -> Option<bool> {
+        let position = self.drag.take()?;
+        // The animation takes over from the released position.
+        self.from = position;
+        self.duration = duration.unwrap_or_default();
+        self.started_at = (!self.duration.is_zero()).then_some(now);
+        Some(position > 0.5)
+    }
+
+    fn current(&self, now: Instant) -> f32 {
+        match self.started_at {
+            Some(started_at) => {
+                let t = now.duration_since(started_at).as_secs_f32() / self.duration.as_secs_f32();
+                self.from + (self.to - self.from) * t.min(1.)
+            }
+            None => self.to,
+        }
+    }
+}
+
+impl TrackGlyph {
+    /// Returns true if the glyph fits in the free space of the track beside the thumb.
+    fn fits(&self, space: Pixels, size: Pixels, window: &Window) -> bool {
+        let width = match self {
+            Self::Icon(_) => size,
+            Self::Label(label) => text_width(label, size, window),
+        };
+        width <= space
+    }
+
+    fn render(self, size: Pixels) -> AnyElement {
+        match self {
+            Self::Icon(icon) => Icon::new(icon)
+                .with_size(Size::Size(size))
+                .into_any_element(),
+            Self::Label(label) => div()
+                .text_size(size)
+                .line_height(relative(1.))
+                .font_semibold()
+                .child(label)
+                .into_any_element(),
+        }
+    }
 }
 
+/// The style refines the outer element of the switch, after its default width in the
+/// [`SwitchLayout::Row`].
+///
+/// The track and the thumb are protected, they're sized by [`Sizable`] only, e.g. `.h()` changes
+/// the height of the switch, not the track.
 impl Styled for Switch {
     fn style(&mut self) -> &mut gpui::StyleRefinement {
         &mut self.style
@@ -101,7 +800,7 @@ impl Styled for Switch {
 
 impl Sizable for Switch {
     fn with_size(mut self, size: impl Into<Size>) -> Self {
-        self.size = size.into();
+        self.size = Some(size.into());
         self
     }
 }
@@ -114,148 +813,2062 @@ impl Disableable for Switch {
 }
 
 impl RenderOnce for Switch {
-    fn render(self, window: &mut Window, cx: &mut App) -> impl IntoElement {
-        let checked = self.checked;
+    fn render(mut self, window: &mut Window, cx: &mut App) -> impl IntoElement {
+        // Disabled by a parent scope, like the own `disabled`.
+        self.disabled |= inherited_disabled(cx);
+        let size = ComponentDensity::resolve(self.size, window, cx);
+        if cfg!(debug_assertions) && self.checked.is_some() && self.default_checked.is_some() {
+            static WARN: Once = Once::new();
+            WARN.call_once(|| {
+                log::warn!(
+                    "Switch: both `checked` and `default_checked` are set, `checked` is used."
+                )
+            });
+        }
+        let binding = self.binding.clone();
+        let form = self.form.clone().zip(self.name.clone());
+        if let Some((form, name)) = &form {
+            let initial = match &binding {
+                Some(binding) => (binding.get)(cx),
+                None => self.checked.or(self.default_checked).unwrap_or_default(),
+            };
+            // Register on the first render, and unregister with the element state.
+            window.use_keyed_state(format!("{}-form", self.id), cx, |_, cx| {
+                form.update(cx, |form, _| form.register(name.clone(), initial.into()));
+                let (weak_form, name) = (form.downgrade(), name.clone());
+                FormRegistration {
+                    _subscriptions: [
+                        cx.observe(form, |_, _, cx| cx.notify()),
+                        cx.on_release(move |_, cx| {
+                            _ = weak_form.update(cx, |form, _| form.unregister(&name));
+                        }),
+                    ],
+                }
+            });
+        }
+        let form_checked = form
+            .as_ref()
+            .and_then(|(form, name)| form.read(cx).value(name)?.as_bool());
+        // Only the uncontrolled switch keeps the checked state, in the form if any.
+        let checked_state =
+            (self.is_uncontrolled() && binding.is_none() && form.is_none()).then(|| {
+                let default_checked = self.default_checked.unwrap_or_default();
+                window.use_keyed_state(format!("{}-checked", self.id), cx, |_, _| default_checked)
+            });
+        let checked = match (&binding, &checked_state) {
+            (Some(binding), _) => (binding.get)(cx),
+            (None, Some(state)) => *state.read(cx),
+            (None, None) => self.checked.or(form_checked).unwrap_or_default(),
+        };
+        if let Some((form, name)) = &form {
+            // The controlled or bound switch keeps the form value in sync.
+            if form_checked != Some(checked) {
+                form.update(cx, |form, _| form.sync(name, checked.into()));
+            }
+            if self.error_message.is_none() {
+                self.error_message = form.read(cx).error(name).cloned().map(Text::from);
+            }
+        }
+        // An indeterminate switch turns on.
+        let next_checked = self.indeterminate || !checked;
+        let async_state = self.on_toggle_async.is_some().then(|| {
+            window.use_keyed_state(format!("{}-async", self.id), cx, |_, _| {
+                AsyncToggleState::default()
+            })
+        });
+        let pending = async_state
+            .as_ref()
+            .is_some_and(|state| state.read(cx).pending);
+        let clickable = self.clickable() && !pending;
         let on_click = self.on_click.clone();
-        let toggle_state = window.use_keyed_state(self.id.clone(), cx, |_, _| checked);
+        let commit = Rc::new(move |window: &mut Window, cx: &mut App| {
+            if let Some(binding) = &binding {
+                (binding.set)(next_checked, cx);
+            } else if let Some(state) = &checked_state {
+                state.update(cx, |this, cx| {
+                    *this = next_checked;
+                    cx.notify();
+                });
+            }
+            if let Some((form, name)) = &form {
+                form.update(cx, |form, cx| {
+                    form.set_value(name.clone(), next_checked, cx)
+                });
+            }
+            crate::feedback::feedback(FeedbackKind::Toggle { on: next_checked }, cx);
+            if let Some(on_click) = &on_click {
+                on_click(&next_checked, window, cx);
+            }
+        });
+        // The click, the keyboard and the drag share the toggle.
+        let toggle = clickable.then(|| {
+            let on_toggle_async = self.on_toggle_async.clone();
+            let async_state = async_state.clone();
+            Rc::new(move |window: &mut Window, cx: &mut App| {
+                cx.stop_propagation();
+                let (Some(on_toggle_async), Some(async_state)) = (&on_toggle_async, &async_state)
+                else {
+                    commit(window, cx);
+                    return;
+                };
+
+                // Commit only if confirmed, the switch is loading until then.
+                let task = on_toggle_async(&next_checked, window, cx);
+                let commit = commit.clone();
+                async_state.update(cx, |state, cx| {
+                    state.pending = true;
+                    state._task = Some(cx.spawn_in(window, async move |this, cx| {
+                        let confirmed = task.await;
+                        _ = this.update_in(cx, |state, window, cx| {
+                            state.pending = false;
+                            if confirmed {
+                                commit(window, cx);
+                            } else {
+                                state.rejected_at = Some(Instant::now());
+                            }
+                            cx.notify();
+                        });
+                    }));
+                    cx.notify();
+                });
+            }) as Rc<dyn Fn(&mut Window, &mut App)>
+        });
+
+        #[cfg(any(test, feature = "test-support"))]
+        test::record(window, &self.id, checked, toggle.clone(), cx);
 
-        let focus_handle = window
-            .use_keyed_state(self.id.clone(), cx, |_, cx| cx.focus_handle())
-            .read(cx)
-            .clone();
+        let focus_handle = match self.focus_handle.clone() {
+            Some(focus_handle) => focus_handle,
+            None => window
+                .use_keyed_state(self.id.clone(), cx, |_, cx| cx.focus_handle())
+                .read(cx)
+                .clone(),
+        };
         let is_focused = focus_handle.is_focused(window);
+        let is_row = self.layout == SwitchLayout::Row;
+        let is_rtl = self
+            .direction
+            .unwrap_or_else(|| crate::direction(cx))
+            .is_rtl();
+        // The label follows the track in the reading direction.
+        let default_side = if is_rtl {
+            Placement::Left
+        } else {
+            Placement::Right
+        };
+        let label_side = match self.label_side.unwrap_or(default_side) {
+            Placement::Top | Placement::Bottom if is_row => {
+                if cfg!(debug_assertions) {
+                    static WARN: Once = Once::new();
+                    WARN.call_once(|| {
+                        log::warn!(
+                            "Switch: the `label_side` Top and Bottom are not supported by \
+                            `SwitchLayout::Row`, Right is used."
+                        )
+                    });
+                }
+                Placement::Right
+            }
+            side => side,
+        };
+        // The vertical switch has the focus ring around the track and the label, like the row.
+        let is_vertical = label_side.is_vertical();
+        let invalid = self.error || self.error_message.is_some();
+        let mut tooltip = self.tooltip;
+        // Also shown on the keyboard focus, the disabled switch isn't focusable.
+        tooltip.focus_handle = (!self.disabled).then(|| focus_handle.clone());
+        if self.read_only && tooltip.text.is_none() && tooltip.builder.is_none() {
+            tooltip.text = Some((t!("Switch.read_only").into(), None));
+        }
+        let disabled_reason = self.disabled_reason.filter(|_| self.disabled);
+        if let Some(reason) = &disabled_reason {
+            tooltip.text = Some((reason.clone(), None));
+            tooltip.builder = None;
+        }
+        let label_size = label_text_size(size, window);
+        // The truncation of the label in the last layout.
+        let label_truncation = self.label_truncate.then(|| {
+            window.use_keyed_state(format!("{}-label-truncation", self.id), cx, |_, _| {
+                TruncateState::default()
+            })
+        });
+        let truncated_label = label_truncation
+            .as_ref()
+            .filter(|state| state.read(cx).is_truncated())
+            .and_then(|_| self.label.as_ref()?.as_plain_str());
+        if let Some(label) = truncated_label {
+            match &mut tooltip.text {
+                Some((text, _)) => *text = format!("{}\n{}", label, text).into(),
+                None if tooltip.builder.is_none() => tooltip.text = Some((label.clone(), None)),
+                None => {}
+            }
+        }
+        let has_slots = self.prefix.is_some() || self.suffix.is_some();
+        let has_label = self.label.is_some() || has_slots;
+        let has_text = has_label || self.description.is_some() || self.error_message.is_some();
+        let ring = if invalid {
+            cx.theme().danger
+        } else {
+            cx.theme().ring
+        };
+        // The focus ring covers the border, so it isn't clipped and hugs the rounding.
+        let inner_ring = |thickness: Pixels| {
+            FocusRingStyle::new()
+                .color(ring)
+                .thickness(thickness)
+                .offset(-thickness)
+        };
 
-        let checked_bg = self.color.unwrap_or(cx.theme().primary);
-        let (bg, toggle_bg) = match checked {
-            true => (checked_bg, cx.theme().switch_thumb),
-            false => (cx.theme().switch, cx.theme().switch_thumb),
+        #[cfg(feature = "mcp")]
+        crate::mcp::mcp_set_element_properties(
+            window,
+            &self.id,
+            serde_json::json!({
+                "role": "switch",
+                "label": self
+                    .label
+                    .as_ref()
+                    .and_then(Text::as_plain_str)
+                    .map(|label| label.to_string()),
+                "description": self
+                    .description
+                    .as_ref()
+                    .and_then(Text::as_plain_str)
+                    .map(|description| description.to_string())
+                    .into_iter()
+                    .chain(disabled_reason.as_ref().map(|reason| reason.to_string()))
+                    .reduce(|description, reason| format!("{description} {reason}")),
+                "checked": checked,
+                "indeterminate": self.indeterminate,
+                "disabled": self.disabled,
+                "readonly": self.read_only,
+                "invalid": invalid,
+                "error": self
+                    .error_message
+                    .as_ref()
+                    .and_then(Text::as_plain_str)
+                    .map(|message| message.to_string()),
+            }),
+        );
+        #[cfg(feature = "mcp")]
+        crate::mcp::mcp_set_element_focus(window, &self.id, &focus_handle);
+
+        let thumb_color = self.thumb_color.unwrap_or(cx.theme().switch_thumb);
+        let is_segmented = matches!(self.variant, SwitchVariant::Segmented { .. });
+        // The custom colors derive their hover and disabled colors as the theme colors.
+        let derive = |color: Option<Hsla>, role: ColorRole, cx: &App| match color {
+            Some(color) => {
+                ColorSet::derive(color, thumb_color, cx.theme().background, cx.theme().mode)
+            }
+            None => cx.theme().colors(role),
+        };
+        let checked_colors = derive(self.checked_bg, ColorRole::Primary, cx);
+        let unchecked_colors = derive(self.unchecked_bg, ColorRole::Switch, cx);
+        let (colors, toggle_bg) = match checked || self.indeterminate {
+            // The highlight of the segments has the checked color.
+            _ if is_segmented => (unchecked_colors, checked_colors.base),
+            true => (checked_colors, thumb_color),
+            false => (unchecked_colors, thumb_color),
         };
 
+        // Dim by the factors, so the custom colors with their own alpha still look disabled.
         let (bg, toggle_bg) = if self.disabled {
             (
-                if checked { bg.alpha(0.5) } else { bg },
-                toggle_bg.alpha(0.35),
+                if (checked || self.indeterminate) && !is_segmented {
+                    colors.disabled
+                } else {
+                    colors.base
+                },
+                toggle_bg.opacity(0.35),
             )
         } else {
-            (bg, toggle_bg)
+            (colors.base, toggle_bg)
         };
+        let hover_bg = colors.hover;
 
-        let (bg_width, bg_height) = match self.size {
-            Size::XSmall | Size::Small => (px(28.), px(16.)),
-            _ => (px(36.), px(20.)),
+        let metrics = SwitchMetrics::new(size);
+        let inset = metrics.inset;
+        // The focus rings are thicker in high contrast.
+        let contrast = cx.theme().contrast();
+        let (ring_thickness, track_ring_thickness) = (
+            contrast.ring_thickness(px(1.)),
+            contrast.ring_thickness(inset),
+        );
+        let (spinner_size, glyph_size) = (metrics.spinner, metrics.glyph);
+        let segment_size = label_size * 0.875;
+        let (bg_width, bg_height, bar_width, bar_height) = match &self.variant {
+            SwitchVariant::Default => (metrics.width, metrics.height, metrics.thumb, metrics.thumb),
+            // The segments have the same width, so the highlight slides like the thumb.
+            SwitchVariant::Segmented {
+                off_label,
+                on_label,
+            } => {
+                let height = metrics.height + inset * 2.;
+                let (off_width, on_width) = (
+                    text_width(off_label, segment_size, window),
+                    text_width(on_label, segment_size, window),
+                );
+                // The padding is half the height on each side.
+                let width = off_width.max(on_width) + height;
+                (width * 2. + inset * 2., height, width, height - inset * 2.)
+            }
         };
-        let bar_width = match self.size {
-            Size::XSmall | Size::Small => px(12.),
-            _ => px(16.),
+        let loading = self.loading || pending;
+        // The thumb slides over the free space, which is also the space of the glyphs.
+        let max_x = bg_width - bar_width - inset * 2;
+        // Expand the hitbox of a clickable track to the touch target, centered on the track.
+        let touch_padding = (
+            ((self.touch_target - bg_width) / 2.).max(px(0.)),
+            ((self.touch_target - bg_height) / 2.).max(px(0.)),
+        );
+        let track_bounds = (toggle.is_some() && touch_padding != (px(0.), px(0.))).then(|| {
+            window.use_keyed_state(format!("{}-track", self.id), cx, |_, _| {
+                Bounds::<Pixels>::default()
+            })
+        });
+        let duration = (!self.disabled && self.animated.unwrap_or(!crate::reduce_motion(cx)))
+            .then_some(self.animation_duration);
+        let to = match (self.indeterminate, checked) {
+            (true, _) => 0.5,
+            (false, true) => 1.,
+            (false, false) => 0.,
         };
-        let inset = px(2.);
-        let radius = if cx.theme().radius >= px(4.) {
-            bg_height
+        let transition =
+            window.use_keyed_state(self.id.clone(), cx, |_, _| ThumbTransition::new(to));
+        let (position, animating) = transition.update(cx, |transition, _| {
+            // Keep the thumb in place while loading, the `checked` can change before the
+            // loading ends.
+            let to = if loading { transition.to } else { to };
+            transition.position(to, duration, Instant::now())
+        });
+        // Shake the thumb after a rejected `on_toggle_async`.
+        let shake = async_state
+            .filter(|_| duration.is_some())
+            .and_then(|state| state.update(cx, |state, _| state.shake(Instant::now())));
+        if animating || shake.is_some() {
+            crate::animation::request_animation_frame(window);
+        }
+        let shake = SHAKE_DISTANCE * shake.unwrap_or(0.);
+        // The glyphs are on the side opposite the thumb.
+        let (on_left, off_left) = if is_rtl {
+            (bar_width, px(0.))
         } else {
-            cx.theme().radius
+            (px(0.), bar_width)
         };
-
-        div().refine_style(&self.style).child(
-            h_flex()
-                .id(self.id.clone())
-                .gap_2()
-                .items_start()
-                .when(!self.disabled, |this| {
-                    this.track_focus(
-                        &focus_handle
-                            .tab_stop(self.tab_stop)
-                            .tab_index(self.tab_index),
-                    )
-                })
-                .when(self.label_side.is_left(), |this| this.flex_row_reverse())
-                .child(
-                    // Switch Bar (needs its own id for tooltip support)
+        let glyphs = [
+            (self.on_glyph.clone(), true, on_left, thumb_color),
+            (
+                self.off_glyph.clone(),
+                false,
+                off_left,
+                cx.theme().muted_foreground,
+            ),
+        ]
+        .into_iter()
+        .filter_map(|(glyph, on, left, color)| {
+            let glyph =
+                glyph.filter(|glyph| !is_segmented && glyph.fits(max_x, glyph_size, window))?;
+            // The glyph is shown on its side, and crossfades with the thumb.
+            let opacity = if on { position } else { 1. - position };
+            Some(
+                div()
+                    .absolute()
+                    .top_0()
+                    .left(left)
+                    .w(max_x)
+                    .h_full()
+                    .flex()
+                    .items_center()
+                    .justify_center()
+                    .text_color(color)
+                    .opacity(opacity)
+                    .child(glyph.render(glyph_size)),
+            )
+        })
+        .collect::<Vec<_>>();
+        let segments = match &self.variant {
+            SwitchVariant::Default => vec![],
+            SwitchVariant::Segmented {
+                off_label,
+                on_label,
+            } => [(off_label.clone(), false), (on_label.clone(), true)]
+                .into_iter()
+                .map(|(label, on)| {
+                    // The label is above the highlight, in the contrast color while active.
+                    let active = (position >= 0.5) == on;
                     div()
-                        .id(ElementId::Name(
-                            format!("switch-bar-{:?}", self.id).into(),
-                        ))
-                        .w(bg_width)
-                        .h(bg_height)
-                        .rounded(radius)
+                        .absolute()
+                        .top_0()
+                        .left(thumb_offset(if on { 1. } else { 0. }, max_x, is_rtl))
+                        .w(bar_width)
+                        .h_full()
                         .flex()
                         .items_center()
-                        .border(inset)
-                        .border_color(if is_focused {
-                            cx.theme().ring
+                        .justify_center()
+                        .text_size(segment_size)
+                        .whitespace_nowrap()
+                        .text_color(if active {
+                            cx.theme().primary_foreground
                         } else {
-                            cx.theme().transparent
+                            cx.theme().muted_foreground
                         })
-                        .bg(bg)
-                        .map(|this| self.tooltip.apply(this))
-                        .child(
-                            // Switch Toggle
-                            div()
-                                .rounded(radius)
-                                .bg(toggle_bg)
-                                .shadow_md()
-                                .size(bar_width)
-                                .map(|this| {
-                                    let prev_checked = toggle_state.read(cx);
-                                    if !self.disabled && *prev_checked != checked {
-                                        let duration = Duration::from_secs_f64(0.15);
-                                        cx.spawn({
-                                            let toggle_state = toggle_state.clone();
-                                            async move |cx| {
-                                                cx.background_executor().timer(duration).await;
-                                                _ = toggle_state
-                                                    .update(cx, |this, _| *this = checked);
-                                            }
-                                        })
-                                        .detach();
-
-                                        this.with_animation(
-                                            ElementId::NamedInteger("move".into(), checked as u64),
-                                            Animation::new(duration),
-                                            move |this, delta| {
-                                                let max_x = bg_width - bar_width - inset * 2;
-                                                let x = if checked {
-                                                    max_x * delta
-                                                } else {
-                                                    max_x - max_x * delta
-                                                };
-                                                this.left(x)
-                                            },
-                                        )
-                                        .into_any_element()
-                                    } else {
-                                        let max_x = bg_width - bar_width - inset * 2;
-                                        let x = if checked { max_x } else { px(0.) };
-                                        this.left(x).into_any_element()
+                        .when(self.disabled, |this| this.opacity(0.5))
+                        .child(label)
+                })
+                .collect(),
+        };
+        let radius = if cx.theme().radius >= px(4.) {
+            bg_height
+        } else {
+            cx.theme().radius
+        };
+
+        // Only call the `on_hover` on the changes, e.g. not again when re-rendered while hovered.
+        let on_hover = self.on_hover.filter(|_| !self.disabled).map(|on_hover| {
+            let hovered = window.use_keyed_state(format!("{}-hovered", self.id), cx, |_, _| false);
+            move |is_hovered: &bool, window: &mut Window, cx: &mut App| {
+                if hovered.read(cx) != is_hovered {
+                    hovered.update(cx, |this, _| *this = *is_hovered);
+                    on_hover(is_hovered, window, cx);
+                }
+            }
+        });
+        let hover_group: SharedString = format!("switch-hover-{:?}", self.id).into();
+        let label = self.label.map(|label| match &label_truncation {
+            Some(state) => label.truncate(TruncateMode::End).track_truncation(state),
+            None => label,
+        });
+        // The track comes first, the row has the label at the start and the track at the end.
+        let reversed = if is_row {
+            !is_rtl
+        } else {
+            label_side == Placement::Left
+        };
+
+        div()
+            .id(ElementId::Name(format!("switch-{:?}", self.id).into()))
+            .when(is_row, |this| this.w_full())
+            // The user style comes last, the track is sized in the children.
+            .refine_style(&self.style)
+            .when_some(on_hover, |this, on_hover| this.on_hover(on_hover))
+            .child(
+                h_flex()
+                    .id(self.id.clone())
+                    .group(hover_group.clone())
+                    // The tooltip is on the whole switch, also on the label, and when disabled.
+                    .map(|this| tooltip.apply(this))
+                    .gap_size(GapSize::Small, cx)
+                    .items_start()
+                    .when(!self.disabled, |this| {
+                        this.track_focus(
+                            &focus_handle
+                                .tab_stop(self.tab_stop.unwrap_or(!self.read_only))
+                                .tab_index(self.tab_index),
+                        )
+                    })
+                    .when(reversed, |this| this.flex_row_reverse())
+                    .when(is_vertical, |this| {
+                        this.map(|this| match label_side {
+                            Placement::Top => this.flex_col_reverse(),
+                            _ => this.flex_col(),
+                        })
+                        .items_center()
+                        .gap_1()
+                        .p_1()
+                        .rounded(cx.theme().radius)
+                        .border_1()
+                        .border_color(cx.theme().transparent)
+                        .focus_ring_with(
+                            is_focused,
+                            inner_ring(ring_thickness),
+                            window,
+                            cx,
+                        )
+                    })
+                    .when(is_row, |this| {
+                        // The focus ring is around the whole row instead of the track.
+                        this.w_full()
+                            .px(cx.theme().gap(GapSize::Small))
+                            .py_1p5()
+                            .rounded(cx.theme().radius)
+                            .border_1()
+                            .border_color(cx.theme().transparent)
+                            .focus_ring_with(is_focused, inner_ring(ring_thickness), window, cx)
+                            .when(clickable, |this| {
+                                this.hover(|this| this.bg(cx.theme().list_hover))
+                            })
+                    })
+                    .child(
+                        // Switch Bar (needs its own id for the drag)
+                        div()
+                            .id(ElementId::Name(format!("switch-bar-{:?}", self.id).into()))
+                            .when_some(toggle.clone(), |this, toggle| {
+                                let release = {
+                                    let transition = transition.clone();
+                                    move |_: &MouseUpEvent, window: &mut Window, cx: &mut App| {
+                                        let Some(released_checked) =
+                                            transition.update(cx, |transition, cx| {
+                                                cx.notify();
+                                                transition.release(duration, Instant::now())
+                                            })
+                                        else {
+                                            return;
+                                        };
+                                        // Without a change, the thumb animates back.
+                                        if released_checked != checked {
+                                            toggle(window, cx);
+                                        }
+                                    }
+                                };
+
+                                this.on_drag(DragThumb(transition.entity_id()), |drag, _, _, cx| {
+                                    cx.stop_propagation();
+                                    cx.new(|_| drag.clone())
+                                })
+                                .on_drag_move({
+                                    let transition = transition.clone();
+                                    move |event: &DragMoveEvent<DragThumb>, _, cx| {
+                                        if event.drag(cx).0 != transition.entity_id() {
+                                            return;
+                                        }
+
+                                        // The center of the thumb follows the pointer.
+                                        let x = event.event.position.x
+                                            - event.bounds.left()
+                                            - inset
+                                            - bar_width / 2.;
+                                        let position = (x / max_x).clamp(0., 1.);
+                                        let position =
+                                            if is_rtl { 1. - position } else { position };
+                                        transition.update(cx, |transition, cx| {
+                                            transition.drag = Some(position);
+                                            cx.notify();
+                                        });
                                     }
+                                })
+                                .on_mouse_up(MouseButton::Left, release.clone())
+                                .on_mouse_up_out(MouseButton::Left, release)
+                            })
+                            .w(bg_width)
+                            .h(bg_height)
+                            .rounded(radius)
+                            .flex()
+                            .items_center()
+                            .border(inset)
+                            // The invalid track keeps the danger border, also when unfocused.
+                            .border_color(if invalid {
+                                ring
+                            } else {
+                                cx.theme().transparent
+                            })
+                            .focus_ring_with(
+                                is_focused && !is_row && !is_vertical,
+                                inner_ring(track_ring_thickness),
+                                window,
+                                cx,
+                            )
+                            .bg(bg)
+                            .when(clickable, |this| {
+                                let hover_style = self.hover_style;
+                                this.group_hover(hover_group, move |style| {
+                                    let mut style = style.bg(hover_bg);
+                                    style.refine(&hover_style);
+                                    style
+                                })
+                            })
+                            .when_some(
+                                toggle.clone().zip(track_bounds),
+                                |this, (toggle, track_bounds)| {
+                                    let (pad_x, pad_y) = touch_padding;
+                                    this.on_prepaint({
+                                        let track_bounds = track_bounds.clone();
+                                        move |bounds, _, cx| {
+                                            track_bounds.update(cx, |this, _| *this = bounds)
+                                        }
+                                    })
+                                    .child(
+                                        div()
+                                            .id(ElementId::Name(
+                                                format!("switch-touch-target-{:?}", self.id).into(),
+                                            ))
+                                            .absolute()
+                                            .left(-pad_x - inset)
+                                            .top(-pad_y - inset)
+                                            .w(bg_width + pad_x * 2.)
+                                            .h(bg_height + pad_y * 2.)
+                                            .on_click(move |event, window, cx| {
+                                                if cx.has_active_drag() {
+                                                    return;
+                                                }
+                                                let distance = distance_to(
+                                                    *track_bounds.read(cx),
+                                                    event.position(),
+                                                );
+                                                claim_tap(distance, toggle.clone(), window, cx);
+                                            }),
+                                    )
+                                },
+                            )
+                            .children(glyphs)
+                            .child(
+                                // Switch Toggle
+                                div()
+                                    .rounded(radius)
+                                    .bg(toggle_bg)
+                                    .shadow_md()
+                                    .w(bar_width)
+                                    .h(bar_height)
+                                    .when(loading, |this| {
+                                        this.flex().items_center().justify_center().child(
+                                            Icon::new(IconName::Loader)
+                                                .with_size(Size::Size(spinner_size))
+                                                .text_color(cx.theme().muted_foreground)
+                                                .spin(SPINNER_DURATION),
+                                        )
+                                    })
+                                    .left(thumb_offset(position, max_x, is_rtl) + shake),
+                            )
+                            .children(segments),
+                    )
+                    .when(has_text, |this| {
+                        // The label line has the height of the track, so the track keeps aligned
+                        // with the first line of the label.
+                        this.child(
+                            v_flex()
+                                .when(!is_vertical, |this| this.flex_1())
+                                .when(is_vertical, |this| this.items_center().text_center())
+                                .overflow_hidden()
+                                .when(has_label, |this| {
+                                    // The slots are dimmed like the track, the label keeps its
+                                    // color to stay readable.
+                                    let slot = |element: AnyElement| {
+                                        div()
+                                            .flex()
+                                            .flex_none()
+                                            .items_center()
+                                            .h(bg_height)
+                                            .when(self.disabled, |this| this.opacity(0.5))
+                                            .child(element)
+                                    };
+                                    this.child(
+                                        div()
+                                            .line_height(bg_height)
+                                            .when_some(self.prefix, |this, prefix| {
+                                                this.child(slot(prefix))
+                                            })
+                                            .children(label)
+                                            .when_some(self.suffix, |this, suffix| {
+                                                this.child(slot(suffix))
+                                            })
+                                            .text_size(label_size)
+                                            .when(
+                                                self.read_only
+                                                    || self.keybinding.is_some()
+                                                    || has_slots,
+                                                |this| this.flex().items_center().gap_1(),
+                                            )
+                                            .when(self.read_only, |this| {
+                                                this.child(
+                                                    Icon::new(IconName::Lock)
+                                                        .xsmall()
+                                                        .text_color(cx.theme().muted_foreground),
+                                                )
+                                            })
+                                            .when_some(self.keybinding, |this, kbd| {
+                                                // The hint shrinks first in narrow layouts.
+                                                this.child(
+                                                    kbd.ml_auto()
+                                                        .flex_shrink()
+                                                        .min_w_0()
+                                                        .overflow_hidden()
+                                                        .whitespace_nowrap()
+                                                        .text_ellipsis(),
+                                                )
+                                            }),
+                                    )
+                                })
+                                .when_some(self.description, |this, description| {
+                                    this.child(
+                                        div()
+                                            .text_sm()
+                                            .text_color(cx.theme().muted_foreground)
+                                            .child(description),
+                                    )
+                                })
+                                .when_some(self.error_message, |this, message| {
+                                    this.child(
+                                        div()
+                                            .text_sm()
+                                            .text_color(cx.theme().danger)
+                                            .child(message),
+                                    )
                                 }),
-                        ),
-                )
-                .when_some(self.label, |this, label| {
-                    this.child(div().line_height(bg_height).child(label).map(
-                        |this| match self.size {
-                            Size::XSmall | Size::Small => this.text_sm(),
-                            _ => this.text_base(),
-                        },
-                    ))
-                })
-                .on_mouse_down(gpui::MouseButton::Left, |_, window, _| {
-                    // Avoid focus on mouse down.
-                    window.prevent_default();
-                })
-                .when_some(
-                    on_click
-                        .as_ref()
-                        .map(|c| c.clone())
-                        .filter(|_| !self.disabled),
-                    |this, on_click| {
-                        let toggle_state = toggle_state.clone();
-                        this.on_click(move |_, window, cx| {
-                            cx.stop_propagation();
-                            _ = toggle_state.update(cx, |this, _| *this = checked);
-                            on_click(&!checked, window, cx);
+                        )
+                    })
+                    .on_mouse_down(gpui::MouseButton::Left, |_, window, _| {
+                        // Avoid focus on mouse down.
+                        window.prevent_default();
+                    })
+                    .when_some(toggle, |this, toggle| {
+                        this.on_click({
+                            let toggle = toggle.clone();
+                            move |_, window, cx| {
+                                // The drag toggles on release.
+                                if !cx.has_active_drag() {
+                                    // The switch itself wins over the touch targets nearby.
+                                    cx.default_global::<PendingTap>().0 = None;
+                                    toggle(window, cx);
+                                }
+                            }
+                        })
+                        .on_key_down(move |event, window, cx| {
+                            if matches!(event.keystroke.key.as_str(), "space" | "enter") {
+                                toggle(window, cx);
+                            }
                         })
-                    },
-                ),
-        )
+                    }),
+            )
+    }
+}
+
+/// An item of the [`SwitchGroup`].
+pub struct SwitchGroupItem {
+    key: SharedString,
+    label: Option<Text>,
+    description: Option<Text>,
+    checked: bool,
+    disabled: bool,
+}
+
+impl SwitchGroupItem {
+    /// Create a new item with the key, the key is passed to the `on_change` of the group.
+    pub fn new(key: impl Into<SharedString>) -> Self {
+        Self {
+            key: key.into(),
+            label: None,
+            description: None,
+            checked: false,
+            disabled: false,
+        }
+    }
+
+    /// Set the label of the item.
+    pub fn label(mut self, label: impl Into<Text>) -> Self {
+        self.label = Some(label.into());
+        self
+    }
+
+    /// Set the description of the item, rendered in muted color under the label.
+    pub fn description(mut self, description: impl Into<Text>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// Set the initial checked state of the item, default is false.
+    pub fn checked(mut self, checked: bool) -> Self {
+        self.checked = checked;
+        self
+    }
+
+    /// Set the disabled state of the item.
+    pub fn disabled(mut self, disabled: bool) -> Self {
+        self.disabled = disabled;
+        self
+    }
+}
+
+/// The state of the [`SwitchGroup`], kept across renders.
+struct SwitchGroupState {
+    checked: HashMap<SharedString, bool>,
+    focus_handles: HashMap<SharedString, FocusHandle>,
+    toggle_all_focus_handle: FocusHandle,
+}
+
+/// A group of related [`Switch`] with a group level `on_change`.
+///
+/// The group keeps the checked state of the items, initialized by [`SwitchGroupItem::checked`].
+/// The items are a [`FocusGroup`]: `tab` focuses the item focused last, the arrow keys, `home`
+/// and `end` move the focus between the items.
+#[derive(IntoElement)]
+pub struct SwitchGroup {
+    id: ElementId,
+    style: StyleRefinement,
+    label: Option<Text>,
+    items: Vec<SwitchGroupItem>,
+    layout: Axis,
+    toggle_all: Option<Text>,
+    size: Option<Size>,
+    disabled: bool,
+    on_change: Option<Rc<dyn Fn(&SharedString, &bool, &mut Window, &mut App)>>,
+}
+
+impl SwitchGroup {
+    fn new(id: impl Into<ElementId>) -> Self {
+        Self {
+            id: id.into(),
+            style: StyleRefinement::default(),
+            label: None,
+            items: vec![],
+            layout: Axis::Vertical,
+            toggle_all: None,
+            size: None,
+            disabled: false,
+            on_change: None,
+        }
+    }
+
+    /// Create a new Switch group with default Vertical layout.
+    pub fn vertical(id: impl Into<ElementId>) -> Self {
+        Self::new(id)
+    }
+
+    /// Create a new Switch group with Horizontal layout.
+    pub fn horizontal(id: impl Into<ElementId>) -> Self {
+        Self::new(id).layout(Axis::Horizontal)
+    }
+
+    /// Set the layout of the Switch group. Default is `Axis::Vertical`.
+    pub fn layout(mut self, layout: Axis) -> Self {
+        self.layout = layout;
+        self
+    }
+
+    /// Set the label of the group, rendered above the items.
+    pub fn label(mut self, label: impl Into<Text>) -> Self {
+        self.label = Some(label.into());
+        self
+    }
+
+    /// Add a master switch with the label to toggle all the items.
+    ///
+    /// The master switch is checked when all the items are on, and indeterminate when mixed.
+    /// The disabled items are not changed.
+    pub fn toggle_all(mut self, label: impl Into<Text>) -> Self {
+        self.toggle_all = Some(label.into());
+        self
+    }
+
+    /// Set the disabled state of all the items.
+    pub fn disabled(mut self, disabled: bool) -> Self {
+        self.disabled = disabled;
+        self
+    }
+
+    /// Add on_change handler when an item changes.
+    ///
+    /// The parameters are the key and the new checked state of the item, the master switch calls
+    /// it for each changed item.
+    pub fn on_change(
+        mut self,
+        handler: impl Fn(&SharedString, &bool, &mut Window, &mut App) + 'static,
+    ) -> Self {
+        self.on_change = Some(Rc::new(handler));
+        self
+    }
+
+    /// Add an item.
+    pub fn child(mut self, item: SwitchGroupItem) -> Self {
+        self.items.push(item);
+        self
+    }
+
+    /// Add multiple items.
+    pub fn children(mut self, items: impl IntoIterator<Item = SwitchGroupItem>) -> Self {
+        self.items.extend(items);
+        self
+    }
+}
+
+impl Styled for SwitchGroup {
+    fn style(&mut self) -> &mut StyleRefinement {
+        &mut self.style
+    }
+}
+
+impl Sizable for SwitchGroup {
+    fn with_size(mut self, size: impl Into<Size>) -> Self {
+        self.size = Some(size.into());
+        self
+    }
+}
+
+/// Returns the keys of the items to change by the master switch, and the new checked state.
+fn toggle_all_changes(items: &[(SharedString, bool, bool)]) -> (Vec<SharedString>, bool) {
+    let enabled = items.iter().filter(|(_, _, disabled)| !disabled);
+    // Turn all on, unless all are on already.
+    let checked = !enabled.clone().all(|(_, checked, _)| *checked);
+    let keys = enabled
+        .filter(|(_, item_checked, _)| *item_checked != checked)
+        .map(|(key, _, _)| key.clone())
+        .collect();
+    (keys, checked)
+}
+
+impl RenderOnce for SwitchGroup {
+    fn render(self, window: &mut Window, cx: &mut App) -> impl IntoElement {
+        let state = window.use_keyed_state(self.id.clone(), cx, |_, cx| SwitchGroupState {
+            checked: HashMap::default(),
+            focus_handles: HashMap::default(),
+            toggle_all_focus_handle: cx.focus_handle(),
+        });
+        state.update(cx, |state, cx| {
+            for item in &self.items {
+                state
+                    .checked
+                    .entry(item.key.clone())
+                    .or_insert(item.checked);
+                state
+                    .focus_handles
+                    .entry(item.key.clone())
+                    .or_insert_with(|| cx.focus_handle());
+            }
+        });
+
+        let disabled = self.disabled;
+        let on_change = self.on_change.clone();
+        // The (key, checked, disabled) of the items.
+        let items = self
+            .items
+            .iter()
+            .map(|item| {
+                let checked = state.read(cx).checked[&item.key];
+                (item.key.clone(), checked, disabled || item.disabled)
+            })
+            .collect::<Vec<_>>();
+
+        // The enabled switches are navigated with the arrow keys, the tab stop is the one focused
+        // last.
+        let focus_group = FocusGroup::new(
+            ElementId::Name(format!("switch-group-focus-{:?}", self.id).into()),
+            window,
+            cx,
+        );
+
+        let set_checked = {
+            let state = state.clone();
+            Rc::new(
+                move |keys: Vec<SharedString>, checked: bool, window: &mut Window, cx: &mut App| {
+                    state.update(cx, |state, cx| {
+                        for key in &keys {
+                            state.checked.insert(key.clone(), checked);
+                        }
+                        cx.notify();
+                    });
+                    if let Some(on_change) = &on_change {
+                        for key in &keys {
+                            on_change(key, &checked, window, cx);
+                        }
+                    }
+                },
+            )
+        };
+
+        let toggle_all = self.toggle_all.map(|label| {
+            let enabled = items.iter().filter(|(_, _, disabled)| !disabled);
+            let all_on = enabled.clone().all(|(_, checked, _)| *checked);
+            let all_off = enabled.clone().all(|(_, checked, _)| !checked);
+            let all_disabled = enabled.count() == 0;
+            let set_checked = set_checked.clone();
+            let items = items.clone();
+            let focus_handle = state.read(cx).toggle_all_focus_handle.clone();
+
+            Switch::new("toggle-all")
+                .label(label)
+                .when_some(self.size, |this, size| this.with_size(size))
+                .checked(all_on && !all_disabled)
+                .indeterminate(!all_on && !all_off)
+                .disabled(all_disabled)
+                .track_focus(&focus_handle)
+                .when(!all_disabled, |this| {
+                    this.tab_stop(focus_group.item(&focus_handle, window, cx))
+                })
+                .on_click(move |_, window, cx| {
+                    let (keys, checked) = toggle_all_changes(&items);
+                    set_checked(keys, checked, window, cx);
+                })
+        });
+
+        let switches = self
+            .items
+            .into_iter()
+            .zip(items.iter())
+            .map(|(item, (key, checked, disabled))| {
+                let key = key.clone();
+                let set_checked = set_checked.clone();
+                let focus_handle = state.read(cx).focus_handles[&key].clone();
+                Switch::new(SharedString::from(format!("item-{}", key)))
+                    .when_some(self.size, |this, size| this.with_size(size))
+                    .checked(*checked)
+                    .disabled(*disabled)
+                    .track_focus(&focus_handle)
+                    .when(!disabled, |this| {
+                        this.tab_stop(focus_group.item(&focus_handle, window, cx))
+                    })
+                    .when_some(item.label, |this, label| this.label(label))
+                    .when_some(item.description, |this, description| {
+                        this.description(description)
+                    })
+                    .on_click(move |checked, window, cx| {
+                        set_checked(vec![key.clone()], *checked, window, cx);
+                    })
+            })
+            .collect::<Vec<_>>();
+
+        let base = if self.layout.is_vertical() {
+            v_flex().gap_3()
+        } else {
+            h_flex().gap_4().flex_wrap().items_start()
+        };
+
+        v_flex()
+            .id(self.id)
+            .gap_3()
+            .refine_style(&self.style)
+            .focus_group(&focus_group)
+            .when_some(self.label, |this, label| {
+                this.child(div().text_sm().font_medium().child(label))
+            })
+            .children(toggle_all)
+            .child(base.children(switches))
+    }
+}
+
+/// Helpers to query and toggle the rendered switches in the tests, with the `test-support`
+/// feature.
+///
+/// The switches are found by the id in the last render of the window, run the pending effects,
+/// e.g. `cx.run_until_parked()`, before querying a changed switch.
+///
+/// ```ignore
+/// cx.update(|window, cx| assert!(switch::test::toggle(window, "wifi", cx)));
+/// cx.run_until_parked();
+/// cx.update(|window, cx| assert_eq!(switch::test::is_checked(window, "wifi", cx), Some(true)));
+/// ```
+#[cfg(any(test, feature = "test-support"))]
+pub mod test {
+    use std::{collections::HashMap, rc::Rc};
+
+    use gpui::{App, ElementId, Global, Window, WindowId};
+
+    struct RenderedSwitch {
+        checked: bool,
+        toggle: Option<Rc<dyn Fn(&mut Window, &mut App)>>,
+    }
+
+    #[derive(Default)]
+    struct RenderedSwitches(HashMap<(WindowId, ElementId), RenderedSwitch>);
+
+    impl Global for RenderedSwitches {}
+
+    pub(super) fn record(
+        window: &Window,
+        id: &ElementId,
+        checked: bool,
+        toggle: Option<Rc<dyn Fn(&mut Window, &mut App)>>,
+        cx: &mut App,
+    ) {
+        let key = (window.window_handle().window_id(), id.clone());
+        cx.default_global::<RenderedSwitches>()
+            .0
+            .insert(key, RenderedSwitch { checked, toggle });
+    }
+
+    /// Returns the checked state of the switch, or `None` if it's not rendered in the window.
+    pub fn is_checked(window: &Window, id: impl Into<ElementId>, cx: &App) -> Option<bool> {
+        let key = (window.window_handle().window_id(), id.into());
+        cx.try_global::<RenderedSwitches>()?
+            .0
+            .get(&key)
+            .map(|switch| switch.checked)
+    }
+
+    /// Toggle the switch like a click, the `on_click` is called.
+    ///
+    /// Returns false if the switch is not rendered in the window, or ignores the clicks, e.g.
+    /// disabled or read-only.
+    pub fn toggle(window: &mut Window, id: impl Into<ElementId>, cx: &mut App) -> bool {
+        let key = (window.window_handle().window_id(), id.into());
+        let Some(toggle) = cx
+            .try_global::<RenderedSwitches>()
+            .and_then(|switches| switches.0.get(&key)?.toggle.clone())
+        else {
+            return false;
+        };
+        toggle(window, cx);
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{GlobalState, disabled_scope, theme::Theme};
+    use gpui::{AppContext as _, Context, Render, TestAppContext};
+    use std::cell::{Cell, RefCell};
+
+    struct SwitchView {
+        /// The switch is uncontrolled if `None`.
+        checked: Option<bool>,
+        disabled: bool,
+        read_only: bool,
+        variant: SwitchVariant,
+        clicked: Rc<Cell<Option<bool>>>,
+    }
+
+    impl Render for SwitchView {
+        fn render(&mut self, _: &mut Window, _: &mut Context<Self>) -> impl IntoElement {
+            let clicked = self.clicked.clone();
+            let switch = Switch::new("switch")
+                .disabled(self.disabled)
+                .read_only(self.read_only)
+                .variant(self.variant.clone())
+                .on_click(move |checked, _, _| clicked.set(Some(*checked)));
+            match self.checked {
+                Some(checked) => switch.checked(checked),
+                None => switch.default_checked(false),
+            }
+        }
+    }
+
+    const LONG_LABEL: &str = "Send me a notification for every new message";
+
+    struct TruncateView {
+        width: Pixels,
+    }
+
+    impl Render for TruncateView {
+        fn render(&mut self, _: &mut Window, _: &mut Context<Self>) -> impl IntoElement {
+            div().w(self.width).child(
+                Switch::new("switch")
+                    .label(LONG_LABEL)
+                    .label_truncate(true)
+                    .tooltip("Notifications"),
+            )
+        }
+    }
+
+    struct FocusView {
+        focus_handle: FocusHandle,
+        clicked: Rc<Cell<Option<bool>>>,
+    }
+
+    impl Render for FocusView {
+        fn render(&mut self, _: &mut Window, _: &mut Context<Self>) -> impl IntoElement {
+            let clicked = self.clicked.clone();
+            Switch::new("switch")
+                .checked(false)
+                .track_focus(&self.focus_handle)
+                .on_click(move |checked, _, _| clicked.set(Some(*checked)))
+        }
+    }
+
+    struct FormView {
+        focus_handle: FocusHandle,
+        form: Entity<FormState>,
+        visible: bool,
+    }
+
+    impl Render for FormView {
+        fn render(&mut self, _: &mut Window, _: &mut Context<Self>) -> impl IntoElement {
+            div().when(self.visible, |this| {
+                this.child(
+                    Switch::new("switch")
+                        .default_checked(true)
+                        .name("notifications.email")
+                        .form(&self.form)
+                        .track_focus(&self.focus_handle),
+                )
+            })
+        }
+    }
+
+    struct HoverView {
+        disabled: bool,
+        hovers: Rc<RefCell<Vec<bool>>>,
+    }
+
+    impl Render for HoverView {
+        fn render(&mut self, _: &mut Window, _: &mut Context<Self>) -> impl IntoElement {
+            let hovers = self.hovers.clone();
+            Switch::new("switch")
+                .label("Preview")
+                .disabled(self.disabled)
+                .on_hover(move |hovered, _, _| hovers.borrow_mut().push(*hovered))
+        }
+    }
+
+    struct StaticView {
+        clicked: Rc<Cell<Option<bool>>>,
+    }
+
+    impl Render for StaticView {
+        fn render(&mut self, _: &mut Window, _: &mut Context<Self>) -> impl IntoElement {
+            let clicked = self.clicked.clone();
+            Switch::new("switch").on_click(move |checked, _, _| clicked.set(Some(*checked)))
+        }
+    }
+
+    struct BindView {
+        value: Entity<bool>,
+    }
+
+    impl Render for BindView {
+        fn render(&mut self, _: &mut Window, _: &mut Context<Self>) -> impl IntoElement {
+            Switch::new("switch").bind(&self.value)
+        }
+    }
+
+    struct ScopeView {
+        disabled: bool,
+        nested: bool,
+        clicked: Rc<Cell<Option<bool>>>,
+    }
+
+    impl Render for ScopeView {
+        fn render(&mut self, _: &mut Window, _: &mut Context<Self>) -> impl IntoElement {
+            let clicked = self.clicked.clone();
+            let switch = Switch::new("switch")
+                .default_checked(false)
+                .on_click(move |checked, _, _| clicked.set(Some(*checked)));
+            disabled_scope(self.disabled).child(if self.nested {
+                disabled_scope(false).child(switch).into_any_element()
+            } else {
+                switch.into_any_element()
+            })
+        }
+    }
+
+    fn switch_view(
+        checked: Option<bool>,
+        cx: &mut TestAppContext,
+    ) -> (
+        Entity<SwitchView>,
+        &mut gpui::VisualTestContext,
+        Rc<Cell<Option<bool>>>,
+    ) {
+        cx.update(|cx| cx.set_global(Theme::default()));
+        let clicked = Rc::new(Cell::new(None));
+        let (view, cx) = cx.add_window_view(|_, _| SwitchView {
+            checked,
+            disabled: false,
+            read_only: false,
+            variant: SwitchVariant::Default,
+            clicked: clicked.clone(),
+        });
+        cx.run_until_parked();
+        cx.update(|window, cx| window.focus_next(cx));
+        (view, cx, clicked)
+    }
+
+    #[gpui::test]
+    fn test_switch_clickable(_cx: &mut gpui::TestAppContext) {
+        assert!(Switch::new("switch").on_click(|_, _, _| {}).clickable());
+        assert!(!Switch::new("switch").checked(false).clickable());
+        // An uncontrolled switch toggles itself, a bare switch is a static display.
+        assert!(Switch::new("switch").default_checked(false).clickable());
+        assert!(!Switch::new("switch").clickable());
+        assert!(
+            !Switch::new("switch")
+                .disabled(true)
+                .on_click(|_, _, _| {})
+                .clickable()
+        );
+
+        let loading = Switch::new("switch").loading(true).on_click(|_, _, _| {});
+        assert!(loading.loading);
+        assert!(!loading.clickable());
+    }
+
+    #[gpui::test]
+    fn test_switch_keyboard(cx: &mut TestAppContext) {
+        let (view, cx, clicked) = switch_view(Some(false), cx);
+
+        cx.simulate_keystrokes("space");
+        assert_eq!(clicked.take(), Some(true));
+
+        view.update(cx, |view, cx| {
+            view.checked = Some(true);
+            cx.notify();
+        });
+        cx.run_until_parked();
+        cx.simulate_keystrokes("enter");
+        assert_eq!(clicked.take(), Some(false));
+
+        // Other keys don't toggle the switch.
+        cx.simulate_keystrokes("a");
+        assert_eq!(clicked.take(), None);
+    }
+
+    #[gpui::test]
+    fn test_switch_feedback(cx: &mut TestAppContext) {
+        let (view, cx, clicked) = switch_view(Some(false), cx);
+        let kinds = Rc::new(RefCell::new(Vec::new()));
+        cx.update(|_, cx| {
+            cx.set_global(GlobalState::new());
+            let kinds = kinds.clone();
+            crate::set_feedback_handler(move |kind, _| kinds.borrow_mut().push(kind), cx);
+        });
+
+        cx.simulate_keystrokes("space");
+        assert_eq!(clicked.take(), Some(true));
+        assert_eq!(*kinds.borrow(), vec![FeedbackKind::Toggle { on: true }]);
+
+        // The ignored keys of the read-only switch don't call the handler.
+        view.update(cx, |view, cx| {
+            view.read_only = true;
+            cx.notify();
+        });
+        cx.run_until_parked();
+        cx.simulate_keystrokes("space");
+        assert_eq!(kinds.borrow().len(), 1);
+    }
+
+    #[gpui::test]
+    fn test_helpers_uncontrolled(cx: &mut TestAppContext) {
+        let (_, cx, clicked) = switch_view(None, cx);
+        cx.update(|window, cx| {
+            assert_eq!(test::is_checked(window, "switch", cx), Some(false));
+            assert_eq!(test::is_checked(window, "other", cx), None);
+            assert!(!test::toggle(window, "other", cx));
+            assert!(test::toggle(window, "switch", cx));
+        });
+        assert_eq!(clicked.take(), Some(true));
+        cx.run_until_parked();
+        cx.update(|window, cx| assert_eq!(test::is_checked(window, "switch", cx), Some(true)));
+    }
+
+    #[gpui::test]
+    fn test_helpers_controlled(cx: &mut TestAppContext) {
+        let (view, cx, clicked) = switch_view(Some(false), cx);
+        cx.update(|window, cx| assert!(test::toggle(window, "switch", cx)));
+        assert_eq!(clicked.take(), Some(true));
+        cx.run_until_parked();
+        // The controlled switch only changes with the new `checked`.
+        cx.update(|window, cx| assert_eq!(test::is_checked(window, "switch", cx), Some(false)));
+
+        view.update(cx, |view, cx| {
+            view.checked = Some(true);
+            cx.notify();
+        });
+        cx.run_until_parked();
+        cx.update(|window, cx| assert_eq!(test::is_checked(window, "switch", cx), Some(true)));
+    }
+
+    #[gpui::test]
+    fn test_helpers_disabled_and_read_only(cx: &mut TestAppContext) {
+        let (view, cx, clicked) = switch_view(None, cx);
+        for (disabled, read_only) in [(true, false), (false, true)] {
+            view.update(cx, |view, cx| {
+                view.disabled = disabled;
+                view.read_only = read_only;
+                cx.notify();
+            });
+            cx.run_until_parked();
+            cx.update(|window, cx| {
+                assert!(!test::toggle(window, "switch", cx));
+                assert_eq!(test::is_checked(window, "switch", cx), Some(false));
+            });
+            assert_eq!(clicked.take(), None);
+        }
+    }
+
+    #[gpui::test]
+    fn test_switch_disabled_scope(cx: &mut TestAppContext) {
+        cx.update(|cx| cx.set_global(Theme::default()));
+        let clicked = Rc::new(Cell::new(None));
+        let (view, cx) = cx.add_window_view(|_, _| ScopeView {
+            disabled: true,
+            nested: false,
+            clicked: clicked.clone(),
+        });
+        cx.run_until_parked();
+
+        // Disabled by the scope, and by a nested scope not disabled.
+        for nested in [false, true] {
+            view.update(cx, |view, cx| {
+                view.nested = nested;
+                cx.notify();
+            });
+            cx.run_until_parked();
+            cx.update(|window, cx| {
+                assert!(!test::toggle(window, "switch", cx));
+                assert_eq!(test::is_checked(window, "switch", cx), Some(false));
+            });
+            assert_eq!(clicked.take(), None);
+        }
+        cx.update(|_, cx| assert!(!inherited_disabled(cx)));
+
+        view.update(cx, |view, cx| {
+            view.disabled = false;
+            cx.notify();
+        });
+        cx.run_until_parked();
+        cx.update(|window, cx| assert!(test::toggle(window, "switch", cx)));
+        assert_eq!(clicked.take(), Some(true));
+    }
+
+    #[gpui::test]
+    fn test_switch_read_only(cx: &mut TestAppContext) {
+        let (view, cx, clicked) = switch_view(Some(false), cx);
+        view.update(cx, |view, cx| {
+            view.read_only = true;
+            cx.notify();
+        });
+        cx.run_until_parked();
+
+        // The focused read-only switch ignores the keys.
+        cx.simulate_keystrokes("space");
+        cx.simulate_keystrokes("enter");
+        assert_eq!(clicked.take(), None);
+
+        let switch = Switch::new("switch").read_only(true).on_click(|_, _, _| {});
+        assert!(!switch.clickable());
+        assert_eq!(switch.tab_stop, None);
+        assert_eq!(
+            Switch::new("switch")
+                .read_only(true)
+                .tab_stop(true)
+                .tab_stop,
+            Some(true)
+        );
+    }
+
+    #[gpui::test]
+    fn test_switch_track_focus(cx: &mut TestAppContext) {
+        cx.update(|cx| cx.set_global(Theme::default()));
+        let clicked = Rc::new(Cell::new(None));
+        let (view, cx) = cx.add_window_view(|_, cx| FocusView {
+            focus_handle: cx.focus_handle(),
+            clicked: clicked.clone(),
+        });
+        cx.run_until_parked();
+
+        // The app moves the focus to the switch with its own focus handle.
+        cx.update(|window, cx| {
+            let focus_handle = view.read(cx).focus_handle.clone();
+            focus_handle.focus(window, cx);
+        });
+        cx.run_until_parked();
+        cx.simulate_keystrokes("space");
+        assert_eq!(clicked.take(), Some(true));
+        cx.update(|window, cx| assert!(view.read(cx).focus_handle.is_focused(window)));
+    }
+
+    #[gpui::test]
+    fn test_switch_form(cx: &mut TestAppContext) {
+        cx.update(|cx| cx.set_global(Theme::default()));
+        let form = cx.new(|_| {
+            FormState::new().validator("notifications.email", |value| {
+                (value != &serde_json::Value::Bool(true)).then(|| "Required".into())
+            })
+        });
+        let (view, cx) = cx.add_window_view(|_, cx| FormView {
+            focus_handle: cx.focus_handle(),
+            form: form.clone(),
+            visible: true,
+        });
+        cx.run_until_parked();
+        cx.update(|_, cx| {
+            assert_eq!(
+                form.read(cx).values(),
+                serde_json::json!({ "notifications.email": true })
+            );
+        });
+
+        cx.update(|window, cx| {
+            let focus_handle = view.read(cx).focus_handle.clone();
+            focus_handle.focus(window, cx);
+        });
+        cx.run_until_parked();
+        cx.simulate_keystrokes("space");
+        cx.update(|_, cx| {
+            let form = form.read(cx);
+            assert_eq!(
+                form.value("notifications.email"),
+                Some(&serde_json::Value::Bool(false))
+            );
+            assert_eq!(
+                form.error("notifications.email"),
+                Some(&SharedString::from("Required"))
+            );
+        });
+
+        // The reset turns the uncontrolled switch back on.
+        form.update(cx, |form, cx| form.reset(cx));
+        cx.run_until_parked();
+        cx.simulate_keystrokes("space");
+        cx.update(|_, cx| assert!(form.read(cx).is_dirty()));
+
+        // The switch unregisters once it is no longer rendered.
+        view.update(cx, |view, cx| {
+            view.visible = false;
+            cx.notify();
+        });
+        cx.run_until_parked();
+        cx.update(|_, cx| assert_eq!(form.read(cx).values(), serde_json::json!({})));
+    }
+
+    #[gpui::test]
+    fn test_switch_on_hover(cx: &mut TestAppContext) {
+        cx.update(|cx| cx.set_global(Theme::default()));
+        let hovers = Rc::new(RefCell::new(Vec::new()));
+        let (view, cx) = cx.add_window_view(|_, _| HoverView {
+            disabled: false,
+            hovers: hovers.clone(),
+        });
+        cx.run_until_parked();
+
+        let inside = gpui::point(px(10.), px(10.));
+        let outside = gpui::point(px(300.), px(300.));
+        let modifiers = gpui::Modifiers::default();
+        cx.simulate_mouse_move(inside, None, modifiers);
+        cx.simulate_mouse_move(inside + gpui::point(px(2.), px(2.)), None, modifiers);
+        cx.simulate_mouse_move(outside, None, modifiers);
+        cx.run_until_parked();
+        assert_eq!(*hovers.borrow(), vec![true, false]);
+
+        // The disabled switch doesn't call the handler.
+        hovers.borrow_mut().clear();
+        view.update(cx, |view, cx| {
+            view.disabled = true;
+            cx.notify();
+        });
+        cx.run_until_parked();
+        cx.simulate_mouse_move(inside, None, modifiers);
+        cx.run_until_parked();
+        assert!(hovers.borrow().is_empty());
+    }
+
+    #[gpui::test]
+    fn test_switch_controlled(cx: &mut TestAppContext) {
+        let (_, cx, clicked) = switch_view(Some(false), cx);
+
+        // Without a new `checked`, the controlled switch keeps the state.
+        cx.simulate_keystrokes("space");
+        assert_eq!(clicked.take(), Some(true));
+        cx.run_until_parked();
+        cx.simulate_keystrokes("space");
+        assert_eq!(clicked.take(), Some(true));
+    }
+
+    #[gpui::test]
+    fn test_switch_uncontrolled(cx: &mut TestAppContext) {
+        let (_, cx, clicked) = switch_view(None, cx);
+
+        cx.simulate_keystrokes("space");
+        assert_eq!(clicked.take(), Some(true));
+        cx.run_until_parked();
+        cx.simulate_keystrokes("space");
+        assert_eq!(clicked.take(), Some(false));
+    }
+
+    #[gpui::test]
+    fn test_switch_static(cx: &mut TestAppContext) {
+        cx.update(|cx| cx.set_global(Theme::default()));
+        let clicked = Rc::new(Cell::new(None));
+        let (_, cx) = cx.add_window_view(|_, _| StaticView {
+            clicked: clicked.clone(),
+        });
+        cx.run_until_parked();
+        cx.update(|window, cx| window.focus_next(cx));
+
+        // Without `checked` nor `default_checked`, the switch reports the clicks but stays off.
+        cx.simulate_keystrokes("space");
+        assert_eq!(clicked.take(), Some(true));
+        cx.run_until_parked();
+        cx.simulate_keystrokes("space");
+        assert_eq!(clicked.take(), Some(true));
+        cx.update(|window, cx| assert_eq!(test::is_checked(window, "switch", cx), Some(false)));
+    }
+
+    #[gpui::test]
+    fn test_switch_bind(cx: &mut TestAppContext) {
+        cx.update(|cx| cx.set_global(Theme::default()));
+        let value = cx.new(|_| false);
+        let (_, cx) = cx.add_window_view({
+            let value = value.clone();
+            move |_, cx| {
+                cx.observe(&value, |_, _, cx| cx.notify()).detach();
+                BindView { value }
+            }
+        });
+        cx.run_until_parked();
+        cx.update(|window, cx| window.focus_next(cx));
+
+        cx.simulate_keystrokes("space");
+        cx.run_until_parked();
+        assert!(cx.update(|_, cx| *value.read(cx)));
+        cx.simulate_keystrokes("space");
+        cx.run_until_parked();
+        assert!(!cx.update(|_, cx| *value.read(cx)));
+
+        // The binding wins over the `checked`.
+        assert!(
+            Switch::new("switch")
+                .checked(false)
+                .bind(&value)
+                .clickable()
+        );
+    }
+
+    struct GroupView {
+        changes: Rc<RefCell<Vec<(SharedString, bool)>>>,
+    }
+
+    impl Render for GroupView {
+        fn render(&mut self, _: &mut Window, _: &mut Context<Self>) -> impl IntoElement {
+            let changes = self.changes.clone();
+            SwitchGroup::vertical("group")
+                .toggle_all("All")
+                .child(SwitchGroupItem::new("a").label("A").checked(true))
+                .child(SwitchGroupItem::new("b").label("B"))
+                .child(SwitchGroupItem::new("c").label("C").disabled(true))
+                .on_change(move |key, checked, _, _| {
+                    changes.borrow_mut().push((key.clone(), *checked));
+                })
+        }
+    }
+
+    #[test]
+    fn test_toggle_all_changes() {
+        let item =
+            |key: &str, checked, disabled| (SharedString::from(key.to_string()), checked, disabled);
+
+        // Mixed turns all on, the disabled items are not changed.
+        let items = [
+            item("a", true, false),
+            item("b", false, false),
+            item("c", false, true),
+        ];
+        assert_eq!(toggle_all_changes(&items), (vec!["b".into()], true));
+
+        // All on turns all off.
+        let items = [
+            item("a", true, false),
+            item("b", true, false),
+            item("c", false, true),
+        ];
+        assert_eq!(
+            toggle_all_changes(&items),
+            (vec!["a".into(), "b".into()], false)
+        );
+    }
+
+    #[gpui::test]
+    fn test_switch_group(cx: &mut TestAppContext) {
+        cx.update(|cx| cx.set_global(Theme::default()));
+        let changes = Rc::new(RefCell::new(vec![]));
+        let (_, cx) = cx.add_window_view(|_, _| GroupView {
+            changes: changes.clone(),
+        });
+        cx.run_until_parked();
+        cx.update(|window, cx| window.focus_next(cx));
+
+        // The master switch is focused first, and turns the mixed items on.
+        cx.simulate_keystrokes("space");
+        cx.run_until_parked();
+        assert_eq!(changes.take(), vec![("b".into(), true)]);
+
+        cx.simulate_keystrokes("down space");
+        cx.run_until_parked();
+        assert_eq!(changes.take(), vec![("a".into(), false)]);
+
+        // Wrap around to the last enabled item.
+        cx.simulate_keystrokes("up up space");
+        cx.run_until_parked();
+        assert_eq!(changes.take(), vec![("b".into(), false)]);
+    }
+
+    #[gpui::test]
+    fn test_switch_indeterminate(_cx: &mut gpui::TestAppContext) {
+        assert!(!Switch::new("switch").indeterminate);
+        assert!(Switch::new("switch").indeterminate(true).indeterminate);
+    }
+
+    #[gpui::test]
+    fn test_switch_description(_cx: &mut gpui::TestAppContext) {
+        let switch = Switch::new("switch")
+            .label("Notifications")
+            .description("Receive a notification for new messages.");
+        assert!(switch.label.is_some());
+        assert!(switch.description.is_some());
+        assert!(Switch::new("switch").description.is_none());
+    }
+
+    #[gpui::test]
+    fn test_switch_label_truncate(cx: &mut TestAppContext) {
+        cx.update(|cx| cx.set_global(Theme::default()));
+        let (_, cx) = cx.add_window_view(|_, _| TruncateView { width: px(120.) });
+        cx.run_until_parked();
+
+        let label = SharedString::from(LONG_LABEL);
+        cx.update(|window, _| {
+            let font_size = label_text_size(Size::Medium, window);
+            assert_eq!(font_size, window.rem_size());
+            // The label doesn't fit beside the track in the constrained parent.
+            assert!(text_width(&label, font_size, window) > px(120.));
+            assert!(text_width(&label, font_size, window) < px(2000.));
+        });
+
+        assert!(Switch::new("switch").label_truncate(true).label_truncate);
+        assert!(!Switch::new("switch").label_truncate);
+    }
+
+    #[gpui::test]
+    fn test_switch_prefix_suffix(_cx: &mut gpui::TestAppContext) {
+        let switch = Switch::new("switch")
+            .label("Notifications")
+            .prefix(Icon::new(IconName::Bell))
+            .suffix("Beta");
+        assert!(switch.prefix.is_some());
+        assert!(switch.suffix.is_some());
+
+        let switch = Switch::new("switch");
+        assert!(switch.prefix.is_none());
+        assert!(switch.suffix.is_none());
+    }
+
+    #[gpui::test]
+    fn test_switch_disabled_reason(_cx: &mut gpui::TestAppContext) {
+        let switch = Switch::new("switch")
+            .disabled(true)
+            .tooltip("Sync")
+            .disabled_reason("Requires the admin permission");
+        assert_eq!(
+            switch.disabled_reason,
+            Some(SharedString::from("Requires the admin permission"))
+        );
+        // The reason replaces the tooltip on render, only while disabled.
+        assert!(switch.tooltip.text.is_some());
+        assert_eq!(Switch::new("switch").disabled_reason, None);
+    }
+
+    #[gpui::test]
+    fn test_switch_tooltip(_cx: &mut gpui::TestAppContext) {
+        let switch = Switch::new("switch")
+            .disabled(true)
+            .tooltip("Sync")
+            .tooltip_side(Placement::Bottom)
+            .tooltip_delay(Duration::from_millis(100))
+            .tooltip_max_width(px(280.))
+            .tooltip_offset(px(4.));
+        assert!(switch.tooltip.text.is_some());
+        assert_eq!(
+            switch.tooltip.options,
+            crate::tooltip::TooltipOptions::new()
+                .side(Placement::Bottom)
+                .delay(Duration::from_millis(100))
+                .max_width(px(280.))
+                .offset(px(4.))
+        );
+    }
+
+    #[gpui::test]
+    fn test_switch_keybinding(_cx: &mut gpui::TestAppContext) {
+        assert!(Switch::new("switch").keybinding.is_none());
+        let switch = Switch::new("switch")
+            .label("Command palette")
+            .keybinding(gpui::Keystroke::parse("secondary-shift-p").unwrap());
+        assert!(switch.keybinding.is_some());
+        // The hint doesn't make the switch clickable.
+        assert!(!switch.checked(false).clickable());
+    }
+
+    #[gpui::test]
+    fn test_switch_label_side(_cx: &mut gpui::TestAppContext) {
+        assert_eq!(Switch::new("switch").label_side, None);
+        assert_eq!(
+            Switch::new("switch")
+                .label_side(crate::Side::Left)
+                .label_side,
+            Some(Placement::Left)
+        );
+        assert_eq!(
+            Switch::new("switch")
+                .label_side(Placement::Bottom)
+                .label_side,
+            Some(Placement::Bottom)
+        );
+    }
+
+    #[gpui::test]
+    fn test_switch_direction(cx: &mut TestAppContext) {
+        let max_x = px(16.);
+        assert_eq!(thumb_offset(1., max_x, false), max_x);
+        assert_eq!(thumb_offset(0., max_x, false), px(0.));
+        assert_eq!(thumb_offset(1., max_x, true), px(0.));
+        assert_eq!(thumb_offset(0., max_x, true), max_x);
+        assert_eq!(thumb_offset(0.25, max_x, true), max_x * 0.75);
+
+        assert_eq!(Switch::new("switch").direction, None);
+        assert_eq!(
+            Switch::new("switch").direction(Direction::Rtl).direction,
+            Some(Direction::Rtl)
+        );
+        cx.update(|cx| {
+            assert_eq!(crate::direction(cx), Direction::Ltr);
+            cx.set_global(GlobalState::new());
+            crate::set_direction(Direction::Rtl, cx);
+            assert_eq!(crate::direction(cx), Direction::Rtl);
+        });
+    }
+
+    #[gpui::test]
+    fn test_switch_error(_cx: &mut gpui::TestAppContext) {
+        let switch = Switch::new("switch").checked(true).on_click(|_, _, _| {});
+        assert!(!switch.error && switch.error_message.is_none());
+
+        // The invalid switch still toggles, unlike the disabled one.
+        let switch = switch.error_message("Requires push notifications.");
+        assert!(switch.error_message.is_some());
+        assert!(switch.clickable());
+        assert!(Switch::new("switch").error(true).error);
+    }
+
+    #[gpui::test]
+    fn test_switch_layout(_cx: &mut gpui::TestAppContext) {
+        assert_eq!(Switch::new("switch").layout, SwitchLayout::Inline);
+        let switch = Switch::new("switch")
+            .label("Wi-Fi")
+            .layout(SwitchLayout::Row);
+        assert_eq!(switch.layout, SwitchLayout::Row);
+    }
+
+    #[test]
+    fn test_thumb_transition() {
+        let now = Instant::now();
+        let duration = Some(THUMB_TRANSITION_DURATION);
+        let half = THUMB_TRANSITION_DURATION / 2;
+        let mut transition = ThumbTransition::new(0.);
+        assert_eq!(transition.position(0., duration, now), (0., false));
+
+        let (position, animating) = transition.position(1., duration, now);
+        assert_eq!(position, 0.);
+        assert!(animating);
+
+        // Flip twice within one frame in the middle of the transition, the thumb doesn't jump.
+        let (position, _) = transition.position(0., duration, now + half);
+        assert!((position - 0.5).abs() < 0.01);
+        let (position, animating) = transition.position(1., duration, now + half);
+        assert!((position - 0.5).abs() < 0.01);
+        assert!(animating);
+
+        // The final position matches the final value.
+        assert_eq!(
+            transition.position(1., duration, now + half + THUMB_TRANSITION_DURATION),
+            (1., false)
+        );
+        assert!(transition.started_at.is_none());
+
+        // Without animation, e.g. disabled, the thumb moves at once.
+        assert_eq!(transition.position(0., None, now), (0., false));
+        assert_eq!(
+            transition.position(1., Some(Duration::ZERO), now),
+            (1., false)
+        );
+    }
+
+    #[test]
+    fn test_thumb_drag() {
+        let now = Instant::now();
+        let duration = Some(THUMB_TRANSITION_DURATION);
+        let mut transition = ThumbTransition::new(0.);
+        assert_eq!(transition.release(duration, now), None);
+
+        // The thumb follows the drag.
+        transition.drag = Some(0.3);
+        assert_eq!(transition.position(0., duration, now), (0.3, false));
+
+        // Released before the midpoint, the thumb animates back from the released position.
+        assert_eq!(transition.release(duration, now), Some(false));
+        assert_eq!(transition.position(0., duration, now), (0.3, true));
+        assert_eq!(
+            transition.position(0., duration, now + THUMB_TRANSITION_DURATION),
+            (0., false)
+        );
+
+        // Released past the midpoint, the animation to the new value starts from there.
+        transition.drag = Some(0.8);
+        assert_eq!(transition.release(duration, now), Some(true));
+        let (position, animating) = transition.position(1., duration, now);
+        assert!((position - 0.8).abs() < 0.01);
+        assert!(animating);
+    }
+
+    #[gpui::test]
+    fn test_switch_animation(cx: &mut TestAppContext) {
+        let switch = Switch::new("switch");
+        assert_eq!(switch.animated, None);
+        assert_eq!(switch.animation_duration, THUMB_TRANSITION_DURATION);
+
+        let switch = Switch::new("switch")
+            .animated(false)
+            .animation_duration(Duration::from_millis(300));
+        assert_eq!(switch.animated, Some(false));
+        assert_eq!(switch.animation_duration, Duration::from_millis(300));
+
+        cx.update(|cx| {
+            assert!(!crate::reduce_motion(cx));
+            cx.set_global(GlobalState::new());
+            crate::set_reduce_motion(true, cx);
+            assert!(crate::reduce_motion(cx));
+        });
+    }
+
+    #[gpui::test]
+    fn test_switch_colors(_cx: &mut gpui::TestAppContext) {
+        let switch = Switch::new("switch");
+        assert!(switch.checked_bg.is_none());
+        assert!(switch.unchecked_bg.is_none());
+        assert!(switch.thumb_color.is_none());
+
+        let orange = gpui::hsla(0.08, 0.9, 0.5, 1.);
+        let switch = Switch::new("switch")
+            .checked_bg(orange)
+            .unchecked_bg(gpui::black())
+            .thumb_color(gpui::white());
+        assert_eq!(switch.checked_bg, Some(orange));
+        assert_eq!(switch.unchecked_bg, Some(gpui::black()));
+        assert_eq!(switch.thumb_color, Some(gpui::white()));
+        assert_eq!(Switch::new("switch").color(orange).checked_bg, Some(orange));
+    }
+
+    #[gpui::test]
+    fn test_switch_track_glyphs(_cx: &mut gpui::TestAppContext) {
+        let switch = Switch::new("switch")
+            .on_icon(IconName::Check)
+            .off_label("O");
+        assert!(matches!(
+            switch.on_glyph,
+            Some(TrackGlyph::Icon(IconName::Check))
+        ));
+        assert!(matches!(&switch.off_glyph, Some(TrackGlyph::Label(label)) if label == "O"));
+
+        // The last glyph wins.
+        let switch = Switch::new("switch").on_label("I").on_icon(IconName::Check);
+        assert!(matches!(switch.on_glyph, Some(TrackGlyph::Icon(_))));
+        assert!(switch.off_glyph.is_none());
+    }
+
+    #[test]
+    fn test_switch_metrics() {
+        let track = |size| {
+            let metrics = SwitchMetrics::new(size);
+            (metrics.width, metrics.height, metrics.thumb)
+        };
+        assert_eq!(track(Size::XSmall), (px(24.), px(14.), px(10.)));
+        assert_eq!(track(Size::Small), (px(28.), px(16.), px(12.)));
+        assert_eq!(track(Size::Medium), (px(36.), px(20.), px(16.)));
+        assert_eq!(track(Size::Large), (px(44.), px(24.), px(20.)));
+        // The custom size scales the medium size, by the track height.
+        assert_eq!(
+            SwitchMetrics::new(Size::Size(px(20.))),
+            SwitchMetrics::new(Size::Medium)
+        );
+        assert_eq!(track(Size::Size(px(40.))), (px(72.), px(40.), px(32.)));
+    }
+
+    #[test]
+    fn test_switch_style() {
+        let switch = Switch::new("switch")
+            .layout(SwitchLayout::Row)
+            .bg(gpui::red())
+            .h(px(80.))
+            .w(px(200.));
+
+        // The user style refines the outer element, the track size only follows the size.
+        assert_eq!(switch.style.background, Some(gpui::red().into()));
+        assert_eq!(switch.style.size.height, Some(px(80.).into()));
+        assert_eq!(switch.style.size.width, Some(px(200.).into()));
+        assert_eq!(
+            SwitchMetrics::new(switch.size.unwrap_or_default()),
+            SwitchMetrics::new(Size::Medium)
+        );
+    }
+
+    #[gpui::test]
+    fn test_switch_segmented(cx: &mut TestAppContext) {
+        let (view, cx, clicked) = switch_view(Some(false), cx);
+        view.update(cx, |view, cx| {
+            view.variant = SwitchVariant::Segmented {
+                off_label: "Metric".into(),
+                on_label: "Imperial".into(),
+            };
+            cx.notify();
+        });
+        cx.run_until_parked();
+
+        // The segmented switch toggles with the keys like the default one.
+        cx.simulate_keystrokes("space");
+        assert_eq!(clicked.take(), Some(true));
+        view.update(cx, |view, cx| {
+            view.checked = Some(true);
+            cx.notify();
+        });
+        cx.run_until_parked();
+        cx.simulate_keystrokes("enter");
+        assert_eq!(clicked.take(), Some(false));
+
+        assert_eq!(Switch::new("switch").variant, SwitchVariant::Default);
+    }
+
+    #[test]
+    fn test_distance_to() {
+        let bounds = Bounds::new(gpui::point(px(10.), px(10.)), gpui::size(px(28.), px(16.)));
+        assert_eq!(distance_to(bounds, gpui::point(px(20.), px(20.))), px(0.));
+        assert_eq!(distance_to(bounds, gpui::point(px(40.), px(20.))), px(2.));
+        assert_eq!(distance_to(bounds, gpui::point(px(20.), px(4.))), px(6.));
+        assert_eq!(distance_to(bounds, gpui::point(px(41.), px(30.))), px(5.));
+    }
+
+    #[gpui::test]
+    fn test_touch_target_nearest(cx: &mut TestAppContext) {
+        let (_, cx, _) = switch_view(Some(false), cx);
+        let toggled = Rc::new(RefCell::new(Vec::new()));
+        let toggle = |name: &'static str| {
+            let toggled = toggled.clone();
+            Rc::new(move |_: &mut Window, _: &mut App| toggled.borrow_mut().push(name))
+                as Rc<dyn Fn(&mut Window, &mut App)>
+        };
+
+        // Only the nearest of the overlapping touch targets toggles.
+        cx.update(|window, cx| {
+            claim_tap(px(4.), toggle("left"), window, cx);
+            claim_tap(px(2.), toggle("right"), window, cx);
+            claim_tap(px(3.), toggle("other"), window, cx);
+        });
+        cx.run_until_parked();
+        assert_eq!(*toggled.borrow(), vec!["right"]);
+
+        assert_eq!(Switch::new("switch").touch_target, TOUCH_TARGET);
+        assert_eq!(
+            Switch::new("switch").touch_target(px(44.)).touch_target,
+            px(44.)
+        );
+    }
+
+    #[test]
+    fn test_async_toggle_shake() {
+        let now = Instant::now();
+        let mut state = AsyncToggleState::default();
+        assert_eq!(state.shake(now), None);
+
+        state.rejected_at = Some(now);
+        assert_eq!(state.shake(now), Some(0.));
+        let offset = state.shake(now + SHAKE_DURATION / 12).unwrap();
+        assert!(offset > 0. && offset <= 1.);
+
+        // The shake ends after the duration.
+        assert_eq!(state.shake(now + SHAKE_DURATION), None);
+        assert!(state.rejected_at.is_none());
+    }
+
+    #[gpui::test]
+    fn test_switch_on_toggle_async(_cx: &mut gpui::TestAppContext) {
+        let switch = Switch::new("switch")
+            .checked(false)
+            .on_toggle_async(|_, _, _| Task::ready(true));
+        assert!(switch.clickable());
+        assert!(!switch.loading(true).clickable());
     }
 }
@@ -581,7 +581,7 @@ impl Styled for Tab {
 
 impl Sizable for Tab {
     fn with_size(mut self, size: impl Into<Size>) -> Self {
-        self.size = size.into();
+        self.size = size.into().clamp("Tab");
         self
     }
 }
@@ -325,7 +325,7 @@ impl Styled for TabBar {
 
 impl Sizable for TabBar {
     fn with_size(mut self, size: impl Into<Size>) -> Self {
-        self.size = size.into();
+        self.size = size.into().clamp("TabBar");
         self
     }
 }
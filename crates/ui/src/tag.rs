@@ -214,7 +214,7 @@ impl Tag {
 
 impl Sizable for Tag {
     fn with_size(mut self, size: impl Into<Size>) -> Self {
-        self.size = size.into();
+        self.size = size.into().clamp("Tag");
         self
     }
 }
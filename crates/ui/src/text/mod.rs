@@ -2,6 +2,7 @@ mod document;
 mod format;
 mod inline;
 mod node;
+mod plain;
 pub(crate) mod selection;
 mod state;
 mod style;
@@ -9,7 +10,11 @@ mod text_view;
 mod utils;
 mod window_selection;
 
-use gpui::{App, ElementId, IntoElement, RenderOnce, SharedString, Window};
+use std::ops::Range;
+
+use gpui::{App, ElementId, Entity, IntoElement, RenderOnce, SharedString, Window};
+pub use plain::{PlainText, TruncateMode, TruncateState};
+pub(crate) use plain::text_width;
 pub use state::*;
 pub use style::*;
 pub use text_view::*;
@@ -38,6 +43,8 @@ pub fn html(source: impl Into<SharedString>) -> TextView {
 pub enum Text {
     String(SharedString),
     TextView(Box<TextView>),
+    /// A plain text with the modifiers, e.g. [`Text::muted`] and [`Text::truncate`].
+    Plain(Box<PlainText>),
 }
 
 impl From<SharedString> for Text {
@@ -70,8 +77,90 @@ impl Text {
     /// Do nothing if this is `String`.
     pub fn style(self, style: TextViewStyle) -> Self {
         match self {
-            Self::String(s) => Self::String(s),
             Self::TextView(e) => Self::TextView(Box::new(e.style(style))),
+            text => text,
+        }
+    }
+
+    /// Apply the modifier to the plain text, do nothing if this is `TextView`.
+    fn plain(self, f: impl FnOnce(&mut PlainText)) -> Self {
+        let mut plain = match self {
+            Self::String(s) => Box::new(PlainText::new(s)),
+            Self::Plain(plain) => plain,
+            Self::TextView(e) => return Self::TextView(e),
+        };
+        f(&mut plain);
+        Self::Plain(plain)
+    }
+
+    /// Render the text with the muted foreground color.
+    ///
+    /// Do nothing if this is `TextView`.
+    pub fn muted(self) -> Self {
+        self.plain(|text| text.muted = true)
+    }
+
+    /// Truncate the text in a single line by the `mode` when it doesn't fit the width.
+    ///
+    /// The truncation is measured in the layout, the [`TruncateMode::Middle`] and
+    /// [`TruncateMode::Start`] fill the width of the parent. The measured width is kept in the
+    /// window by the code location, use [`Text::track_truncation`] for the texts rendered in a
+    /// loop, or to know if the text is truncated.
+    ///
+    /// Do nothing if this is `TextView`.
+    #[track_caller]
+    pub fn truncate(self, mode: TruncateMode) -> Self {
+        let id = ElementId::CodeLocation(*std::panic::Location::caller());
+        self.plain(|text| {
+            text.truncate = Some(mode);
+            text.state_id.get_or_insert(id);
+        })
+    }
+
+    /// Wrap the text in at most `lines` lines, the end of the last line is truncated.
+    ///
+    /// See [`Text::truncate`] for the measured width. Do nothing if this is `TextView`.
+    #[track_caller]
+    pub fn max_lines(self, lines: usize) -> Self {
+        let id = ElementId::CodeLocation(*std::panic::Location::caller());
+        self.plain(|text| {
+            text.max_lines = Some(lines.max(1));
+            text.state_id.get_or_insert(id);
+        })
+    }
+
+    /// Highlight the byte `ranges` of the text, e.g. the matches of a search.
+    ///
+    /// Do nothing if this is `TextView`.
+    pub fn highlight(self, ranges: impl IntoIterator<Item = Range<usize>>) -> Self {
+        self.plain(|text| text.highlights.extend(ranges))
+    }
+
+    /// Keep the truncation of the text in the `state`, to check if the text was truncated in
+    /// the last layout, e.g. to show the full text in a tooltip.
+    ///
+    /// ```ignore
+    /// let state = window.use_keyed_state("name-truncation", cx, |_, _| TruncateState::default());
+    /// let tooltip = state.read(cx).is_truncated().then(|| name.clone());
+    /// div()
+    ///     .id("name")
+    ///     .child(Text::from(name).truncate(TruncateMode::Middle).track_truncation(&state))
+    ///     .when_some(tooltip, |this, name| this.tooltip(move |window, cx| {
+    ///         Tooltip::new(name.clone()).build(window, cx)
+    ///     }))
+    /// ```
+    ///
+    /// Do nothing if this is `TextView`.
+    pub fn track_truncation(self, state: &Entity<TruncateState>) -> Self {
+        self.plain(|text| text.state = Some(state.clone()))
+    }
+
+    /// Returns the string of a plain text, `None` if this is `TextView`.
+    pub(crate) fn as_plain_str(&self) -> Option<&SharedString> {
+        match self {
+            Self::String(s) => Some(s),
+            Self::Plain(plain) => Some(&plain.text),
+            Self::TextView(_) => None,
         }
     }
 
@@ -79,6 +168,7 @@ impl Text {
     pub(crate) fn get_text(&self, cx: &App) -> SharedString {
         match self {
             Self::String(s) => s.clone(),
+            Self::Plain(plain) => plain.text.clone(),
             Self::TextView(view) => {
                 if let Some(state) = &view.state {
                     state.read(cx).source()
@@ -91,10 +181,11 @@ impl Text {
 }
 
 impl RenderOnce for Text {
-    fn render(self, _: &mut Window, _: &mut App) -> impl IntoElement {
+    fn render(self, window: &mut Window, cx: &mut App) -> impl IntoElement {
         match self {
             Self::String(s) => s.into_any_element(),
             Self::TextView(e) => e.into_any_element(),
+            Self::Plain(plain) => plain.render(window, cx).into_any_element(),
         }
     }
 }
@@ -0,0 +1,335 @@
+use std::ops::Range;
+
+use gpui::{
+    App, Div, ElementId, Entity, HighlightStyle, ParentElement as _, Pixels, SharedString,
+    Styled as _, StyledText, Window, div, prelude::FluentBuilder as _, px,
+};
+
+use crate::{ActiveTheme as _, ElementExt as _};
+
+/// The ellipsis replacing the truncated part of a [`Text`](super::Text).
+const ELLIPSIS: &str = "…";
+
+/// Where a [`Text`](super::Text) is truncated when it doesn't fit in a single line, see
+/// [`Text::truncate`](super::Text::truncate).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TruncateMode {
+    /// Keep the start of the text, e.g. `Send me a notific…`.
+    #[default]
+    End,
+    /// Keep the start and the end of the text, e.g. `Send me a…new message`, for paths and file
+    /// names.
+    Middle,
+    /// Keep the end of the text, e.g. `…for every new message`.
+    Start,
+}
+
+/// The truncation of a [`Text`](super::Text) in the last layout, see
+/// [`Text::track_truncation`](super::Text::track_truncation).
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct TruncateState {
+    width: Option<Pixels>,
+    truncated: bool,
+}
+
+impl TruncateState {
+    /// Returns true if the text was truncated in the last layout, by the
+    /// [`Text::truncate`](super::Text::truncate) or the
+    /// [`Text::max_lines`](super::Text::max_lines).
+    pub fn is_truncated(&self) -> bool {
+        self.truncated
+    }
+}
+
+/// A plain text with the modifiers of [`Text`](super::Text).
+#[derive(Clone)]
+pub struct PlainText {
+    pub(super) text: SharedString,
+    pub(super) muted: bool,
+    pub(super) truncate: Option<TruncateMode>,
+    pub(super) max_lines: Option<usize>,
+    pub(super) highlights: Vec<Range<usize>>,
+    /// The id of the keyed state, the code location of the modifier by default.
+    pub(super) state_id: Option<ElementId>,
+    pub(super) state: Option<Entity<TruncateState>>,
+}
+
+impl PlainText {
+    pub(super) fn new(text: SharedString) -> Self {
+        Self {
+            text,
+            muted: false,
+            truncate: None,
+            max_lines: None,
+            highlights: vec![],
+            state_id: None,
+            state: None,
+        }
+    }
+
+    /// Returns the state to measure the truncation, `None` if the text isn't truncated.
+    fn truncate_state(&self, window: &mut Window, cx: &mut App) -> Option<Entity<TruncateState>> {
+        if self.truncate.is_none() && self.max_lines.is_none() {
+            return None;
+        }
+
+        self.state.clone().or_else(|| {
+            let id = self.state_id.clone()?;
+            Some(window.use_keyed_state(id, cx, |_, _| TruncateState::default()))
+        })
+    }
+
+    pub(super) fn render(self, window: &mut Window, cx: &mut App) -> Div {
+        let state = self.truncate_state(window, cx);
+        let width = state.as_ref().and_then(|state| state.read(cx).width);
+
+        // The start or the middle is replaced by the ellipsis in the last measured width, the end
+        // is truncated by the layout.
+        let mut text = self.text.clone();
+        let mut highlights = self.highlights.clone();
+        if let (Some(mode @ (TruncateMode::Middle | TruncateMode::Start)), Some(width)) =
+            (self.truncate, width)
+        {
+            let font_size = window.text_style().font_size.to_pixels(window.rem_size());
+            let removed = truncate_range(&self.text, mode, |text| {
+                !is_truncated(&text.to_string().into(), font_size, width, window)
+            });
+            if let Some(removed) = removed {
+                text = format!(
+                    "{}{}{}",
+                    &self.text[..removed.start],
+                    ELLIPSIS,
+                    &self.text[removed.end..]
+                )
+                .into();
+                highlights = highlights
+                    .iter()
+                    .flat_map(|range| remap_range(range, &removed))
+                    .collect();
+            }
+        }
+        highlights.sort_by_key(|range| range.start);
+        let highlight_style = HighlightStyle {
+            color: Some(cx.theme().blue),
+            ..Default::default()
+        };
+
+        let full_text = self.text;
+        let max_lines = self.max_lines;
+        let is_single_line = self.truncate.is_some() && max_lines.is_none();
+        div()
+            .when(self.muted, |this| {
+                this.text_color(cx.theme().muted_foreground)
+            })
+            .when(is_single_line, |this| {
+                this.min_w_0()
+                    .overflow_hidden()
+                    .whitespace_nowrap()
+                    .text_ellipsis()
+                    // The width doesn't follow the truncated text, to grow back with the parent.
+                    .when(self.truncate != Some(TruncateMode::End), |this| {
+                        this.w_full()
+                    })
+            })
+            .when_some(max_lines, |this, lines| {
+                this.min_w_0()
+                    .overflow_hidden()
+                    .line_clamp(lines)
+                    .text_ellipsis()
+            })
+            .when_some(state, |this, state| {
+                this.on_prepaint(move |bounds, window, cx| {
+                    let width = bounds.size.width;
+                    let font_size = window.text_style().font_size.to_pixels(window.rem_size());
+                    let truncated = match max_lines {
+                        Some(lines) => line_count(&full_text, font_size, width, window) > lines,
+                        None => is_truncated(&full_text, font_size, width, window),
+                    };
+                    let measured = TruncateState {
+                        width: Some(width),
+                        truncated,
+                    };
+                    state.update(cx, |state, cx| {
+                        if *state != measured {
+                            *state = measured;
+                            cx.notify();
+                        }
+                    });
+                })
+            })
+            .child(
+                StyledText::new(text).with_highlights(
+                    highlights
+                        .into_iter()
+                        .map(|range| (range, highlight_style))
+                        .collect::<Vec<_>>(),
+                ),
+            )
+    }
+}
+
+/// Returns the width of the text laid out in a single line with the window text style.
+pub(crate) fn text_width(text: &SharedString, font_size: Pixels, window: &Window) -> Pixels {
+    let run = window.text_style().to_run(text.len());
+    window
+        .text_system()
+        .shape_line(text.clone(), font_size, &[run], None)
+        .width
+}
+
+/// Returns true if the text in a single line is wider than the width.
+fn is_truncated(
+    text: &SharedString,
+    font_size: Pixels,
+    width: Pixels,
+    window: &Window,
+) -> bool {
+    // Ignore the rounding of the layout.
+    text_width(text, font_size, window) > width + px(0.5)
+}
+
+/// Returns the number of lines of the text wrapped in the width.
+fn line_count(text: &SharedString, font_size: Pixels, width: Pixels, window: &Window) -> usize {
+    let run = window.text_style().to_run(text.len());
+    window
+        .text_system()
+        .shape_text(text.clone(), font_size, &[run], Some(width), None)
+        .map(|lines| {
+            lines
+                .iter()
+                .map(|line| line.wrap_boundaries.len() + 1)
+                .sum()
+        })
+        .unwrap_or(1)
+}
+
+/// Returns the byte range of the text to replace with the ellipsis, to keep the most characters
+/// that `fits`, `None` if the whole text fits.
+fn truncate_range(
+    text: &str,
+    mode: TruncateMode,
+    fits: impl Fn(&str) -> bool,
+) -> Option<Range<usize>> {
+    if fits(text) {
+        return None;
+    }
+
+    let offsets = text
+        .char_indices()
+        .map(|(ix, _)| ix)
+        .chain([text.len()])
+        .collect::<Vec<_>>();
+    let chars = offsets.len() - 1;
+    // The removed range keeping the `kept` characters.
+    let removed = |kept: usize| match mode {
+        TruncateMode::End => offsets[kept]..text.len(),
+        TruncateMode::Start => 0..offsets[chars - kept],
+        TruncateMode::Middle => offsets[kept.div_ceil(2)]..offsets[chars - kept / 2],
+    };
+    let fits_kept = |kept: usize| {
+        let removed = removed(kept);
+        fits(&format!(
+            "{}{}{}",
+            &text[..removed.start],
+            ELLIPSIS,
+            &text[removed.end..]
+        ))
+    };
+
+    // The most characters kept that fit, at least the ellipsis is shown.
+    let (mut low, mut high) = (0, chars);
+    while low < high {
+        let mid = (low + high).div_ceil(2);
+        if fits_kept(mid) {
+            low = mid;
+        } else {
+            high = mid - 1;
+        }
+    }
+    Some(removed(low))
+}
+
+/// Returns the parts of the `range` outside the `removed` range, in the offsets of the text with
+/// the ellipsis.
+fn remap_range(range: &Range<usize>, removed: &Range<usize>) -> Vec<Range<usize>> {
+    let shift = |offset: usize| offset - removed.len() + ELLIPSIS.len();
+    let mut ranges = vec![];
+    if range.start < removed.start {
+        ranges.push(range.start..range.end.min(removed.start));
+    }
+    if range.end > removed.end {
+        ranges.push(shift(range.start.max(removed.end))..shift(range.end));
+    }
+    ranges
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::text::Text;
+
+    fn truncate(text: &str, mode: TruncateMode, max_chars: usize) -> String {
+        match truncate_range(text, mode, |text| text.chars().count() <= max_chars) {
+            Some(removed) => format!(
+                "{}{}{}",
+                &text[..removed.start],
+                ELLIPSIS,
+                &text[removed.end..]
+            ),
+            None => text.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_truncate_range() {
+        let text = "Hello, World!";
+        assert_eq!(truncate(text, TruncateMode::End, 20), text);
+        assert_eq!(truncate(text, TruncateMode::End, 6), "Hello…");
+        assert_eq!(truncate(text, TruncateMode::Start, 6), "…orld!");
+        assert_eq!(truncate(text, TruncateMode::Middle, 6), "Hel…d!");
+        assert_eq!(truncate(text, TruncateMode::Middle, 7), "Hel…ld!");
+        // At least the ellipsis is shown.
+        assert_eq!(truncate(text, TruncateMode::Middle, 0), "…");
+
+        // The multi-byte characters are kept whole.
+        assert_eq!(
+            truncate("你好世界和平", TruncateMode::Middle, 5),
+            "你好…和平"
+        );
+    }
+
+    #[test]
+    fn test_text_modifiers() {
+        let text = Text::from("Hello, World!")
+            .muted()
+            .truncate(TruncateMode::Middle)
+            .max_lines(0)
+            .highlight([0..5]);
+        let Text::Plain(plain) = text else {
+            panic!("expected a plain text");
+        };
+        assert!(plain.muted);
+        assert_eq!(plain.truncate, Some(TruncateMode::Middle));
+        assert_eq!(plain.max_lines, Some(1));
+        assert_eq!(plain.highlights, vec![0..5]);
+        assert!(plain.state_id.is_some());
+        assert_eq!(
+            Text::Plain(plain)
+                .as_plain_str()
+                .map(|text| text.to_string()),
+            Some("Hello, World!".to_string())
+        );
+    }
+
+    #[test]
+    fn test_remap_range() {
+        // "Hello, World!" -> "Hel…d!"
+        let removed = 3..11;
+        assert_eq!(remap_range(&(0..2), &removed), vec![0..2]);
+        assert_eq!(remap_range(&(1..5), &removed), vec![1..3]);
+        assert_eq!(remap_range(&(4..8), &removed), Vec::<Range<usize>>::new());
+        assert_eq!(remap_range(&(7..13), &removed), vec![6..8]);
+        // The ellipsis isn't highlighted.
+        assert_eq!(remap_range(&(0..13), &removed), vec![0..3, 6..8]);
+    }
+}
@@ -0,0 +1,110 @@
+use gpui::{App, Global, Window};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::{Theme, ThemeMode};
+
+/// The requested appearance of the application, see [`set_appearance`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Appearance {
+    /// Follow the light/dark setting of the system.
+    System,
+    #[default]
+    Light,
+    Dark,
+}
+
+impl Appearance {
+    /// Return lower_case appearance name: `system`, `light`, `dark`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Appearance::System => "system",
+            Appearance::Light => "light",
+            Appearance::Dark => "dark",
+        }
+    }
+}
+
+/// A global updated every time the active theme is changed, e.g. on the light/dark switching
+/// or the reloading of the themes.
+///
+/// Use `cx.observe_global::<ThemeChanged>` to invalidate the caches depending on the theme.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ThemeChanged {
+    /// The mode of the active theme.
+    pub mode: ThemeMode,
+}
+
+impl Global for ThemeChanged {}
+
+/// Follow the light/dark setting of the system, switching the active theme between the light
+/// and dark theme of the [`Theme`] when it changes, without restarting the application.
+///
+/// The windows using the [`crate::Root`] are repainted on the change.
+pub fn sync_with_system(cx: &mut App) {
+    set_appearance(Appearance::System, cx);
+}
+
+/// Set the appearance of the application, use [`Appearance::System`] to follow the system,
+/// or `Light`/`Dark` to override it.
+pub fn set_appearance(appearance: Appearance, cx: &mut App) {
+    Theme::global_mut(cx).appearance = appearance;
+    let mode = match appearance {
+        Appearance::System => cx.window_appearance().into(),
+        Appearance::Light => ThemeMode::Light,
+        Appearance::Dark => ThemeMode::Dark,
+    };
+    Theme::change(mode, None, cx);
+    cx.refresh_windows();
+}
+
+/// Called by the [`crate::Root`] when the appearance of the window changed.
+pub(crate) fn window_appearance_changed(window: &mut Window, cx: &mut App) {
+    if Theme::global(cx).appearance != Appearance::System {
+        return;
+    }
+
+    let mode = ThemeMode::from(window.appearance());
+    if Theme::global(cx).mode == mode {
+        return;
+    }
+    Theme::change(mode, None, cx);
+    cx.refresh_windows();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gpui::TestAppContext;
+    use std::{cell::RefCell, rc::Rc};
+
+    #[gpui::test]
+    fn test_set_appearance(cx: &mut TestAppContext) {
+        let changes = Rc::new(RefCell::new(Vec::new()));
+        cx.update(|cx| {
+            crate::theme::init(cx);
+            assert_eq!(Theme::global(cx).appearance, Appearance::Light);
+
+            cx.observe_global::<ThemeChanged>({
+                let changes = changes.clone();
+                move |cx| changes.borrow_mut().push(cx.global::<ThemeChanged>().mode)
+            })
+            .detach();
+
+            set_appearance(Appearance::Dark, cx);
+            assert!(Theme::global(cx).is_dark());
+
+            // The test platform is light.
+            sync_with_system(cx);
+            assert_eq!(Theme::global(cx).appearance, Appearance::System);
+            assert_eq!(Theme::global(cx).mode, ThemeMode::Light);
+        });
+        cx.run_until_parked();
+
+        cx.update(|cx| {
+            assert_eq!(cx.global::<ThemeChanged>().mode, ThemeMode::Light);
+            assert_eq!(changes.borrow().last(), Some(&ThemeMode::Light));
+        });
+    }
+}
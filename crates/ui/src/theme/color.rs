@@ -270,18 +270,18 @@ impl Colorize for Hsla {
         if rgb.a < 1. {
             return format!(
                 "#{:02X}{:02X}{:02X}{:02X}",
-                ((rgb.r * 255.) as u32),
-                ((rgb.g * 255.) as u32),
-                ((rgb.b * 255.) as u32),
-                ((self.a * 255.) as u32)
+                ((rgb.r * 255.).round() as u32),
+                ((rgb.g * 255.).round() as u32),
+                ((rgb.b * 255.).round() as u32),
+                ((self.a * 255.).round() as u32)
             );
         }
 
         format!(
             "#{:02X}{:02X}{:02X}",
-            ((rgb.r * 255.) as u32),
-            ((rgb.g * 255.) as u32),
-            ((rgb.b * 255.) as u32)
+            ((rgb.r * 255.).round() as u32),
+            ((rgb.g * 255.).round() as u32),
+            ((rgb.b * 255.).round() as u32)
         )
     }
 
@@ -664,6 +664,8 @@ color_methods!(rose);
 ///
 /// - `#RRGGBB` - The HEX color string.
 /// - `#RRGGBBAA` - The HEX color string with alpha.
+/// - `hsl(h, s%, l%)` - The HSL color, `h` in degrees `0..360`, `s` and `l` in percent.
+/// - `hsla(h, s%, l%, a)` - The HSL color with alpha, `a` in `0.0..1.0`.
 ///
 /// Or the Tailwind Color format:
 ///
@@ -677,6 +679,9 @@ pub fn try_parse_color(color: &str) -> Result<Hsla> {
         let rgba = gpui::Rgba::try_from(color)?;
         return Ok(rgba.into());
     }
+    if color.starts_with("hsl") {
+        return parse_hsla(color);
+    }
 
     let mut name = String::new();
     let mut scale = None;
@@ -738,6 +743,30 @@ pub fn try_parse_color(color: &str) -> Result<Hsla> {
     Ok(hsla)
 }
 
+/// Parse the `hsl(h, s%, l%)` or `hsla(h, s%, l%, a)` color.
+fn parse_hsla(color: &str) -> Result<Hsla> {
+    let args = color
+        .strip_prefix("hsla(")
+        .or_else(|| color.strip_prefix("hsl("))
+        .and_then(|args| args.strip_suffix(')'))
+        .ok_or_else(|| anyhow!("Invalid hsla color: {}", color))?;
+
+    let values = args
+        .split(',')
+        .map(|value| value.trim().trim_end_matches('%').parse::<f32>())
+        .collect::<Result<Vec<_>, _>>()?;
+    let (h, s, l, a) = match values.as_slice() {
+        [h, s, l] => (*h, *s, *l, 1.),
+        [h, s, l, a] => (*h, *s, *l, *a),
+        _ => return Err(anyhow!("Invalid hsla color: {}", color)),
+    };
+    if !(0. ..=100.).contains(&s) || !(0. ..=100.).contains(&l) || !(0. ..=1.).contains(&a) {
+        return Err(anyhow!("Invalid hsla color: {}", color));
+    }
+
+    Ok(hsl(h.rem_euclid(360.), s, l).opacity(a))
+}
+
 #[cfg(test)]
 mod tests {
     use gpui::{rgb, rgba};
@@ -914,5 +943,15 @@ mod tests {
             try_parse_color("orange-300/66").ok(),
             Some(crate::orange_300().opacity(0.66))
         );
+        assert_eq!(
+            try_parse_color("hsl(260, 94%, 80%)").ok(),
+            Some(hsl(260., 94., 80.))
+        );
+        assert_eq!(
+            try_parse_color("hsla(200, 50%, 40%, 0.5)").ok(),
+            Some(hsl(200., 50., 40.).opacity(0.5))
+        );
+        assert!(try_parse_color("hsla(200, 50%, 140%, 0.5)").is_err());
+        assert!(try_parse_color("hsl(200, 50%)").is_err());
     }
 }
@@ -0,0 +1,180 @@
+use gpui::Hsla;
+
+use crate::{Colorize as _, Theme, ThemeMode};
+
+/// The opacity of the base color blended on the background for the hover color.
+const HOVER_OPACITY: f32 = 0.9;
+/// The opacity of the base color for the disabled color.
+const DISABLED_OPACITY: f32 = 0.5;
+
+/// The color roles of the theme having the interaction states, see [`Theme::colors`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ColorRole {
+    Primary,
+    Secondary,
+    Success,
+    Info,
+    Warning,
+    Danger,
+    /// The unchecked background of the [`crate::switch::Switch`].
+    Switch,
+}
+
+/// A base color with the colors of its interaction states.
+///
+/// The states not defined by the theme are derived from the base color, see [`ColorSet::derive`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ColorSet {
+    pub base: Hsla,
+    /// The foreground color on the `base`.
+    pub foreground: Hsla,
+    pub hover: Hsla,
+    pub active: Hsla,
+    pub disabled: Hsla,
+}
+
+impl ColorSet {
+    /// Derive the interaction states from the `base` color:
+    ///
+    /// - `hover`: the base with 90% opacity blended on the `background`.
+    /// - `active`: the base darkened by 10% in light mode, or 20% in dark mode.
+    /// - `disabled`: the base with 50% opacity.
+    pub fn derive(base: Hsla, foreground: Hsla, background: Hsla, mode: ThemeMode) -> Self {
+        let active_darken = if mode.is_dark() { 0.2 } else { 0.1 };
+
+        Self {
+            base,
+            foreground,
+            hover: background.blend(base.opacity(HOVER_OPACITY)),
+            active: base.darken(active_darken),
+            disabled: base.opacity(DISABLED_OPACITY),
+        }
+    }
+}
+
+impl Theme {
+    /// Returns the [`ColorSet`] of the `role`, with the hover and active colors of the theme.
+    ///
+    /// ```ignore
+    /// let danger = cx.theme().colors(ColorRole::Danger);
+    /// div().bg(danger.base).hover(|this| this.bg(danger.hover))
+    /// ```
+    pub fn colors(&self, role: ColorRole) -> ColorSet {
+        let derive =
+            |base, foreground| ColorSet::derive(base, foreground, self.background, self.mode);
+
+        match role {
+            ColorRole::Primary => ColorSet {
+                hover: self.primary_hover,
+                active: self.primary_active,
+                ..derive(self.primary, self.primary_foreground)
+            },
+            ColorRole::Secondary => ColorSet {
+                hover: self.secondary_hover,
+                active: self.secondary_active,
+                ..derive(self.secondary, self.secondary_foreground)
+            },
+            ColorRole::Success => ColorSet {
+                hover: self.success_hover,
+                active: self.success_active,
+                ..derive(self.success, self.success_foreground)
+            },
+            ColorRole::Info => ColorSet {
+                hover: self.info_hover,
+                active: self.info_active,
+                ..derive(self.info, self.info_foreground)
+            },
+            ColorRole::Warning => ColorSet {
+                hover: self.warning_hover,
+                active: self.warning_active,
+                ..derive(self.warning, self.warning_foreground)
+            },
+            ColorRole::Danger => ColorSet {
+                hover: self.danger_hover,
+                active: self.danger_active,
+                ..derive(self.danger, self.danger_foreground)
+            },
+            ColorRole::Switch => derive(self.switch, self.switch_thumb),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gpui::hsla;
+
+    fn assert_lightness(color: Hsla, lightness: f32) {
+        assert!(
+            (color.l - lightness).abs() < 0.001,
+            "expected lightness {lightness}, got {}",
+            color.l
+        );
+    }
+
+    #[test]
+    fn test_derive_light() {
+        let base = hsla(0., 0., 0.2, 1.);
+        let set = ColorSet::derive(base, gpui::white(), gpui::white(), ThemeMode::Light);
+
+        assert_eq!(set.base, base);
+        assert_eq!(set.foreground, gpui::white());
+        // 90% of 0.2 over 1.0
+        assert_lightness(set.hover, 0.28);
+        assert_eq!(set.hover.a, 1.);
+        assert_lightness(set.active, 0.18);
+        assert_lightness(set.disabled, 0.2);
+        assert_eq!(set.disabled.a, 0.5);
+    }
+
+    #[test]
+    fn test_derive_dark() {
+        let base = hsla(0., 0., 0.2, 1.);
+        let set = ColorSet::derive(base, gpui::white(), gpui::black(), ThemeMode::Dark);
+
+        // 90% of 0.2 over 0.0
+        assert_lightness(set.hover, 0.18);
+        assert_lightness(set.active, 0.16);
+        assert_eq!(set.disabled.a, 0.5);
+    }
+
+    #[test]
+    fn test_theme_colors() {
+        for mode in ["light", "dark"] {
+            let theme = Theme::from_json(&format!(
+                r##"{{
+                    "mode": "{mode}",
+                    "colors": {{
+                        "danger.background": "#DC2626",
+                        "danger.hover.background": "#FF0000"
+                    }}
+                }}"##
+            ))
+            .unwrap();
+
+            let danger = theme.colors(ColorRole::Danger);
+            let derived = ColorSet::derive(
+                theme.danger,
+                theme.danger_foreground,
+                theme.background,
+                theme.mode,
+            );
+            assert_eq!(danger.base, theme.danger);
+            // The explicit color of the theme wins over the derived one.
+            assert_eq!(danger.hover, crate::try_parse_color("#FF0000").unwrap());
+            assert_eq!(danger.active, derived.active);
+            assert_eq!(danger.disabled, derived.disabled);
+
+            let switch = theme.colors(ColorRole::Switch);
+            assert_eq!(
+                switch,
+                ColorSet::derive(
+                    theme.switch,
+                    theme.switch_thumb,
+                    theme.background,
+                    theme.mode
+                )
+            );
+        }
+    }
+}
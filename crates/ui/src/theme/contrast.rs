@@ -0,0 +1,141 @@
+use gpui::{App, Hsla, Pixels, px};
+
+use crate::{ColorRole, ColorSet, Theme};
+
+/// The width of the structural borders, e.g. the bottom border of the [`crate::TitleBar`].
+const BORDER_WIDTH: Pixels = px(1.);
+/// The width of the structural borders in the high-contrast mode.
+const HIGH_CONTRAST_BORDER_WIDTH: Pixels = px(2.);
+/// The factor of the focus ring thickness in the high-contrast mode.
+const HIGH_CONTRAST_RING_FACTOR: f32 = 2.;
+
+/// The styles depending on the high-contrast mode, see [`Theme::contrast`].
+///
+/// ```ignore
+/// let contrast = cx.theme().contrast();
+/// div()
+///     .border_b(contrast.border_width())
+///     .when_some(contrast.outline(), |this, outline| this.border_color(outline))
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct Contrast<'a> {
+    theme: &'a Theme,
+}
+
+impl Contrast<'_> {
+    /// Returns true in the high-contrast mode, see [`set_high_contrast`].
+    #[inline]
+    pub fn is_high(&self) -> bool {
+        self.theme.high_contrast
+    }
+
+    /// Returns the width of the structural borders, e.g. the bottom border of the
+    /// [`crate::TitleBar`] or the frame of the [`crate::WindowBorder`], 2px in high contrast.
+    #[inline]
+    pub fn border_width(&self) -> Pixels {
+        if self.is_high() {
+            HIGH_CONTRAST_BORDER_WIDTH
+        } else {
+            BORDER_WIDTH
+        }
+    }
+
+    /// Returns the focus ring `thickness`, doubled in high contrast.
+    #[inline]
+    pub fn ring_thickness(&self, thickness: Pixels) -> Pixels {
+        if self.is_high() {
+            thickness * HIGH_CONTRAST_RING_FACTOR
+        } else {
+            thickness
+        }
+    }
+
+    /// Returns the color of the outline around the hovered or pressed controls, only in high
+    /// contrast.
+    #[inline]
+    pub fn outline(&self) -> Option<Hsla> {
+        self.is_high().then_some(self.theme.foreground)
+    }
+
+    /// Returns the [`ColorSet`] of the `role`, see [`Theme::colors`].
+    ///
+    /// In high contrast, the hover and active states use the saturated base color instead of
+    /// a subtle tint, the [`ColorRole::Secondary`] uses the [`ColorRole::Primary`] one, and the
+    /// `foreground` is the one on the hover color.
+    pub fn colors(&self, role: ColorRole) -> ColorSet {
+        let colors = self.theme.colors(role);
+        if !self.is_high() {
+            return colors;
+        }
+
+        let strong = match role {
+            ColorRole::Secondary | ColorRole::Switch => self.theme.colors(ColorRole::Primary),
+            _ => colors,
+        };
+        ColorSet {
+            foreground: strong.foreground,
+            hover: strong.base,
+            active: strong.active,
+            ..colors
+        }
+    }
+}
+
+impl Theme {
+    /// Returns the styles depending on the high-contrast mode, the components use it instead of
+    /// checking the mode themselves.
+    #[inline]
+    pub fn contrast(&self) -> Contrast<'_> {
+        Contrast { theme: self }
+    }
+}
+
+/// Returns true if the high-contrast setting of the system is enabled.
+///
+/// GPUI doesn't report the setting yet, so only the high-contrast GTK themes are detected on
+/// Linux.
+pub(crate) fn system_high_contrast() -> bool {
+    cfg!(target_os = "linux")
+        && std::env::var("GTK_THEME").is_ok_and(|theme| theme.contains("HighContrast"))
+}
+
+/// Set to true to use the high-contrast styles, default follows the system, see
+/// [`Theme::contrast`].
+pub fn set_high_contrast(high_contrast: bool, cx: &mut App) {
+    Theme::global_mut(cx).high_contrast = high_contrast;
+    cx.refresh_windows();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_contrast() {
+        let mut theme = Theme::default();
+        let contrast = theme.contrast();
+        assert!(!contrast.is_high());
+        assert_eq!(contrast.border_width(), px(1.));
+        assert_eq!(contrast.ring_thickness(px(1.)), px(1.));
+        assert_eq!(contrast.outline(), None);
+        assert_eq!(
+            contrast.colors(ColorRole::Secondary),
+            theme.colors(ColorRole::Secondary)
+        );
+
+        theme.high_contrast = true;
+        let contrast = theme.contrast();
+        assert_eq!(contrast.border_width(), px(2.));
+        assert_eq!(contrast.ring_thickness(px(1.)), px(2.));
+        assert_eq!(contrast.outline(), Some(theme.foreground));
+
+        let primary = theme.colors(ColorRole::Primary);
+        let secondary = contrast.colors(ColorRole::Secondary);
+        assert_eq!(secondary.base, theme.secondary);
+        assert_eq!(secondary.hover, primary.base);
+        assert_eq!(secondary.foreground, primary.foreground);
+
+        let danger = contrast.colors(ColorRole::Danger);
+        assert_eq!(danger.hover, theme.danger);
+    }
+}
@@ -1,22 +1,31 @@
 use crate::{
-    highlighter::HighlightTheme, list::ListSettings, notification::NotificationSettings,
+    GapSize, highlighter::HighlightTheme, list::ListSettings, notification::NotificationSettings,
     scroll::ScrollbarShow, sheet::SheetSettings,
 };
-use gpui::{App, Global, Hsla, Pixels, SharedString, Window, WindowAppearance, px};
+use gpui::{App, Global, Hsla, Pixels, SharedString, Window, WindowAppearance, WindowId, px};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use std::{
+    collections::HashMap,
     ops::{Deref, DerefMut},
     rc::Rc,
     sync::Arc,
 };
 
+mod appearance;
 mod color;
+mod color_set;
+mod contrast;
+mod overrides;
 mod registry;
 mod schema;
 mod theme_color;
 
+pub use appearance::*;
 pub use color::*;
+pub use color_set::*;
+pub use contrast::*;
+pub use overrides::*;
 pub use registry::*;
 pub use schema::*;
 pub use theme_color::*;
@@ -30,6 +39,7 @@ pub fn init(cx: &mut App) {
 }
 
 pub trait ActiveTheme {
+    /// Returns the active theme, the colors include the overrides of the window being drawn.
     fn theme(&self) -> &Theme;
 }
 
@@ -49,6 +59,12 @@ pub struct Theme {
     pub dark_theme: Rc<ThemeConfig>,
 
     pub mode: ThemeMode,
+    /// The requested appearance, see [`set_appearance`].
+    #[serde(skip)]
+    pub appearance: Appearance,
+    /// Use the high-contrast styles, see [`set_high_contrast`] and [`Theme::contrast`].
+    #[serde(skip)]
+    pub high_contrast: bool,
     /// The font family for the application, default is `.SystemUIFont`.
     pub font_family: SharedString,
     /// The base font size for the application, default is 16px.
@@ -67,6 +83,8 @@ pub struct Theme {
     pub radius: Pixels,
     /// Radius for the large elements, e.g.: Dialog, Notification border radius.
     pub radius_lg: Pixels,
+    /// The unit of the spacing scale of the named gaps, default is 4px, see [`GapSize`].
+    pub spacing: Pixels,
     pub shadow: bool,
     pub transparent: Hsla,
     /// Show the scrollbar mode, default: Scrolling
@@ -84,6 +102,15 @@ pub struct Theme {
     pub list: ListSettings,
     /// The sheet settings.
     pub sheet: SheetSettings,
+    /// The color overrides of all windows, see [`set_overrides`].
+    #[serde(skip)]
+    overrides: ThemeOverrides,
+    /// The color overrides of the windows, see [`set_window_overrides`].
+    #[serde(skip)]
+    window_overrides: HashMap<WindowId, ThemeOverrides>,
+    /// The colors of the windows with the overrides.
+    #[serde(skip)]
+    window_colors: HashMap<WindowId, ThemeColor>,
 }
 
 impl Default for Theme {
@@ -96,7 +123,7 @@ impl Deref for Theme {
     type Target = ThemeColor;
 
     fn deref(&self) -> &Self::Target {
-        &self.colors
+        self.current_colors()
     }
 }
 
@@ -158,6 +185,9 @@ impl Theme {
     }
 
     /// Change the theme mode.
+    ///
+    /// This doesn't change the [`Appearance`], use [`set_appearance`] to stop following the
+    /// system.
     pub fn change(mode: impl Into<ThemeMode>, window: Option<&mut Window>, cx: &mut App) {
         let mode = mode.into();
         if !cx.has_global::<Theme>() {
@@ -174,12 +204,19 @@ impl Theme {
         } else {
             theme.apply_config(&theme.light_theme.clone());
         }
+        cx.set_global(ThemeChanged { mode });
 
         if let Some(window) = window {
             window.refresh();
         }
     }
 
+    /// Returns the size of the named gap in the spacing scale of the theme.
+    #[inline]
+    pub fn gap(&self, size: GapSize) -> Pixels {
+        self.spacing * size.units()
+    }
+
     /// Get the input background color.
     ///
     /// For dark, use a transparent color mixed with the input border: `cx.theme().input`,
@@ -207,6 +244,8 @@ impl From<&ThemeColor> for Theme {
     fn from(colors: &ThemeColor) -> Self {
         Theme {
             mode: ThemeMode::default(),
+            appearance: Appearance::default(),
+            high_contrast: false,
             transparent: Hsla::transparent_black(),
             font_family: ".SystemUIFont".into(),
             font_size: px(16.),
@@ -221,6 +260,7 @@ impl From<&ThemeColor> for Theme {
             mono_font_size: px(13.),
             radius: px(6.),
             radius_lg: px(8.),
+            spacing: px(4.),
             shadow: true,
             scrollbar_show: ScrollbarShow::default(),
             notification: NotificationSettings::default(),
@@ -233,6 +273,9 @@ impl From<&ThemeColor> for Theme {
             dark_theme: Rc::new(ThemeConfig::default()),
             highlight_theme: HighlightTheme::default_light(),
             sheet: SheetSettings::default(),
+            overrides: ThemeOverrides::default(),
+            window_overrides: HashMap::new(),
+            window_colors: HashMap::new(),
         }
     }
 }
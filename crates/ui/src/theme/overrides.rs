@@ -0,0 +1,333 @@
+use std::{cell::Cell, collections::BTreeMap};
+
+use gpui::{App, Hsla, SharedString, Window, WindowId};
+
+use crate::{Colorize, Theme, ThemeColor, ThemeConfig};
+
+thread_local! {
+    /// The window being drawn, set by the [`crate::Root`] until the end of the draw.
+    static CURRENT_WINDOW: Cell<Option<WindowId>> = const { Cell::new(None) };
+}
+
+/// The color overrides applied on top of the active theme, keyed by the color token of the
+/// theme config, e.g. `switch.background` or `title_bar.background`.
+///
+/// ```ignore
+/// use gpui_component::{ThemeOverrides, set_overrides};
+///
+/// set_overrides(ThemeOverrides::new().color("switch.background", gpui::blue()), cx);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ThemeOverrides {
+    colors: BTreeMap<SharedString, Hsla>,
+}
+
+impl ThemeOverrides {
+    /// Create an empty overrides.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Override the color of the `token`.
+    pub fn color(mut self, token: impl Into<SharedString>, color: impl Into<Hsla>) -> Self {
+        self.insert(token, color);
+        self
+    }
+
+    /// Override the color of the `token`, returns the previous override.
+    pub fn insert(
+        &mut self,
+        token: impl Into<SharedString>,
+        color: impl Into<Hsla>,
+    ) -> Option<Hsla> {
+        self.colors.insert(token.into(), color.into())
+    }
+
+    /// Remove the override of the `token`, returns the removed override.
+    pub fn remove(&mut self, token: &str) -> Option<Hsla> {
+        self.colors.remove(token)
+    }
+
+    /// Returns the override of the `token`.
+    pub fn get(&self, token: &str) -> Option<Hsla> {
+        self.colors.get(token).copied()
+    }
+
+    /// Returns true if there is no override.
+    pub fn is_empty(&self) -> bool {
+        self.colors.is_empty()
+    }
+
+    /// Returns the overrides sorted by the token.
+    pub fn iter(&self) -> impl Iterator<Item = (&SharedString, Hsla)> {
+        self.colors.iter().map(|(token, color)| (token, *color))
+    }
+
+    /// Returns the `config` with the overridden colors, and the unknown tokens.
+    fn apply(&self, config: &ThemeConfig) -> (ThemeConfig, Vec<SharedString>) {
+        let mut config = config.clone();
+        if self.is_empty() {
+            return (config, vec![]);
+        }
+
+        let mut unknown_tokens = vec![];
+        let Ok(serde_json::Value::Object(mut colors)) = serde_json::to_value(&config.colors) else {
+            return (config, vec![]);
+        };
+        for (token, color) in self.iter() {
+            match colors.get_mut(&**token) {
+                Some(value) => *value = color.to_hex().into(),
+                None => unknown_tokens.push(token.clone()),
+            }
+        }
+        if let Ok(colors) = serde_json::from_value(serde_json::Value::Object(colors)) {
+            config.colors = colors;
+        }
+
+        (config, unknown_tokens)
+    }
+}
+
+impl Theme {
+    /// Apply the global and the window overrides to the colors of the `config`.
+    pub(super) fn apply_overrides(&mut self, config: &ThemeConfig, default_colors: &ThemeColor) {
+        let (global_config, unknown_tokens) = self.overrides.apply(config);
+        for token in unknown_tokens {
+            tracing::warn!("Ignored unknown theme color override: {}", token);
+        }
+        self.colors.apply_config(&global_config, default_colors);
+
+        let mut window_colors = std::mem::take(&mut self.window_colors);
+        window_colors.clear();
+        for (window_id, overrides) in self.window_overrides.iter() {
+            let (window_config, unknown_tokens) = overrides.apply(&global_config);
+            for token in unknown_tokens {
+                tracing::warn!("Ignored unknown theme color override: {}", token);
+            }
+            let mut colors = self.colors;
+            colors.apply_config(&window_config, default_colors);
+            window_colors.insert(*window_id, colors);
+        }
+        self.window_colors = window_colors;
+    }
+
+    /// Returns the colors of the window being drawn, with its overrides, or the colors with the
+    /// global overrides outside of a draw, see [`Theme::for_window`].
+    #[inline]
+    pub(super) fn current_colors(&self) -> &ThemeColor {
+        if self.window_colors.is_empty() {
+            return &self.colors;
+        }
+
+        CURRENT_WINDOW
+            .get()
+            .and_then(|window_id| self.window_colors.get(&window_id))
+            .unwrap_or(&self.colors)
+    }
+
+    /// Returns the global overrides and the overrides of the window.
+    pub(crate) fn overrides_for_window(
+        &self,
+        window_id: Option<WindowId>,
+    ) -> (&ThemeOverrides, Option<&ThemeOverrides>) {
+        let window_overrides =
+            window_id.and_then(|window_id| self.window_overrides.get(&window_id));
+        (&self.overrides, window_overrides)
+    }
+
+    /// Returns the colors of the `window` with its overrides, e.g. in the event handlers or the
+    /// tasks, where `cx.theme()` only has the global overrides.
+    ///
+    /// ```ignore
+    /// let title_bar = cx.theme().for_window(window).title_bar;
+    /// ```
+    #[inline]
+    pub fn for_window(&self, window: &Window) -> &ThemeColor {
+        self.colors_for_window(window.window_handle().window_id())
+    }
+
+    /// Returns the colors of the window, with its overrides.
+    pub(crate) fn colors_for_window(&self, window_id: WindowId) -> &ThemeColor {
+        self.window_colors.get(&window_id).unwrap_or(&self.colors)
+    }
+}
+
+/// Set the window being drawn, called by the [`crate::Root`] on rendering.
+///
+/// The previous window is restored once the draw is done, so the event handlers and the tasks
+/// don't see the overrides of the window drawn last.
+pub(crate) fn set_current_window(window: &Window, cx: &mut App) {
+    let previous = CURRENT_WINDOW.replace(Some(window.window_handle().window_id()));
+    window.defer(cx, move |_, _| CURRENT_WINDOW.set(previous));
+}
+
+/// Set the color overrides applied on top of the active theme in all windows, replacing the
+/// previous overrides.
+///
+/// The unknown tokens are ignored with a warning.
+pub fn set_overrides(overrides: ThemeOverrides, cx: &mut App) {
+    Theme::global_mut(cx).overrides = overrides;
+    reapply(cx);
+}
+
+/// Set the color overrides of the `window`, applied on top of the global overrides, replacing
+/// the previous overrides of the window.
+///
+/// The window overrides are used by the [`crate::ActiveTheme`] while drawing the window, which
+/// must be rendered by the [`crate::Root`]. Use [`Theme::for_window`] to get them outside of the
+/// draw.
+pub fn set_window_overrides(overrides: ThemeOverrides, window: &Window, cx: &mut App) {
+    let window_id = window.window_handle().window_id();
+    let open_windows = cx
+        .windows()
+        .iter()
+        .map(|window| window.window_id())
+        .collect::<Vec<_>>();
+
+    let theme = Theme::global_mut(cx);
+    theme
+        .window_overrides
+        .retain(|window_id, _| open_windows.contains(window_id));
+    if overrides.is_empty() {
+        theme.window_overrides.remove(&window_id);
+    } else {
+        theme.window_overrides.insert(window_id, overrides);
+    }
+    reapply(cx);
+}
+
+/// Remove the override of the `token` of the `window`, or the global override if `None`,
+/// returns the removed override.
+pub fn remove_override(token: &str, window: Option<&Window>, cx: &mut App) -> Option<Hsla> {
+    let theme = Theme::global_mut(cx);
+    let removed = match window {
+        Some(window) => {
+            let window_id = window.window_handle().window_id();
+            let overrides = theme.window_overrides.get_mut(&window_id)?;
+            let removed = overrides.remove(token);
+            if overrides.is_empty() {
+                theme.window_overrides.remove(&window_id);
+            }
+            removed
+        }
+        None => theme.overrides.remove(token),
+    };
+
+    if removed.is_some() {
+        reapply(cx);
+    }
+    removed
+}
+
+/// Returns the active overrides of the `window`, the global overrides merged with the window
+/// overrides, or the global overrides if `None`.
+pub fn active_overrides(window: Option<&Window>, cx: &App) -> ThemeOverrides {
+    let window_id = window.map(|window| window.window_handle().window_id());
+    let (overrides, window_overrides) = Theme::global(cx).overrides_for_window(window_id);
+    let mut overrides = overrides.clone();
+    if let Some(window_overrides) = window_overrides {
+        overrides.colors.extend(window_overrides.colors.clone());
+    }
+    overrides
+}
+
+/// Apply the active theme config again with the overrides.
+fn reapply(cx: &mut App) {
+    let mode = Theme::global(cx).mode;
+    Theme::change(mode, None, cx);
+    cx.refresh_windows();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ActiveTheme as _;
+    use gpui::TestAppContext;
+
+    #[gpui::test]
+    fn test_global_overrides(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            crate::theme::init(cx);
+            let switch = cx.theme().switch;
+            let red = crate::try_parse_color("#FF0000").unwrap();
+
+            set_overrides(
+                ThemeOverrides::new()
+                    .color("switch.background", gpui::red())
+                    .color("unknown.background", gpui::blue()),
+                cx,
+            );
+            assert_eq!(cx.theme().switch, red);
+            assert_eq!(
+                active_overrides(None, cx).get("switch.background"),
+                Some(gpui::red())
+            );
+
+            // The overrides are kept on the light/dark switching.
+            crate::set_appearance(crate::Appearance::Dark, cx);
+            assert_eq!(cx.theme().switch, red);
+            crate::set_appearance(crate::Appearance::Light, cx);
+
+            assert_eq!(
+                remove_override("switch.background", None, cx),
+                Some(gpui::red())
+            );
+            assert_eq!(cx.theme().switch, switch);
+            assert_eq!(remove_override("switch.background", None, cx), None);
+        });
+    }
+
+    #[gpui::test]
+    fn test_window_overrides(cx: &mut TestAppContext) {
+        cx.update(crate::theme::init);
+        let cx = cx.add_empty_window();
+
+        cx.update(|window, cx| {
+            let title_bar = cx.theme().title_bar;
+            let red = crate::try_parse_color("#FF0000").unwrap();
+            set_overrides(
+                ThemeOverrides::new().color("switch.background", gpui::red()),
+                cx,
+            );
+            set_window_overrides(
+                ThemeOverrides::new().color("title_bar.background", gpui::green()),
+                window,
+                cx,
+            );
+
+            // Outside the draw, only the global overrides are used.
+            assert_eq!(cx.theme().title_bar, title_bar);
+            let green = crate::try_parse_color(&gpui::green().to_hex()).unwrap();
+            assert_eq!(cx.theme().for_window(window).title_bar, green);
+
+            set_current_window(window, cx);
+            assert_eq!(cx.theme().title_bar, green);
+            assert_eq!(cx.theme().switch, red);
+        });
+
+        // Restored at the end of the draw.
+        cx.update(|window, cx| {
+            let red = crate::try_parse_color("#FF0000").unwrap();
+            let green = crate::try_parse_color(&gpui::green().to_hex()).unwrap();
+            assert_ne!(cx.theme().title_bar, green);
+            assert_eq!(cx.theme().switch, red);
+            set_current_window(window, cx);
+            let title_bar = Theme::global(cx).colors.title_bar;
+
+            let overrides = active_overrides(Some(window), cx);
+            let tokens = overrides
+                .iter()
+                .map(|(token, _)| token.to_string())
+                .collect::<Vec<_>>();
+            assert_eq!(tokens, vec!["switch.background", "title_bar.background"]);
+
+            remove_override("title_bar.background", Some(window), cx);
+            assert_eq!(cx.theme().title_bar, title_bar);
+            assert!(
+                active_overrides(Some(window), cx)
+                    .get("title_bar.background")
+                    .is_none()
+            );
+        });
+    }
+}
@@ -1,4 +1,6 @@
-use crate::{Theme, ThemeColor, ThemeConfig, ThemeMode, ThemeSet, highlighter::HighlightTheme};
+use crate::{
+    Appearance, Theme, ThemeColor, ThemeConfig, ThemeMode, ThemeSet, highlighter::HighlightTheme,
+};
 #[allow(unused)]
 use anyhow::Result;
 use gpui::{App, Global, SharedString};
@@ -72,11 +74,67 @@ pub(super) fn init(cx: &mut App) {
     .detach();
 }
 
+/// Register the `theme`, e.g. parsed by [`Theme::from_json`], to the [`ThemeRegistry`] with the
+/// `name`, replacing the theme with the same name.
+///
+/// The registered themes are kept when the themes directory is reloaded.
+pub fn register(name: impl Into<SharedString>, theme: Theme, cx: &mut App) {
+    let mut config = if theme.is_dark() {
+        (*theme.dark_theme).clone()
+    } else {
+        (*theme.light_theme).clone()
+    };
+    config.name = name.into();
+    let config = Rc::new(config);
+
+    let registry = ThemeRegistry::global_mut(cx);
+    registry
+        .registered_themes
+        .insert(config.name.clone(), config.clone());
+    registry.themes.insert(config.name.clone(), config);
+    registry.has_custom_themes = true;
+}
+
+/// Activate the theme with the `name` in the [`ThemeRegistry`], as the light or dark theme by
+/// its mode.
+///
+/// When following the system appearance, the theme is shown while the system is in its mode,
+/// otherwise the appearance is switched to its mode.
+pub fn activate(name: &str, cx: &mut App) -> Result<()> {
+    let Some(config) = ThemeRegistry::global(cx).themes().get(name).cloned() else {
+        return Err(anyhow::anyhow!("Theme not found: {}", name));
+    };
+
+    let theme = Theme::global_mut(cx);
+    let mode = config.mode;
+    if mode.is_dark() {
+        theme.dark_theme = config;
+    } else {
+        theme.light_theme = config;
+    }
+    let mode = if theme.appearance == Appearance::System {
+        theme.mode
+    } else {
+        theme.appearance = if mode.is_dark() {
+            Appearance::Dark
+        } else {
+            Appearance::Light
+        };
+        mode
+    };
+
+    Theme::change(mode, None, cx);
+    cx.refresh_windows();
+    Ok(())
+}
+
 #[derive(Default, Debug)]
 pub struct ThemeRegistry {
     themes_dir: PathBuf,
     default_themes: HashMap<ThemeMode, Rc<ThemeConfig>>,
     themes: HashMap<SharedString, Rc<ThemeConfig>>,
+    /// The themes added by [`register`].
+    registered_themes: HashMap<SharedString, Rc<ThemeConfig>>,
     has_custom_themes: bool,
 }
 
@@ -267,6 +325,10 @@ impl ThemeRegistry {
                 .insert(theme.name.clone(), Rc::new((**theme).clone()));
         }
 
+        for (name, theme) in self.registered_themes.iter() {
+            self.themes.insert(name.clone(), theme.clone());
+        }
+
         for theme in themes.iter() {
             if self.themes.contains_key(&theme.name) {
                 continue;
@@ -285,3 +347,31 @@ impl ThemeRegistry {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gpui::TestAppContext;
+
+    #[gpui::test]
+    fn test_register_and_activate(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            crate::theme::init(cx);
+
+            let theme =
+                Theme::from_json(r##"{ "mode": "dark", "colors": { "background": "#1E1E2E" } }"##)
+                    .unwrap();
+            register("My Dark", theme, cx);
+            assert!(ThemeRegistry::global(cx).themes().contains_key("My Dark"));
+
+            activate("My Dark", cx).unwrap();
+            let theme = Theme::global(cx);
+            assert!(theme.is_dark());
+            assert_eq!(theme.appearance, Appearance::Dark);
+            assert_eq!(theme.theme_name().to_string(), "My Dark");
+            assert_eq!(theme.background, crate::try_parse_color("#1E1E2E").unwrap());
+
+            assert!(activate("Unknown", cx).is_err());
+        });
+    }
+}
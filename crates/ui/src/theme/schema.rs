@@ -1,11 +1,11 @@
 use std::{rc::Rc, sync::Arc};
 
-use gpui::{SharedString, px};
+use gpui::{SharedString, hsla, px};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    Colorize, Theme, ThemeColor, ThemeMode,
+    ColorSet, Colorize, Theme, ThemeColor, ThemeMode,
     highlighter::{HighlightTheme, HighlightThemeStyle},
     try_parse_color,
 };
@@ -57,6 +57,11 @@ pub struct ThemeConfig {
     /// The border radius for large elements like Dialogs and Notifications, default is 8.
     #[serde(rename = "radius.lg")]
     pub radius_lg: Option<usize>,
+    /// The unit of the spacing scale of the named gaps, default is 4.
+    ///
+    /// The gaps are multiples of the unit, e.g. a compact theme with `3` tightens all of them.
+    #[serde(rename = "spacing")]
+    pub spacing: Option<f32>,
     /// Set shadows in the theme, for example the Input and Button, default is true.
     #[serde(rename = "shadow")]
     pub shadow: Option<bool>,
@@ -359,6 +364,12 @@ pub struct ThemeConfigColors {
     /// TitleBar border color.
     #[serde(rename = "title_bar.border")]
     pub title_bar_border: Option<SharedString>,
+    /// TitleBar background color of the inactive window.
+    #[serde(rename = "title_bar.inactive.background")]
+    pub title_bar_inactive: Option<SharedString>,
+    /// TitleBar text color of the inactive window.
+    #[serde(rename = "title_bar.inactive.foreground")]
+    pub title_bar_inactive_foreground: Option<SharedString>,
     /// StatusBar background color, use for the bottom status bar.
     #[serde(rename = "status_bar.background")]
     pub status_bar: Option<SharedString>,
@@ -390,6 +401,20 @@ pub struct ThemeConfigColors {
     /// This is only works on Linux, other platforms we can't change the window border color.
     #[serde(rename = "window.border")]
     pub window_border: Option<SharedString>,
+    /// Window border color of the active window.
+    ///
+    /// # Platform specific:
+    ///
+    /// This is only works on Linux, other platforms we can't change the window border color.
+    #[serde(rename = "window.border.active")]
+    pub window_border_active: Option<SharedString>,
+    /// Window shadow color.
+    ///
+    /// # Platform specific:
+    ///
+    /// This is only works on Linux, other platforms we can't change the window shadow.
+    #[serde(rename = "window.shadow")]
+    pub window_shadow: Option<SharedString>,
 
     /// Base blue color.
     #[serde(rename = "base.blue")]
@@ -428,6 +453,191 @@ pub struct ThemeConfigColors {
     yellow_light: Option<String>,
 }
 
+/// A problem of a color token, found by [`Theme::from_json_with_warnings`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ThemeWarning {
+    /// The color token is missing, the color falls back to the base theme.
+    Missing(SharedString),
+    /// The color can't be parsed, the color falls back to the base theme.
+    Malformed {
+        token: SharedString,
+        value: SharedString,
+    },
+    /// The token isn't a known color token, it's ignored.
+    Unknown(SharedString),
+}
+
+impl std::fmt::Display for ThemeWarning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Missing(token) => write!(f, "missing color `{}`", token),
+            Self::Malformed { token, value } => {
+                write!(f, "malformed color `{}`: {:?}", token, value)
+            }
+            Self::Unknown(token) => write!(f, "unknown color `{}`", token),
+        }
+    }
+}
+
+impl ThemeConfigColors {
+    /// Create the config with all the colors of the `ThemeColor`, in HEX.
+    pub(crate) fn from_colors(colors: &ThemeColor) -> Self {
+        macro_rules! colors {
+            ($($field:ident),* $(,)?) => {
+                Self {
+                    $($field: Some(colors.$field.to_hex().into()),)*
+                    group_box_title_foreground: Some(colors.group_box_foreground.to_hex().into()),
+                }
+            };
+        }
+
+        colors!(
+            accent,
+            accent_foreground,
+            accordion,
+            accordion_hover,
+            background,
+            border,
+            button_primary,
+            button_primary_active,
+            button_primary_foreground,
+            button_primary_hover,
+            group_box,
+            group_box_foreground,
+            caret,
+            chart_1,
+            chart_2,
+            chart_3,
+            chart_4,
+            chart_5,
+            chart_bullish,
+            chart_bearish,
+            danger,
+            danger_active,
+            danger_foreground,
+            danger_hover,
+            description_list_label,
+            description_list_label_foreground,
+            drag_border,
+            drop_target,
+            foreground,
+            info,
+            info_active,
+            info_foreground,
+            info_hover,
+            input,
+            link,
+            link_active,
+            link_hover,
+            list,
+            list_active,
+            list_active_border,
+            list_even,
+            list_head,
+            list_hover,
+            muted,
+            muted_foreground,
+            popover,
+            popover_foreground,
+            primary,
+            primary_active,
+            primary_foreground,
+            primary_hover,
+            progress_bar,
+            ring,
+            scrollbar,
+            scrollbar_thumb,
+            scrollbar_thumb_hover,
+            secondary,
+            secondary_active,
+            secondary_foreground,
+            secondary_hover,
+            selection,
+            sidebar,
+            sidebar_accent,
+            sidebar_accent_foreground,
+            sidebar_border,
+            sidebar_foreground,
+            sidebar_primary,
+            sidebar_primary_foreground,
+            skeleton,
+            slider_bar,
+            slider_thumb,
+            success,
+            success_foreground,
+            success_hover,
+            success_active,
+            switch,
+            switch_thumb,
+            tab,
+            tab_active,
+            tab_active_foreground,
+            tab_bar,
+            tab_bar_segmented,
+            tab_foreground,
+            table,
+            table_active,
+            table_active_border,
+            table_even,
+            table_head,
+            table_head_foreground,
+            table_foot,
+            table_foot_foreground,
+            table_hover,
+            table_row_border,
+            title_bar,
+            title_bar_border,
+            title_bar_inactive,
+            title_bar_inactive_foreground,
+            status_bar,
+            status_bar_border,
+            tiles,
+            warning,
+            warning_active,
+            warning_hover,
+            warning_foreground,
+            overlay,
+            window_border,
+            window_border_active,
+            window_shadow,
+            blue,
+            blue_light,
+            cyan,
+            cyan_light,
+            green,
+            green_light,
+            magenta,
+            magenta_light,
+            red,
+            red_light,
+            yellow,
+            yellow_light,
+        )
+    }
+
+    /// Return the warnings of the missing and malformed colors.
+    fn validate(&self) -> anyhow::Result<Vec<ThemeWarning>> {
+        let serde_json::Value::Object(colors) = serde_json::to_value(self)? else {
+            return Ok(vec![]);
+        };
+
+        Ok(colors
+            .into_iter()
+            .filter_map(|(token, value)| match value {
+                serde_json::Value::String(value) => {
+                    try_parse_color(&value)
+                        .is_err()
+                        .then(|| ThemeWarning::Malformed {
+                            token: token.into(),
+                            value: value.into(),
+                        })
+                }
+                _ => Some(ThemeWarning::Missing(token.into())),
+            })
+            .collect())
+    }
+}
+
 impl ThemeColor {
     /// Create a new `ThemeColor` from a `ThemeConfig`.
     pub(crate) fn apply_config(&mut self, config: &ThemeConfig, default_theme: &ThemeColor) {
@@ -447,10 +657,11 @@ impl ThemeColor {
             };
             // With fallback
             ($config_field:ident, fallback = $fallback:expr) => {
-                if let Some(value) = colors.$config_field {
-                    if let Ok(color) = try_parse_color(&value) {
-                        self.$config_field = color;
-                    }
+                if let Some(color) = colors
+                    .$config_field
+                    .and_then(|value| try_parse_color(&value).ok())
+                {
+                    self.$config_field = color;
                 } else {
                     self.$config_field = $fallback;
                 }
@@ -499,19 +710,17 @@ impl ThemeColor {
             fallback = self.muted.blend(self.foreground.opacity(0.7))
         );
 
-        // Button colors
-        let active_darken = if config.mode.is_dark() { 0.2 } else { 0.1 };
-        let hover_opacity = 0.9;
+        // Button colors, the hover and active colors are derived from the base color if missing.
         apply_color!(primary);
         apply_color!(primary_foreground, fallback = self.foreground);
-        apply_color!(
-            primary_hover,
-            fallback = self.background.blend(self.primary.opacity(hover_opacity))
-        );
-        apply_color!(
-            primary_active,
-            fallback = self.primary.darken(active_darken)
+        let primary = ColorSet::derive(
+            self.primary,
+            self.primary_foreground,
+            self.background,
+            config.mode,
         );
+        apply_color!(primary_hover, fallback = primary.hover);
+        apply_color!(primary_active, fallback = primary.active);
         apply_color!(button_primary, fallback = self.primary);
         apply_color!(
             button_primary_foreground,
@@ -521,41 +730,44 @@ impl ThemeColor {
         apply_color!(button_primary_active, fallback = self.primary_active);
         apply_color!(secondary);
         apply_color!(secondary_foreground, fallback = self.foreground);
-        apply_color!(
-            secondary_hover,
-            fallback = self.background.blend(self.secondary.opacity(hover_opacity))
-        );
-        apply_color!(
-            secondary_active,
-            fallback = self.secondary.darken(active_darken)
+        let secondary = ColorSet::derive(
+            self.secondary,
+            self.secondary_foreground,
+            self.background,
+            config.mode,
         );
+        apply_color!(secondary_hover, fallback = secondary.hover);
+        apply_color!(secondary_active, fallback = secondary.active);
         apply_color!(success, fallback = self.green);
         apply_color!(success_foreground, fallback = self.primary_foreground);
-        apply_color!(
-            success_hover,
-            fallback = self.background.blend(self.success.opacity(hover_opacity))
-        );
-        apply_color!(
-            success_active,
-            fallback = self.success.darken(active_darken)
+        let success = ColorSet::derive(
+            self.success,
+            self.success_foreground,
+            self.background,
+            config.mode,
         );
+        apply_color!(success_hover, fallback = success.hover);
+        apply_color!(success_active, fallback = success.active);
         apply_color!(info, fallback = self.cyan);
         apply_color!(info_foreground, fallback = self.primary_foreground);
-        apply_color!(
-            info_hover,
-            fallback = self.background.blend(self.info.opacity(hover_opacity))
+        let info = ColorSet::derive(
+            self.info,
+            self.info_foreground,
+            self.background,
+            config.mode,
         );
-        apply_color!(info_active, fallback = self.info.darken(active_darken));
+        apply_color!(info_hover, fallback = info.hover);
+        apply_color!(info_active, fallback = info.active);
         apply_color!(warning, fallback = self.yellow);
         apply_color!(warning_foreground, fallback = self.primary_foreground);
-        apply_color!(
-            warning_hover,
-            fallback = self.background.blend(self.warning.opacity(0.9))
-        );
-        apply_color!(
-            warning_active,
-            fallback = self.background.blend(self.warning.darken(active_darken))
+        let warning = ColorSet::derive(
+            self.warning,
+            self.warning_foreground,
+            self.background,
+            config.mode,
         );
+        apply_color!(warning_hover, fallback = warning.hover);
+        apply_color!(warning_active, fallback = warning.active);
 
         // Other colors
         apply_color!(accent, fallback = self.secondary);
@@ -581,12 +793,15 @@ impl ThemeColor {
         apply_color!(chart_bullish, fallback = self.green);
         apply_color!(chart_bearish, fallback = self.red);
         apply_color!(danger, fallback = self.red);
-        apply_color!(danger_active, fallback = self.danger.darken(active_darken));
         apply_color!(danger_foreground, fallback = self.primary_foreground);
-        apply_color!(
-            danger_hover,
-            fallback = self.background.blend(self.danger.opacity(0.9))
+        let danger = ColorSet::derive(
+            self.danger,
+            self.danger_foreground,
+            self.background,
+            config.mode,
         );
+        apply_color!(danger_active, fallback = danger.active);
+        apply_color!(danger_hover, fallback = danger.hover);
         apply_color!(
             description_list_label,
             fallback = self.background.blend(self.border.opacity(0.2))
@@ -657,11 +872,21 @@ impl ThemeColor {
         apply_color!(table_row_border, fallback = self.border);
         apply_color!(title_bar, fallback = self.background);
         apply_color!(title_bar_border, fallback = self.border);
+        apply_color!(
+            title_bar_inactive,
+            fallback = self.title_bar.mix(self.background, 0.5)
+        );
+        apply_color!(
+            title_bar_inactive_foreground,
+            fallback = self.muted_foreground
+        );
         apply_color!(status_bar, fallback = self.title_bar);
         apply_color!(status_bar_border, fallback = self.title_bar_border);
         apply_color!(tiles, fallback = self.background);
         apply_color!(overlay);
         apply_color!(window_border, fallback = self.border);
+        apply_color!(window_border_active, fallback = self.window_border);
+        apply_color!(window_shadow, fallback = hsla(0., 0., 0., 0.3));
 
         // TODO: Apply default fallback colors to highlight.
 
@@ -673,6 +898,66 @@ impl ThemeColor {
 }
 
 impl Theme {
+    /// Parse a theme from the JSON of a single theme config, e.g. a `my-theme.json`:
+    ///
+    /// ```json
+    /// { "name": "My Theme", "mode": "dark", "colors": { "background": "#1E1E2E" } }
+    /// ```
+    ///
+    /// The colors can be HEX, `hsla(h, s%, l%, a)` or Tailwind color names, see
+    /// [`try_parse_color`]. The missing or malformed colors fall back to the base light or dark
+    /// theme, use [`Theme::from_json_with_warnings`] to report them.
+    pub fn from_json(json: &str) -> anyhow::Result<Theme> {
+        Self::from_json_with_warnings(json).map(|(theme, _)| theme)
+    }
+
+    /// Like [`Theme::from_json`], and return the warnings of the missing, malformed and unknown
+    /// color tokens.
+    pub fn from_json_with_warnings(json: &str) -> anyhow::Result<(Theme, Vec<ThemeWarning>)> {
+        let value = serde_json::from_str::<serde_json::Value>(json)?;
+        let config = serde_json::from_value::<ThemeConfig>(value.clone())?;
+
+        let mut warnings = config.colors.validate()?;
+        let known_tokens = serde_json::to_value(&config.colors)?;
+        if let Some(colors) = value.get("colors").and_then(|colors| colors.as_object()) {
+            for token in colors.keys() {
+                if known_tokens.get(token).is_none() {
+                    warnings.push(ThemeWarning::Unknown(token.clone().into()));
+                }
+            }
+        }
+
+        let mut theme = Theme::default();
+        theme.apply_config(&Rc::new(config));
+        Ok((theme, warnings))
+    }
+
+    /// Export the current theme to the JSON of a single theme config, with all the colors, as
+    /// a starting point of a new theme.
+    pub fn to_json(&self) -> anyhow::Result<String> {
+        let current = if self.is_dark() {
+            &self.dark_theme
+        } else {
+            &self.light_theme
+        };
+        let config = ThemeConfig {
+            is_default: false,
+            name: current.name.clone(),
+            mode: self.mode,
+            font_size: Some(self.font_size.as_f32()),
+            font_family: Some(self.font_family.clone()),
+            mono_font_family: Some(self.mono_font_family.clone()),
+            mono_font_size: Some(self.mono_font_size.as_f32()),
+            radius: Some(self.radius.as_f32() as usize),
+            radius_lg: Some(self.radius_lg.as_f32() as usize),
+            spacing: Some(self.spacing.as_f32()),
+            shadow: Some(self.shadow),
+            colors: ThemeConfigColors::from_colors(&self.colors),
+            highlight: current.highlight.clone(),
+        };
+        Ok(serde_json::to_string_pretty(&config)?)
+    }
+
     /// Apply the given theme configuration to the current theme.
     pub fn apply_config(&mut self, config: &Rc<ThemeConfig>) {
         if config.mode.is_dark() {
@@ -723,9 +1008,77 @@ impl Theme {
             .radius_lg
             .map(|r| px(r as f32))
             .unwrap_or(defaults.radius_lg);
+        self.spacing = config.spacing.map(px).unwrap_or(defaults.spacing);
         self.shadow = config.shadow.unwrap_or(defaults.shadow);
 
-        self.colors.apply_config(&config, &default_colors);
+        self.apply_overrides(config, &default_colors);
         self.mode = config.mode;
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hsl;
+
+    #[test]
+    fn test_theme_from_json() {
+        let (theme, warnings) = Theme::from_json_with_warnings(
+            r##"{
+                "name": "My Theme",
+                "mode": "dark",
+                "colors": {
+                    "background": "#1E1E2E",
+                    "foreground": "hsla(0, 0%, 90%, 1)",
+                    "primary.background": "not-a-color",
+                    "primary.bakground": "#FF0000"
+                }
+            }"##,
+        )
+        .unwrap();
+
+        assert!(theme.is_dark());
+        assert_eq!(theme.theme_name().to_string(), "My Theme");
+        assert_eq!(theme.background, try_parse_color("#1E1E2E").unwrap());
+        assert_eq!(theme.foreground, hsl(0., 0., 90.));
+        assert_eq!(theme.primary, ThemeColor::dark().primary);
+
+        assert!(warnings.contains(&ThemeWarning::Malformed {
+            token: "primary.background".into(),
+            value: "not-a-color".into(),
+        }));
+        assert!(warnings.contains(&ThemeWarning::Unknown("primary.bakground".into())));
+        assert!(warnings.contains(&ThemeWarning::Missing("accent.background".into())));
+        assert!(!warnings.contains(&ThemeWarning::Missing("background".into())));
+
+        assert!(Theme::from_json("{ \"mode\": \"dim\" }").is_err());
+    }
+
+    #[test]
+    fn test_theme_to_json() {
+        let theme = Theme::from_json(
+            r##"{ "name": "My Theme", "mode": "dark", "colors": { "background": "#1E1E2E" } }"##,
+        )
+        .unwrap();
+        let json = theme.to_json().unwrap();
+
+        let (imported, warnings) = Theme::from_json_with_warnings(&json).unwrap();
+        assert!(warnings.is_empty(), "{:?}", warnings);
+        assert!(imported.is_dark());
+        assert_eq!(imported.theme_name().to_string(), "My Theme");
+        assert_eq!(imported.background, theme.background);
+        assert_eq!(imported.to_json().unwrap(), json);
+    }
+
+    #[test]
+    fn test_theme_spacing() {
+        let theme = Theme::from_json(r##"{ "name": "Compact", "spacing": 3 }"##).unwrap();
+        assert_eq!(theme.spacing, px(3.));
+        assert_eq!(theme.gap(crate::GapSize::Small), px(6.));
+
+        // The spacing of the previous theme doesn't leak.
+        let mut theme = theme;
+        theme.apply_config(&Rc::new(ThemeConfig::default()));
+        assert_eq!(theme.spacing, px(4.));
+    }
+}
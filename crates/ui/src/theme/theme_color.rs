@@ -199,6 +199,11 @@ pub struct ThemeColor {
     pub title_bar: Hsla,
     /// TitleBar border color.
     pub title_bar_border: Hsla,
+    /// TitleBar background color of the inactive window, default is a mix of the `title_bar` and
+    /// the `background`.
+    pub title_bar_inactive: Hsla,
+    /// TitleBar text color of the inactive window, default is the `muted_foreground`.
+    pub title_bar_inactive_foreground: Hsla,
     /// StatusBar background color, use for the bottom status bar.
     pub status_bar: Hsla,
     /// StatusBar border color.
@@ -221,6 +226,18 @@ pub struct ThemeColor {
     ///
     /// This is only works on Linux, other platforms we can't change the window border color.
     pub window_border: Hsla,
+    /// Window border color of the active window, default is the `window_border`.
+    ///
+    /// # Platform specific:
+    ///
+    /// This is only works on Linux, other platforms we can't change the window border color.
+    pub window_border_active: Hsla,
+    /// Window shadow color.
+    ///
+    /// # Platform specific:
+    ///
+    /// This is only works on Linux, other platforms we can't change the window shadow.
+    pub window_shadow: Hsla,
 
     /// The base red color.
     pub red: Hsla,
@@ -937,7 +937,7 @@ impl Calendar {
 
 impl Sizable for Calendar {
     fn with_size(mut self, size: impl Into<Size>) -> Self {
-        self.size = size.into();
+        self.size = size.into().clamp("Calendar");
         self
     }
 }
@@ -1,21 +1,46 @@
-use std::rc::Rc;
-
 use crate::{
-    ActiveTheme, Icon, IconName, Sizable as _, StyledExt, h_flex,
+    ActiveTheme, CloseBehavior, Colorize as _, ComponentDensity, ElementExt as _, GapSize, Side,
+    Sizable as _, StyledExt, WindowControls, WindowExt as _, h_flex, h_flex_between,
+    h_flex_centered,
+    menu::{AppMenuBar, TitleBarMenu},
+    tooltip::{Tooltip, TooltipOptions},
 };
 use gpui::{
-    AnyElement, App, ClickEvent, Context, Hsla, InteractiveElement, IntoElement,
-    MAX_BUTTONS_PER_SIDE, MouseButton, ParentElement, Pixels, Point, Render, RenderOnce,
-    StatefulInteractiveElement as _, StyleRefinement, Styled, TitlebarOptions, Window,
-    WindowButton, WindowButtonLayout, WindowControlArea, div, prelude::FluentBuilder as _, px,
+    AnyElement, App, Bounds, ClickEvent, CursorStyle, Global, Hsla, InteractiveElement,
+    IntoElement, MAX_BUTTONS_PER_SIDE, MouseButton, ParentElement, Pixels, Point, RenderOnce,
+    SharedString, StatefulInteractiveElement as _, StyleRefinement, Styled, TitlebarOptions,
+    Window, WindowButton, WindowButtonLayout, WindowControlArea, WindowId, WindowOptions, div,
+    prelude::FluentBuilder as _, px,
 };
 use smallvec::SmallVec;
+use std::{collections::HashMap, rc::Rc};
+
+mod placement;
+
+pub use placement::{JsonPlacementStorage, PlacementStorage, SavedPlacement, WindowPlacement};
 
 pub const TITLE_BAR_HEIGHT: Pixels = px(34.);
+/// The height of the title bar in the [`ComponentDensity::Compact`].
+pub const TITLE_BAR_COMPACT_HEIGHT: Pixels = px(28.);
 #[cfg(target_os = "macos")]
 const TITLE_BAR_LEFT_PADDING: Pixels = px(80.);
 #[cfg(not(target_os = "macos"))]
 const TITLE_BAR_LEFT_PADDING: Pixels = px(12.);
+/// The height of the strip at the top of the title bar that starts a window resize.
+pub const TITLE_BAR_TOP_RESIZE_ZONE: Pixels = px(8.);
+
+/// The height of the [`TitleBarAccentStyle::Strip`].
+const ACCENT_STRIP_HEIGHT: Pixels = px(3.);
+
+/// How the [`TitleBar::accent`] color is rendered.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TitleBarAccentStyle {
+    /// A thin strip along the top edge.
+    #[default]
+    Strip,
+    /// Tint the title bar background with the accent color.
+    Tint,
+}
 
 /// TitleBar used to customize the appearance of the title bar.
 ///
@@ -24,11 +49,23 @@ const TITLE_BAR_LEFT_PADDING: Pixels = px(12.);
 pub struct TitleBar {
     style: StyleRefinement,
     children: SmallVec<[AnyElement; 1]>,
-    on_close_window: Option<Rc<Box<dyn Fn(&ClickEvent, &mut Window, &mut App)>>>,
+    close_behavior: CloseBehavior,
     button_layout: Option<WindowButtonLayout>,
     /// Optional centered title overlay — rendered absolutely across the full
     /// titlebar width so it stays visually centered regardless of controls.
     title_overlay: Option<AnyElement>,
+    auto_title: bool,
+    title_tooltip: bool,
+    control_tooltip: Option<TooltipOptions>,
+    control_focusable: bool,
+    transparent: bool,
+    hover_highlight: bool,
+    top_resize_zone: Pixels,
+    menus: Vec<TitleBarMenu>,
+    on_drag_out: Option<(Pixels, Rc<dyn Fn(&Point<Pixels>, &mut Window, &mut App)>)>,
+    accent: Option<Hsla>,
+    accent_style: TitleBarAccentStyle,
+    placement_key: Option<SharedString>,
 }
 
 impl TitleBar {
@@ -37,9 +74,21 @@ impl TitleBar {
         Self {
             style: StyleRefinement::default(),
             children: SmallVec::new(),
-            on_close_window: None,
+            close_behavior: CloseBehavior::default(),
             button_layout: None,
             title_overlay: None,
+            auto_title: false,
+            title_tooltip: true,
+            control_tooltip: None,
+            control_focusable: false,
+            transparent: false,
+            hover_highlight: false,
+            top_resize_zone: TITLE_BAR_TOP_RESIZE_ZONE,
+            menus: Vec::new(),
+            on_drag_out: None,
+            accent: None,
+            accent_style: TitleBarAccentStyle::default(),
+            placement_key: None,
         }
     }
 
@@ -52,14 +101,36 @@ impl TitleBar {
         }
     }
 
+    /// Returns the default window options for compatible with the [`crate::TitleBar`].
+    ///
+    /// This includes the [`TitleBar::title_bar_options`], and on Linux the client decorations
+    /// with a transparent background for the [`crate::WindowBorder`] shadow.
+    pub fn window_options() -> WindowOptions {
+        WindowOptions {
+            titlebar: Some(Self::title_bar_options()),
+            #[cfg(target_os = "linux")]
+            window_background: gpui::WindowBackgroundAppearance::Transparent,
+            #[cfg(target_os = "linux")]
+            window_decorations: Some(gpui::WindowDecorations::Client),
+            ..Default::default()
+        }
+    }
+
     /// Add custom for close window event, default is None, then click X button will call `window.remove_window()`.
     /// This works on Linux and Windows. On macOS, the native traffic lights handle window close.
     pub fn on_close_window(
         mut self,
         f: impl Fn(&ClickEvent, &mut Window, &mut App) + 'static,
     ) -> Self {
+        self.close_behavior(CloseBehavior::callback(f))
+    }
+
+    /// Set the behavior of the close button, default is [`CloseBehavior::RemoveWindow`].
+    ///
+    /// This works on Linux and Windows. On macOS, the native traffic lights handle window close.
+    pub fn close_behavior(mut self, behavior: CloseBehavior) -> Self {
         if !cfg!(target_os = "macos") {
-            self.on_close_window = Some(Rc::new(Box::new(f)));
+            self.close_behavior = behavior;
         }
         self
     }
@@ -83,227 +154,147 @@ impl TitleBar {
         self.title_overlay = Some(element.into_any_element());
         self
     }
-}
 
-// The Windows control buttons have a fixed width of 35px.
-//
-// We don't need implementation the click event for the control buttons.
-// If user clicked in the bounds, the window event will be triggered.
-#[derive(IntoElement, Clone)]
-enum ControlIcon {
-    Minimize,
-    Restore,
-    Maximize,
-    Close {
-        on_close_window: Option<Rc<Box<dyn Fn(&ClickEvent, &mut Window, &mut App)>>>,
-    },
-}
-
-impl ControlIcon {
-    fn minimize() -> Self {
-        Self::Minimize
+    /// Set to show the window title (from `window.set_window_title`) as the centered title,
+    /// default is false.
+    ///
+    /// The title is read from the window on each render. If [`TitleBar::title`] is also set,
+    /// the explicit title wins.
+    pub fn auto_title(mut self, auto_title: bool) -> Self {
+        self.auto_title = auto_title;
+        self
     }
 
-    fn restore() -> Self {
-        Self::Restore
+    /// Set to show the full window title in a tooltip when the [`TitleBar::auto_title`] is
+    /// truncated, default is true.
+    ///
+    /// The tooltip is only attached when the title doesn't fit the title bar width. A custom
+    /// [`TitleBar::title`] element should add its own tooltip.
+    pub fn title_tooltip(mut self, title_tooltip: bool) -> Self {
+        self.title_tooltip = title_tooltip;
+        self
     }
 
-    fn maximize() -> Self {
-        Self::Maximize
+    /// Set to show the tooltips of the window control buttons with the [`TooltipOptions`],
+    /// default is None.
+    ///
+    /// See also [`WindowControls::tooltip`].
+    pub fn control_tooltip(mut self, options: impl Into<Option<TooltipOptions>>) -> Self {
+        self.control_tooltip = options.into();
+        self
     }
 
-    fn close(on_close_window: Option<Rc<Box<dyn Fn(&ClickEvent, &mut Window, &mut App)>>>) -> Self {
-        Self::Close { on_close_window }
+    /// Set to focus the window control buttons by keyboard, default is false.
+    ///
+    /// See also [`WindowControls::focusable`].
+    pub fn control_focusable(mut self, focusable: bool) -> Self {
+        self.control_focusable = focusable;
+        self
     }
 
-    fn id(&self) -> &'static str {
-        match self {
-            Self::Minimize => "minimize",
-            Self::Restore => "restore",
-            Self::Maximize => "maximize",
-            Self::Close { .. } => "close",
-        }
+    /// Set to highlight the empty drag region on hover, and while a drag is being started,
+    /// default is false.
+    ///
+    /// Only used on Linux, like the GNOME client side decorated headers.
+    pub fn hover_highlight(mut self, hover_highlight: bool) -> Self {
+        self.hover_highlight = hover_highlight;
+        self
     }
 
-    fn icon(&self) -> IconName {
-        if cfg!(target_os = "linux") {
-            match self {
-                Self::Minimize => IconName::GenericMinimize,
-                Self::Restore => IconName::GenericRestore,
-                Self::Maximize => IconName::GenericMaximize,
-                Self::Close { .. } => IconName::GenericClose,
-            }
-        } else {
-            match self {
-                Self::Minimize => IconName::WindowMinimize,
-                Self::Restore => IconName::WindowRestore,
-                Self::Maximize => IconName::WindowMaximize,
-                Self::Close { .. } => IconName::WindowClose,
-            }
-        }
+    /// Set the height of the strip at the top edge that starts a window resize instead of a move.
+    ///
+    /// Used on Windows and on Linux with client decorations, default is [`TITLE_BAR_TOP_RESIZE_ZONE`].
+    pub fn top_resize_zone(mut self, zone: impl Into<Pixels>) -> Self {
+        self.top_resize_zone = zone.into();
+        self
     }
 
-    fn window_control_area(&self) -> WindowControlArea {
-        match self {
-            Self::Minimize => WindowControlArea::Min,
-            Self::Restore | Self::Maximize => WindowControlArea::Max,
-            Self::Close { .. } => WindowControlArea::Close,
-        }
+    /// Set the menus rendered as a menu bar in the left slot, like File, Edit, View.
+    ///
+    /// Click a root to open its menu, while a menu is open hovering another root switches to it.
+    /// `escape` closes the menu, and releasing `alt` toggles the keyboard focus to the first root
+    /// (not on macOS).
+    pub fn menu_bar(mut self, menus: impl IntoIterator<Item = TitleBarMenu>) -> Self {
+        self.menus.extend(menus);
+        self
     }
 
-    fn is_close(&self) -> bool {
-        matches!(self, Self::Close { .. })
+    /// Set the callback when the title bar is dragged out by more than `threshold` with `ctrl`
+    /// held, e.g. to detach a tab into a new window.
+    ///
+    /// The argument is the mouse position on the screen. The window doesn't move during this
+    /// gesture, releasing the mouse before the threshold ends it like a click, as the native
+    /// move can't start once the mouse is released, and dragging without the modifier still
+    /// moves the window natively.
+    ///
+    /// This works on Linux and Windows.
+    pub fn on_drag_out(
+        mut self,
+        threshold: impl Into<Pixels>,
+        f: impl Fn(&Point<Pixels>, &mut Window, &mut App) + 'static,
+    ) -> Self {
+        self.on_drag_out = Some((threshold.into(), Rc::new(f)));
+        self
     }
 
-    #[inline]
-    fn hover_fg(&self, cx: &App) -> Hsla {
-        if self.is_close() {
-            cx.theme().danger_foreground
-        } else {
-            cx.theme().secondary_foreground
-        }
+    /// Set the accent color to identify the window, e.g. per project, default is None.
+    ///
+    /// The height, border and drag region are not affected. See [`TitleBar::accent_style`].
+    pub fn accent(mut self, color: impl Into<Hsla>) -> Self {
+        self.accent = Some(color.into());
+        self
     }
 
-    #[inline]
-    fn hover_bg(&self, cx: &App) -> Hsla {
-        if self.is_close() {
-            cx.theme().danger
-        } else {
-            cx.theme().secondary_hover
-        }
+    /// Set how the accent color is rendered, default is [`TitleBarAccentStyle::Strip`].
+    ///
+    /// The [`TitleBarAccentStyle::Tint`] has no effect when [`TitleBar::transparent`].
+    pub fn accent_style(mut self, style: TitleBarAccentStyle) -> Self {
+        self.accent_style = style;
+        self
     }
 
-    #[inline]
-    fn active_bg(&self, cx: &mut App) -> Hsla {
-        if self.is_close() {
-            cx.theme().danger_active
-        } else {
-            cx.theme().secondary_active
-        }
+    /// Save the bounds and the maximized state of the window with the `key`, default is None.
+    ///
+    /// Use [`WindowPlacement::restore`] with the same key to open the window at the saved
+    /// placement, see [`WindowPlacement::track`].
+    pub fn remember_placement(mut self, key: impl Into<SharedString>) -> Self {
+        self.placement_key = Some(key.into());
+        self
     }
-}
-
-impl RenderOnce for ControlIcon {
-    fn render(self, _: &mut Window, cx: &mut App) -> impl IntoElement {
-        let is_linux = cfg!(target_os = "linux");
-        let is_macos = cfg!(target_os = "macos");
-        let is_windows = cfg!(target_os = "windows");
-        let hover_fg = self.hover_fg(cx);
-        let hover_bg = self.hover_bg(cx);
-        let active_bg = self.active_bg(cx);
-        let icon = self.clone();
-        let fg_muted = cx.theme().muted_foreground;
-        let on_close_window = match &self {
-            ControlIcon::Close { on_close_window } => on_close_window.clone(),
-            _ => None,
-        };
 
-        div()
-            .id(self.id())
-            .flex()
-            .flex_shrink_0()
-            .justify_center()
-            .content_center()
-            .items_center()
-            // Windows: tall rectangular buttons spanning the full title bar height
-            .when(!is_linux, |this| {
-                this.w(TITLE_BAR_HEIGHT)
-                    .h_full()
-                    .text_color(cx.theme().foreground)
-                    .hover(|style| style.bg(hover_bg).text_color(hover_fg))
-                    .active(|style| style.bg(active_bg).text_color(hover_fg))
-            })
-            // Linux: small rounded circle buttons (20×20px) with uniform muted colors
-            .when(is_linux, |this| {
-                let bg_muted = cx.theme().muted;
-                let fg = cx.theme().foreground;
-                this.size_5()
-                    .rounded_2xl()
-                    .cursor_pointer()
-                    .text_color(fg_muted)
-                    .hover(|style| style.bg(bg_muted).text_color(fg))
-                    .active(|style| style.bg(bg_muted).text_color(fg))
-            })
-            .when(is_windows, |this| {
-                this.window_control_area(self.window_control_area())
-            })
-            .when(!is_macos, |this| {
-                this.on_mouse_down(MouseButton::Left, move |_, window, cx| {
-                    window.prevent_default();
-                    cx.stop_propagation();
-                })
-                .on_click(move |_, window, cx| {
-                    cx.stop_propagation();
-                    match icon {
-                        Self::Minimize => window.minimize_window(),
-                        Self::Restore | Self::Maximize => {
-                            #[cfg(target_os = "windows")]
-                            toggle_maximize_win32(window);
-                            #[cfg(not(target_os = "windows"))]
-                            window.zoom_window();
-                        }
-                        Self::Close { .. } => {
-                            if let Some(f) = on_close_window.clone() {
-                                f(&ClickEvent::default(), window, cx);
-                            } else {
-                                window.remove_window();
-                            }
-                        }
-                    }
-                })
-            })
-            .child(
-                Icon::new(self.icon())
-                    .when(is_linux, |this| this.size_4().text_color(fg_muted))
-                    .when(!is_linux, |this| this.small())
-            )
+    /// Set to render the title bar without background and bottom border, default is false.
+    ///
+    /// The drag region, double-click and window controls keep working, the content
+    /// behind the title bar shows through. Window controls only get a translucent
+    /// backdrop while hovered.
+    pub fn transparent(mut self, transparent: bool) -> Self {
+        self.transparent = transparent;
+        self
     }
 }
 
-#[derive(IntoElement)]
-struct WindowControls {
-    id: &'static str,
-    buttons: [Option<WindowButton>; MAX_BUTTONS_PER_SIDE],
-    on_close_window: Option<Rc<Box<dyn Fn(&ClickEvent, &mut Window, &mut App)>>>,
+/// The style refines the title bar after its default background, border and paddings.
+///
+/// The height is protected, it's applied over the style to match the window controls and the
+/// drag region of the platform, see [`TITLE_BAR_HEIGHT`] and [`TITLE_BAR_COMPACT_HEIGHT`].
+impl Styled for TitleBar {
+    fn style(&mut self) -> &mut gpui::StyleRefinement {
+        &mut self.style
+    }
 }
 
-impl RenderOnce for WindowControls {
-    fn render(self, window: &mut Window, _: &mut App) -> impl IntoElement {
-        let is_linux = cfg!(target_os = "linux");
-        let is_maximized = window.is_maximized();
-        let on_close = self.on_close_window;
-
-        let icons: Vec<ControlIcon> = self
-            .buttons
-            .iter()
-            .filter_map(|b| *b)
-            .map(|button| match button {
-                WindowButton::Minimize => ControlIcon::minimize(),
-                WindowButton::Maximize => {
-                    if is_maximized {
-                        ControlIcon::restore()
-                    } else {
-                        ControlIcon::maximize()
-                    }
-                }
-                WindowButton::Close => ControlIcon::close(on_close.clone()),
-            })
-            .collect();
-
-        h_flex()
-            .id(self.id)
-            .items_center()
-            .flex_shrink_0()
-            .h_full()
-            // Linux: spaced rounded buttons with padding
-            .when(is_linux, |this| this.gap_2().px_3())
-            .children(icons)
-    }
+/// Returns the properties of the title bar applied over the style of [`TitleBar`].
+fn protected_style(density: ComponentDensity) -> StyleRefinement {
+    StyleRefinement::default()
+        .flex_shrink_0()
+        .h(title_bar_height(density))
 }
 
-impl Styled for TitleBar {
-    fn style(&mut self) -> &mut gpui::StyleRefinement {
-        &mut self.style
+/// Returns the height of the title bar in the `density`.
+pub fn title_bar_height(density: ComponentDensity) -> Pixels {
+    match density {
+        ComponentDensity::Comfortable => TITLE_BAR_HEIGHT,
+        ComponentDensity::Compact => TITLE_BAR_COMPACT_HEIGHT,
     }
 }
 
@@ -313,24 +304,184 @@ impl ParentElement for TitleBar {
     }
 }
 
+/// The state of the custom drag region.
+///
+/// Only compiled for Windows and Linux: macOS handles the title bar natively, so any use of
+/// the state there fails to build instead of allocating an unused entity per window.
+#[cfg(not(target_os = "macos"))]
+#[derive(Default)]
 struct TitleBarState {
     should_move: bool,
+    /// Whether the platform close request should minimize the window, see [`CloseBehavior::Hide`].
+    hide_on_close: bool,
+    close_intercepted: bool,
     drag_start_pos: Option<Point<Pixels>>,
+    /// The mouse down position of a drag out gesture, see [`TitleBar::on_drag_out`].
+    drag_out_start: Option<Point<Pixels>>,
     #[cfg(target_os = "windows")]
     last_mousedown_time: Option<std::time::Instant>,
     #[cfg(target_os = "windows")]
     last_mousedown_pos: Option<Point<Pixels>>,
+    /// The window rect before a vertical maximize, restored on the next double-click
+    /// on the top resize strip.
+    #[cfg(target_os = "windows")]
+    vertical_restore_rect: Option<windows::Win32::Foundation::RECT>,
 }
 
-// TODO: Remove this when GPUI has released v0.2.3
-impl Render for TitleBarState {
-    fn render(&mut self, _: &mut Window, _: &mut Context<Self>) -> impl IntoElement {
+#[cfg(not(target_os = "macos"))]
+impl TitleBarState {
+    /// Start tracking a possible window move, the move starts on the next mouse move.
+    fn begin_drag(&mut self, position: Point<Pixels>) {
+        self.should_move = true;
+        self.drag_start_pos = Some(position);
+    }
+
+    /// Returns true and stops tracking if the mouse down position is excluded from the drag region.
+    fn drag_excluded(&mut self, position: Point<Pixels>, exclusions: &[Bounds<Pixels>]) -> bool {
+        let excluded = contains_position(exclusions, position);
+        if excluded {
+            self.should_move = false;
+            self.drag_start_pos = None;
+        }
+        excluded
+    }
+
+    /// Returns true if the window move should start on this mouse move.
+    fn should_start_move(&mut self, position: Point<Pixels>) -> bool {
+        if !self.should_move {
+            return false;
+        }
+
+        // On Windows, only start drag after exceeding a movement threshold (4px),
+        // so that double-clicks aren't swallowed by the drag modal loop.
+        if cfg!(target_os = "windows") {
+            let Some(start) = self.drag_start_pos else {
+                return false;
+            };
+            let delta = position - start;
+            let threshold = px(4.0);
+            if delta.x <= threshold
+                && delta.x >= -threshold
+                && delta.y <= threshold
+                && delta.y >= -threshold
+            {
+                return false;
+            }
+        }
+
+        self.should_move = false;
+        self.drag_start_pos = None;
+        true
+    }
+
+    /// Start tracking a drag out gesture instead of a window move.
+    fn begin_drag_out(&mut self, position: Point<Pixels>) {
+        self.should_move = false;
+        self.drag_start_pos = None;
+        self.drag_out_start = Some(position);
+    }
+
+    /// Returns true and stops tracking if the drag out gesture exceeds the threshold on this
+    /// mouse move.
+    fn should_drag_out(&mut self, position: Point<Pixels>, threshold: Pixels) -> bool {
+        let Some(start) = self.drag_out_start else {
+            return false;
+        };
+        let delta = position - start;
+        if delta.x.abs() <= threshold && delta.y.abs() <= threshold {
+            return false;
+        }
+
+        self.drag_out_start = None;
+        true
+    }
+
+    /// Stop tracking the window move and the drag out gesture on mouse up, a drag out released
+    /// below the threshold ends like a click, the next drag moves the window natively.
+    fn release(&mut self) {
+        self.should_move = false;
+        self.drag_start_pos = None;
+        self.drag_out_start = None;
+    }
+
+    /// Record a left mouse down, returns true if it completes a double-click.
+    #[cfg(target_os = "windows")]
+    fn record_mousedown(&mut self, position: Point<Pixels>) -> bool {
+        let now = std::time::Instant::now();
+        let is_double_click = match (self.last_mousedown_time, self.last_mousedown_pos) {
+            (Some(last_time), Some(last_pos)) => {
+                let elapsed = now.duration_since(last_time);
+                let delta = position - last_pos;
+                let threshold = px(4.0);
+                elapsed.as_millis() < 500
+                    && delta.x > -threshold
+                    && delta.x < threshold
+                    && delta.y > -threshold
+                    && delta.y < threshold
+            }
+            _ => false,
+        };
+
+        if is_double_click {
+            self.should_move = false;
+            self.drag_start_pos = None;
+            self.last_mousedown_time = None;
+            self.last_mousedown_pos = None;
+        } else {
+            self.last_mousedown_time = Some(now);
+            self.last_mousedown_pos = Some(position);
+        }
+        is_double_click
+    }
+}
+
+/// The bounds excluded from the TitleBar drag region in the current frame, per window.
+#[derive(Default)]
+struct DragExclusions(HashMap<WindowId, SmallVec<[Bounds<Pixels>; 4]>>);
+
+impl Global for DragExclusions {}
+
+/// Exclude the bounds from the TitleBar drag region of the window for the current frame.
+///
+/// See [`crate::InteractiveElementExt::exclude_from_drag`].
+pub(crate) fn exclude_from_drag(bounds: Bounds<Pixels>, window: &Window, cx: &mut App) {
+    let window_id = window.window_handle().window_id();
+    cx.default_global::<DragExclusions>()
+        .0
+        .entry(window_id)
+        .or_default()
+        .push(bounds);
+}
+
+#[cfg(not(target_os = "macos"))]
+fn drag_exclusions(window: &Window, cx: &App) -> SmallVec<[Bounds<Pixels>; 4]> {
+    let window_id = window.window_handle().window_id();
+    cx.try_global::<DragExclusions>()
+        .and_then(|exclusions| exclusions.0.get(&window_id))
+        .cloned()
+        .unwrap_or_default()
+}
+
+#[cfg(not(target_os = "macos"))]
+#[inline]
+fn contains_position(bounds: &[Bounds<Pixels>], position: Point<Pixels>) -> bool {
+    bounds.iter().any(|bounds| bounds.contains(&position))
+}
+
+// TODO: Remove this when GPUI has released v0.2.3, `window.listener_for` requires `Render`.
+#[cfg(not(target_os = "macos"))]
+impl gpui::Render for TitleBarState {
+    fn render(&mut self, _: &mut Window, _: &mut gpui::Context<Self>) -> impl IntoElement {
         div()
     }
 }
 
 impl RenderOnce for TitleBar {
     fn render(self, window: &mut Window, cx: &mut App) -> impl IntoElement {
+        if let Some(key) = self.placement_key.clone() {
+            WindowPlacement::track(window, key, cx);
+        }
+
         let is_macos = cfg!(target_os = "macos");
         let is_linux = cfg!(target_os = "linux");
 
@@ -365,138 +516,146 @@ impl RenderOnce for TitleBar {
             })
         };
 
+        #[cfg(debug_assertions)]
+        {
+            if self.auto_title && self.title_overlay.is_some() {
+                static WARN: std::sync::Once = std::sync::Once::new();
+                WARN.call_once(|| warn("both `auto_title` and `title` are set, `title` wins."));
+            }
+            check_window_setup(window, cx);
+        }
+        // The width available to the title, measured in the last prepaint.
+        let title_width = window.use_keyed_state("title-bar-title-width", cx, |_, _| Pixels::ZERO);
+        let title_overlay = self.title_overlay.or_else(|| {
+            self.auto_title.then(|| {
+                let title = SharedString::from(window.window_title());
+                let truncated = self.title_tooltip
+                    && measure_text_width(&title, window) > *title_width.read(cx);
+                div()
+                    .id("title-bar-title")
+                    .min_w_0()
+                    .truncate()
+                    .child(title.clone())
+                    .when(truncated, |this| {
+                        this.tooltip(move |window, cx| {
+                            Tooltip::new(title.clone()).build(window, cx)
+                        })
+                    })
+                    .into_any_element()
+            })
+        });
+
+        // Children register their exclusions again while this frame is prepainted, the
+        // exclusions of the closed windows are dropped.
+        let window_id = window.window_handle().window_id();
+        let open_windows = cx
+            .windows()
+            .iter()
+            .map(|window| window.window_id())
+            .collect::<Vec<_>>();
+        cx.default_global::<DragExclusions>()
+            .0
+            .retain(|id, _| *id != window_id && open_windows.contains(id));
+
         let has_left_controls = button_layout
             .as_ref()
             .is_some_and(|l| l.left.iter().any(|b| b.is_some()));
 
-        let state = window.use_state(cx, |_, _| TitleBarState {
-            should_move: false,
-            drag_start_pos: None,
-            #[cfg(target_os = "windows")]
-            last_mousedown_time: None,
-            #[cfg(target_os = "windows")]
-            last_mousedown_pos: None,
-        });
+        // The drag state is only used with the custom drag region, not on macOS.
+        #[cfg(not(target_os = "macos"))]
+        let state = window.use_state(cx, |_, _| TitleBarState::default());
+
+        // Keep the platform close request (e.g. Alt-F4) consistent with the close button.
+        #[cfg(not(target_os = "macos"))]
+        {
+            let hide_on_close = self.close_behavior.is_hide();
+            let needs_intercept = state.update(cx, |state, _| {
+                state.hide_on_close = hide_on_close;
+                let needs_intercept = hide_on_close && !state.close_intercepted;
+                state.close_intercepted |= hide_on_close;
+                needs_intercept
+            });
+            if needs_intercept {
+                let state = state.downgrade();
+                window.on_window_should_close(cx, move |window, cx| {
+                    let hide = state
+                        .read_with(cx, |state, _| state.hide_on_close)
+                        .unwrap_or(false);
+                    if hide {
+                        window.minimize_window();
+                    }
+                    !hide
+                });
+            }
+        }
+
+        let menu_bar =
+            (!self.menus.is_empty()).then(|| AppMenuBar::title_bar(self.menus, window, cx));
+
+        #[cfg(feature = "mcp")]
+        if let Some(accent) = self.accent {
+            crate::mcp::mcp_set_window_properties(
+                window,
+                serde_json::json!({ "accent": accent.to_hex() }),
+            );
+        }
+        let accent_strip = self
+            .accent
+            .filter(|_| self.accent_style == TitleBarAccentStyle::Strip);
+        // The window is refreshed when the activation changes, so this follows the focus.
+        let is_active = window.window_active(cx);
+        let base_bg = if is_active {
+            cx.theme().title_bar
+        } else {
+            cx.theme().title_bar_inactive
+        };
+        let title_bar_bg = match self.accent {
+            Some(accent) if self.accent_style == TitleBarAccentStyle::Tint => {
+                accent.mix(base_bg, 0.12)
+            }
+            _ => base_bg,
+        };
+
+        let top_resize_zone = self.top_resize_zone;
+        let density = crate::window_density(window, cx);
+        let hover_highlight = is_linux && self.hover_highlight;
+        #[cfg(not(target_os = "macos"))]
+        let is_pressed = hover_highlight && state.read(cx).should_move;
+        #[cfg(target_os = "macos")]
+        let is_pressed = false;
+        let show_top_resize_cursor = is_linux
+            && matches!(
+                window.window_decorations(),
+                gpui::Decorations::Client { tiling } if !tiling.top
+            );
 
         // Main title bar container - all event handlers go here (like Zed's approach)
         h_flex()
             .id("title-bar")
-            .flex_shrink_0()
             // Mark as drag zone for the platform
             .window_control_area(WindowControlArea::Drag)
             .w_full()
-            .h(TITLE_BAR_HEIGHT)
             // Left padding: skip if left window controls will provide spacing
             .when(!has_left_controls, |this| this.pl(TITLE_BAR_LEFT_PADDING))
-            .border_b_1()
-            .border_color(cx.theme().title_bar_border)
-            .bg(cx.theme().title_bar)
+            .when(!self.transparent, |this| {
+                this.border_b(cx.theme().contrast().border_width())
+                    .border_color(cx.theme().title_bar_border)
+                    .bg(title_bar_bg)
+            })
+            .when(!is_active, |this| {
+                this.text_color(cx.theme().title_bar_inactive_foreground)
+            })
+            // The user style comes last, only the protected properties are applied over it.
             .refine_style(&self.style)
-            // Mouse event handlers for drag
-            .on_mouse_down_out(window.listener_for(&state, |state, _, _, _| {
-                state.should_move = false;
-                state.drag_start_pos = None;
-            }))
-            .on_mouse_down(
-                MouseButton::Left,
-                window.listener_for(&state, |state, event: &gpui::MouseDownEvent, window, cx| {
-                    // On Windows, a focusable parent element's auto-focus handler
-                    // calls prevent_default() on every mouse-down, which blocks
-                    // DefWindowProc from handling NC events (drag, resize, etc.).
-                    // We must stop propagation for ALL title bar clicks and handle
-                    // everything ourselves via the Win32 API.
-                    #[cfg(target_os = "windows")]
-                    {
-                        window.prevent_default();
-                        cx.stop_propagation();
-                    }
-
-                    // On Windows, handle the top resize zone (~8px) by posting
-                    // WM_NCLBUTTONDOWN + HTTOP directly, since DefWindowProc can't.
-                    #[cfg(target_os = "windows")]
-                    if event.position.y < px(8.0) {
-                        start_top_resize_win32(window);
-                        return;
-                    }
-
-                    // On Windows, detect double-clicks ourselves because GPUI's
-                    // click_count() doesn't work reliably with WindowControlArea::Drag
-                    // (WM_NCHITTEST returns HTCAPTION, bypassing normal click tracking).
-                    #[cfg(target_os = "windows")]
-                    {
-                        let now = std::time::Instant::now();
-                        let is_double_click = match (state.last_mousedown_time, state.last_mousedown_pos) {
-                            (Some(last_time), Some(last_pos)) => {
-                                let elapsed = now.duration_since(last_time);
-                                let delta = event.position - last_pos;
-                                let threshold = px(4.0);
-                                elapsed.as_millis() < 500
-                                    && delta.x > -threshold && delta.x < threshold
-                                    && delta.y > -threshold && delta.y < threshold
-                            }
-                            _ => false,
-                        };
-
-                        if is_double_click {
-                            state.should_move = false;
-                            state.drag_start_pos = None;
-                            state.last_mousedown_time = None;
-                            state.last_mousedown_pos = None;
-                            toggle_maximize_win32(window);
-                            return;
-                        }
-
-                        state.last_mousedown_time = Some(now);
-                        state.last_mousedown_pos = Some(event.position);
-                    }
-
-                    let _ = (window, &cx); // suppress unused warnings on non-Windows
-                    state.should_move = true;
-                    state.drag_start_pos = Some(event.position);
-                }),
-            )
-            .on_mouse_up(
-                MouseButton::Left,
-                window.listener_for(&state, |state, _, _, _| {
-                    state.should_move = false;
-                    state.drag_start_pos = None;
-                }),
-            )
-            .on_mouse_move(window.listener_for(&state, |state, #[cfg_attr(not(target_os = "windows"), allow(unused_variables))] event: &gpui::MouseMoveEvent, window, _| {
-                if state.should_move {
-                    #[cfg(not(target_os = "windows"))]
-                    {
-                        state.should_move = false;
-                        window.start_window_move();
-                    }
-                    #[cfg(target_os = "windows")]
-                    {
-                        // Only start drag after exceeding a movement threshold (4px),
-                        // so that double-clicks aren't swallowed by the drag modal loop.
-                        if let Some(start) = state.drag_start_pos {
-                            let delta = event.position - start;
-                            let threshold = px(4.0);
-                            if delta.x > threshold
-                                || delta.x < -threshold
-                                || delta.y > threshold
-                                || delta.y < -threshold
-                            {
-                                state.should_move = false;
-                                state.drag_start_pos = None;
-                                start_window_move_win32(window);
-                            }
-                        }
-                    }
-                }
-            }))
-            // Double-click to maximize/restore
-            // Linux: use GPUI's click_count(). Windows: handled in on_mouse_down above.
-            .when(cfg!(target_os = "linux"), |this| {
-                this.on_click(|event, window, _| {
-                    if event.click_count() == 2 {
-                        window.zoom_window();
-                    }
-                })
+            .refine_style(&protected_style(density))
+            // Drag, resize and double-click handlers, macOS handles them natively
+            .map(|this| {
+                #[cfg(not(target_os = "macos"))]
+                let this =
+                    drag_region_listeners(this, &state, top_resize_zone, self.on_drag_out, window);
+                #[cfg(target_os = "macos")]
+                let _ = self.on_drag_out;
+                this
             })
             .when(is_macos, |this| {
                 this.on_click(|event, window, _| {
@@ -513,19 +672,42 @@ impl RenderOnce for TitleBar {
             })
             // content_stretch ensures empty space in children is still clickable
             .content_stretch()
+            // Linux: hover highlight for the drag region. A dedicated bottom layer tracks
+            // its own hover, so moving across children doesn't toggle the highlight.
+            .when(hover_highlight, |this| {
+                let hover_bg = cx.theme().secondary_hover;
+                let pressed_bg = cx.theme().secondary_active;
+                this.child(
+                    div()
+                        .id("title-bar-drag-highlight")
+                        .absolute()
+                        .top_0()
+                        .bottom_0()
+                        .left_0()
+                        .right_0()
+                        .when(is_pressed, |this| this.bg(pressed_bg))
+                        .when(!is_pressed, |this| this.hover(|style| style.bg(hover_bg))),
+                )
+            })
             // Title overlay — absolutely centered across the full titlebar width.
             // Rendered first (z-bottom); interactive elements render on top.
-            .when_some(self.title_overlay, |el, title| {
+            .when_some(title_overlay, |el, title| {
                 el.child(
-                    div()
+                    h_flex_centered()
                         .absolute()
                         .top_0()
                         .bottom_0()
                         .left_0()
                         .right_0()
-                        .flex()
-                        .items_center()
-                        .justify_center()
+                        .overflow_hidden()
+                        .on_prepaint(move |bounds, _, cx| {
+                            title_width.update(cx, |width, cx| {
+                                if *width != bounds.size.width {
+                                    *width = bounds.size.width;
+                                    cx.notify();
+                                }
+                            });
+                        })
                         .child(title),
                 )
             })
@@ -533,47 +715,279 @@ impl RenderOnce for TitleBar {
             .when_some(
                 button_layout.filter(|l| l.left.iter().any(|b| b.is_some())),
                 |el, layout| {
-                    el.child(WindowControls {
-                        id: "window-controls-left",
-                        buttons: layout.left,
-                        on_close_window: self.on_close_window.clone(),
-                    })
+                    el.child(
+                        WindowControls::new("window-controls-left")
+                            .layout(&layout, Side::Left)
+                            .close_behavior(self.close_behavior.clone())
+                            .transparent(self.transparent)
+                            .tooltip(self.control_tooltip)
+                            .focusable(self.control_focusable)
+                            .when(density.is_compact(), |this| this.small()),
+                    )
                 },
             )
+            // Menu bar, the roots are excluded from the drag region
+            .when_some(menu_bar, |el, menu_bar| {
+                el.child(h_flex().h_full().flex_shrink_0().child(menu_bar))
+            })
             // Children container — in the flex flow, respects control spacing
             .child(
-                div()
-                    .flex()
-                    .flex_row()
-                    .items_center()
-                    .justify_between()
+                h_flex_between()
                     .overflow_x_hidden()
                     .w_full()
                     .h_full()
-                    .when(window.is_fullscreen(), |this| this.pl_3())
+                    .when(window.is_fullscreen(), |this| {
+                        this.pl(cx.theme().gap(GapSize::Medium))
+                    })
                     .children(self.children),
             )
             // Right window controls (standard: minimize, maximize, close)
             .when_some(
                 button_layout.filter(|l| l.right.iter().any(|b| b.is_some())),
                 |el, layout| {
-                    el.child(WindowControls {
-                        id: "window-controls-right",
-                        buttons: layout.right,
-                        on_close_window: self.on_close_window.clone(),
-                    })
+                    el.child(
+                        WindowControls::new("window-controls-right")
+                            .layout(&layout, Side::Right)
+                            .close_behavior(self.close_behavior.clone())
+                            .transparent(self.transparent)
+                            .tooltip(self.control_tooltip)
+                            .focusable(self.control_focusable)
+                            .when(density.is_compact(), |this| this.small()),
+                    )
                 },
             )
+            .when_some(accent_strip, |this, accent| {
+                this.child(
+                    div()
+                        .absolute()
+                        .top_0()
+                        .left_0()
+                        .right_0()
+                        .h(ACCENT_STRIP_HEIGHT)
+                        .bg(accent),
+                )
+            })
+            // Linux: resize cursor for the top resize strip, the resize itself is
+            // started in `on_mouse_down` above.
+            .when(show_top_resize_cursor, |this| {
+                this.child(
+                    div()
+                        .absolute()
+                        .top_0()
+                        .left_0()
+                        .right_0()
+                        .h(top_resize_zone)
+                        .cursor(CursorStyle::ResizeUpDown),
+                )
+            })
+    }
+}
+
+/// Add the drag, resize and double-click handlers to the title bar.
+///
+/// Not used on macOS, where the native title bar handles them.
+#[cfg(not(target_os = "macos"))]
+fn drag_region_listeners(
+    this: gpui::Stateful<gpui::Div>,
+    state: &gpui::Entity<TitleBarState>,
+    top_resize_zone: Pixels,
+    on_drag_out: Option<(Pixels, Rc<dyn Fn(&Point<Pixels>, &mut Window, &mut App)>)>,
+    window: &Window,
+) -> gpui::Stateful<gpui::Div> {
+    let drag_out_threshold = on_drag_out.as_ref().map(|(threshold, _)| *threshold);
+    let on_drag_out = on_drag_out.map(|(_, f)| f);
+
+    this
+        .on_mouse_down_out(window.listener_for(state, |state, _, _, _| state.release()))
+        .on_mouse_down(
+            MouseButton::Left,
+            window.listener_for(state, move |state, event: &gpui::MouseDownEvent, window, cx| {
+                // Children marked with `exclude_from_drag` are occluded, so this is only
+                // a fallback for the manual move tracking below.
+                if state.drag_excluded(event.position, &drag_exclusions(window, cx)) {
+                    return;
+                }
+
+                // On Windows, a focusable parent element's auto-focus handler
+                // calls prevent_default() on every mouse-down, which blocks
+                // DefWindowProc from handling NC events (drag, resize, etc.).
+                // We must stop propagation for ALL title bar clicks and handle
+                // everything ourselves via the Win32 API.
+                #[cfg(target_os = "windows")]
+                {
+                    window.prevent_default();
+                    cx.stop_propagation();
+                }
+
+                // On Windows, handle the top resize zone (~8px) by posting
+                // WM_NCLBUTTONDOWN + HTTOP directly, since DefWindowProc can't.
+                // Double-click on it maximizes vertically, like native windows.
+                #[cfg(target_os = "windows")]
+                if event.position.y < top_resize_zone {
+                    if state.record_mousedown(event.position) {
+                        toggle_vertical_maximize_win32(
+                            window,
+                            &mut state.vertical_restore_rect,
+                        );
+                    } else {
+                        start_top_resize_win32(window);
+                    }
+                    return;
+                }
+
+                // On Linux with client decorations, the top strip of the visible
+                // frame starts a resize rather than a move, unless the height is fixed.
+                #[cfg(target_os = "linux")]
+                if is_in_top_resize_zone(event.position, top_resize_zone, window, cx)
+                    && crate::WindowSizeConstraints::of(window, cx).resize_edges().top
+                {
+                    state.should_move = false;
+                    state.drag_start_pos = None;
+                    window.start_window_resize(gpui::ResizeEdge::Top);
+                    return;
+                }
+
+                // On Windows, detect double-clicks ourselves because GPUI's
+                // click_count() doesn't work reliably with WindowControlArea::Drag
+                // (WM_NCHITTEST returns HTCAPTION, bypassing normal click tracking).
+                #[cfg(target_os = "windows")]
+                if state.record_mousedown(event.position) {
+                    state.vertical_restore_rect = None;
+                    toggle_maximize_win32(window);
+                    return;
+                }
+
+                let _ = (window, top_resize_zone); // suppress unused warnings on other platforms
+                if drag_out_threshold.is_some() && event.modifiers.secondary() {
+                    state.begin_drag_out(event.position);
+                    return;
+                }
+                state.begin_drag(event.position);
+                cx.notify();
+            }),
+        )
+        .on_mouse_up(
+            MouseButton::Left,
+            window.listener_for(state, |state, _, _, cx| {
+                state.release();
+                cx.notify();
+            }),
+        )
+        .on_mouse_move(window.listener_for(state, move |state, event: &gpui::MouseMoveEvent, window, cx| {
+            if let Some((threshold, on_drag_out)) =
+                drag_out_threshold.zip(on_drag_out.as_ref())
+            {
+                if state.should_drag_out(event.position, threshold) {
+                    let screen_position = window.bounds().origin + event.position;
+                    on_drag_out(&screen_position, window, cx);
+                    return;
+                }
+            }
+
+            if state.should_start_move(event.position) {
+                cx.notify();
+                crate::window_border::begin_snap_move(event.position, window, cx);
+                #[cfg(not(target_os = "windows"))]
+                window.start_window_move();
+                #[cfg(target_os = "windows")]
+                start_window_move_win32(window);
+            }
+        }))
+        // Double-click to maximize/restore
+        // Linux: use GPUI's click_count(). Windows: handled in on_mouse_down above.
+        .when(cfg!(target_os = "linux"), |this| {
+            this.on_click(|event, window, cx| {
+                if event.click_count() == 2
+                    && (window.is_maximized()
+                        || crate::WindowSizeConstraints::of(window, cx).can_maximize())
+                {
+                    window.zoom_window();
+                }
+            })
+        })
+}
+
+/// Returns the width of the text laid out in a single line with the window text style.
+fn measure_text_width(text: &SharedString, window: &Window) -> Pixels {
+    let text_style = window.text_style();
+    let font_size = text_style.font_size.to_pixels(window.rem_size());
+    let run = text_style.to_run(text.len());
+    window
+        .text_system()
+        .shape_line(text.clone(), font_size, &[run], None)
+        .width
+}
+
+/// Log a TitleBar warning, also to the MCP log when enabled.
+#[cfg(debug_assertions)]
+fn warn(message: &str) {
+    log::warn!("TitleBar: {}", message);
+    #[cfg(feature = "mcp")]
+    crate::mcp::mcp_log(format!("[warn] TitleBar: {}", message));
+}
+
+/// The windows that have rendered a TitleBar in the current frame.
+#[cfg(debug_assertions)]
+#[derive(Default)]
+struct RenderedTitleBars(std::collections::HashSet<gpui::WindowId>);
+
+#[cfg(debug_assertions)]
+impl gpui::Global for RenderedTitleBars {}
+
+/// Warn about common setup mistakes: a window without the TitleBar options and
+/// more than one TitleBar rendered in the same window.
+#[cfg(debug_assertions)]
+fn check_window_setup(window: &mut Window, cx: &mut App) {
+    let window_id = window.window_handle().window_id();
+    if !cx.default_global::<RenderedTitleBars>().0.insert(window_id) {
+        static WARN: std::sync::Once = std::sync::Once::new();
+        WARN.call_once(|| warn("more than one TitleBar rendered in the same window."));
+        return;
+    }
+
+    window.on_next_frame(move |_, cx| {
+        cx.default_global::<RenderedTitleBars>()
+            .0
+            .remove(&window_id);
+    });
+
+    // The window decorations are the only part of the titlebar configuration that GPUI
+    // reports back, server side decorations mean the native title bar is drawn as well.
+    if cfg!(target_os = "linux") && matches!(window.window_decorations(), gpui::Decorations::Server)
+    {
+        static WARN: std::sync::Once = std::sync::Once::new();
+        WARN.call_once(|| {
+            warn(
+                "the window uses server side decorations, create it with \
+                `TitleBar::window_options()` to avoid a duplicated title bar.",
+            )
+        });
     }
 }
 
+/// Returns true if the position is within the top resize strip of a client decorated window
+/// that is not tiled on the top edge, the strip covers the border and the top of the content.
+#[cfg(target_os = "linux")]
+fn is_in_top_resize_zone(position: Point<Pixels>, zone: Pixels, window: &Window, cx: &App) -> bool {
+    let gpui::Decorations::Client { tiling } = crate::window_border::window_decorations(window)
+    else {
+        return false;
+    };
+    if tiling.top {
+        return false;
+    }
+    let frame_top = crate::window_paddings(window).top;
+    let content_top = crate::window_content_bounds(window, cx).top();
+    position.y >= frame_top && position.y < content_top + zone
+}
+
 /// Toggle between maximized and restored window state on Windows.
 ///
 /// GPUI's `zoom_window()` only maximizes on Windows (calls SW_MAXIMIZE),
 /// it does not toggle like the macOS equivalent. This helper checks
 /// `is_maximized()` and calls SW_RESTORE or SW_MAXIMIZE accordingly.
 #[cfg(target_os = "windows")]
-fn toggle_maximize_win32(window: &mut gpui::Window) {
+pub(crate) fn toggle_maximize_win32(window: &mut gpui::Window) {
     use raw_window_handle::HasWindowHandle;
     if let Ok(handle) = window.window_handle() {
         if let raw_window_handle::RawWindowHandle::Win32(win32) = handle.as_ref() {
@@ -592,6 +1006,59 @@ fn toggle_maximize_win32(window: &mut gpui::Window) {
     }
 }
 
+/// Toggle a vertical maximize on Windows: full work-area height, keeping x and width.
+///
+/// The previous rect is stored in `restore_rect`, and restored on the next call.
+#[cfg(target_os = "windows")]
+fn toggle_vertical_maximize_win32(
+    window: &mut gpui::Window,
+    restore_rect: &mut Option<windows::Win32::Foundation::RECT>,
+) {
+    use raw_window_handle::HasWindowHandle;
+    if let Ok(handle) = window.window_handle() {
+        if let raw_window_handle::RawWindowHandle::Win32(win32) = handle.as_ref() {
+            unsafe {
+                use windows::Win32::Foundation::*;
+                use windows::Win32::Graphics::Gdi::*;
+                use windows::Win32::UI::WindowsAndMessaging::*;
+                let hwnd = HWND(win32.hwnd.get() as *mut _);
+                let rect = match restore_rect.take() {
+                    Some(rect) => rect,
+                    None => {
+                        let mut rect = RECT::default();
+                        if GetWindowRect(hwnd, &mut rect).is_err() {
+                            return;
+                        }
+                        let monitor = MonitorFromWindow(hwnd, MONITOR_DEFAULTTONEAREST);
+                        let mut info = MONITORINFO {
+                            cbSize: std::mem::size_of::<MONITORINFO>() as u32,
+                            ..Default::default()
+                        };
+                        if !GetMonitorInfoW(monitor, &mut info).as_bool() {
+                            return;
+                        }
+                        *restore_rect = Some(rect);
+                        RECT {
+                            top: info.rcWork.top,
+                            bottom: info.rcWork.bottom,
+                            ..rect
+                        }
+                    }
+                };
+                let _ = SetWindowPos(
+                    hwnd,
+                    None,
+                    rect.left,
+                    rect.top,
+                    rect.right - rect.left,
+                    rect.bottom - rect.top,
+                    SWP_NOZORDER | SWP_NOACTIVATE,
+                );
+            }
+        }
+    }
+}
+
 /// Send WM_NCLBUTTONDOWN + HTTOP to initiate a top-edge resize on Windows.
 ///
 /// When GPUI dispatches NC mouse events through the element tree, a focusable
@@ -635,3 +1102,80 @@ fn start_window_move_win32(window: &mut gpui::Window) {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gpui::{point, size};
+
+    #[test]
+    fn test_title_bar_style() {
+        let title_bar = TitleBar::new().bg(gpui::red()).h(px(80.));
+        let mut style = title_bar.style.clone();
+        style.refine(&protected_style(ComponentDensity::Comfortable));
+
+        // The background of the user takes effect, the height is protected.
+        assert_eq!(style.background, Some(gpui::red().into()));
+        assert_eq!(style.size.height, Some(TITLE_BAR_HEIGHT.into()));
+        assert_eq!(style.flex_shrink, Some(0.));
+
+        style.refine(&protected_style(ComponentDensity::Compact));
+        assert_eq!(style.size.height, Some(TITLE_BAR_COMPACT_HEIGHT.into()));
+    }
+
+    #[cfg(not(target_os = "macos"))]
+    #[test]
+    fn test_drag_exclusion() {
+        let exclusions = [Bounds::new(point(px(100.), px(0.)), size(px(80.), px(34.)))];
+
+        // A mouse down on an excluded child never starts a window move.
+        let mut state = TitleBarState::default();
+        assert!(state.drag_excluded(point(px(120.), px(10.)), &exclusions));
+        assert!(!state.should_move);
+        assert!(!state.should_start_move(point(px(200.), px(30.))));
+
+        // The empty drag region still starts a window move.
+        let mut state = TitleBarState::default();
+        assert!(!state.drag_excluded(point(px(20.), px(10.)), &exclusions));
+        state.begin_drag(point(px(20.), px(10.)));
+        assert!(state.should_start_move(point(px(60.), px(10.))));
+        assert!(!state.should_move);
+    }
+
+    #[cfg(not(target_os = "macos"))]
+    #[test]
+    fn test_drag_out() {
+        let mut state = TitleBarState::default();
+        state.begin_drag_out(point(px(20.), px(10.)));
+        assert!(!state.should_move);
+        assert!(!state.should_start_move(point(px(60.), px(10.))));
+
+        // Below the threshold the gesture keeps tracking, the window doesn't move.
+        assert!(!state.should_drag_out(point(px(40.), px(30.)), px(40.)));
+        assert!(state.should_drag_out(point(px(20.), px(60.)), px(40.)));
+
+        // Fired once per gesture.
+        assert!(!state.should_drag_out(point(px(20.), px(90.)), px(40.)));
+    }
+
+    #[cfg(not(target_os = "macos"))]
+    #[test]
+    fn test_drag_out_released_below_threshold() {
+        let mut state = TitleBarState::default();
+        state.begin_drag_out(point(px(20.), px(10.)));
+
+        // The moves below the threshold keep tracking the gesture, the window doesn't move.
+        assert!(!state.should_drag_out(point(px(30.), px(15.)), px(40.)));
+        assert!(!state.should_start_move(point(px(30.), px(15.))));
+        assert!(!state.should_drag_out(point(px(50.), px(40.)), px(40.)));
+        assert!(state.drag_out_start.is_some());
+
+        // Released below the threshold, the gesture ends without firing.
+        state.release();
+        assert!(!state.should_drag_out(point(px(20.), px(90.)), px(40.)));
+
+        // The next drag without the modifier moves the window natively.
+        state.begin_drag(point(px(20.), px(10.)));
+        assert!(state.should_start_move(point(px(60.), px(10.))));
+    }
+}
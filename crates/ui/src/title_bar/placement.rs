@@ -0,0 +1,438 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    path::PathBuf,
+    rc::Rc,
+    time::Duration,
+};
+
+use gpui::{
+    App, AppContext as _, Bounds, Context, Entity, Global, Pixels, SharedString, Subscription,
+    Task, Window, WindowBounds, WindowId, WindowOptions, point, px, size,
+};
+use serde::{Deserialize, Serialize};
+
+/// The delay to save the placement after the last bounds change.
+const SAVE_DEBOUNCE: Duration = Duration::from_millis(500);
+/// The file name of the [`JsonPlacementStorage`] in the config dir of the app.
+const PLACEMENTS_FILE_NAME: &str = "window-placements.json";
+
+/// The saved bounds of a window, see [`WindowPlacement`].
+///
+/// The bounds are the normal (un-maximized) bounds, in screen coordinates.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct SavedPlacement {
+    pub x: f32,
+    pub y: f32,
+    pub width: f32,
+    pub height: f32,
+    #[serde(default)]
+    pub maximized: bool,
+}
+
+impl SavedPlacement {
+    /// Returns the normal bounds of the placement.
+    pub fn bounds(&self) -> Bounds<Pixels> {
+        Bounds::new(
+            point(px(self.x), px(self.y)),
+            size(px(self.width), px(self.height)),
+        )
+    }
+
+    /// Create the placement of the window bounds.
+    ///
+    /// A fullscreen window is saved with its normal bounds, not as maximized.
+    pub fn from_window_bounds(window_bounds: WindowBounds) -> Self {
+        let bounds = window_bounds.get_bounds();
+        Self {
+            x: bounds.origin.x.as_f32(),
+            y: bounds.origin.y.as_f32(),
+            width: bounds.size.width.as_f32(),
+            height: bounds.size.height.as_f32(),
+            maximized: matches!(window_bounds, WindowBounds::Maximized(_)),
+        }
+    }
+}
+
+/// The storage of the [`SavedPlacement`]s by key, see [`WindowPlacement::set_storage`].
+pub trait PlacementStorage: 'static {
+    /// Returns the saved placement of the `key`.
+    fn load(&self, key: &str) -> Option<SavedPlacement>;
+    /// Save the placement of the `key`.
+    fn save(&self, key: &str, placement: SavedPlacement);
+}
+
+/// The default [`PlacementStorage`], a JSON file of the placements by key.
+pub struct JsonPlacementStorage {
+    path: Option<PathBuf>,
+}
+
+impl JsonPlacementStorage {
+    /// Create a storage saving to the JSON file at `path`.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: Some(path.into()),
+        }
+    }
+
+    /// Returns the default path of the file, `window-placements.json` in the platform config
+    /// dir of the app, named after the executable.
+    pub fn default_path() -> Option<PathBuf> {
+        let app_name = std::env::current_exe()
+            .ok()?
+            .file_stem()?
+            .to_string_lossy()
+            .into_owned();
+        Some(config_dir()?.join(app_name).join(PLACEMENTS_FILE_NAME))
+    }
+
+    fn read_all(&self) -> BTreeMap<String, SavedPlacement> {
+        self.path
+            .as_ref()
+            .and_then(|path| std::fs::read_to_string(path).ok())
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+}
+
+impl Default for JsonPlacementStorage {
+    fn default() -> Self {
+        Self {
+            path: Self::default_path(),
+        }
+    }
+}
+
+impl PlacementStorage for JsonPlacementStorage {
+    fn load(&self, key: &str) -> Option<SavedPlacement> {
+        self.read_all().remove(key)
+    }
+
+    fn save(&self, key: &str, placement: SavedPlacement) {
+        let Some(path) = self.path.as_ref() else {
+            return;
+        };
+
+        let mut placements = self.read_all();
+        placements.insert(key.to_string(), placement);
+        let result = path
+            .parent()
+            .map_or(Ok(()), std::fs::create_dir_all)
+            .and_then(|_| {
+                let content = serde_json::to_string_pretty(&placements)?;
+                std::fs::write(path, content)
+            });
+        if let Err(err) = result {
+            tracing::warn!("Failed to save the window placement to {:?}: {}", path, err);
+        }
+    }
+}
+
+/// Returns the platform config dir.
+fn config_dir() -> Option<PathBuf> {
+    let env_dir = |key: &str| {
+        std::env::var_os(key)
+            .filter(|dir| !dir.is_empty())
+            .map(PathBuf::from)
+    };
+
+    if cfg!(target_os = "windows") {
+        env_dir("APPDATA")
+    } else if cfg!(target_os = "macos") {
+        env_dir("HOME").map(|home| home.join("Library").join("Application Support"))
+    } else {
+        env_dir("XDG_CONFIG_HOME").or_else(|| env_dir("HOME").map(|home| home.join(".config")))
+    }
+}
+
+struct GlobalPlacementStorage(Rc<dyn PlacementStorage>);
+
+impl Global for GlobalPlacementStorage {}
+
+#[derive(Default)]
+struct GlobalPlacementTrackers(HashMap<WindowId, Entity<PlacementTracker>>);
+
+impl Global for GlobalPlacementTrackers {}
+
+/// Save and restore the bounds and the maximized state of the windows across launches.
+///
+/// ```ignore
+/// use gpui_component::WindowPlacement;
+///
+/// let mut options = WindowOptions::default();
+/// WindowPlacement::restore(&mut options, "main", cx);
+/// cx.open_window(options, |window, cx| {
+///     WindowPlacement::track(window, "main", cx);
+///     // ...
+/// })?;
+/// ```
+///
+/// The placements are saved by the [`JsonPlacementStorage`] by default, see
+/// [`WindowPlacement::set_storage`].
+pub struct WindowPlacement;
+
+impl WindowPlacement {
+    /// Set the storage of the placements, replacing the [`JsonPlacementStorage`].
+    pub fn set_storage(storage: impl PlacementStorage, cx: &mut App) {
+        cx.set_global(GlobalPlacementStorage(Rc::new(storage)));
+    }
+
+    fn storage(cx: &mut App) -> Rc<dyn PlacementStorage> {
+        if !cx.has_global::<GlobalPlacementStorage>() {
+            cx.set_global(GlobalPlacementStorage(Rc::new(
+                JsonPlacementStorage::default(),
+            )));
+        }
+        cx.global::<GlobalPlacementStorage>().0.clone()
+    }
+
+    /// Apply the saved placement of the `key` to the `options`, returns true if restored.
+    ///
+    /// The saved bounds are clamped to the current displays, so the window is never restored
+    /// off-screen, e.g. after a monitor was disconnected. A maximized window is restored as
+    /// maximized over its saved normal bounds, so un-maximizing returns to them.
+    pub fn restore(options: &mut WindowOptions, key: &str, cx: &mut App) -> bool {
+        let Some(placement) = Self::storage(cx).load(key) else {
+            return false;
+        };
+        let displays = cx.displays();
+        let visible_bounds = displays
+            .iter()
+            .map(|display| display.visible_bounds())
+            .collect::<Vec<_>>();
+        let Some((ix, bounds)) = clamp_to_displays(placement.bounds(), &visible_bounds) else {
+            return false;
+        };
+
+        options.display_id = Some(displays[ix].id());
+        options.window_bounds = Some(if placement.maximized {
+            WindowBounds::Maximized(bounds)
+        } else {
+            WindowBounds::Windowed(bounds)
+        });
+        true
+    }
+
+    /// Save the placement of the `window` with the `key` on its bounds and maximize changes.
+    ///
+    /// The changes are debounced, tracking the window again with the same key does nothing.
+    pub fn track(window: &mut Window, key: impl Into<SharedString>, cx: &mut App) {
+        let key = key.into();
+        let window_id = window.window_handle().window_id();
+        if cx
+            .try_global::<GlobalPlacementTrackers>()
+            .and_then(|trackers| trackers.0.get(&window_id))
+            .is_some_and(|tracker| tracker.read(cx).key == key)
+        {
+            return;
+        }
+
+        let tracker = cx.new(|cx| PlacementTracker::new(key, window, cx));
+        let open_windows = cx
+            .windows()
+            .iter()
+            .map(|window| window.window_id())
+            .collect::<Vec<_>>();
+        let trackers = &mut cx.default_global::<GlobalPlacementTrackers>().0;
+        trackers.retain(|window_id, _| open_windows.contains(window_id));
+        trackers.insert(window_id, tracker);
+    }
+}
+
+/// Save the placement of a window, see [`WindowPlacement::track`].
+struct PlacementTracker {
+    key: SharedString,
+    saved: Option<SavedPlacement>,
+    _subscription: Subscription,
+    _save_task: Option<Task<()>>,
+}
+
+impl PlacementTracker {
+    fn new(key: SharedString, window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let mut this = Self {
+            key,
+            saved: None,
+            _subscription: cx
+                .observe_window_bounds(window, |this, window, cx| this.schedule_save(window, cx)),
+            _save_task: None,
+        };
+        this.schedule_save(window, cx);
+        this
+    }
+
+    /// Debounce the save, the task is replaced on every bounds change.
+    fn schedule_save(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let placement = SavedPlacement::from_window_bounds(window.window_bounds());
+        if self.saved == Some(placement) {
+            self._save_task = None;
+            return;
+        }
+
+        self._save_task = Some(cx.spawn(async move |this, cx| {
+            cx.background_executor().timer(SAVE_DEBOUNCE).await;
+            _ = this.update(cx, |this, cx| {
+                WindowPlacement::storage(cx).save(&this.key, placement);
+                this.saved = Some(placement);
+                this._save_task = None;
+            });
+        }));
+    }
+}
+
+/// Returns the index of the display and the bounds clamped into its visible bounds, None if
+/// there is no display.
+///
+/// The display is the one with the largest overlap with the `bounds`, or the first (primary)
+/// display if the bounds are fully off-screen.
+fn clamp_to_displays(
+    bounds: Bounds<Pixels>,
+    displays: &[Bounds<Pixels>],
+) -> Option<(usize, Bounds<Pixels>)> {
+    let overlap = |display: &Bounds<Pixels>| {
+        let intersection = bounds.intersect(display);
+        intersection.size.width.as_f32().max(0.) * intersection.size.height.as_f32().max(0.)
+    };
+    let (ix, display) = displays
+        .iter()
+        .enumerate()
+        .filter(|(_, display)| overlap(display) > 0.)
+        .max_by(|(_, a), (_, b)| overlap(a).total_cmp(&overlap(b)))
+        .or_else(|| displays.first().map(|display| (0, display)))?;
+
+    let size = size(
+        bounds.size.width.min(display.size.width),
+        bounds.size.height.min(display.size.height),
+    );
+    let origin = point(
+        bounds
+            .origin
+            .x
+            .min(display.right() - size.width)
+            .max(display.left()),
+        bounds
+            .origin
+            .y
+            .min(display.bottom() - size.height)
+            .max(display.top()),
+    );
+    Some((ix, Bounds::new(origin, size)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+
+    fn bounds(x: f32, y: f32, width: f32, height: f32) -> Bounds<Pixels> {
+        Bounds::new(point(px(x), px(y)), size(px(width), px(height)))
+    }
+
+    #[test]
+    fn test_clamp_to_displays() {
+        let displays = [
+            bounds(0., 0., 1920., 1080.),
+            bounds(1920., 0., 1280., 1024.),
+        ];
+
+        // A visible window is kept.
+        assert_eq!(
+            clamp_to_displays(bounds(100., 100., 800., 600.), &displays),
+            Some((0, bounds(100., 100., 800., 600.)))
+        );
+        // Mostly on the second display, moved inside it.
+        assert_eq!(
+            clamp_to_displays(bounds(1800., 100., 800., 600.), &displays),
+            Some((1, bounds(1920., 100., 800., 600.)))
+        );
+        // Fully off-screen, e.g. a disconnected display, moved to the primary display.
+        assert_eq!(
+            clamp_to_displays(bounds(4000., 2000., 800., 600.), &displays),
+            Some((0, bounds(1120., 480., 800., 600.)))
+        );
+        // Larger than the display, shrunk to it.
+        assert_eq!(
+            clamp_to_displays(bounds(-50., -50., 3000., 2000.), &displays[..1]),
+            Some((0, bounds(0., 0., 1920., 1080.)))
+        );
+        assert_eq!(clamp_to_displays(bounds(0., 0., 800., 600.), &[]), None);
+    }
+
+    #[test]
+    fn test_json_placement_storage() {
+        let path = std::env::temp_dir()
+            .join(format!("gpui-component-placement-{}", std::process::id()))
+            .join(PLACEMENTS_FILE_NAME);
+        let storage = JsonPlacementStorage::new(&path);
+        let placement = SavedPlacement {
+            x: 10.,
+            y: 20.,
+            width: 800.,
+            height: 600.,
+            maximized: true,
+        };
+
+        assert_eq!(storage.load("main"), None);
+        storage.save("main", placement);
+        storage.save(
+            "settings",
+            SavedPlacement {
+                maximized: false,
+                ..placement
+            },
+        );
+        assert_eq!(storage.load("main"), Some(placement));
+        assert_eq!(
+            JsonPlacementStorage::new(&path)
+                .load("settings")
+                .map(|p| p.maximized),
+            Some(false)
+        );
+
+        _ = std::fs::remove_dir_all(path.parent().unwrap());
+    }
+
+    struct MemoryStorage(RefCell<BTreeMap<String, SavedPlacement>>);
+
+    impl PlacementStorage for MemoryStorage {
+        fn load(&self, key: &str) -> Option<SavedPlacement> {
+            self.0.borrow().get(key).copied()
+        }
+
+        fn save(&self, key: &str, placement: SavedPlacement) {
+            self.0.borrow_mut().insert(key.to_string(), placement);
+        }
+    }
+
+    #[gpui::test]
+    fn test_restore_maximized(cx: &mut gpui::TestAppContext) {
+        cx.update(|cx| {
+            let placement = SavedPlacement {
+                x: 40.,
+                y: 40.,
+                width: 640.,
+                height: 480.,
+                maximized: true,
+            };
+            WindowPlacement::set_storage(
+                MemoryStorage(RefCell::new(BTreeMap::from([(
+                    "main".to_string(),
+                    placement,
+                )]))),
+                cx,
+            );
+
+            let mut options = WindowOptions::default();
+            assert!(!WindowPlacement::restore(&mut options, "unknown", cx));
+            assert!(WindowPlacement::restore(&mut options, "main", cx));
+
+            // Maximized over the saved normal bounds.
+            let Some(WindowBounds::Maximized(bounds)) = options.window_bounds else {
+                panic!("expected maximized bounds, got {:?}", options.window_bounds);
+            };
+            let display = cx.primary_display().unwrap().visible_bounds();
+            assert_eq!(
+                Some(bounds),
+                clamp_to_displays(placement.bounds(), &[display]).map(|(_, bounds)| bounds)
+            );
+        });
+    }
+}
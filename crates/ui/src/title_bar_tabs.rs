@@ -0,0 +1,355 @@
+use std::rc::Rc;
+
+use gpui::{
+    App, ClickEvent, ElementId, InteractiveElement, IntoElement, KeyBinding, MouseButton,
+    ParentElement, Pixels, RenderOnce, ScrollHandle, SharedString, StatefulInteractiveElement as _,
+    StyleRefinement, Styled, Window, div, prelude::FluentBuilder as _, px,
+};
+
+use crate::{
+    ActiveTheme, Icon, IconName, InteractiveElementExt as _, Sizable as _, StyledExt,
+    actions::{SelectLeft, SelectRight},
+    button::{Button, ButtonVariants as _},
+    h_flex,
+};
+
+const CONTEXT: &str = "TitleBarTabs";
+const MIN_TAB_WIDTH: Pixels = px(72.);
+const MAX_TAB_WIDTH: Pixels = px(220.);
+
+pub(crate) fn init(cx: &mut App) {
+    cx.bind_keys([
+        KeyBinding::new("left", SelectLeft, Some(CONTEXT)),
+        KeyBinding::new("right", SelectRight, Some(CONTEXT)),
+    ]);
+}
+
+/// A tab item of the [`TitleBarTabs`].
+#[derive(Clone)]
+pub struct TitleBarTab {
+    title: SharedString,
+    icon: Option<Icon>,
+    closable: bool,
+}
+
+impl TitleBarTab {
+    /// Create a new tab with the title.
+    pub fn new(title: impl Into<SharedString>) -> Self {
+        Self {
+            title: title.into(),
+            icon: None,
+            closable: true,
+        }
+    }
+
+    /// Set the icon before the title.
+    pub fn icon(mut self, icon: impl Into<Icon>) -> Self {
+        self.icon = Some(icon.into());
+        self
+    }
+
+    /// Set to show the close button, default is true.
+    pub fn closable(mut self, closable: bool) -> Self {
+        self.closable = closable;
+        self
+    }
+}
+
+impl<T: Into<SharedString>> From<T> for TitleBarTab {
+    fn from(title: T) -> Self {
+        Self::new(title)
+    }
+}
+
+/// A browser-style tab strip to be placed in the [`crate::TitleBar`].
+///
+/// The empty space after the last tab stays part of the window drag region, the tabs
+/// themselves never start a window move. Tabs compress down to the min width before the
+/// strip scrolls.
+///
+/// Left and right arrows move the selection when the strip is focused, switching tabs by
+/// `ctrl-tab` is left to the app.
+#[derive(IntoElement)]
+pub struct TitleBarTabs {
+    id: ElementId,
+    style: StyleRefinement,
+    tabs: Vec<TitleBarTab>,
+    selected_index: usize,
+    min_tab_width: Pixels,
+    max_tab_width: Pixels,
+    on_select: Option<Rc<dyn Fn(&usize, &mut Window, &mut App)>>,
+    on_close: Option<Rc<dyn Fn(&usize, &mut Window, &mut App)>>,
+    on_new_tab: Option<Rc<dyn Fn(&ClickEvent, &mut Window, &mut App)>>,
+}
+
+impl TitleBarTabs {
+    /// Create a new TitleBarTabs.
+    pub fn new(id: impl Into<ElementId>) -> Self {
+        Self {
+            id: id.into(),
+            style: StyleRefinement::default(),
+            tabs: Vec::new(),
+            selected_index: 0,
+            min_tab_width: MIN_TAB_WIDTH,
+            max_tab_width: MAX_TAB_WIDTH,
+            on_select: None,
+            on_close: None,
+            on_new_tab: None,
+        }
+    }
+
+    /// Add a tab.
+    pub fn tab(mut self, tab: impl Into<TitleBarTab>) -> Self {
+        self.tabs.push(tab.into());
+        self
+    }
+
+    /// Add tabs.
+    pub fn tabs(mut self, tabs: impl IntoIterator<Item = impl Into<TitleBarTab>>) -> Self {
+        self.tabs.extend(tabs.into_iter().map(Into::into));
+        self
+    }
+
+    /// Set the index of the active tab.
+    pub fn selected_index(mut self, ix: usize) -> Self {
+        self.selected_index = ix;
+        self
+    }
+
+    /// Set the min width of the tabs before the strip scrolls, default is 72px.
+    pub fn min_tab_width(mut self, width: impl Into<Pixels>) -> Self {
+        self.min_tab_width = width.into();
+        self
+    }
+
+    /// Set the max width of the tabs, default is 220px.
+    pub fn max_tab_width(mut self, width: impl Into<Pixels>) -> Self {
+        self.max_tab_width = width.into();
+        self
+    }
+
+    /// Set the callback when a tab is selected, the argument is the tab index.
+    pub fn on_select(mut self, f: impl Fn(&usize, &mut Window, &mut App) + 'static) -> Self {
+        self.on_select = Some(Rc::new(f));
+        self
+    }
+
+    /// Set the callback when the close button (or middle click) of a tab is clicked,
+    /// the argument is the tab index.
+    pub fn on_close(mut self, f: impl Fn(&usize, &mut Window, &mut App) + 'static) -> Self {
+        self.on_close = Some(Rc::new(f));
+        self
+    }
+
+    /// Set the callback of the new tab button, the button is only shown when this is set.
+    pub fn on_new_tab(mut self, f: impl Fn(&ClickEvent, &mut Window, &mut App) + 'static) -> Self {
+        self.on_new_tab = Some(Rc::new(f));
+        self
+    }
+
+    /// Returns the tab index to select when moving by `delta`, clamped to the tabs.
+    fn step_index(&self, delta: isize) -> Option<usize> {
+        if self.tabs.is_empty() {
+            return None;
+        }
+        let ix = (self.selected_index as isize + delta).clamp(0, self.tabs.len() as isize - 1);
+        Some(ix as usize).filter(|ix| *ix != self.selected_index)
+    }
+}
+
+impl Styled for TitleBarTabs {
+    fn style(&mut self) -> &mut StyleRefinement {
+        &mut self.style
+    }
+}
+
+impl RenderOnce for TitleBarTabs {
+    fn render(self, window: &mut Window, cx: &mut App) -> impl IntoElement {
+        let focus_handle = window
+            .use_keyed_state(self.id.clone(), cx, |_, cx| cx.focus_handle())
+            .read(cx)
+            .clone();
+        let scroll_handle = window
+            .use_keyed_state(
+                ElementId::Name(format!("{}-scroll", self.id).into()),
+                cx,
+                |_, _| ScrollHandle::new(),
+            )
+            .read(cx)
+            .clone();
+        // Only scroll to the selected tab when the selection changed, to keep the scrolling of
+        // the user.
+        let scrolled_index = window.use_keyed_state(
+            ElementId::Name(format!("{}-scrolled-index", self.id).into()),
+            cx,
+            |_, _| None,
+        );
+        if *scrolled_index.read(cx) != Some(self.selected_index) {
+            scroll_handle.scroll_to_item(self.selected_index);
+            scrolled_index.update(cx, |ix, _| *ix = Some(self.selected_index));
+        }
+
+        #[cfg(feature = "mcp")]
+        crate::mcp::mcp_set_element_properties(
+            window,
+            &self.id,
+            serde_json::json!({ "role": "tablist", "selected_index": self.selected_index }),
+        );
+
+        let prev_index = self.step_index(-1);
+        let next_index = self.step_index(1);
+        let selected_index = self.selected_index;
+        let (min_tab_width, max_tab_width) = (self.min_tab_width, self.max_tab_width);
+        let on_select = self.on_select.clone();
+        let on_close = self.on_close.clone();
+
+        let tabs = self.tabs.into_iter().enumerate().map(|(ix, tab)| {
+            let selected = ix == selected_index;
+            let tab_id = ElementId::NamedInteger(format!("{}-tab", self.id).into(), ix as u64);
+
+            #[cfg(feature = "mcp")]
+            crate::mcp::mcp_set_element_properties(
+                window,
+                &tab_id,
+                serde_json::json!({ "role": "tab", "title": tab.title, "selected": selected }),
+            );
+
+            h_flex()
+                .id(tab_id)
+                .exclude_from_drag()
+                .h_full()
+                .flex_shrink()
+                .flex_basis(max_tab_width)
+                .min_w(min_tab_width)
+                .max_w(max_tab_width)
+                .gap_1()
+                .px_2()
+                .rounded_t(cx.theme().radius)
+                .text_sm()
+                .map(|this| {
+                    if selected {
+                        this.bg(cx.theme().tab_active)
+                            .text_color(cx.theme().tab_active_foreground)
+                    } else {
+                        this.text_color(cx.theme().tab_foreground)
+                            .hover(|this| this.bg(cx.theme().secondary_hover))
+                    }
+                })
+                .when_some(tab.icon, |this, icon| this.child(icon.small()))
+                .child(div().flex_1().min_w_0().truncate().child(tab.title))
+                .when(tab.closable, |this| {
+                    this.child(
+                        Button::new(("close", ix))
+                            .icon(IconName::Close)
+                            .ghost()
+                            .xsmall()
+                            .when_some(on_close.clone(), |this, on_close| {
+                                this.on_click(move |_, window, cx| {
+                                    cx.stop_propagation();
+                                    on_close(&ix, window, cx);
+                                })
+                            }),
+                    )
+                })
+                .when_some(on_select.clone(), |this, on_select| {
+                    this.on_click(move |_, window, cx| on_select(&ix, window, cx))
+                })
+                .when_some(
+                    on_close.clone().filter(|_| tab.closable),
+                    |this, on_close| {
+                        this.on_mouse_down(MouseButton::Middle, move |_, window, cx| {
+                            cx.stop_propagation();
+                            on_close(&ix, window, cx);
+                        })
+                    },
+                )
+        });
+
+        h_flex()
+            .id(self.id.clone())
+            .key_context(CONTEXT)
+            .track_focus(&focus_handle)
+            .flex_1()
+            .min_w_0()
+            .h_full()
+            .items_end()
+            .refine_style(&self.style)
+            .when_some(
+                prev_index.zip(self.on_select.clone()),
+                |this, (ix, on_select)| {
+                    this.on_action(move |_: &SelectLeft, window, cx| on_select(&ix, window, cx))
+                },
+            )
+            .when_some(
+                next_index.zip(self.on_select.clone()),
+                |this, (ix, on_select)| {
+                    this.on_action(move |_: &SelectRight, window, cx| on_select(&ix, window, cx))
+                },
+            )
+            .child(
+                h_flex()
+                    .id("tabs")
+                    .min_w_0()
+                    .h_full()
+                    .items_end()
+                    .overflow_x_scroll()
+                    .track_scroll(&scroll_handle)
+                    .children(tabs),
+            )
+            .when_some(self.on_new_tab, |this, on_new_tab| {
+                this.child(
+                    div().id("new-tab").exclude_from_drag().px_1().child(
+                        Button::new("new-tab-button")
+                            .icon(IconName::Plus)
+                            .ghost()
+                            .xsmall()
+                            .on_click(move |event, window, cx| on_new_tab(event, window, cx)),
+                    ),
+                )
+            })
+            // The empty space after the tabs keeps the window drag region.
+            .child(div().flex_1().h_full())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[gpui::test]
+    fn test_title_bar_tabs_builder(_cx: &mut gpui::TestAppContext) {
+        let tabs = TitleBarTabs::new("tabs")
+            .tab("Home")
+            .tabs(["Docs", "Settings"])
+            .tab(TitleBarTab::new("Pinned").closable(false))
+            .selected_index(1)
+            .min_tab_width(px(60.))
+            .max_tab_width(px(180.))
+            .on_select(|_, _, _| {})
+            .on_close(|_, _, _| {})
+            .on_new_tab(|_, _, _| {});
+
+        assert_eq!(tabs.tabs.len(), 4);
+        assert_eq!(tabs.tabs[1].title, SharedString::from("Docs"));
+        assert!(!tabs.tabs[3].closable);
+        assert_eq!(tabs.selected_index, 1);
+        assert_eq!(tabs.min_tab_width, px(60.));
+        assert_eq!(tabs.max_tab_width, px(180.));
+        assert!(tabs.on_select.is_some());
+        assert!(tabs.on_close.is_some());
+        assert!(tabs.on_new_tab.is_some());
+    }
+
+    #[gpui::test]
+    fn test_title_bar_tabs_step_index(_cx: &mut gpui::TestAppContext) {
+        let tabs = TitleBarTabs::new("tabs").tabs(["A", "B", "C"]);
+        assert_eq!(tabs.step_index(-1), None);
+        assert_eq!(tabs.step_index(1), Some(1));
+
+        let tabs = tabs.selected_index(2);
+        assert_eq!(tabs.step_index(-1), Some(1));
+        assert_eq!(tabs.step_index(1), None);
+
+        assert_eq!(TitleBarTabs::new("empty").step_index(1), None);
+    }
+}
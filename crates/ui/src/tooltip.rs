@@ -2,18 +2,20 @@ use std::{cell::Cell, rc::Rc, time::Duration};
 
 use gpui::{
     Action, AnyElement, AnyView, App, AppContext, Bounds, Context, Display, Element, ElementId,
-    GlobalElementId, Half, InspectorElementId, IntoElement, LayoutId, MouseButton, ParentElement,
-    Pixels, Point, Position, Render, SharedString, Size, StatefulInteractiveElement, Style,
-    StyleRefinement, Styled, Task, Window, deferred, div, point, prelude::FluentBuilder, px,
+    FocusHandle, GlobalElementId, Half, InspectorElementId, IntoElement, Keystroke, LayoutId,
+    MouseButton, ParentElement, Pixels, Point, Position, Render, SharedString, Size,
+    StatefulInteractiveElement, Style, StyleRefinement, Styled, Task, Window, deferred, div, point,
+    prelude::FluentBuilder, px,
 };
 
 use crate::{
-    ActiveTheme, StyledExt,
+    ActiveTheme, ComponentDensity, Placement, StyledExt,
     animation::{Transition, ease_in_out_cubic, ease_out_cubic},
     h_flex,
     kbd::Kbd,
     root::Root,
     text::Text,
+    v_flex,
 };
 
 pub(crate) fn init(_cx: &mut App) {
@@ -25,6 +27,70 @@ pub(crate) fn init(_cx: &mut App) {
 enum TooltipContext {
     Text(Text),
     Element(Box<dyn Fn(&mut Window, &mut App) -> AnyElement>),
+    Rich {
+        title: Option<Text>,
+        body: Option<Text>,
+    },
+}
+
+/// The default max width of the rich tooltips, to wrap the long body to multiple lines.
+const RICH_MAX_WIDTH: Pixels = px(320.);
+
+/// The display options of a tooltip shown by the components, e.g. [`Tooltip::delay`].
+///
+/// The options not set fall back to the defaults: shown after 500ms above the trigger, without a
+/// max width.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct TooltipOptions {
+    /// The delay before showing.
+    pub delay: Option<Duration>,
+    /// The preferred side of the trigger, flipped to the opposite side if it doesn't fit.
+    pub side: Option<Placement>,
+    /// The max width, the text is wrapped to multiple lines.
+    pub max_width: Option<Pixels>,
+    /// The gap between the trigger and the tooltip.
+    pub offset: Option<Pixels>,
+}
+
+impl TooltipOptions {
+    /// Create the default options.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the delay before showing, default is 500ms.
+    pub fn delay(mut self, delay: Duration) -> Self {
+        self.delay = Some(delay);
+        self
+    }
+
+    /// Set the preferred side of the trigger, default is [`Placement::Top`].
+    pub fn side(mut self, side: Placement) -> Self {
+        self.side = Some(side);
+        self
+    }
+
+    /// Set the max width, default is unlimited.
+    pub fn max_width(mut self, max_width: impl Into<Pixels>) -> Self {
+        self.max_width = Some(max_width.into());
+        self
+    }
+
+    /// Set the gap between the trigger and the tooltip, default is 0.
+    pub fn offset(mut self, offset: impl Into<Pixels>) -> Self {
+        self.offset = Some(offset.into());
+        self
+    }
+
+    /// Returns the options with the unset options taken from the `other`.
+    fn or(self, other: Self) -> Self {
+        Self {
+            delay: self.delay.or(other.delay),
+            side: self.side.or(other.side),
+            max_width: self.max_width.or(other.max_width),
+            offset: self.offset.or(other.offset),
+        }
+    }
 }
 
 /// A Tooltip element that can display text or custom content,
@@ -34,6 +100,7 @@ pub struct Tooltip {
     content: TooltipContext,
     key_binding: Option<Kbd>,
     action: Option<(Box<dyn Action>, Option<SharedString>)>,
+    options: TooltipOptions,
 }
 
 impl Tooltip {
@@ -44,6 +111,7 @@ impl Tooltip {
             content: TooltipContext::Text(text.into()),
             key_binding: None,
             action: None,
+            options: TooltipOptions::default(),
         }
     }
 
@@ -57,12 +125,70 @@ impl Tooltip {
             style: StyleRefinement::default(),
             key_binding: None,
             action: None,
+            options: TooltipOptions::default(),
             content: TooltipContext::Element(Box::new(move |window, cx| {
                 builder(window, cx).into_any_element()
             })),
         }
     }
 
+    /// Create a rich Tooltip, with a bold [`Tooltip::title`], a muted [`Tooltip::body`] and a
+    /// [`Tooltip::keybinding`] hint.
+    ///
+    /// The rich tooltips are limited to 320px width by default, see [`Tooltip::max_width`]. Use
+    /// [`Tooltip::element`] for the fully custom content.
+    pub fn rich() -> Self {
+        Self {
+            style: StyleRefinement::default(),
+            content: TooltipContext::Rich {
+                title: None,
+                body: None,
+            },
+            key_binding: None,
+            action: None,
+            options: TooltipOptions::default(),
+        }
+    }
+
+    /// Set the title of the rich tooltip, see [`Tooltip::rich`].
+    ///
+    /// The text of a [`Tooltip::new`] becomes the body.
+    pub fn title(mut self, title: impl Into<Text>) -> Self {
+        let title = Some(title.into());
+        self.content = match self.content {
+            TooltipContext::Rich { body, .. } => TooltipContext::Rich { title, body },
+            TooltipContext::Text(text) => TooltipContext::Rich {
+                title,
+                body: Some(text),
+            },
+            TooltipContext::Element(_) => TooltipContext::Rich { title, body: None },
+        };
+        self
+    }
+
+    /// Set the body of the rich tooltip, see [`Tooltip::rich`].
+    ///
+    /// The text of a [`Tooltip::new`] becomes the title.
+    pub fn body(mut self, body: impl Into<Text>) -> Self {
+        let body = Some(body.into());
+        self.content = match self.content {
+            TooltipContext::Rich { title, .. } => TooltipContext::Rich { title, body },
+            TooltipContext::Text(text) => TooltipContext::Rich {
+                title: Some(text),
+                body,
+            },
+            TooltipContext::Element(_) => TooltipContext::Rich { title: None, body },
+        };
+        self
+    }
+
+    /// Set the [`Keystroke`] hint, rendered with the modifier symbols of the platform, e.g. `⌘S`
+    /// on macOS and `Ctrl+S` on others.
+    pub fn keybinding(mut self, keystroke: Keystroke) -> Self {
+        self.key_binding = Some(Kbd::new(keystroke));
+        self
+    }
+
     /// Set Action to display key binding information for the tooltip if it exists.
     pub fn action(mut self, action: &dyn Action, context: Option<&str>) -> Self {
         self.action = Some((action.boxed_clone(), context.map(SharedString::new)));
@@ -75,6 +201,43 @@ impl Tooltip {
         self
     }
 
+    /// Set the delay before showing, default is 500ms.
+    ///
+    /// Only used by the tooltips of the components, not by the `.tooltip()` of GPUI.
+    pub fn delay(mut self, delay: Duration) -> Self {
+        self.options.delay = Some(delay);
+        self
+    }
+
+    /// Set the preferred side of the trigger, default is [`Placement::Top`].
+    ///
+    /// The tooltip flips to the opposite side if it doesn't fit in the window. Only used by the
+    /// tooltips of the components, not by the `.tooltip()` of GPUI.
+    pub fn side(mut self, side: Placement) -> Self {
+        self.options.side = Some(side);
+        self
+    }
+
+    /// Set the max width, the text is wrapped to multiple lines, default is unlimited.
+    pub fn max_width(mut self, max_width: impl Into<Pixels>) -> Self {
+        self.options.max_width = Some(max_width.into());
+        self
+    }
+
+    /// Set the gap between the trigger and the tooltip, default is 0.
+    ///
+    /// Only used by the tooltips of the components, not by the `.tooltip()` of GPUI.
+    pub fn offset(mut self, offset: impl Into<Pixels>) -> Self {
+        self.options.offset = Some(offset.into());
+        self
+    }
+
+    /// Set the [`TooltipOptions`], replacing the options set before.
+    pub fn options(mut self, options: TooltipOptions) -> Self {
+        self.options = options;
+        self
+    }
+
     /// Build the tooltip and return it as an `AnyView`.
     pub fn build(self, _: &mut Window, cx: &mut App) -> AnyView {
         cx.new(|_| self).into()
@@ -103,6 +266,14 @@ impl Render for Tooltip {
             }
         };
 
+        let kbd = key_binding.map(|kbd| {
+            div()
+                .text_xs()
+                .flex_shrink_0()
+                .text_color(cx.theme().muted_foreground)
+                .child(kbd.appearance(false))
+        });
+
         div().child(
             // Wrap in a child, to ensure the left margin is applied to the tooltip
             h_flex()
@@ -116,25 +287,54 @@ impl Render for Tooltip {
                 .shadow_md()
                 .rounded(px(6.))
                 .justify_between()
-                .py_0p5()
-                .px_2()
+                .map(|this| match crate::window_density(window, cx) {
+                    ComponentDensity::Comfortable => this.py_0p5().px_2(),
+                    ComponentDensity::Compact => this.py(px(1.)).px_1p5(),
+                })
                 .text_sm()
                 .gap_3()
-                .refine_style(&self.style)
-                .map(|this| {
-                    this.child(div().map(|this| match self.content {
-                        TooltipContext::Text(ref text) => this.child(text.clone()),
-                        TooltipContext::Element(ref builder) => this.child(builder(window, cx)),
-                    }))
+                .map(|this| match (&self.content, self.options.max_width) {
+                    (_, Some(max_width)) => this.max_w(max_width),
+                    (TooltipContext::Rich { .. }, None) => this.max_w(RICH_MAX_WIDTH),
+                    _ => this,
                 })
-                .when_some(key_binding, |this, kbd| {
-                    this.child(
-                        div()
-                            .text_xs()
-                            .flex_shrink_0()
-                            .text_color(cx.theme().muted_foreground)
-                            .child(kbd.appearance(false)),
-                    )
+                .refine_style(&self.style)
+                .map(|this| match self.content {
+                    TooltipContext::Text(ref text) => this
+                        .child(div().min_w_0().child(text.clone()))
+                        .children(kbd),
+                    TooltipContext::Element(ref builder) => this
+                        .child(div().min_w_0().child(builder(window, cx)))
+                        .children(kbd),
+                    TooltipContext::Rich {
+                        ref title,
+                        ref body,
+                    } => this.child(
+                        v_flex()
+                            .flex_1()
+                            .min_w_0()
+                            .py_1()
+                            .gap_0p5()
+                            .when(title.is_some() || kbd.is_some(), |this| {
+                                this.child(
+                                    h_flex()
+                                        .gap_3()
+                                        .justify_between()
+                                        .when_some(title.clone(), |this, title| {
+                                            this.child(div().min_w_0().font_semibold().child(title))
+                                        })
+                                        .when_some(kbd, |this, kbd| this.child(kbd.ml_auto())),
+                                )
+                            })
+                            .when_some(body.clone(), |this, body| {
+                                this.child(
+                                    div()
+                                        .min_w_0()
+                                        .text_color(cx.theme().muted_foreground)
+                                        .child(body),
+                                )
+                            }),
+                    ),
                 }),
         )
     }
@@ -156,6 +356,8 @@ const TOOLTIP_WINDOW_MARGIN: Pixels = px(4.);
 enum TooltipPlacement {
     Above,
     Below,
+    Left,
+    Right,
 }
 
 #[derive(Clone, Copy, Debug, PartialEq)]
@@ -164,35 +366,74 @@ struct TooltipOverlayPosition {
     placement: TooltipPlacement,
 }
 
+/// Returns the position of the tooltip on the `side` of the trigger, default is above, with the
+/// `offset` gap from the trigger.
+///
+/// The tooltip flips to the opposite side if it doesn't fit, or to the larger side if neither
+/// fits.
 fn tooltip_overlay_position(
     trigger_bounds: Bounds<Pixels>,
     tooltip_size: Size<Pixels>,
     viewport_size: Size<Pixels>,
     margin: Pixels,
+    side: Option<Placement>,
+    offset: Pixels,
 ) -> TooltipOverlayPosition {
     let centered_x = trigger_bounds.center().x - tooltip_size.width.half();
-    let above_bounds = Bounds::new(
-        point(centered_x, trigger_bounds.top() - tooltip_size.height),
-        tooltip_size,
-    );
-    let below_bounds = Bounds::new(point(centered_x, trigger_bounds.bottom()), tooltip_size);
-
+    let centered_y = trigger_bounds.center().y - tooltip_size.height.half();
+    let right_limit = (viewport_size.width - margin).max(margin);
     let bottom_limit = (viewport_size.height - margin).max(margin);
-    let available_above = (trigger_bounds.top() - margin).max(px(0.));
-    let available_below = (bottom_limit - trigger_bounds.bottom()).max(px(0.));
-
-    let (bounds, placement) = if above_bounds.top() >= margin {
-        (above_bounds, TooltipPlacement::Above)
-    } else if below_bounds.bottom() <= bottom_limit {
-        (below_bounds, TooltipPlacement::Below)
-    } else if available_below >= available_above {
-        (below_bounds, TooltipPlacement::Below)
+
+    let bounds_at = |placement| {
+        let origin = match placement {
+            TooltipPlacement::Above => point(
+                centered_x,
+                trigger_bounds.top() - offset - tooltip_size.height,
+            ),
+            TooltipPlacement::Below => point(centered_x, trigger_bounds.bottom() + offset),
+            TooltipPlacement::Left => point(
+                trigger_bounds.left() - offset - tooltip_size.width,
+                centered_y,
+            ),
+            TooltipPlacement::Right => point(trigger_bounds.right() + offset, centered_y),
+        };
+        Bounds::new(origin, tooltip_size)
+    };
+    let fits = |placement| {
+        let bounds = bounds_at(placement);
+        match placement {
+            TooltipPlacement::Above => bounds.top() >= margin,
+            TooltipPlacement::Below => bounds.bottom() <= bottom_limit,
+            TooltipPlacement::Left => bounds.left() >= margin,
+            TooltipPlacement::Right => bounds.right() <= right_limit,
+        }
+    };
+    let available = |placement| {
+        match placement {
+            TooltipPlacement::Above => trigger_bounds.top() - margin,
+            TooltipPlacement::Below => bottom_limit - trigger_bounds.bottom(),
+            TooltipPlacement::Left => trigger_bounds.left() - margin,
+            TooltipPlacement::Right => right_limit - trigger_bounds.right(),
+        }
+        .max(px(0.))
+    };
+
+    let (preferred, opposite) = match side.unwrap_or(Placement::Top) {
+        Placement::Top => (TooltipPlacement::Above, TooltipPlacement::Below),
+        Placement::Bottom => (TooltipPlacement::Below, TooltipPlacement::Above),
+        Placement::Left => (TooltipPlacement::Left, TooltipPlacement::Right),
+        Placement::Right => (TooltipPlacement::Right, TooltipPlacement::Left),
+    };
+    let placement = if fits(preferred) {
+        preferred
+    } else if fits(opposite) || available(opposite) >= available(preferred) {
+        opposite
     } else {
-        (above_bounds, TooltipPlacement::Above)
+        preferred
     };
 
     TooltipOverlayPosition {
-        bounds: clamp_tooltip_bounds(bounds, viewport_size, margin),
+        bounds: clamp_tooltip_bounds(bounds_at(placement), viewport_size, margin),
         placement,
     }
 }
@@ -224,6 +465,7 @@ fn clamp_tooltip_bounds(
 
 struct TooltipOverlayPositioner {
     trigger_bounds: Bounds<Pixels>,
+    options: TooltipOptions,
     children: Vec<AnyElement>,
 }
 
@@ -231,9 +473,13 @@ struct TooltipOverlayPositionerState {
     child_layout_ids: Vec<LayoutId>,
 }
 
-fn tooltip_overlay_positioner(trigger_bounds: Bounds<Pixels>) -> TooltipOverlayPositioner {
+fn tooltip_overlay_positioner(
+    trigger_bounds: Bounds<Pixels>,
+    options: TooltipOptions,
+) -> TooltipOverlayPositioner {
     TooltipOverlayPositioner {
         trigger_bounds,
+        options,
         children: Vec::new(),
     }
 }
@@ -313,6 +559,8 @@ impl Element for TooltipOverlayPositioner {
             tooltip_size,
             window.viewport_size(),
             TOOLTIP_WINDOW_MARGIN + client_inset,
+            self.options.side,
+            self.options.offset.unwrap_or_default(),
         );
 
         let offset = tooltip_position.bounds.origin - bounds.origin;
@@ -354,12 +602,15 @@ impl IntoElement for TooltipOverlayPositioner {
 pub(crate) struct TooltipContent {
     pub build: Rc<dyn Fn(&mut Window, &mut App) -> AnyView>,
     pub trigger_bounds: Bounds<Pixels>,
+    /// The options of the component, override the options of the [`Tooltip`].
+    pub options: TooltipOptions,
 }
 
 /// Manages tooltip lifecycle: delay, grace period, animations, and rendering.
 ///
 /// A single instance lives in [`Root`] per window. Components register hover
-/// via [`ManagedTooltipExt::managed_tooltip`] which calls into this overlay.
+/// via [`ManagedTooltipExt::managed_tooltip`] which calls into this overlay,
+/// and the keyboard focus via [`ManagedTooltipExt::managed_tooltip_with_focus`].
 pub struct TooltipOverlay {
     content: Option<TooltipContent>,
     prev_trigger_bounds: Option<Bounds<Pixels>>,
@@ -367,6 +618,11 @@ pub struct TooltipOverlay {
     had_recent_tooltip: bool,
     animation_epoch: usize,
     is_switching: bool,
+    /// The focused trigger the tooltip is shown or pending for.
+    focus_trigger: Option<FocusHandle>,
+    /// The focused trigger without the tooltip until blurred, focused by the mouse or dismissed
+    /// with `escape`.
+    suppressed_focus: Option<FocusHandle>,
 
     _show_task: Option<Task<()>>,
     _hide_task: Option<Task<()>>,
@@ -381,6 +637,8 @@ impl TooltipOverlay {
             had_recent_tooltip: false,
             animation_epoch: 0,
             is_switching: false,
+            focus_trigger: None,
+            suppressed_focus: None,
             _show_task: None,
             _hide_task: None,
         }
@@ -395,13 +653,18 @@ impl TooltipOverlay {
     /// hidden, shows immediately with a slide animation. Otherwise starts a delay.
     pub(crate) fn request_show(
         &mut self,
-        content: TooltipContent,
+        mut content: TooltipContent,
         window: &mut Window,
         cx: &mut Context<Self>,
     ) {
         // Cancel any pending hide
         self._hide_task = None;
 
+        // The options of the built tooltip are needed for the delay and the position.
+        if let Ok(tooltip) = (content.build)(window, cx).downcast::<Tooltip>() {
+            content.options = content.options.or(tooltip.read(cx).options);
+        }
+
         let was_visible = self.content.is_some();
         let in_grace = self.had_recent_tooltip;
 
@@ -417,8 +680,9 @@ impl TooltipOverlay {
             // New: delay then show with slideDown
             let epoch = self.next_epoch();
             let content = content.clone();
+            let delay = content.options.delay.unwrap_or(SHOW_DELAY);
             self._show_task = Some(cx.spawn_in(window, async move |this, cx| {
-                cx.background_executor().timer(SHOW_DELAY).await;
+                cx.background_executor().timer(delay).await;
                 let _ = this.update_in(cx, |this, _, cx| {
                     if this.epoch != epoch {
                         return;
@@ -461,6 +725,50 @@ impl TooltipOverlay {
         }));
     }
 
+    /// Show the tooltip while the trigger has the focus, or hide it on blur, called on each
+    /// prepaint of the trigger.
+    pub(crate) fn sync_focus(
+        &mut self,
+        focus_handle: &FocusHandle,
+        content: TooltipContent,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let is_trigger = self.focus_trigger.as_ref() == Some(focus_handle);
+        if !focus_handle.is_focused(window) {
+            if self.suppressed_focus.as_ref() == Some(focus_handle) {
+                self.suppressed_focus = None;
+            }
+            if is_trigger {
+                self.focus_trigger = None;
+                self.request_hide(window, cx);
+            }
+            return;
+        }
+
+        if is_trigger || self.suppressed_focus.as_ref() == Some(focus_handle) {
+            return;
+        }
+        self.focus_trigger = Some(focus_handle.clone());
+        self.request_show(content, window, cx);
+    }
+
+    /// Hide the tooltip, and don't show it for the focus of the trigger until it's blurred.
+    ///
+    /// Returns true if the tooltip of the focused trigger was visible.
+    pub(crate) fn dismiss_focus(
+        &mut self,
+        focus_handle: &FocusHandle,
+        cx: &mut Context<Self>,
+    ) -> bool {
+        let was_visible =
+            self.focus_trigger.as_ref() == Some(focus_handle) && self.content.is_some();
+        self.focus_trigger = None;
+        self.suppressed_focus = Some(focus_handle.clone());
+        self.hide(cx);
+        was_visible
+    }
+
     pub(crate) fn hide(&mut self, cx: &mut Context<Self>) {
         if self.clear_state() {
             cx.notify();
@@ -494,12 +802,16 @@ impl Render for TooltipOverlay {
 
         let content_view = (content.build)(window, cx);
         let trigger_bounds = content.trigger_bounds;
+        let options = content.options;
+        if let Ok(tooltip) = content_view.clone().downcast::<Tooltip>() {
+            tooltip.update(cx, |tooltip, _| tooltip.options = options);
+        }
         let animation_epoch = self.animation_epoch;
         let is_switching = self.is_switching;
         let prev_trigger_bounds = self.prev_trigger_bounds;
 
-        deferred(
-            tooltip_overlay_positioner(trigger_bounds).child(div().child(content_view).map(|el| {
+        deferred(tooltip_overlay_positioner(trigger_bounds, options).child(
+            div().child(content_view).map(|el| {
                 if is_switching {
                     let Some(prev_bounds) = prev_trigger_bounds else {
                         return el.into_any_element();
@@ -536,8 +848,8 @@ impl Render for TooltipOverlay {
                         )
                         .into_any_element()
                 }
-            })),
-        )
+            }),
+        ))
         .with_priority(2)
         .into_any_element()
     }
@@ -556,15 +868,22 @@ pub(crate) struct ComponentTooltip {
         Option<(Rc<Box<dyn Action>>, Option<SharedString>)>,
     )>,
     pub builder: Option<Rc<dyn Fn(&mut Window, &mut App) -> AnyView>>,
+    pub options: TooltipOptions,
+    /// The focus handle of the component, to show the tooltip on the keyboard focus.
+    pub focus_handle: Option<FocusHandle>,
 }
 
 impl ComponentTooltip {
     /// Apply this tooltip to a `Stateful<Div>` (or any `ManagedTooltipExt` element).
     pub fn apply<E: ManagedTooltipExt>(self, el: E) -> E {
+        let options = self.options;
+        let focus_handle = self.focus_handle;
         if let Some(builder) = self.builder {
-            el.managed_tooltip(move |window, cx| builder(window, cx))
+            el.managed_tooltip_with_focus(options, focus_handle, move |window, cx| {
+                builder(window, cx)
+            })
         } else if let Some((text, action)) = self.text {
-            el.managed_tooltip(move |window, cx| {
+            el.managed_tooltip_with_focus(options, focus_handle, move |window, cx| {
                 Tooltip::new(text.clone())
                     .when_some(action.clone(), |this, (action, context)| {
                         this.action(
@@ -588,15 +907,75 @@ pub(crate) trait ManagedTooltipExt:
     fn managed_tooltip(
         self,
         build_tooltip: impl Fn(&mut Window, &mut App) -> AnyView + 'static,
+    ) -> Self {
+        self.managed_tooltip_with(TooltipOptions::default(), build_tooltip)
+    }
+
+    /// Like [`ManagedTooltipExt::managed_tooltip`], with the [`TooltipOptions`] overriding the
+    /// options of the built [`Tooltip`].
+    fn managed_tooltip_with(
+        self,
+        options: TooltipOptions,
+        build_tooltip: impl Fn(&mut Window, &mut App) -> AnyView + 'static,
+    ) -> Self {
+        self.managed_tooltip_with_focus(options, None, build_tooltip)
+    }
+
+    /// Like [`ManagedTooltipExt::managed_tooltip_with`], also shown when the `focus_handle` has
+    /// the keyboard focus for the delay.
+    ///
+    /// The tooltip hides on blur or `escape`, and isn't shown for the focus by a mouse down.
+    fn managed_tooltip_with_focus(
+        self,
+        options: TooltipOptions,
+        focus_handle: Option<FocusHandle>,
+        build_tooltip: impl Fn(&mut Window, &mut App) -> AnyView + 'static,
     ) -> Self {
         let build_tooltip = Rc::new(build_tooltip);
         let trigger_bounds_cell: Rc<Cell<Bounds<Pixels>>> = Rc::new(Cell::new(Bounds::default()));
         let bounds_writer = trigger_bounds_cell.clone();
 
-        self.on_prepaint(move |bounds, _, _| {
-            bounds_writer.set(bounds);
-        })
-        .on_hover({
+        let this = self.on_prepaint({
+            let focus_handle = focus_handle.clone();
+            let build_tooltip = build_tooltip.clone();
+            move |bounds, window, cx| {
+                bounds_writer.set(bounds);
+
+                let Some(focus_handle) = focus_handle else {
+                    return;
+                };
+                if let Some(overlay) = Root::tooltip_overlay(window, cx) {
+                    overlay.update(cx, |o: &mut TooltipOverlay, cx| {
+                        o.sync_focus(
+                            &focus_handle,
+                            TooltipContent {
+                                build: build_tooltip,
+                                trigger_bounds: bounds,
+                                options,
+                            },
+                            window,
+                            cx,
+                        );
+                    });
+                }
+            }
+        });
+        // Hide the tooltip of the focus, before the `escape` is handled by the parents.
+        let this = match focus_handle.clone() {
+            Some(focus_handle) => this.on_key_down(move |event, window, cx| {
+                if event.keystroke.key != "escape" {
+                    return;
+                }
+                if let Some(overlay) = Root::tooltip_overlay(window, cx) {
+                    if overlay.update(cx, |overlay, cx| overlay.dismiss_focus(&focus_handle, cx)) {
+                        cx.stop_propagation();
+                    }
+                }
+            }),
+            None => this,
+        };
+
+        this.on_hover({
             let trigger_bounds_cell = trigger_bounds_cell.clone();
             let build_tooltip = build_tooltip.clone();
             move |hovered, window, cx| {
@@ -608,6 +987,7 @@ pub(crate) trait ManagedTooltipExt:
                                 TooltipContent {
                                     build: build_tooltip.clone(),
                                     trigger_bounds: bounds,
+                                    options,
                                 },
                                 window,
                                 cx,
@@ -623,8 +1003,11 @@ pub(crate) trait ManagedTooltipExt:
         })
         .on_mouse_down(MouseButton::Left, move |_, window, cx| {
             if let Some(overlay) = Root::tooltip_overlay(window, cx) {
-                overlay.update(cx, |overlay, cx| {
-                    overlay.hide(cx);
+                overlay.update(cx, |overlay, cx| match &focus_handle {
+                    Some(focus_handle) => {
+                        overlay.dismiss_focus(focus_handle, cx);
+                    }
+                    None => overlay.hide(cx),
                 });
             }
         })
@@ -636,12 +1019,13 @@ impl<E: StatefulInteractiveElement + crate::ElementExt> ManagedTooltipExt for E
 #[cfg(test)]
 mod tests {
     use super::*;
-    use gpui::size;
+    use gpui::{InteractiveElement as _, TestAppContext, VisualTestContext, size};
 
     fn test_content(bounds: Bounds<Pixels>) -> TooltipContent {
         TooltipContent {
             build: Rc::new(|window, cx| Tooltip::new("Test tooltip").build(window, cx)),
             trigger_bounds: bounds,
+            options: TooltipOptions::default(),
         }
     }
 
@@ -653,6 +1037,77 @@ mod tests {
         size(px(width), px(height))
     }
 
+    struct FocusView {
+        trigger: FocusHandle,
+        other: FocusHandle,
+    }
+
+    impl Render for FocusView {
+        fn render(&mut self, _: &mut Window, _: &mut Context<Self>) -> impl IntoElement {
+            div()
+                .child(
+                    div()
+                        .id("trigger")
+                        .track_focus(&self.trigger)
+                        .managed_tooltip_with_focus(
+                            TooltipOptions::default(),
+                            Some(self.trigger.clone()),
+                            |window, cx| Tooltip::new("Test tooltip").build(window, cx),
+                        ),
+                )
+                .child(div().id("other").track_focus(&self.other))
+        }
+    }
+
+    #[gpui::test]
+    fn tooltip_overlay_shows_on_focus(cx: &mut TestAppContext) {
+        cx.update(crate::init);
+        let mut view = None;
+        let (root, cx) = cx.add_window_view(|window, cx| {
+            let focus_view = cx.new(|cx| FocusView {
+                trigger: cx.focus_handle(),
+                other: cx.focus_handle(),
+            });
+            view = Some(focus_view.clone());
+            Root::new(focus_view, window, cx)
+        });
+        let view = view.unwrap();
+        let overlay = root.read_with(cx, |root, _| root.tooltip_overlay.clone());
+        let is_visible = |cx: &mut VisualTestContext| {
+            overlay.read_with(cx, |overlay, _| overlay.content.is_some())
+        };
+        let focus = |handle: FocusHandle, cx: &mut VisualTestContext| {
+            cx.update(|window, cx| handle.focus(window, cx));
+            cx.run_until_parked();
+        };
+        let wait = |cx: &mut VisualTestContext| {
+            cx.executor().advance_clock(SHOW_DELAY + GRACE_PERIOD);
+            cx.run_until_parked();
+        };
+        let (trigger, other) =
+            view.read_with(cx, |view, _| (view.trigger.clone(), view.other.clone()));
+
+        // Shown after the delay, hidden on blur.
+        focus(trigger.clone(), cx);
+        assert!(!is_visible(cx));
+        wait(cx);
+        assert!(is_visible(cx));
+        focus(other.clone(), cx);
+        wait(cx);
+        assert!(!is_visible(cx));
+
+        // Hidden by `escape`, until focused again.
+        focus(trigger.clone(), cx);
+        wait(cx);
+        cx.simulate_keystrokes("escape");
+        wait(cx);
+        assert!(!is_visible(cx));
+        focus(other, cx);
+        focus(trigger, cx);
+        wait(cx);
+        assert!(is_visible(cx));
+    }
+
     #[test]
     fn tooltip_overlay_clear_state_resets_active_tooltip() {
         let mut overlay = TooltipOverlay::new();
@@ -680,6 +1135,8 @@ mod tests {
             test_size(120., 30.),
             test_size(300., 200.),
             TOOLTIP_WINDOW_MARGIN,
+            None,
+            px(0.),
         );
 
         assert_eq!(position.placement, TooltipPlacement::Above);
@@ -696,6 +1153,8 @@ mod tests {
             test_size(240., 32.),
             test_size(520., 260.),
             TOOLTIP_WINDOW_MARGIN,
+            None,
+            px(0.),
         );
 
         assert_eq!(position.placement, TooltipPlacement::Below);
@@ -711,6 +1170,8 @@ mod tests {
             test_size(120., 30.),
             test_size(300., 200.),
             TOOLTIP_WINDOW_MARGIN,
+            None,
+            px(0.),
         );
 
         assert_eq!(position.placement, TooltipPlacement::Above);
@@ -725,10 +1186,120 @@ mod tests {
             test_size(160., 120.),
             test_size(300., 100.),
             TOOLTIP_WINDOW_MARGIN,
+            None,
+            px(0.),
         );
 
         assert_eq!(position.placement, TooltipPlacement::Below);
         assert_eq!(position.bounds.top(), TOOLTIP_WINDOW_MARGIN);
         assert_eq!(position.bounds.left(), px(60.));
     }
+    #[test]
+    fn tooltip_overlay_position_follows_side() {
+        let trigger_bounds = test_bounds(100., 80., 80., 24.);
+        let position = tooltip_overlay_position(
+            trigger_bounds,
+            test_size(120., 30.),
+            test_size(300., 200.),
+            TOOLTIP_WINDOW_MARGIN,
+            Some(Placement::Bottom),
+            px(0.),
+        );
+        assert_eq!(position.placement, TooltipPlacement::Below);
+        assert_eq!(position.bounds.top(), trigger_bounds.bottom());
+
+        let position = tooltip_overlay_position(
+            trigger_bounds,
+            test_size(60., 20.),
+            test_size(300., 200.),
+            TOOLTIP_WINDOW_MARGIN,
+            Some(Placement::Right),
+            px(0.),
+        );
+        assert_eq!(position.placement, TooltipPlacement::Right);
+        assert_eq!(position.bounds.left(), trigger_bounds.right());
+        assert_eq!(position.bounds.origin.y, px(82.));
+
+        // Flips to the left without the space on the right.
+        let position = tooltip_overlay_position(
+            test_bounds(200., 80., 80., 24.),
+            test_size(60., 20.),
+            test_size(300., 200.),
+            TOOLTIP_WINDOW_MARGIN,
+            Some(Placement::Right),
+            px(0.),
+        );
+        assert_eq!(position.placement, TooltipPlacement::Left);
+    }
+
+    #[test]
+    fn tooltip_overlay_position_applies_offset() {
+        let trigger_bounds = test_bounds(100., 80., 80., 24.);
+        let position = tooltip_overlay_position(
+            trigger_bounds,
+            test_size(120., 30.),
+            test_size(300., 200.),
+            TOOLTIP_WINDOW_MARGIN,
+            Some(Placement::Bottom),
+            px(6.),
+        );
+        assert_eq!(position.placement, TooltipPlacement::Below);
+        assert_eq!(position.bounds.top(), trigger_bounds.bottom() + px(6.));
+
+        let position = tooltip_overlay_position(
+            trigger_bounds,
+            test_size(120., 30.),
+            test_size(300., 200.),
+            TOOLTIP_WINDOW_MARGIN,
+            None,
+            px(6.),
+        );
+        assert_eq!(position.placement, TooltipPlacement::Above);
+        assert_eq!(position.bounds.bottom(), trigger_bounds.top() - px(6.));
+    }
+
+    #[test]
+    fn tooltip_options_fall_back_to_tooltip_options() {
+        let component = TooltipOptions::new().side(Placement::Bottom);
+        let tooltip = TooltipOptions::new()
+            .side(Placement::Left)
+            .delay(Duration::ZERO)
+            .max_width(px(280.));
+
+        assert_eq!(
+            component.or(tooltip),
+            TooltipOptions::new()
+                .side(Placement::Bottom)
+                .delay(Duration::ZERO)
+                .max_width(px(280.))
+        );
+    }
+
+    #[test]
+    fn rich_tooltip_content() {
+        let tooltip = Tooltip::rich().title("Save").body("Save the document");
+        assert!(matches!(
+            tooltip.content,
+            TooltipContext::Rich {
+                title: Some(_),
+                body: Some(_)
+            }
+        ));
+        assert!(tooltip.key_binding.is_none());
+
+        let tooltip = tooltip.keybinding(Keystroke::parse("secondary-s").unwrap());
+        assert!(tooltip.key_binding.is_some());
+
+        // The text of a simple tooltip is kept as the body.
+        let tooltip = Tooltip::new("Save the document").title("Save");
+        let TooltipContext::Rich {
+            title: Some(Text::String(title)),
+            body: Some(Text::String(body)),
+        } = tooltip.content
+        else {
+            panic!("expected the rich content");
+        };
+        assert_eq!(title, "Save");
+        assert_eq!(body, "Save the document");
+    }
 }
@@ -1,42 +1,385 @@
 // From:
 // https://github.com/zed-industries/zed/blob/56daba28d40301ee4c05546fadb691d070b7b2b6/crates/gpui/examples/window_shadow.rs
+use std::{
+    collections::{HashMap, HashSet},
+    rc::Rc,
+    time::{Duration, Instant},
+};
+
 use gpui::{
-    AnyElement, App, CursorStyle, Decorations, Edges, Hsla, InteractiveElement as _,
-    IntoElement, MouseButton, ParentElement, Pixels, Point, RenderOnce, ResizeEdge, Size,
-    Styled as _, Tiling, Window, div, point, prelude::FluentBuilder as _, px,
+    AnyElement, App, Bounds, Context, CursorStyle, Decorations, Edges, Entity, FocusHandle, Global,
+    Hsla, InteractiveElement as _, IntoElement, KeyDownEvent, Keystroke, Modifiers, MouseButton,
+    MouseDownEvent, MouseMoveEvent, ParentElement, Pixels, Point, RenderOnce, ResizeEdge, Size,
+    StyleRefinement, Styled, Subscription, Task, Tiling, WeakEntity, WeakFocusHandle, Window,
+    WindowId, actions, canvas, div, point, prelude::FluentBuilder as _, px,
+};
+use rust_i18n::t;
+
+use crate::{
+    ActiveTheme, Colorize as _, StyledExt as _, WindowExt as _,
+    animation::{Lerp, ease_out_cubic},
+    v_flex,
+    window_ext::observe_window_active,
 };
 
-use crate::ActiveTheme;
+mod snap;
+
+#[cfg_attr(target_os = "macos", allow(unused_imports))]
+pub(crate) use snap::begin_snap_move;
+use snap::{GlobalSnapStates, SnapState};
+
+actions!(
+    window_border,
+    [
+        /// Start the keyboard resize mode of the [`WindowBorder`] in the active window.
+        ///
+        /// Not bound by default, `alt-f8` is the suggested binding.
+        StartKeyboardResize
+    ]
+);
 
 #[cfg(not(target_os = "linux"))]
 pub(crate) const SHADOW_SIZE: Pixels = px(0.0);
 #[cfg(target_os = "linux")]
 pub(crate) const SHADOW_SIZE: Pixels = px(12.0);
-const BORDER_SIZE: Pixels = px(1.0);
 /// Half-width of the resize hit band on each side of the visible frame (inner border).
+///
+/// Also the max distance a [`WindowBorder::resize_handle_size`] handle reaches into the content.
 const RESIZE_HIT_SIZE: Pixels = px(4.0);
 pub(crate) const BORDER_RADIUS: Pixels = px(0.0);
+/// The distance from a corner of the visible frame, along the resize band, where the diagonal
+/// resize wins over the adjacent edge.
+const CORNER_SIZE: Pixels = px(24.0);
+/// The duration of the shadow transition when the window activation changes.
+const SHADOW_TRANSITION_DURATION: Duration = Duration::from_millis(100);
+/// The time without bounds changes after which a resize is considered ended.
+const RESIZE_END_DELAY: Duration = Duration::from_millis(200);
+/// The min interval between two [`WindowBorder::on_resizing`] calls.
+const RESIZING_THROTTLE: Duration = Duration::from_millis(50);
+/// The keyboard resize steps, for the arrow keys without and with `shift` or `ctrl`.
+const KEYBOARD_RESIZE_STEP: Pixels = px(16.0);
+const KEYBOARD_RESIZE_FINE_STEP: Pixels = px(1.0);
+const KEYBOARD_RESIZE_COARSE_STEP: Pixels = px(64.0);
+
+pub(crate) fn init(cx: &mut App) {
+    cx.on_action(|_: &StartKeyboardResize, cx| {
+        let Some(active_window) = cx.active_window() else {
+            return;
+        };
+
+        cx.defer(move |cx| {
+            _ = active_window.update(cx, |_, window, cx| {
+                WindowBorder::start_keyboard_resize(window, cx);
+            });
+        });
+    });
+}
 
 /// Create a new window border.
 pub fn window_border() -> WindowBorder {
     WindowBorder::new()
 }
 
+/// The min and max size of the visible window frame, set by [`WindowBorder::min_size`] and
+/// [`WindowBorder::max_size`].
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct WindowSizeConstraints {
+    /// The min size of the visible window frame.
+    pub min_size: Option<Size<Pixels>>,
+    /// The max size of the visible window frame.
+    pub max_size: Option<Size<Pixels>>,
+}
+
+#[derive(Default)]
+struct GlobalWindowSizeConstraints(HashMap<WindowId, WindowSizeConstraints>);
+
+impl Global for GlobalWindowSizeConstraints {}
+
+/// The border radius of the [`WindowBorder`] in the windows, for [`window_content_bounds`].
+#[derive(Default)]
+struct GlobalWindowBorderRadius(HashMap<WindowId, Pixels>);
+
+impl Global for GlobalWindowBorderRadius {}
+
+fn update_border_radius(radius: Pixels, window: &Window, cx: &mut App) {
+    let window_id = window.window_handle().window_id();
+    if radius > px(0.) {
+        cx.default_global::<GlobalWindowBorderRadius>()
+            .0
+            .insert(window_id, radius);
+    } else if cx.has_global::<GlobalWindowBorderRadius>() {
+        cx.global_mut::<GlobalWindowBorderRadius>()
+            .0
+            .remove(&window_id);
+    }
+}
+
+impl WindowSizeConstraints {
+    /// Returns the size constraints of the window, set by the [`WindowBorder`] rendered in it.
+    pub fn of(window: &Window, cx: &App) -> Self {
+        cx.try_global::<GlobalWindowSizeConstraints>()
+            .and_then(|constraints| constraints.0.get(&window.window_handle().window_id()))
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Returns true if the window can be maximized, that is there is no max size.
+    pub fn can_maximize(&self) -> bool {
+        self.max_size.is_none()
+    }
+
+    /// Returns the size clamped to the constraints, the min size wins over the max size.
+    pub fn clamp(&self, size: Size<Pixels>) -> Size<Pixels> {
+        let mut size = size;
+        if let Some(max_size) = self.max_size {
+            size.width = size.width.min(max_size.width);
+            size.height = size.height.min(max_size.height);
+        }
+        if let Some(min_size) = self.min_size {
+            size.width = size.width.max(min_size.width);
+            size.height = size.height.max(min_size.height);
+        }
+        size
+    }
+
+    /// Returns the sides that can be resized, a side is locked when any resize of its axis
+    /// would violate the constraints.
+    pub(crate) fn resize_edges(&self) -> Edges<bool> {
+        let (width, height) = match (self.min_size, self.max_size) {
+            (Some(min_size), Some(max_size)) => (
+                min_size.width < max_size.width,
+                min_size.height < max_size.height,
+            ),
+            _ => (true, true),
+        };
+        Edges {
+            top: height,
+            bottom: height,
+            left: width,
+            right: width,
+        }
+    }
+
+    fn update(self, window: &Window, cx: &mut App) {
+        let window_id = window.window_handle().window_id();
+        if self != Self::default() {
+            if !cx
+                .default_global::<GlobalWindowSizeConstraints>()
+                .0
+                .contains_key(&window_id)
+            {
+                // Drop the constraints of the closed windows when a new window is constrained.
+                let open_windows = cx
+                    .windows()
+                    .iter()
+                    .map(|window| window.window_id())
+                    .collect::<Vec<_>>();
+                cx.global_mut::<GlobalWindowSizeConstraints>()
+                    .0
+                    .retain(|window_id, _| open_windows.contains(window_id));
+            }
+            cx.global_mut::<GlobalWindowSizeConstraints>()
+                .0
+                .insert(window_id, self);
+        } else if cx.has_global::<GlobalWindowSizeConstraints>() {
+            cx.global_mut::<GlobalWindowSizeConstraints>()
+                .0
+                .remove(&window_id);
+        }
+    }
+}
+
+/// The background behind the rounded corners of the [`WindowBorder`], see
+/// [`WindowBorder::corner_backdrop`].
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum CornerBackdrop {
+    /// Transparent, this needs a compositor with per-pixel alpha, otherwise the corners are
+    /// black.
+    #[default]
+    Transparent,
+    /// A solid color.
+    Solid(Hsla),
+    /// Transparent when the window supports transparency, otherwise the theme background.
+    Auto,
+}
+
+impl CornerBackdrop {
+    fn color(&self, transparent: bool, background: Hsla) -> Hsla {
+        match self {
+            Self::Transparent => gpui::transparent_black(),
+            Self::Solid(color) => *color,
+            Self::Auto if transparent => gpui::transparent_black(),
+            Self::Auto => background,
+        }
+    }
+}
+
+/// Returns true if the window supports transparency.
+///
+/// GPUI doesn't report the compositor, but on Linux it only uses client decorations when a
+/// compositor is running. The other platforms are always composited.
+fn supports_transparency(window: &Window) -> bool {
+    cfg!(not(target_os = "linux"))
+        || matches!(window.window_decorations(), Decorations::Client { .. })
+}
+
+/// The windows that have logged the [`CornerBackdrop::Auto`] detection.
+#[cfg(feature = "mcp")]
+#[derive(Default)]
+struct LoggedCornerBackdrops(HashSet<WindowId>);
+
+#[cfg(feature = "mcp")]
+impl Global for LoggedCornerBackdrops {}
+
+/// Log the transparency detection of the [`CornerBackdrop::Auto`] once per window, for corner
+/// artifact reports.
+#[cfg(feature = "mcp")]
+fn log_corner_backdrop(transparent: bool, window: &Window, cx: &mut App) {
+    let window_id = window.window_handle().window_id();
+    if cx
+        .default_global::<LoggedCornerBackdrops>()
+        .0
+        .insert(window_id)
+    {
+        crate::mcp::mcp_log(format!(
+            "[info] WindowBorder: window {:?} supports transparency: {}, corner backdrop is {}.",
+            window_id,
+            transparent,
+            if transparent {
+                "transparent"
+            } else {
+                "the theme background"
+            }
+        ));
+    }
+}
+
+/// The shadow of the [`WindowBorder`], for the active or inactive window.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WindowShadow {
+    /// The opacity applied to the shadow color, in range of 0.0 .. 1.0.
+    pub opacity: f32,
+    /// The blur radius of the shadow.
+    pub blur_radius: Pixels,
+}
+
+impl WindowShadow {
+    /// Create a new window shadow.
+    pub fn new(opacity: f32, blur_radius: impl Into<Pixels>) -> Self {
+        Self {
+            opacity,
+            blur_radius: blur_radius.into(),
+        }
+    }
+}
+
+impl Lerp for WindowShadow {
+    fn lerp(&self, target: &Self, t: f32) -> Self {
+        Self {
+            opacity: self.opacity.lerp(&target.opacity, t),
+            blur_radius: self.blur_radius.lerp(&target.blur_radius, t),
+        }
+    }
+}
+
+/// Animates the shadow when the window activation changes.
+#[derive(Default)]
+struct ShadowTransition {
+    active: Option<bool>,
+    from: Option<WindowShadow>,
+    last: Option<WindowShadow>,
+    started_at: Option<Instant>,
+}
+
+impl ShadowTransition {
+    /// Returns the shadow to render, and true if the transition is still running.
+    fn shadow(&mut self, active: bool, target: WindowShadow, now: Instant) -> (WindowShadow, bool) {
+        if self.active != Some(active) {
+            // Start from the last rendered shadow, so a change in the middle doesn't jump.
+            if self.active.is_some() {
+                self.from = self.last;
+                self.started_at = Some(now);
+            }
+            self.active = Some(active);
+        }
+
+        let shadow = match (self.from, self.started_at) {
+            (Some(from), Some(started_at)) => {
+                let t = now.duration_since(started_at).as_secs_f32()
+                    / SHADOW_TRANSITION_DURATION.as_secs_f32();
+                if t >= 1. {
+                    self.started_at = None;
+                    target
+                } else {
+                    from.lerp(&target, ease_out_cubic(t))
+                }
+            }
+            _ => target,
+        };
+        self.last = Some(shadow);
+        (shadow, self.started_at.is_some())
+    }
+}
+
 /// Renders a custom window border and shadow on Linux.
 #[derive(IntoElement)]
 pub struct WindowBorder {
+    style: StyleRefinement,
     shadow_size: Pixels,
     resize_hit_size: Pixels,
+    resize_handle_size: Option<Pixels>,
+    corner_size: Pixels,
+    edge_double_click_expand: bool,
+    window_menu_on_border: bool,
     border_radius: Pixels,
+    clip_content: bool,
+    scale_with_dpi: bool,
+    corner_backdrop: CornerBackdrop,
+    shadow_color: Option<Hsla>,
+    active_shadow: Option<WindowShadow>,
+    inactive_shadow: Option<WindowShadow>,
+    border_color: Option<Hsla>,
+    resizable: bool,
+    resize_edges: Edges<bool>,
+    size_constraints: WindowSizeConstraints,
+    on_resize_start: Option<Rc<dyn Fn(&mut Window, &mut App)>>,
+    on_resize_end: Option<Rc<dyn Fn(&Bounds<Pixels>, &mut Window, &mut App)>>,
+    on_resizing: Option<Rc<dyn Fn(&Size<Pixels>, &mut Window, &mut App)>>,
+    on_edge_hover: Option<Rc<dyn Fn(&Option<ResizeEdge>, &mut Window, &mut App)>>,
+    modifier_move: Option<Modifiers>,
+    modifier_resize: Option<Modifiers>,
+    snap_preview: bool,
     children: Vec<AnyElement>,
 }
 
 impl Default for WindowBorder {
     fn default() -> Self {
         Self {
+            style: StyleRefinement::default(),
             shadow_size: SHADOW_SIZE,
             resize_hit_size: RESIZE_HIT_SIZE,
+            resize_handle_size: None,
+            corner_size: CORNER_SIZE,
+            edge_double_click_expand: false,
+            window_menu_on_border: true,
             border_radius: BORDER_RADIUS,
+            clip_content: true,
+            scale_with_dpi: true,
+            corner_backdrop: CornerBackdrop::Transparent,
+            shadow_color: None,
+            active_shadow: None,
+            inactive_shadow: None,
+            border_color: None,
+            resizable: true,
+            resize_edges: Edges::all(true),
+            size_constraints: WindowSizeConstraints::default(),
+            on_resize_start: None,
+            on_resize_end: None,
+            on_resizing: None,
+            on_edge_hover: None,
+            modifier_move: None,
+            modifier_resize: None,
+            snap_preview: false,
             children: Vec::new(),
         }
     }
@@ -63,39 +406,956 @@ impl WindowBorder {
         self
     }
 
-    /// Set the corner radius for the visible window frame.
-    ///
-    /// Default: [`BORDER_RADIUS`]
-    pub fn border_radius(mut self, radius: impl Into<Pixels>) -> Self {
-        self.border_radius = radius.into();
-        self
+    /// Set the size of the resize handle outside the visible frame, independent of the shadow size.
+    ///
+    /// The handle is clamped to the shadow padding, and reaches [`RESIZE_HIT_SIZE`] into the
+    /// content. This takes precedence over [`WindowBorder::resize_hit_size`].
+    ///
+    /// Default: None, uses the [`WindowBorder::resize_hit_size`] band.
+    pub fn resize_handle_size(mut self, size: impl Into<Pixels>) -> Self {
+        self.resize_handle_size = Some(size.into());
+        self
+    }
+
+    /// Set the distance from the corners of the visible frame where the diagonal resize wins
+    /// over the adjacent edges.
+    ///
+    /// This only extends the corners along the resize band, so the content inside the corners
+    /// never starts a resize beyond the band.
+    ///
+    /// Default: [`CORNER_SIZE`]
+    pub fn corner_size(mut self, size: impl Into<Pixels>) -> Self {
+        self.corner_size = size.into();
+        self
+    }
+
+    /// Returns the resize edge at the position in a window of the size, where the visible frame
+    /// is inset by the `insets`.
+    ///
+    /// This is the hit test used by the window border, with the default resize band and corner
+    /// size. A side with a zero inset is treated as tiled, and is never a resize edge.
+    pub fn resize_edge(
+        pos: Point<Pixels>,
+        size: Size<Pixels>,
+        insets: Edges<Pixels>,
+    ) -> Option<ResizeEdge> {
+        let tiling = Tiling {
+            top: insets.top <= px(0.),
+            bottom: insets.bottom <= px(0.),
+            left: insets.left <= px(0.),
+            right: insets.right <= px(0.),
+        };
+        let handle = ResizeHandle::new(None, RESIZE_HIT_SIZE, px(0.)).corner_size(CORNER_SIZE);
+        resize_edge(pos, size, insets, &tiling, handle)
+    }
+
+    /// Start the keyboard resize mode of the window border rendered in the window.
+    ///
+    /// The arrow keys resize the window in 16px steps, 1px with `shift` and 64px with `ctrl`,
+    /// `enter` or `escape` exits. The mode also exits when the window loses focus.
+    ///
+    /// Bind [`StartKeyboardResize`] to start it by a keystroke, e.g. `alt-f8`.
+    pub fn start_keyboard_resize(window: &mut Window, cx: &mut App) {
+        let window_id = window.window_handle().window_id();
+        let Some(state) = cx
+            .try_global::<GlobalKeyboardResizeStates>()
+            .and_then(|states| states.0.get(&window_id))
+            .and_then(|state| state.upgrade())
+        else {
+            return;
+        };
+
+        state.update(cx, |state, cx| state.start(window, cx));
+    }
+
+    /// Set to expand the window to the edge of the display work area by double clicking the
+    /// edge or the corner of the window, default is false.
+    ///
+    /// The opposite edge stays put, double click the edge again to restore the previous size.
+    /// The top and left edges move the window, so they are only supported on Windows, as GPUI
+    /// can't move the window on the other platforms.
+    pub fn edge_double_click_expand(mut self, expand: bool) -> Self {
+        self.edge_double_click_expand = expand;
+        self
+    }
+
+    /// Set to show the window menu by a right click in the shadow padding or the border, like the
+    /// empty area of the [`crate::TitleBar`], default is true.
+    pub fn window_menu_on_border(mut self, show: bool) -> Self {
+        self.window_menu_on_border = show;
+        self
+    }
+
+    /// Set the corner radius for the visible window frame.
+    ///
+    /// Default: [`BORDER_RADIUS`]
+    pub fn border_radius(mut self, radius: impl Into<Pixels>) -> Self {
+        self.border_radius = radius.into();
+        self
+    }
+
+    /// Set to clip the content to the visible window frame, default is true.
+    ///
+    /// Without the clip, the content can paint into the shadow padding, e.g. a popover that
+    /// slightly extends past the frame, but never past the OS window bounds. The content is
+    /// always clipped when [`WindowBorder::border_radius`] is set, to keep the rounded corners.
+    pub fn clip_content(mut self, clip: bool) -> Self {
+        self.clip_content = clip;
+        self
+    }
+
+    /// Set to round the shadow and resize handle sizes to whole device pixels of the display,
+    /// default is true.
+    ///
+    /// The sizes stay in logical pixels, like the rest of the layout, only the fraction of a
+    /// device pixel is rounded away on the fractional scale factors. The border is always
+    /// rounded to whole device pixels, at least one device pixel.
+    pub fn scale_with_dpi(mut self, scale: bool) -> Self {
+        self.scale_with_dpi = scale;
+        self
+    }
+
+    /// Set the background behind the rounded corners and the shadow padding.
+    ///
+    /// Default: [`CornerBackdrop::Transparent`]
+    pub fn corner_backdrop(mut self, backdrop: CornerBackdrop) -> Self {
+        self.corner_backdrop = backdrop;
+        self
+    }
+
+    /// Set the shadow color, this overrides the `window.shadow` theme color.
+    ///
+    /// Default: None, uses the [`crate::ThemeColor::window_shadow`].
+    pub fn shadow_color(mut self, color: impl Into<Hsla>) -> Self {
+        self.shadow_color = Some(color.into());
+        self
+    }
+
+    /// Set the shadow of the active window.
+    ///
+    /// Default: full opacity of the shadow color, the blur radius is half of the shadow size.
+    pub fn active_shadow(mut self, shadow: WindowShadow) -> Self {
+        self.active_shadow = Some(shadow);
+        self
+    }
+
+    /// Set the shadow of the inactive window.
+    ///
+    /// Default: half opacity of the shadow color, the blur radius is a third of the shadow size.
+    pub fn inactive_shadow(mut self, shadow: WindowShadow) -> Self {
+        self.inactive_shadow = Some(shadow);
+        self
+    }
+
+    /// Set the border color, this overrides the `window.border` and `window.border.active`
+    /// theme colors.
+    ///
+    /// Default: None, uses the [`crate::ThemeColor::window_border_active`] when the window is
+    /// active, otherwise the [`crate::ThemeColor::window_border`].
+    pub fn border_color(mut self, color: impl Into<Hsla>) -> Self {
+        self.border_color = Some(color.into());
+        self
+    }
+
+    /// Set whether the window can be resized by dragging the border, default is true.
+    ///
+    /// When false, the shadow and border are still rendered, but no resize hit zones are inserted.
+    pub fn resizable(mut self, resizable: bool) -> Self {
+        self.resizable = resizable;
+        self
+    }
+
+    /// Set the sides of the window that can be resized, default is all sides.
+    ///
+    /// Only the [`ResizeEdge::Top`], [`ResizeEdge::Bottom`], [`ResizeEdge::Left`] and
+    /// [`ResizeEdge::Right`] sides are used, a corner is resizable when both of its adjacent
+    /// sides are allowed.
+    pub fn resize_edges(mut self, edges: &[ResizeEdge]) -> Self {
+        self.resize_edges = Edges {
+            top: edges.contains(&ResizeEdge::Top),
+            bottom: edges.contains(&ResizeEdge::Bottom),
+            left: edges.contains(&ResizeEdge::Left),
+            right: edges.contains(&ResizeEdge::Right),
+        };
+        self
+    }
+
+    /// Set the min size of the visible window frame.
+    ///
+    /// GPUI only supports the `window_min_size` when opening a window, so the window is resized
+    /// back when the frame is smaller, and the sides are not resizable if the min size is not
+    /// less than the max size. Use [`WindowSizeConstraints::of`] to query it.
+    pub fn min_size(mut self, size: impl Into<Size<Pixels>>) -> Self {
+        self.size_constraints.min_size = Some(size.into());
+        self
+    }
+
+    /// Set the max size of the visible window frame, the window can't be maximized by the
+    /// [`crate::TitleBar`] when this is set.
+    ///
+    /// See also [`WindowBorder::min_size`].
+    pub fn max_size(mut self, size: impl Into<Size<Pixels>>) -> Self {
+        self.size_constraints.max_size = Some(size.into());
+        self
+    }
+
+    /// Set the callback when a resize is started by dragging the border.
+    pub fn on_resize_start(mut self, f: impl Fn(&mut Window, &mut App) + 'static) -> Self {
+        self.on_resize_start = Some(Rc::new(f));
+        self
+    }
+
+    /// Set the callback when a resize started by dragging the border is ended, the argument is
+    /// the final window bounds.
+    ///
+    /// The platform doesn't report the end of the drag, so this is called once the window
+    /// bounds stop changing for a short delay.
+    pub fn on_resize_end(
+        mut self,
+        f: impl Fn(&Bounds<Pixels>, &mut Window, &mut App) + 'static,
+    ) -> Self {
+        self.on_resize_end = Some(Rc::new(f));
+        self
+    }
+
+    /// Set the callback when the window size changes during a resize, throttled to at most
+    /// one call per 50ms.
+    pub fn on_resizing(
+        mut self,
+        f: impl Fn(&Size<Pixels>, &mut Window, &mut App) + 'static,
+    ) -> Self {
+        self.on_resizing = Some(Rc::new(f));
+        self
+    }
+
+    /// Set the callback when the hovered resize edge changes, e.g. to show an edge glow.
+    ///
+    /// The argument is None when the mouse leaves the resize edges.
+    pub fn on_edge_hover(
+        mut self,
+        f: impl Fn(&Option<ResizeEdge>, &mut Window, &mut App) + 'static,
+    ) -> Self {
+        self.on_edge_hover = Some(Rc::new(f));
+        self
+    }
+
+    /// Set the modifiers to move the window by a left mouse down anywhere in the content,
+    /// e.g. `Modifiers::alt()`, default is None.
+    ///
+    /// The mouse down is not propagated to the content when the modifiers are held.
+    pub fn modifier_move(mut self, modifiers: Modifiers) -> Self {
+        self.modifier_move = Some(modifiers);
+        self
+    }
+
+    /// Set the modifiers to resize the window by a middle mouse down anywhere in the content,
+    /// default is None.
+    ///
+    /// The resize edge is picked by which third of the window the cursor is in, the center
+    /// resizes from the bottom right corner.
+    pub fn modifier_resize(mut self, modifiers: Modifiers) -> Self {
+        self.modifier_resize = Some(modifiers);
+        self
+    }
+
+    /// Set to preview the snap of the window when moved by the [`crate::TitleBar`] to the top
+    /// edge of the work area, default is false.
+    ///
+    /// Releasing the mouse at the top edge maximizes the window. The half and quarter snaps are
+    /// not supported, as GPUI can't move the window. The preview is not used when the window
+    /// can't be maximized, or when the compositor snaps the window natively, e.g. it reports the
+    /// tiled states, or doesn't report the window position during the move like Wayland.
+    pub fn snap_preview(mut self, snap_preview: bool) -> Self {
+        self.snap_preview = snap_preview;
+        self
+    }
+
+    /// Returns the color of the border, the [`WindowBorder::border_color`] or the theme color of
+    /// the focused or unfocused window.
+    fn resolved_border_color(&self, is_active: bool, colors: &crate::ThemeColor) -> Hsla {
+        self.border_color.unwrap_or(if is_active {
+            colors.window_border_active
+        } else {
+            colors.window_border
+        })
+    }
+}
+
+/// Tracks a resize started from the border, for the resize callbacks of the [`WindowBorder`].
+#[derive(Default)]
+struct ResizeState {
+    resizing: bool,
+    last_bounds: Option<Bounds<Pixels>>,
+    last_resizing_at: Option<Instant>,
+    on_resize_end: Option<Rc<dyn Fn(&Bounds<Pixels>, &mut Window, &mut App)>>,
+    on_resizing: Option<Rc<dyn Fn(&Size<Pixels>, &mut Window, &mut App)>>,
+    hovered_edge: Option<ResizeEdge>,
+    /// The window width and height before expanding to the work area by an edge double click.
+    restore_width: Option<Pixels>,
+    restore_height: Option<Pixels>,
+    on_edge_hover: Option<Rc<dyn Fn(&Option<ResizeEdge>, &mut Window, &mut App)>>,
+    _end_task: Option<Task<()>>,
+}
+
+impl ResizeState {
+    fn start(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        self.resizing = true;
+        self.last_bounds = Some(window.bounds());
+        self.last_resizing_at = None;
+        self.schedule_end(window, cx);
+    }
+
+    /// Called on every render, the window is refreshed when its bounds change.
+    fn observe_bounds(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let bounds = window.bounds();
+        if !self.resizing || self.last_bounds == Some(bounds) {
+            return;
+        }
+
+        self.last_bounds = Some(bounds);
+        if self.should_notify_resizing(Instant::now())
+            && let Some(on_resizing) = self.on_resizing.clone()
+        {
+            on_resizing(&bounds.size, window, cx);
+        }
+        self.schedule_end(window, cx);
+    }
+
+    /// Returns the window bounds after toggling the expand of the edge to the work area, None if
+    /// the edge can't be expanded.
+    ///
+    /// The `insets` are the shadow paddings, so the visible frame touches the work area edge.
+    /// The left and top edges move the origin so that the opposite edge stays put, they are
+    /// only expanded if `can_move`.
+    fn toggle_expand(
+        &mut self,
+        edge: ResizeEdge,
+        bounds: Bounds<Pixels>,
+        work_area: Bounds<Pixels>,
+        insets: Edges<Pixels>,
+        can_move: bool,
+    ) -> Option<Bounds<Pixels>> {
+        let left = matches!(
+            edge,
+            ResizeEdge::Left | ResizeEdge::TopLeft | ResizeEdge::BottomLeft
+        );
+        let right = matches!(
+            edge,
+            ResizeEdge::Right | ResizeEdge::TopRight | ResizeEdge::BottomRight
+        );
+        let top = matches!(
+            edge,
+            ResizeEdge::Top | ResizeEdge::TopLeft | ResizeEdge::TopRight
+        );
+        let bottom = matches!(
+            edge,
+            ResizeEdge::Bottom | ResizeEdge::BottomLeft | ResizeEdge::BottomRight
+        );
+        if (left || top) && !can_move {
+            return None;
+        }
+
+        let mut expanded = bounds;
+        if left || right {
+            expanded.size.width = match self.restore_width.take() {
+                Some(width) => width,
+                None => {
+                    self.restore_width = Some(bounds.size.width);
+                    if left {
+                        bounds.right() - work_area.left() + insets.left
+                    } else {
+                        work_area.right() - bounds.left() + insets.right
+                    }
+                }
+            };
+            if left {
+                expanded.origin.x = bounds.right() - expanded.size.width;
+            }
+        }
+        if top || bottom {
+            expanded.size.height = match self.restore_height.take() {
+                Some(height) => height,
+                None => {
+                    self.restore_height = Some(bounds.size.height);
+                    if top {
+                        bounds.bottom() - work_area.top() + insets.top
+                    } else {
+                        work_area.bottom() - bounds.top() + insets.bottom
+                    }
+                }
+            };
+            if top {
+                expanded.origin.y = bounds.bottom() - expanded.size.height;
+            }
+        }
+        Some(expanded)
+    }
+
+    fn set_hovered_edge(
+        &mut self,
+        edge: Option<ResizeEdge>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if self.hovered_edge == edge {
+            return;
+        }
+
+        self.hovered_edge = edge;
+        if let Some(on_edge_hover) = self.on_edge_hover.clone() {
+            on_edge_hover(&edge, window, cx);
+        }
+    }
+
+    fn should_notify_resizing(&mut self, now: Instant) -> bool {
+        if self
+            .last_resizing_at
+            .is_some_and(|last| now.duration_since(last) < RESIZING_THROTTLE)
+        {
+            return false;
+        }
+
+        self.last_resizing_at = Some(now);
+        true
+    }
+
+    /// Debounce the end of the resize, the task is replaced on every bounds change.
+    fn schedule_end(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        self._end_task = Some(cx.spawn_in(window, async move |this, cx| {
+            cx.background_executor().timer(RESIZE_END_DELAY).await;
+            _ = this.update_in(cx, |this, window, cx| this.end(window, cx));
+        }));
+    }
+
+    fn end(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        self.resizing = false;
+        self.last_resizing_at = None;
+        self._end_task = None;
+        if let Some(on_resize_end) = self.on_resize_end.clone() {
+            on_resize_end(&window.bounds(), window, cx);
+        }
+    }
+}
+
+#[derive(Default)]
+struct GlobalKeyboardResizeStates(HashMap<WindowId, WeakEntity<KeyboardResizeState>>);
+
+impl Global for GlobalKeyboardResizeStates {}
+
+/// The keyboard resize mode of the [`WindowBorder`], see [`WindowBorder::start_keyboard_resize`].
+struct KeyboardResizeState {
+    focus_handle: FocusHandle,
+    active: bool,
+    previous_focus: Option<WeakFocusHandle>,
+    size_constraints: WindowSizeConstraints,
+    resize_edges: Edges<bool>,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl KeyboardResizeState {
+    fn new(cx: &mut Context<Self>) -> Self {
+        Self {
+            focus_handle: cx.focus_handle(),
+            active: false,
+            previous_focus: None,
+            size_constraints: WindowSizeConstraints::default(),
+            resize_edges: Edges::all(true),
+            _subscriptions: Vec::new(),
+        }
+    }
+
+    fn start(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        if self.active || !(self.resize_edges.right || self.resize_edges.bottom) {
+            return;
+        }
+
+        self.active = true;
+        self.previous_focus = window.focused(cx).map(|handle| handle.downgrade());
+        self.focus_handle.focus(window, cx);
+        self._subscriptions = vec![
+            cx.on_blur(&self.focus_handle, window, |this, _, cx| this.stop(cx)),
+            observe_window_active(window, cx, |this, window, cx| {
+                if !window.window_active(cx) {
+                    this.stop(cx);
+                }
+            }),
+        ];
+        cx.notify();
+    }
+
+    fn stop(&mut self, cx: &mut Context<Self>) {
+        self.active = false;
+        self.previous_focus = None;
+        self._subscriptions.clear();
+        cx.notify();
+    }
+
+    fn on_key_down(&mut self, event: &KeyDownEvent, window: &mut Window, cx: &mut Context<Self>) {
+        if !self.active {
+            return;
+        }
+
+        cx.stop_propagation();
+        match event.keystroke.key.as_str() {
+            "enter" | "escape" => {
+                let previous_focus = self
+                    .previous_focus
+                    .take()
+                    .and_then(|handle| handle.upgrade());
+                self.stop(cx);
+                if let Some(handle) = previous_focus {
+                    handle.focus(window, cx);
+                }
+            }
+            _ => {
+                let insets = window_paddings(window);
+                let inset_size = Size::new(insets.left + insets.right, insets.top + insets.bottom);
+                let frame_size = window.bounds().size - inset_size;
+                if let Some(size) = keyboard_resize_size(
+                    &event.keystroke,
+                    frame_size,
+                    &self.size_constraints,
+                    &self.resize_edges,
+                ) && size != frame_size
+                {
+                    window.resize(size + inset_size);
+                }
+            }
+        }
+    }
+}
+
+/// Returns the frame size after the keyboard resize step of the keystroke, None if the keystroke
+/// is not an arrow key.
+///
+/// GPUI keeps the window origin, so the arrows move the right and bottom edges.
+fn keyboard_resize_size(
+    keystroke: &Keystroke,
+    size: Size<Pixels>,
+    constraints: &WindowSizeConstraints,
+    resize_edges: &Edges<bool>,
+) -> Option<Size<Pixels>> {
+    let step = if keystroke.modifiers.shift {
+        KEYBOARD_RESIZE_FINE_STEP
+    } else if keystroke.modifiers.control {
+        KEYBOARD_RESIZE_COARSE_STEP
+    } else {
+        KEYBOARD_RESIZE_STEP
+    };
+
+    let mut new_size = size;
+    match keystroke.key.as_str() {
+        "left" if resize_edges.right => new_size.width = (size.width - step).max(px(0.)),
+        "right" if resize_edges.right => new_size.width = size.width + step,
+        "up" if resize_edges.bottom => new_size.height = (size.height - step).max(px(0.)),
+        "down" if resize_edges.bottom => new_size.height = size.height + step,
+        "left" | "right" | "up" | "down" => {}
+        _ => return None,
+    }
+    Some(constraints.clamp(new_size))
+}
+
+/// Per-side inset of the visible frame from the outer window bounds.
+fn client_frame_insets(shadow_size: Pixels, tiling: &Tiling) -> Edges<Pixels> {
+    let mut insets = Edges::all(shadow_size);
+    if tiling.top {
+        insets.top = px(0.0);
+    }
+    if tiling.bottom {
+        insets.bottom = px(0.0);
+    }
+    if tiling.left {
+        insets.left = px(0.0);
+    }
+    if tiling.right {
+        insets.right = px(0.0);
+    }
+    insets
+}
+
+/// Returns the sides that can not be resized, either tiled or not in the allowed `edges`.
+fn locked_sides(tiling: &Tiling, edges: &Edges<bool>) -> Tiling {
+    Tiling {
+        top: tiling.top || !edges.top,
+        bottom: tiling.bottom || !edges.bottom,
+        left: tiling.left || !edges.left,
+        right: tiling.right || !edges.right,
+    }
+}
+
+/// Returns true if the edge, or one of the sides of the corner, can not be resized.
+fn is_edge_locked(edge: ResizeEdge, locked: &Tiling) -> bool {
+    match edge {
+        ResizeEdge::Top => locked.top,
+        ResizeEdge::Bottom => locked.bottom,
+        ResizeEdge::Left => locked.left,
+        ResizeEdge::Right => locked.right,
+        ResizeEdge::TopLeft => locked.top || locked.left,
+        ResizeEdge::TopRight => locked.top || locked.right,
+        ResizeEdge::BottomLeft => locked.bottom || locked.left,
+        ResizeEdge::BottomRight => locked.bottom || locked.right,
+    }
+}
+
+/// Returns the resize edge by which third of the window the position is in, the center is
+/// the bottom right corner.
+fn resize_edge_by_thirds(pos: Point<Pixels>, size: Size<Pixels>) -> ResizeEdge {
+    let left = pos.x < size.width / 3.;
+    let right = pos.x > size.width * 2. / 3.;
+    let top = pos.y < size.height / 3.;
+    let bottom = pos.y > size.height * 2. / 3.;
+
+    match (left, right, top, bottom) {
+        (true, _, true, _) => ResizeEdge::TopLeft,
+        (_, true, true, _) => ResizeEdge::TopRight,
+        (true, _, _, true) => ResizeEdge::BottomLeft,
+        (true, _, _, _) => ResizeEdge::Left,
+        (_, true, false, false) => ResizeEdge::Right,
+        (false, false, true, _) => ResizeEdge::Top,
+        (false, false, _, true) => ResizeEdge::Bottom,
+        _ => ResizeEdge::BottomRight,
+    }
+}
+
+/// Returns the resize edge at the position, None if the window can't be resized there.
+///
+/// This uses the same [`resize_zones`] as the cursor overlay of the rendered frame, so the mouse
+/// down always agrees with the cursor.
+fn hovered_resize_edge(
+    position: Point<Pixels>,
+    window_size: Size<Pixels>,
+    shadow_size: Pixels,
+    tiling: &Tiling,
+    resize_edges: &Edges<bool>,
+    handle: ResizeHandle,
+) -> Option<ResizeEdge> {
+    let insets = client_frame_insets(shadow_size, tiling);
+    resize_edge(
+        position,
+        window_size,
+        insets,
+        &locked_sides(tiling, resize_edges),
+        handle,
+    )
+}
+
+/// Start a window resize from the edge, and run the resize start callback.
+fn start_resize(
+    edge: ResizeEdge,
+    on_resize_start: Option<&Rc<dyn Fn(&mut Window, &mut App)>>,
+    resize_state: &Entity<ResizeState>,
+    window: &mut Window,
+    cx: &mut App,
+) {
+    window.start_window_resize(edge);
+    if let Some(on_resize_start) = on_resize_start {
+        on_resize_start(window, cx);
+    }
+    resize_state.update(cx, |state, cx| state.start(window, cx));
+}
+
+/// Returns true if no side can be resized.
+fn is_fully_locked(locked: &Tiling) -> bool {
+    locked.top && locked.bottom && locked.left && locked.right
+}
+
+/// The resize handle extents around the visible frame edge.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct ResizeHandle {
+    /// The distance outside the visible frame, into the shadow padding.
+    outside: Pixels,
+    /// The distance inside the visible frame, into the content.
+    inside: Pixels,
+    /// The distance from the corner of the visible frame along the band, for the diagonal edges.
+    corner: Pixels,
+}
+
+impl ResizeHandle {
+    fn new(handle_size: Option<Pixels>, hit_size: Pixels, shadow_size: Pixels) -> Self {
+        match handle_size {
+            // Never exceed the padding plus a few px inside the content.
+            Some(size) => Self {
+                outside: size.min(shadow_size),
+                inside: size.min(RESIZE_HIT_SIZE),
+                corner: px(0.),
+            },
+            None => Self {
+                outside: hit_size,
+                inside: hit_size,
+                corner: px(0.),
+            },
+        }
+    }
+
+    /// Set the corner size, the corners are at least the intersection of the bands.
+    fn corner_size(mut self, size: Pixels) -> Self {
+        self.corner = size;
+        self
+    }
+
+    /// Round the handle to whole device pixels at the display scale factor.
+    fn round_to_device_pixels(self, scale_factor: f32) -> Self {
+        Self {
+            outside: round_to_device_pixels(self.outside, scale_factor),
+            inside: round_to_device_pixels(self.inside, scale_factor),
+            corner: round_to_device_pixels(self.corner, scale_factor),
+        }
+    }
+}
+
+/// Move and resize the window from the `from` to the `to` bounds on Windows, GPUI can't move
+/// the window.
+///
+/// The change is applied to the platform window rect, which may include the invisible resize
+/// borders, so only the difference of the bounds is converted to physical pixels.
+#[cfg(target_os = "windows")]
+fn set_window_bounds_win32(window: &mut Window, from: Bounds<Pixels>, to: Bounds<Pixels>) {
+    use raw_window_handle::HasWindowHandle;
+    let scale_factor = window.scale_factor();
+    let physical = |pixels: Pixels| (f32::from(pixels) * scale_factor).round() as i32;
+    if let Ok(handle) = window.window_handle() {
+        if let raw_window_handle::RawWindowHandle::Win32(win32) = handle.as_ref() {
+            unsafe {
+                use windows::Win32::Foundation::*;
+                use windows::Win32::UI::WindowsAndMessaging::*;
+                let hwnd = HWND(win32.hwnd.get() as *mut _);
+                let mut rect = RECT::default();
+                if GetWindowRect(hwnd, &mut rect).is_err() {
+                    return;
+                }
+                let _ = SetWindowPos(
+                    hwnd,
+                    None,
+                    rect.left + physical(to.left() - from.left()),
+                    rect.top + physical(to.top() - from.top()),
+                    rect.right - rect.left + physical(to.size.width - from.size.width),
+                    rect.bottom - rect.top + physical(to.size.height - from.size.height),
+                    SWP_NOZORDER | SWP_NOACTIVATE,
+                );
+            }
+        }
+    }
+}
+
+/// Returns the window decorations, a maximized or fullscreen window is treated as tiled on all
+/// sides, some compositors don't report it as tiled.
+pub(crate) fn window_decorations(window: &Window) -> Decorations {
+    match window.window_decorations() {
+        Decorations::Client { .. } if window.is_maximized() || window.is_fullscreen() => {
+            Decorations::Client {
+                tiling: Tiling {
+                    top: true,
+                    bottom: true,
+                    left: true,
+                    right: true,
+                },
+            }
+        }
+        decorations => decorations,
+    }
+}
+
+/// Get the window paddings.
+///
+/// The paddings follow the [`WindowBorder::shadow_size`] of the window, which is kept as the
+/// window client inset, not the resize handle size.
+pub fn window_paddings(window: &Window) -> Edges<Pixels> {
+    let shadow_size = window.client_inset().unwrap_or(SHADOW_SIZE);
+    match window_decorations(window) {
+        Decorations::Server => Edges::all(px(0.0)),
+        Decorations::Client { tiling } => client_frame_insets(shadow_size, &tiling),
+    }
+}
+
+/// The windows that have rendered a [`WindowBorder`] in the current frame.
+#[derive(Default)]
+struct RenderedWindowBorders {
+    windows: HashSet<WindowId>,
+    warned: bool,
+}
+
+impl Global for RenderedWindowBorders {}
+
+/// Returns false if a window border was already rendered in the window in the current frame,
+/// that is the border is nested in another one.
+fn enter_window_border(window: &mut Window, cx: &mut App) -> bool {
+    let window_id = window.window_handle().window_id();
+    let rendered = cx.default_global::<RenderedWindowBorders>();
+    if !rendered.windows.insert(window_id) {
+        if !rendered.warned {
+            rendered.warned = true;
+            #[cfg(debug_assertions)]
+            {
+                let message = "nested window_border() is rendered as a plain div, the window is \
+                    already wrapped by a WindowBorder or the Root.";
+                log::warn!("WindowBorder: {}", message);
+                #[cfg(feature = "mcp")]
+                crate::mcp::mcp_log(format!("[warn] WindowBorder: {}", message));
+            }
+        }
+        return false;
+    }
+
+    window.on_next_frame(move |_, cx| {
+        cx.default_global::<RenderedWindowBorders>()
+            .windows
+            .remove(&window_id);
+    });
+    true
+}
+
+/// Returns the size rounded to whole device pixels at the scale factor, at least one device pixel.
+fn device_pixel_size(size: Pixels, scale_factor: f32) -> Pixels {
+    px((f32::from(size) * scale_factor).round().max(1.) / scale_factor)
+}
+
+/// Returns the logical size rounded to whole device pixels at the scale factor.
+fn round_to_device_pixels(size: Pixels, scale_factor: f32) -> Pixels {
+    px((f32::from(size) * scale_factor).round() / scale_factor)
+}
+
+/// Returns the shadow size used as the platform client inset, in logical pixels, rounded to
+/// whole device pixels if `round` is set, see [`WindowBorder::scale_with_dpi`].
+fn shadow_inset(shadow_size: Pixels, scale_factor: f32, round: bool) -> Pixels {
+    if round {
+        round_to_device_pixels(shadow_size, scale_factor)
+    } else {
+        shadow_size
+    }
+}
+
+/// Returns the size of the content to fill the window frame, applied over the style of
+/// [`WindowBorder`].
+fn content_size_style(decorations: Decorations) -> StyleRefinement {
+    match decorations {
+        Decorations::Server => StyleRefinement::default().size_full(),
+        Decorations::Client { .. } => StyleRefinement::default()
+            .size_auto()
+            .flex_1()
+            .min_h_0()
+            .min_w_0(),
     }
 }
 
-/// Per-side inset of the visible frame from the outer window bounds.
-fn client_frame_insets(shadow_size: Pixels, tiling: &Tiling) -> Edges<Pixels> {
-    let mut insets = Edges::all(shadow_size);
-    if tiling.top {
-        insets.top = px(0.0);
+/// Returns the clip of the content in the client decorated window frame, the corners that are
+/// not tiled are rounded.
+///
+/// The clip follows the rounded corners, so it can only be opted out without a radius.
+fn content_clip_style(
+    clip_content: bool,
+    border_radius: Pixels,
+    tiling: &Tiling,
+) -> StyleRefinement {
+    let mut style = StyleRefinement::default();
+    if clip_content || border_radius > px(0.) {
+        style = style.overflow_hidden();
     }
-    if tiling.bottom {
-        insets.bottom = px(0.0);
+    if !(tiling.top || tiling.right) {
+        style = style.rounded_tr(border_radius);
     }
-    if tiling.left {
-        insets.left = px(0.0);
+    if !(tiling.top || tiling.left) {
+        style = style.rounded_tl(border_radius);
     }
-    if tiling.right {
-        insets.right = px(0.0);
+    if !(tiling.bottom || tiling.right) {
+        style = style.rounded_br(border_radius);
+    }
+    if !(tiling.bottom || tiling.left) {
+        style = style.rounded_bl(border_radius);
+    }
+    style
+}
+
+/// Per-side inset of the content from the outer window bounds: the shadow padding, the border
+/// and the part of the rounded corners that would clip a rectangle.
+fn client_content_insets(
+    shadow_size: Pixels,
+    border_size: Pixels,
+    border_radius: Pixels,
+    tiling: &Tiling,
+) -> Edges<Pixels> {
+    let mut insets = client_frame_insets(shadow_size, tiling);
+    let frame_inset = border_size + border_radius * (1. - std::f32::consts::FRAC_1_SQRT_2);
+    if !tiling.top {
+        insets.top += frame_inset;
+    }
+    if !tiling.bottom {
+        insets.bottom += frame_inset;
+    }
+    if !tiling.left {
+        insets.left += frame_inset;
+    }
+    if !tiling.right {
+        insets.right += frame_inset;
     }
     insets
 }
 
-/// Get the window paddings.
-pub fn window_paddings(window: &Window) -> Edges<Pixels> {
-    let shadow_size = window.client_inset().unwrap_or(SHADOW_SIZE);
-    match window.window_decorations() {
-        Decorations::Server => Edges::all(px(0.0)),
-        Decorations::Client { tiling } => client_frame_insets(shadow_size, &tiling),
+/// Get the bounds the content of the [`WindowBorder`] occupies, in window coordinates.
+///
+/// This is inside the [`window_paddings`], the 1px border on the sides that are not tiled and,
+/// when [`WindowBorder::border_radius`] is set, the part of the rounded corners that would clip
+/// the content.
+pub fn window_content_bounds(window: &Window, cx: &App) -> Bounds<Pixels> {
+    let window_size = window.window_bounds().get_bounds().size;
+    let insets = match window_decorations(window) {
+        Decorations::Server => return Bounds::new(Point::default(), window_size),
+        Decorations::Client { tiling } => {
+            let shadow_size = window.client_inset().unwrap_or(SHADOW_SIZE);
+            let border_radius = cx
+                .try_global::<GlobalWindowBorderRadius>()
+                .and_then(|radius| radius.0.get(&window.window_handle().window_id()))
+                .copied()
+                .unwrap_or(BORDER_RADIUS);
+            let border_size =
+                device_pixel_size(cx.theme().contrast().border_width(), window.scale_factor());
+            client_content_insets(shadow_size, border_size, border_radius, &tiling)
+        }
+    };
+    content_bounds(window_size, insets)
+}
+
+/// Returns the bounds of the content in the window of the `window_size`, inside the `insets`.
+fn content_bounds(window_size: Size<Pixels>, insets: Edges<Pixels>) -> Bounds<Pixels> {
+    Bounds::new(
+        point(insets.left, insets.top),
+        Size::new(
+            window_size.width - insets.left - insets.right,
+            window_size.height - insets.top - insets.bottom,
+        ),
+    )
+}
+
+/// Returns true if a right click at the `position` shows the window menu, in the shadow padding
+/// or the border around the `content_bounds`, see [`WindowBorder::window_menu_on_border`].
+fn is_window_menu_area(position: Point<Pixels>, content_bounds: Bounds<Pixels>) -> bool {
+    !content_bounds.contains(&position)
+}
+
+/// Convert a point in the [`window_content_bounds`] to the window coordinates.
+pub fn content_to_window_point(
+    position: Point<Pixels>,
+    window: &Window,
+    cx: &App,
+) -> Point<Pixels> {
+    position + window_content_bounds(window, cx).origin
+}
+
+/// Convert a point in the window coordinates to the [`window_content_bounds`].
+pub fn window_to_content_point(
+    position: Point<Pixels>,
+    window: &Window,
+    cx: &App,
+) -> Point<Pixels> {
+    position - window_content_bounds(window, cx).origin
+}
+
+/// The style refines the content of the window, inside the border and the shadow.
+///
+/// The size of the content is protected, it's applied over the style to fill the window frame,
+/// and so are the border and the rounded corners of the frame.
+impl Styled for WindowBorder {
+    fn style(&mut self) -> &mut StyleRefinement {
+        &mut self.style
     }
 }
 
@@ -107,26 +1367,206 @@ impl ParentElement for WindowBorder {
 
 impl RenderOnce for WindowBorder {
     fn render(self, window: &mut Window, cx: &mut App) -> impl IntoElement {
-        let decorations = window.window_decorations();
+        // A nested border renders the children only, the outer one owns the window chrome.
+        if !enter_window_border(window, cx) {
+            return div()
+                .refine_style(&self.style)
+                .refine_style(&content_size_style(Decorations::Server))
+                .children(self.children)
+                .into_any_element();
+        }
+
+        let decorations = window_decorations(window);
         // Keep the platform client inset stable. When the window is tiled on all sides we stop drawing
         // shadow padding, but `set_client_inset` must still use the full shadow size. Clearing it
         // makes the first resize after restore double-count the shadow in `compute_outer_size`, and
         // the window jumps larger.
-        let platform_inset = self.shadow_size;
+        // The window is refreshed when it moves to a display with another scale factor.
+        let scale_factor = window.scale_factor();
+        // The border is thicker in high contrast.
+        let border_size = device_pixel_size(cx.theme().contrast().border_width(), scale_factor);
+        let platform_inset = shadow_inset(self.shadow_size, scale_factor, self.scale_with_dpi);
         let visual_shadow = match decorations {
             Decorations::Client { tiling }
                 if tiling.top && tiling.bottom && tiling.left && tiling.right =>
             {
                 px(0.0)
             }
-            _ => self.shadow_size,
+            _ => platform_inset,
         };
-        let resize_hit_size = self.resize_hit_size;
+        let mut resize_handle = ResizeHandle::new(
+            self.resize_handle_size,
+            self.resize_hit_size,
+            platform_inset,
+        )
+        .corner_size(self.corner_size);
+        if self.scale_with_dpi {
+            resize_handle = resize_handle.round_to_device_pixels(scale_factor);
+        }
         let border_radius = self.border_radius;
+        let clip_content = self.clip_content;
+        let transparent = supports_transparency(window);
+        #[cfg(feature = "mcp")]
+        if self.corner_backdrop == CornerBackdrop::Auto {
+            log_corner_backdrop(transparent, window, cx);
+        }
+        let corner_backdrop = self
+            .corner_backdrop
+            .color(transparent, cx.theme().background);
+        // The window is refreshed when the activation changes, so this follows the focus.
+        let is_active = window.window_active(cx);
+        let border_color = self.resolved_border_color(is_active, cx.theme());
+        let shadow_color = self.shadow_color.unwrap_or(cx.theme().window_shadow);
+        let target_shadow = if is_active {
+            self.active_shadow
+                .unwrap_or(WindowShadow::new(1., visual_shadow / 2.))
+        } else {
+            self.inactive_shadow
+                .unwrap_or(WindowShadow::new(0.5, visual_shadow / 3.))
+        };
+        let (shadow, animating) = window
+            .use_keyed_state("window-border-shadow", cx, |_, _| {
+                ShadowTransition::default()
+            })
+            .update(cx, |transition, _| {
+                transition.shadow(is_active, target_shadow, Instant::now())
+            });
+        if animating {
+            crate::animation::request_animation_frame(window);
+        }
+        let resizable = self.resizable;
+        let size_constraints = self.size_constraints;
+        size_constraints.update(window, cx);
+        update_border_radius(border_radius, window, cx);
+        let resize_edges = size_constraints.resize_edges();
+        let resize_edges = Edges {
+            top: self.resize_edges.top && resize_edges.top,
+            bottom: self.resize_edges.bottom && resize_edges.bottom,
+            left: self.resize_edges.left && resize_edges.left,
+            right: self.resize_edges.right && resize_edges.right,
+        };
         if matches!(decorations, Decorations::Client { .. }) {
             window.set_client_inset(platform_inset);
         }
         let window_size = window.window_bounds().get_bounds().size;
+        let resize_state =
+            window.use_keyed_state("window-border-resize", cx, |_, _| ResizeState::default());
+        resize_state.update(cx, |state, cx| {
+            state.on_resize_end = self.on_resize_end.clone();
+            state.on_resizing = self.on_resizing.clone();
+            state.on_edge_hover = self.on_edge_hover.clone();
+            state.observe_bounds(window, cx);
+        });
+        let keyboard_resize_state =
+            window.use_keyed_state("window-border-keyboard-resize", cx, |_, cx| {
+                KeyboardResizeState::new(cx)
+            });
+        keyboard_resize_state.update(cx, |state, _| {
+            state.size_constraints = size_constraints;
+            state.resize_edges = if resizable {
+                resize_edges
+            } else {
+                Edges::default()
+            };
+        });
+        cx.default_global::<GlobalKeyboardResizeStates>().0.insert(
+            window.window_handle().window_id(),
+            keyboard_resize_state.downgrade(),
+        );
+        let keyboard_resize_active = keyboard_resize_state.read(cx).active;
+        let snap_state =
+            window.use_keyed_state("window-border-snap", cx, |_, _| SnapState::default());
+        snap_state.update(cx, |state, cx| {
+            state.enabled = self.snap_preview;
+            state.observe_bounds(window, cx);
+        });
+        let window_id = window.window_handle().window_id();
+        if !cx
+            .default_global::<GlobalSnapStates>()
+            .0
+            .contains_key(&window_id)
+        {
+            // Drop the states of the closed windows when a new window is rendered.
+            let open_windows = cx
+                .windows()
+                .iter()
+                .map(|window| window.window_id())
+                .collect::<Vec<_>>();
+            cx.global_mut::<GlobalSnapStates>()
+                .0
+                .retain(|window_id, _| open_windows.contains(window_id));
+        }
+        cx.global_mut::<GlobalSnapStates>()
+            .0
+            .insert(window_id, snap_state.downgrade());
+        let snap_moving = snap_state.read(cx).is_moving();
+        let snap_preview_bounds = snap_state.read(cx).preview_bounds(window);
+        let keyboard_resize_focus_handle = keyboard_resize_state.read(cx).focus_handle.clone();
+        // The edge at a position in the rendered frame, for the mouse events.
+        let frame_tiling = match decorations {
+            Decorations::Client { tiling } if resizable => Some(tiling),
+            _ => None,
+        };
+        let edge_at = move |position: Point<Pixels>| {
+            frame_tiling.and_then(|tiling| {
+                hovered_resize_edge(
+                    position,
+                    window_size,
+                    platform_inset,
+                    &tiling,
+                    &resize_edges,
+                    resize_handle,
+                )
+            })
+        };
+        // Track the hovered edge on mouse move, only when it is observed.
+        let hover_resize_state = resize_state.clone();
+        let on_hover_move = self.on_edge_hover.is_some().then(|| {
+            let resize_state = resize_state.clone();
+            move |event: &MouseMoveEvent, window: &mut Window, cx: &mut App| {
+                let edge = edge_at(event.position);
+                resize_state.update(cx, |state, cx| state.set_hovered_edge(edge, window, cx));
+            }
+        });
+        let on_resize_start = self.on_resize_start.clone();
+        let edge_double_click_expand = self.edge_double_click_expand;
+        let window_menu_on_border = self.window_menu_on_border;
+        let (modifier_move, modifier_resize) = (self.modifier_move, self.modifier_resize);
+        let on_modifier_mouse_down = {
+            let on_resize_start = on_resize_start.clone();
+            let resize_state = resize_state.clone();
+            move |event: &MouseDownEvent, window: &mut Window, cx: &mut App| match event.button {
+                MouseButton::Left if modifier_move == Some(event.modifiers) => {
+                    cx.stop_propagation();
+                    window.start_window_move();
+                }
+                MouseButton::Middle if resizable && modifier_resize == Some(event.modifiers) => {
+                    cx.stop_propagation();
+                    let tiling = match window_decorations(window) {
+                        Decorations::Client { tiling } => tiling,
+                        Decorations::Server => Tiling::default(),
+                    };
+                    let size = window.window_bounds().get_bounds().size;
+                    let edge = resize_edge_by_thirds(event.position, size);
+                    if !is_edge_locked(edge, &locked_sides(&tiling, &resize_edges)) {
+                        start_resize(edge, on_resize_start.as_ref(), &resize_state, window, cx);
+                    }
+                }
+                _ => {}
+            }
+        };
+        // Resize the window back into the constraints, the compositor resize is not clamped.
+        if let Decorations::Client { tiling } = decorations
+            && !tiling.is_tiled()
+        {
+            let insets = client_frame_insets(platform_inset, &tiling);
+            let inset_size = Size::new(insets.left + insets.right, insets.top + insets.bottom);
+            let frame_size = window_size - inset_size;
+            let clamped_size = size_constraints.clamp(frame_size);
+            if clamped_size != frame_size {
+                window.resize(clamped_size + inset_size);
+            }
+        }
 
         div()
             .id("window-backdrop")
@@ -137,7 +1577,7 @@ impl RenderOnce for WindowBorder {
                     .flex()
                     .flex_col()
                     .overflow_hidden()
-                    .bg(gpui::transparent_black())
+                    .bg(corner_backdrop)
                     .when(!(tiling.top || tiling.right), |div| {
                         div.rounded_tr(border_radius)
                     })
@@ -154,91 +1594,201 @@ impl RenderOnce for WindowBorder {
                     .when(!tiling.bottom, |div| div.pb(visual_shadow))
                     .when(!tiling.left, |div| div.pl(visual_shadow))
                     .when(!tiling.right, |div| div.pr(visual_shadow))
-                    .on_mouse_down(MouseButton::Left, move |_, window, _| {
-                        let Decorations::Client { tiling } = window.window_decorations() else {
-                            return;
-                        };
-                        if tiling.top && tiling.bottom && tiling.left && tiling.right {
-                            return;
-                        }
-                        let size = window.window_bounds().get_bounds().size;
-                        let pos = window.mouse_position();
-                        let insets = client_frame_insets(platform_inset, &tiling);
-
-                        match resize_edge(pos, size, insets, &tiling, resize_hit_size) {
-                            Some(edge) => window.start_window_resize(edge),
-                            None => {}
-                        };
+                    .when(resizable, |div| {
+                        div.on_mouse_down(MouseButton::Left, move |event, window, cx| {
+                            if let Some(edge) = edge_at(event.position) {
+                                if edge_double_click_expand
+                                    && event.click_count == 2
+                                    && let Some(work_area) =
+                                        window.display(cx).map(|display| display.visible_bounds())
+                                {
+                                    let (bounds, insets) =
+                                        (window.bounds(), window_paddings(window));
+                                    if let Some(expanded) = resize_state.update(cx, |state, _| {
+                                        state.toggle_expand(
+                                            edge,
+                                            bounds,
+                                            work_area,
+                                            insets,
+                                            cfg!(target_os = "windows"),
+                                        )
+                                    }) {
+                                        if expanded.origin == bounds.origin {
+                                            window.resize(expanded.size);
+                                        } else {
+                                            #[cfg(target_os = "windows")]
+                                            set_window_bounds_win32(window, bounds, expanded);
+                                        }
+                                        return;
+                                    }
+                                }
+
+                                start_resize(
+                                    edge,
+                                    on_resize_start.as_ref(),
+                                    &resize_state,
+                                    window,
+                                    cx,
+                                );
+                            }
+                        })
+                    })
+                    .when(window_menu_on_border, |div| {
+                        div.on_mouse_down(MouseButton::Right, |event, window, cx| {
+                            if is_window_menu_area(
+                                event.position,
+                                window_content_bounds(window, cx),
+                            ) {
+                                window.show_window_menu(event.position);
+                            }
+                        })
                     }),
             })
+            .when_some(on_hover_move.clone(), |div, on_hover_move| {
+                div.on_mouse_move(on_hover_move)
+                    .on_hover(move |hovered, window, cx| {
+                        if !hovered {
+                            hover_resize_state
+                                .update(cx, |state, cx| state.set_hovered_edge(None, window, cx));
+                        }
+                    })
+            })
             .size_full()
             .child(
                 div()
                     .cursor(CursorStyle::default())
+                    .bg(gpui::transparent_black())
+                    // The user style comes last, only the window frame is applied over it.
+                    .refine_style(&self.style)
+                    .refine_style(&content_size_style(decorations))
                     .map(|div| match decorations {
-                        Decorations::Server => div.size_full(),
+                        Decorations::Server => div,
                         Decorations::Client { tiling } => div
-                            .flex_1()
-                            .min_h_0()
-                            .min_w_0()
-                            .overflow_hidden()
-                            .when(!(tiling.top || tiling.right), |div| {
-                                div.rounded_tr(border_radius)
-                            })
-                            .when(!(tiling.top || tiling.left), |div| {
-                                div.rounded_tl(border_radius)
-                            })
-                            .when(!(tiling.bottom || tiling.right), |div| {
-                                div.rounded_br(border_radius)
-                            })
-                            .when(!(tiling.bottom || tiling.left), |div| {
-                                div.rounded_bl(border_radius)
-                            })
-                            .border_color(cx.theme().window_border)
-                            .when(!tiling.top, |div| div.border_t(BORDER_SIZE))
-                            .when(!tiling.bottom, |div| div.border_b(BORDER_SIZE))
-                            .when(!tiling.left, |div| div.border_l(BORDER_SIZE))
-                            .when(!tiling.right, |div| div.border_r(BORDER_SIZE))
+                            .refine_style(&content_clip_style(clip_content, border_radius, &tiling))
+                            .border_color(border_color)
+                            .when(!tiling.top, |div| div.border_t(border_size))
+                            .when(!tiling.bottom, |div| div.border_b(border_size))
+                            .when(!tiling.left, |div| div.border_l(border_size))
+                            .when(!tiling.right, |div| div.border_r(border_size))
                             .when(!tiling.is_tiled(), |div| {
                                 div.shadow(vec![gpui::BoxShadow {
-                                    color: Hsla {
-                                        h: 0.,
-                                        s: 0.,
-                                        l: 0.,
-                                        a: 0.3,
-                                    },
-                                    blur_radius: visual_shadow / 2.,
+                                    color: shadow_color.opacity(shadow.opacity),
+                                    blur_radius: shadow.blur_radius,
                                     spread_radius: px(0.),
                                     offset: point(px(0.0), px(0.0)),
                                     inset: false,
                                 }])
                             }),
                     })
-                    .on_mouse_move(|_e, _, cx| {
+                    .on_mouse_move(move |event, window, cx| {
+                        if let Some(on_hover_move) = on_hover_move.as_ref() {
+                            on_hover_move(event, window, cx);
+                        }
                         cx.stop_propagation();
                     })
-                    .bg(gpui::transparent_black())
+                    .when(
+                        modifier_move.is_some() || modifier_resize.is_some(),
+                        |div| div.capture_any_mouse_down(on_modifier_mouse_down),
+                    )
                     .children(self.children),
             )
-            .when(
-                matches!(decorations, Decorations::Client { .. }),
-                |this| {
-                    let Decorations::Client { tiling, .. } = decorations else {
-                        return this;
-                    };
-                    this.child(
-                        div()
-                            .absolute()
-                            .size_full()
-                            .children(resize_hit_zones(
-                                window_size,
-                                platform_inset,
-                                resize_hit_size,
-                                &tiling,
-                            )),
+            .when(snap_moving, |this| {
+                // The compositor grabs the pointer during the move, the first mouse event after
+                // it is the release.
+                this.child(
+                    canvas(
+                        |_, _, _| {},
+                        move |_, _, window, _| {
+                            let snap_state = snap_state.clone();
+                            window.on_mouse_event(
+                                move |event: &MouseMoveEvent, phase, window, cx| {
+                                    if phase.capture() {
+                                        snap_state.update(cx, |state, cx| {
+                                            state.release(event.pressed_button, window, cx)
+                                        });
+                                    }
+                                },
+                            );
+                        },
                     )
-                },
-            )
+                    .absolute()
+                    .size_0(),
+                )
+            })
+            .when_some(snap_preview_bounds, |this, bounds| {
+                this.child(
+                    div()
+                        .absolute()
+                        .left(bounds.origin.x)
+                        .top(bounds.origin.y)
+                        .size(bounds.size)
+                        .rounded(cx.theme().radius)
+                        .border_2()
+                        .border_color(cx.theme().ring.opacity(0.6))
+                        .bg(cx.theme().ring.opacity(0.15)),
+                )
+            })
+            .when(keyboard_resize_active, |this| {
+                let insets = window_paddings(window);
+                let frame_size =
+                    window_size - Size::new(insets.left + insets.right, insets.top + insets.bottom);
+                this.child(
+                    div()
+                        .absolute()
+                        .top_0()
+                        .left_0()
+                        .size_full()
+                        .flex()
+                        .items_center()
+                        .justify_center()
+                        .track_focus(&keyboard_resize_focus_handle)
+                        .on_key_down(move |event, window, cx| {
+                            keyboard_resize_state
+                                .update(cx, |state, cx| state.on_key_down(event, window, cx));
+                        })
+                        .child(
+                            v_flex()
+                                .items_center()
+                                .gap_1()
+                                .px_3()
+                                .py_2()
+                                .rounded(cx.theme().radius)
+                                .border_1()
+                                .border_color(cx.theme().border)
+                                .bg(cx.theme().popover)
+                                .text_color(cx.theme().popover_foreground)
+                                .shadow_lg()
+                                .child(div().text_lg().child(format!(
+                                    "{} × {}",
+                                    f32::from(frame_size.width).round(),
+                                    f32::from(frame_size.height).round()
+                                )))
+                                .child(
+                                    div()
+                                        .text_xs()
+                                        .text_color(cx.theme().muted_foreground)
+                                        .child(t!("WindowBorder.keyboard_resize_hint").to_string()),
+                                ),
+                        ),
+                )
+            })
+            .when(resizable, |this| {
+                // Skip the cursor overlay when no side can be resized, e.g. fully tiled.
+                let Decorations::Client { tiling, .. } = decorations else {
+                    return this;
+                };
+                if is_fully_locked(&locked_sides(&tiling, &resize_edges)) {
+                    return this;
+                }
+                this.child(div().absolute().size_full().children(resize_hit_zones(
+                    window_size,
+                    platform_inset,
+                    resize_handle,
+                    &tiling,
+                    &resize_edges,
+                )))
+            })
+            .into_any_element()
     }
 }
 
@@ -257,96 +1807,143 @@ fn cursor_style_for_resize_edge(edge: ResizeEdge) -> CursorStyle {
 fn resize_hit_zones(
     window_size: Size<Pixels>,
     shadow_size: Pixels,
-    hit_size: Pixels,
+    handle: ResizeHandle,
     tiling: &Tiling,
+    resize_edges: &Edges<bool>,
 ) -> Vec<AnyElement> {
-    if tiling.top && tiling.bottom && tiling.left && tiling.right {
-        return Vec::new();
-    }
-
     let insets = client_frame_insets(shadow_size, tiling);
-    let inner_left = insets.left;
-    let inner_right = window_size.width - insets.right;
-    let inner_top = insets.top;
-    let inner_bottom = window_size.height - insets.bottom;
+    let locked = locked_sides(tiling, resize_edges);
     // Overlay is laid out in the padded content box; convert from window coords.
     let frame_origin = point(insets.left, insets.top);
-    let band = hit_size + hit_size;
-    let span_x = inner_right - inner_left + band;
-    let span_y = inner_bottom - inner_top + band;
-
-    let mut zones: Vec<AnyElement> = Vec::new();
 
-    let mut push_zone = |edge: ResizeEdge, origin: Point<Pixels>, zone_size: Size<Pixels>| {
-        let origin = origin - frame_origin;
-        zones.push(
+    resize_zones(window_size, insets, &locked, handle)
+        .into_iter()
+        .map(|(edge, bounds)| {
+            let origin = bounds.origin - frame_origin;
             div()
                 .absolute()
                 .left(origin.x)
                 .top(origin.y)
-                .w(zone_size.width)
-                .h(zone_size.height)
+                .w(bounds.size.width)
+                .h(bounds.size.height)
                 .cursor(cursor_style_for_resize_edge(edge))
-                .into_any_element(),
-        );
+                .into_any_element()
+        })
+        .collect()
+}
+
+/// Returns the resize zones of the visible frame in window coordinates, a later zone wins where
+/// the zones overlap, like the hitboxes of the cursor overlay.
+///
+/// This is the only hit test of the border, the cursor overlay renders the zones and
+/// [`resize_edge`] finds the zone at a position, so both always agree.
+fn resize_zones(
+    window_size: Size<Pixels>,
+    insets: Edges<Pixels>,
+    tiling: &Tiling,
+    handle: ResizeHandle,
+) -> Vec<(ResizeEdge, Bounds<Pixels>)> {
+    if is_fully_locked(tiling) {
+        return Vec::new();
+    }
+
+    let inner_left = insets.left;
+    let inner_right = window_size.width - insets.right;
+    let inner_top = insets.top;
+    let inner_bottom = window_size.height - insets.bottom;
+    let ResizeHandle {
+        outside, inside, ..
+    } = handle;
+    // Each edge only applies along its corresponding inner-frame segment; it does not extend
+    // along the "extension lines" of the shadow padding.
+    let band = outside + inside;
+    let span_x = inner_right - inner_left + outside + outside;
+    let span_y = inner_bottom - inner_top + outside + outside;
+
+    let mut zones = Vec::new();
+    let mut push_zone = |edge: ResizeEdge, origin: Point<Pixels>, size: Size<Pixels>| {
+        zones.push((edge, Bounds::new(origin, size)));
     };
 
     if !tiling.top {
         push_zone(
             ResizeEdge::Top,
-            point(inner_left - hit_size, inner_top - hit_size),
+            point(inner_left - outside, inner_top - outside),
             Size::new(span_x, band),
         );
     }
     if !tiling.bottom {
         push_zone(
             ResizeEdge::Bottom,
-            point(inner_left - hit_size, inner_bottom - hit_size),
+            point(inner_left - outside, inner_bottom - inside),
             Size::new(span_x, band),
         );
     }
     if !tiling.left {
         push_zone(
             ResizeEdge::Left,
-            point(inner_left - hit_size, inner_top - hit_size),
+            point(inner_left - outside, inner_top - outside),
             Size::new(band, span_y),
         );
     }
     if !tiling.right {
         push_zone(
             ResizeEdge::Right,
-            point(inner_right - hit_size, inner_top - hit_size),
+            point(inner_right - inside, inner_top - outside),
             Size::new(band, span_y),
         );
     }
 
     // Corners are pushed after edge strips so hit-testing prefers them over adjacent edges.
+    // Each corner covers `corner` along both of its bands.
+    let corner = handle.corner.max(inside);
     if !tiling.top && !tiling.left {
+        let origin = point(inner_left - outside, inner_top - outside);
         push_zone(
             ResizeEdge::TopLeft,
-            point(inner_left - hit_size, inner_top - hit_size),
-            Size::new(band, band),
+            origin,
+            Size::new(outside + corner, band),
+        );
+        push_zone(
+            ResizeEdge::TopLeft,
+            origin,
+            Size::new(band, outside + corner),
         );
     }
     if !tiling.top && !tiling.right {
         push_zone(
             ResizeEdge::TopRight,
-            point(inner_right - hit_size, inner_top - hit_size),
-            Size::new(band, band),
+            point(inner_right - corner, inner_top - outside),
+            Size::new(corner + outside, band),
+        );
+        push_zone(
+            ResizeEdge::TopRight,
+            point(inner_right - inside, inner_top - outside),
+            Size::new(band, outside + corner),
         );
     }
     if !tiling.bottom && !tiling.left {
         push_zone(
             ResizeEdge::BottomLeft,
-            point(inner_left - hit_size, inner_bottom - hit_size),
-            Size::new(band, band),
+            point(inner_left - outside, inner_bottom - inside),
+            Size::new(outside + corner, band),
+        );
+        push_zone(
+            ResizeEdge::BottomLeft,
+            point(inner_left - outside, inner_bottom - corner),
+            Size::new(band, corner + outside),
         );
     }
     if !tiling.bottom && !tiling.right {
         push_zone(
             ResizeEdge::BottomRight,
-            point(inner_right - hit_size, inner_bottom - hit_size),
-            Size::new(band, band),
+            point(inner_right - corner, inner_bottom - inside),
+            Size::new(corner + outside, band),
+        );
+        push_zone(
+            ResizeEdge::BottomRight,
+            point(inner_right - inside, inner_bottom - corner),
+            Size::new(band, corner + outside),
         );
     }
 
@@ -359,54 +1956,661 @@ fn resize_edge(
     size: Size<Pixels>,
     insets: Edges<Pixels>,
     tiling: &Tiling,
-    hit_size: Pixels,
+    handle: ResizeHandle,
 ) -> Option<ResizeEdge> {
-    let inner_left = insets.left;
-    let inner_right = size.width - insets.right;
-    let inner_top = insets.top;
-    let inner_bottom = size.height - insets.bottom;
-
-    // Each edge only applies along its corresponding inner-frame segment; it does not extend along the "extension lines" of the shadow padding.
-    let on_left = pos.x >= inner_left - hit_size
-        && pos.x <= inner_left + hit_size
-        && pos.y >= inner_top - hit_size
-        && pos.y <= inner_bottom + hit_size;
-    let on_right = pos.x >= inner_right - hit_size
-        && pos.x <= inner_right + hit_size
-        && pos.y >= inner_top - hit_size
-        && pos.y <= inner_bottom + hit_size;
-    let on_top = pos.y >= inner_top - hit_size
-        && pos.y <= inner_top + hit_size
-        && pos.x >= inner_left - hit_size
-        && pos.x <= inner_right + hit_size;
-    let on_bottom = pos.y >= inner_bottom - hit_size
-        && pos.y <= inner_bottom + hit_size
-        && pos.x >= inner_left - hit_size
-        && pos.x <= inner_right + hit_size;
-
-    if !tiling.top && !tiling.left && on_top && on_left {
-        return Some(ResizeEdge::TopLeft);
-    }
-    if !tiling.top && !tiling.right && on_top && on_right {
-        return Some(ResizeEdge::TopRight);
-    }
-    if !tiling.bottom && !tiling.left && on_bottom && on_left {
-        return Some(ResizeEdge::BottomLeft);
-    }
-    if !tiling.bottom && !tiling.right && on_bottom && on_right {
-        return Some(ResizeEdge::BottomRight);
-    }
-    if !tiling.top && on_top {
-        return Some(ResizeEdge::Top);
-    }
-    if !tiling.bottom && on_bottom {
-        return Some(ResizeEdge::Bottom);
-    }
-    if !tiling.left && on_left {
-        return Some(ResizeEdge::Left);
-    }
-    if !tiling.right && on_right {
-        return Some(ResizeEdge::Right);
-    }
-    None
+    // The topmost zone wins, with the same containment as the hitboxes of the zones.
+    resize_zones(size, insets, tiling, handle)
+        .into_iter()
+        .rev()
+        .find(|(_, bounds)| bounds.contains(&pos))
+        .map(|(edge, _)| edge)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gpui::size;
+
+    #[test]
+    fn test_resize_handle_size() {
+        let tiling = Tiling::default();
+        let insets = Edges::all(px(20.));
+        let window_size = size(px(400.), px(300.));
+
+        // Clamped to the shadow padding outside, and a few px inside the content.
+        let handle = ResizeHandle::new(Some(px(30.)), RESIZE_HIT_SIZE, px(20.));
+        assert_eq!(handle.outside, px(20.));
+        assert_eq!(handle.inside, RESIZE_HIT_SIZE);
+
+        let handle = ResizeHandle::new(Some(px(12.)), RESIZE_HIT_SIZE, px(20.));
+        let edge =
+            |x: f32, y: f32| resize_edge(point(px(x), px(y)), window_size, insets, &tiling, handle);
+        assert_eq!(edge(9., 150.), Some(ResizeEdge::Left));
+        assert_eq!(edge(7., 150.), None);
+        assert_eq!(edge(23., 150.), Some(ResizeEdge::Left));
+        assert_eq!(edge(25., 150.), None);
+        assert_eq!(edge(10., 10.), Some(ResizeEdge::TopLeft));
+
+        // Without a handle size, the symmetric hit band is used.
+        let handle = ResizeHandle::new(None, RESIZE_HIT_SIZE, px(20.));
+        assert_eq!(handle.outside, RESIZE_HIT_SIZE);
+        assert_eq!(handle.inside, RESIZE_HIT_SIZE);
+    }
+
+    #[test]
+    fn test_resize_zones_agree_with_mouse_down() {
+        let window_size = size(px(400.), px(300.));
+        let shadow_size = px(20.);
+        let tilings = [
+            Tiling::default(),
+            Tiling {
+                top: true,
+                ..Default::default()
+            },
+            Tiling {
+                left: true,
+                bottom: true,
+                ..Default::default()
+            },
+        ];
+        let handles = [
+            ResizeHandle::new(None, RESIZE_HIT_SIZE, shadow_size).corner_size(CORNER_SIZE),
+            ResizeHandle::new(Some(px(12.)), RESIZE_HIT_SIZE, shadow_size),
+        ];
+
+        for tiling in &tilings {
+            for handle in handles {
+                let insets = client_frame_insets(shadow_size, tiling);
+                let zones = resize_zones(window_size, insets, tiling, handle);
+                assert!(!zones.is_empty());
+                // The edge of the topmost zone, as the cursor overlay shows it.
+                let cursor_edge = |pos: Point<Pixels>| {
+                    zones
+                        .iter()
+                        .rev()
+                        .find(|(_, bounds)| bounds.contains(&pos))
+                        .map(|(edge, _)| *edge)
+                };
+
+                for (_, bounds) in &zones {
+                    let (left, top) = (bounds.left(), bounds.top());
+                    let (right, bottom) = (bounds.right(), bounds.bottom());
+                    let (center_x, center_y) = (bounds.center().x, bounds.center().y);
+                    // Points on every boundary line of the zone, and just inside of it.
+                    let points = [
+                        point(left, top),
+                        point(center_x, top),
+                        point(right, top),
+                        point(left, center_y),
+                        point(right, center_y),
+                        point(left, bottom),
+                        point(center_x, bottom),
+                        point(right, bottom),
+                        point(right - px(0.5), bottom - px(0.5)),
+                    ];
+                    for pos in points {
+                        let edge = hovered_resize_edge(
+                            pos,
+                            window_size,
+                            shadow_size,
+                            tiling,
+                            &Edges::all(true),
+                            handle,
+                        );
+                        assert_eq!(edge, cursor_edge(pos), "at {:?}", pos);
+                    }
+                    // Where the cursor shows a resize arrow, the mouse down starts a resize.
+                    assert!(
+                        hovered_resize_edge(
+                            point(left, top),
+                            window_size,
+                            shadow_size,
+                            tiling,
+                            &Edges::all(true),
+                            handle,
+                        )
+                        .is_some()
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_public_resize_edge() {
+        let window_size = size(px(200.), px(100.));
+        let edge = |x: f32, y: f32| {
+            WindowBorder::resize_edge(point(px(x), px(y)), window_size, Edges::all(px(10.)))
+        };
+
+        // The visible frame is from (10, 10) to (190, 90).
+        assert_eq!(edge(100., 8.), Some(ResizeEdge::Top));
+        assert_eq!(edge(100., 92.), Some(ResizeEdge::Bottom));
+        assert_eq!(edge(8., 50.), Some(ResizeEdge::Left));
+        assert_eq!(edge(192., 50.), Some(ResizeEdge::Right));
+        assert_eq!(edge(8., 8.), Some(ResizeEdge::TopLeft));
+        assert_eq!(edge(192., 8.), Some(ResizeEdge::TopRight));
+        assert_eq!(edge(8., 92.), Some(ResizeEdge::BottomLeft));
+        assert_eq!(edge(192., 92.), Some(ResizeEdge::BottomRight));
+        assert_eq!(edge(100., 50.), None);
+        assert_eq!(edge(2., 50.), None);
+
+        // Corners win within the corner size along the bands.
+        assert_eq!(edge(8., 30.), Some(ResizeEdge::TopLeft));
+        assert_eq!(edge(30., 8.), Some(ResizeEdge::TopLeft));
+        assert_eq!(edge(170., 92.), Some(ResizeEdge::BottomRight));
+        assert_eq!(edge(8., 40.), Some(ResizeEdge::Left));
+
+        // The bands include the start and exclude the end on every side.
+        assert_eq!(edge(6., 50.), Some(ResizeEdge::Left));
+        assert_eq!(edge(14., 50.), None);
+        assert_eq!(edge(186., 50.), Some(ResizeEdge::Right));
+        assert_eq!(edge(194., 50.), None);
+        assert_eq!(edge(100., 6.), Some(ResizeEdge::Top));
+        assert_eq!(edge(100., 14.), None);
+        assert_eq!(edge(100., 86.), Some(ResizeEdge::Bottom));
+        assert_eq!(edge(100., 94.), None);
+
+        // A zero inset side is tiled, and not resizable.
+        let insets = Edges {
+            top: px(0.),
+            ..Edges::all(px(10.))
+        };
+        let edge =
+            |x: f32, y: f32| WindowBorder::resize_edge(point(px(x), px(y)), window_size, insets);
+        assert_eq!(edge(100., 2.), None);
+        assert_eq!(edge(8., 2.), Some(ResizeEdge::Left));
+        assert_eq!(edge(8., 92.), Some(ResizeEdge::BottomLeft));
+        assert_eq!(
+            WindowBorder::resize_edge(point(px(0.), px(50.)), window_size, Edges::default()),
+            None
+        );
+    }
+
+    #[test]
+    fn test_resize_corner_size() {
+        let tiling = Tiling::default();
+        let insets = Edges::all(px(20.));
+        let window_size = size(px(400.), px(300.));
+        let handle = ResizeHandle::new(None, RESIZE_HIT_SIZE, px(20.)).corner_size(CORNER_SIZE);
+        let edge =
+            |x: f32, y: f32| resize_edge(point(px(x), px(y)), window_size, insets, &tiling, handle);
+
+        // Along the bands within the corner size, the diagonal edge wins.
+        assert_eq!(edge(18., 40.), Some(ResizeEdge::TopLeft));
+        assert_eq!(edge(40., 18.), Some(ResizeEdge::TopLeft));
+        assert_eq!(edge(382., 270.), Some(ResizeEdge::BottomRight));
+        assert_eq!(edge(360., 282.), Some(ResizeEdge::BottomRight));
+        assert_eq!(edge(18., 50.), Some(ResizeEdge::Left));
+        // The content inside the corner beyond the band is never a resize edge.
+        assert_eq!(edge(30., 30.), None);
+
+        // Without a corner size, the corners are the intersection of the bands.
+        let handle = ResizeHandle::new(None, RESIZE_HIT_SIZE, px(20.));
+        let edge =
+            |x: f32, y: f32| resize_edge(point(px(x), px(y)), window_size, insets, &tiling, handle);
+        assert_eq!(edge(18., 40.), Some(ResizeEdge::Left));
+        assert_eq!(edge(18., 18.), Some(ResizeEdge::TopLeft));
+    }
+
+    #[test]
+    fn test_resize_edges() {
+        let border = WindowBorder::new().resize_edges(&[ResizeEdge::Bottom, ResizeEdge::Right]);
+        let locked = locked_sides(&Tiling::default(), &border.resize_edges);
+        assert!(locked.top && locked.left);
+        assert!(!locked.bottom && !locked.right);
+
+        let insets = Edges::all(px(20.));
+        let window_size = size(px(400.), px(300.));
+        let handle = ResizeHandle::new(None, RESIZE_HIT_SIZE, px(20.));
+        let edge =
+            |x: f32, y: f32| resize_edge(point(px(x), px(y)), window_size, insets, &locked, handle);
+        assert_eq!(edge(20., 150.), None);
+        assert_eq!(edge(200., 20.), None);
+        assert_eq!(edge(20., 280.), Some(ResizeEdge::Bottom));
+        assert_eq!(edge(380., 20.), Some(ResizeEdge::Right));
+        assert_eq!(edge(380., 280.), Some(ResizeEdge::BottomRight));
+
+        // Tiled sides stay locked even when allowed.
+        let tiling = Tiling {
+            right: true,
+            ..Default::default()
+        };
+        assert!(locked_sides(&tiling, &border.resize_edges).right);
+        assert!(!is_fully_locked(&locked));
+        assert!(is_fully_locked(&locked_sides(
+            &tiling,
+            &WindowBorder::new()
+                .resize_edges(&[ResizeEdge::Right])
+                .resize_edges
+        )));
+
+        assert!(!WindowBorder::new().resizable(false).resizable);
+    }
+
+    #[test]
+    fn test_window_size_constraints() {
+        let constraints = WindowSizeConstraints::default();
+        assert!(constraints.can_maximize());
+        assert_eq!(
+            constraints.clamp(size(px(10.), px(10.))),
+            size(px(10.), px(10.))
+        );
+        assert_eq!(constraints.resize_edges(), Edges::all(true));
+
+        let border = WindowBorder::new()
+            .min_size(size(px(200.), px(100.)))
+            .max_size(size(px(800.), px(100.)));
+        let constraints = border.size_constraints;
+        assert!(!constraints.can_maximize());
+        assert_eq!(
+            constraints.clamp(size(px(100.), px(50.))),
+            size(px(200.), px(100.))
+        );
+        assert_eq!(
+            constraints.clamp(size(px(900.), px(300.))),
+            size(px(800.), px(100.))
+        );
+
+        // The height is fixed, so only the left and right sides are resizable.
+        let edges = constraints.resize_edges();
+        assert!(edges.left && edges.right);
+        assert!(!edges.top && !edges.bottom);
+    }
+
+    #[test]
+    fn test_modifier_resize_edge() {
+        let border = WindowBorder::new()
+            .modifier_move(Modifiers::alt())
+            .modifier_resize(Modifiers::alt());
+        assert_eq!(border.modifier_move, Some(Modifiers::alt()));
+        assert_eq!(border.modifier_resize, Some(Modifiers::alt()));
+
+        let window_size = size(px(300.), px(300.));
+        let edge = |x: f32, y: f32| resize_edge_by_thirds(point(px(x), px(y)), window_size);
+        assert_eq!(edge(10., 10.), ResizeEdge::TopLeft);
+        assert_eq!(edge(150., 10.), ResizeEdge::Top);
+        assert_eq!(edge(290., 10.), ResizeEdge::TopRight);
+        assert_eq!(edge(10., 150.), ResizeEdge::Left);
+        assert_eq!(edge(150., 150.), ResizeEdge::BottomRight);
+        assert_eq!(edge(290., 150.), ResizeEdge::Right);
+        assert_eq!(edge(10., 290.), ResizeEdge::BottomLeft);
+        assert_eq!(edge(150., 290.), ResizeEdge::Bottom);
+        assert_eq!(edge(290., 290.), ResizeEdge::BottomRight);
+
+        let locked = Tiling {
+            top: true,
+            ..Default::default()
+        };
+        assert!(is_edge_locked(ResizeEdge::TopRight, &locked));
+        assert!(!is_edge_locked(ResizeEdge::BottomRight, &locked));
+    }
+
+    #[gpui::test]
+    fn test_resize_callbacks(cx: &mut gpui::TestAppContext) {
+        let events = Rc::new(std::cell::RefCell::new(Vec::new()));
+        let border = WindowBorder::new()
+            .on_resize_start({
+                let events = events.clone();
+                move |_, _| events.borrow_mut().push("start")
+            })
+            .on_resize_end({
+                let events = events.clone();
+                move |_, _, _| events.borrow_mut().push("end")
+            });
+        let cx = cx.add_empty_window();
+        let resize_state = cx.update(|_, cx| {
+            cx.new(|_| ResizeState {
+                on_resize_end: border.on_resize_end.clone(),
+                ..Default::default()
+            })
+        });
+
+        cx.update(|window, cx| {
+            start_resize(
+                ResizeEdge::Right,
+                border.on_resize_start.as_ref(),
+                &resize_state,
+                window,
+                cx,
+            )
+        });
+        assert_eq!(*events.borrow(), ["start"]);
+        assert!(resize_state.read_with(cx, |state, _| state.resizing));
+
+        // The end is debounced, and fired once.
+        cx.executor().advance_clock(RESIZE_END_DELAY);
+        cx.run_until_parked();
+        assert_eq!(*events.borrow(), ["start", "end"]);
+        assert!(!resize_state.read_with(cx, |state, _| state.resizing));
+        cx.executor().advance_clock(RESIZE_END_DELAY);
+        cx.run_until_parked();
+        assert_eq!(*events.borrow(), ["start", "end"]);
+
+        let mut state = ResizeState::default();
+        let now = Instant::now();
+        assert!(state.should_notify_resizing(now));
+        assert!(!state.should_notify_resizing(now + Duration::from_millis(20)));
+        assert!(state.should_notify_resizing(now + RESIZING_THROTTLE));
+    }
+
+    #[test]
+    fn test_edge_double_click_expand() {
+        let work_area = Bounds::new(point(px(0.), px(0.)), size(px(1000.), px(800.)));
+        let bounds = Bounds::new(point(px(100.), px(100.)), size(px(400.), px(300.)));
+        let insets = Edges::all(px(10.));
+        let mut state = ResizeState::default();
+        let bounds_of = |x: f32, y: f32, width: f32, height: f32| {
+            Some(Bounds::new(
+                point(px(x), px(y)),
+                size(px(width), px(height)),
+            ))
+        };
+
+        assert_eq!(
+            state.toggle_expand(ResizeEdge::Bottom, bounds, work_area, insets, false),
+            bounds_of(100., 100., 400., 710.)
+        );
+        // The second double click restores the previous height.
+        let expanded = Bounds::new(bounds.origin, size(px(400.), px(710.)));
+        assert_eq!(
+            state.toggle_expand(ResizeEdge::Bottom, expanded, work_area, insets, false),
+            bounds_of(100., 100., 400., 300.)
+        );
+
+        assert_eq!(
+            state.toggle_expand(ResizeEdge::BottomRight, bounds, work_area, insets, false),
+            bounds_of(100., 100., 910., 710.)
+        );
+        let mut state = ResizeState::default();
+        // The left and top edges move the window, unsupported if it can't be moved.
+        assert_eq!(
+            state.toggle_expand(ResizeEdge::Top, bounds, work_area, insets, false),
+            None
+        );
+        assert_eq!(
+            state.toggle_expand(ResizeEdge::TopLeft, bounds, work_area, insets, false),
+            None
+        );
+    }
+
+    #[test]
+    fn test_edge_double_click_expand_left_top() {
+        let work_area = Bounds::new(point(px(0.), px(0.)), size(px(1000.), px(800.)));
+        let bounds = Bounds::new(point(px(100.), px(100.)), size(px(400.), px(300.)));
+        let insets = Edges::all(px(10.));
+        let mut state = ResizeState::default();
+        let bounds_of = |x: f32, y: f32, width: f32, height: f32| {
+            Some(Bounds::new(
+                point(px(x), px(y)),
+                size(px(width), px(height)),
+            ))
+        };
+
+        // The left edge moves to the work area, the right edge stays put.
+        assert_eq!(
+            state.toggle_expand(ResizeEdge::Left, bounds, work_area, insets, true),
+            bounds_of(-10., 100., 510., 300.)
+        );
+        let expanded = Bounds::new(point(px(-10.), px(100.)), size(px(510.), px(300.)));
+        assert_eq!(
+            state.toggle_expand(ResizeEdge::Left, expanded, work_area, insets, true),
+            bounds_of(100., 100., 400., 300.)
+        );
+
+        // The top edge moves to the work area, the bottom edge stays put.
+        assert_eq!(
+            state.toggle_expand(ResizeEdge::Top, bounds, work_area, insets, true),
+            bounds_of(100., -10., 400., 410.)
+        );
+        let expanded = Bounds::new(point(px(100.), px(-10.)), size(px(400.), px(410.)));
+        assert_eq!(
+            state.toggle_expand(ResizeEdge::Top, expanded, work_area, insets, true),
+            bounds_of(100., 100., 400., 300.)
+        );
+
+        assert_eq!(
+            state.toggle_expand(ResizeEdge::TopLeft, bounds, work_area, insets, true),
+            bounds_of(-10., -10., 510., 410.)
+        );
+        assert!(
+            WindowBorder::new()
+                .edge_double_click_expand(true)
+                .edge_double_click_expand
+        );
+    }
+
+    #[test]
+    fn test_window_menu_on_border() {
+        let window_size = size(px(400.), px(300.));
+        let at = |x: f32, y: f32, tiling: &Tiling| {
+            let insets = client_content_insets(px(10.), px(1.), px(0.), tiling);
+            is_window_menu_area(point(px(x), px(y)), content_bounds(window_size, insets))
+        };
+        let tiling = Tiling::default();
+
+        // The shadow padding and the border show the menu, the content doesn't.
+        assert!(at(5., 150., &tiling));
+        assert!(at(10.5, 150., &tiling));
+        assert!(at(200., 295., &tiling));
+        assert!(!at(200., 150., &tiling));
+        assert!(!at(11.5, 150., &tiling));
+
+        // The content reaches the tiled edge.
+        let tiling = Tiling {
+            top: true,
+            ..Default::default()
+        };
+        assert!(!at(200., 2., &tiling));
+        assert!(at(5., 150., &tiling));
+    }
+
+    #[test]
+    fn test_shadow_transition() {
+        let active = WindowShadow::new(1., px(6.));
+        let inactive = WindowShadow::new(0.5, px(4.));
+        let mut transition = ShadowTransition::default();
+        let now = Instant::now();
+
+        // The first render doesn't animate.
+        assert_eq!(transition.shadow(true, active, now), (active, false));
+
+        let (shadow, animating) = transition.shadow(false, inactive, now);
+        assert_eq!(shadow, active);
+        assert!(animating);
+        let (shadow, animating) =
+            transition.shadow(false, inactive, now + SHADOW_TRANSITION_DURATION / 2);
+        assert!(shadow.opacity < active.opacity && shadow.opacity > inactive.opacity);
+        assert!(animating);
+        assert_eq!(
+            transition.shadow(false, inactive, now + SHADOW_TRANSITION_DURATION),
+            (inactive, false)
+        );
+
+        let border = WindowBorder::new()
+            .active_shadow(active)
+            .inactive_shadow(inactive);
+        assert_eq!(border.active_shadow, Some(active));
+        assert_eq!(border.inactive_shadow, Some(inactive));
+    }
+
+    #[test]
+    fn test_window_border_colors() {
+        let colors = crate::ThemeColor {
+            window_border: gpui::red(),
+            window_border_active: gpui::blue(),
+            ..Default::default()
+        };
+
+        // The focused window uses the active color.
+        let border = WindowBorder::new();
+        assert_eq!(border.resolved_border_color(true, &colors), gpui::blue());
+        assert_eq!(border.resolved_border_color(false, &colors), gpui::red());
+
+        // The color of the border wins in both states.
+        let border = WindowBorder::new().border_color(gpui::green());
+        assert_eq!(border.resolved_border_color(true, &colors), gpui::green());
+        assert_eq!(border.resolved_border_color(false, &colors), gpui::green());
+    }
+
+    #[test]
+    fn test_client_content_insets() {
+        let tiling = Tiling {
+            top: true,
+            ..Default::default()
+        };
+        let insets = client_content_insets(px(10.), px(1.), px(0.), &tiling);
+        assert_eq!(insets.top, px(0.));
+        assert_eq!(insets.bottom, px(11.));
+        assert_eq!(insets.left, px(11.));
+        assert_eq!(insets.right, px(11.));
+
+        let insets = client_content_insets(px(10.), px(1.), px(10.), &Tiling::default());
+        assert!(insets.left > px(13.) && insets.left < px(14.));
+        assert_eq!(insets.left, insets.top);
+
+        let tiling = Tiling {
+            top: true,
+            bottom: true,
+            left: true,
+            right: true,
+        };
+        let insets = client_content_insets(px(10.), px(1.), px(10.), &tiling);
+        assert_eq!(insets, Edges::all(px(0.)));
+    }
+
+    #[test]
+    fn test_scale_with_dpi() {
+        assert_eq!(device_pixel_size(px(1.), 1.), px(1.));
+        assert_eq!(device_pixel_size(px(1.), 2.), px(1.));
+        assert_eq!(device_pixel_size(px(1.), 1.5), px(2. / 1.5));
+        assert_eq!(device_pixel_size(px(1.), 0.5), px(2.));
+
+        // The shadow stays in logical pixels, only rounded to whole device pixels.
+        assert_eq!(shadow_inset(px(12.), 1., true), px(12.));
+        assert_eq!(shadow_inset(px(12.), 2., true), px(12.));
+        assert_eq!(shadow_inset(px(12.), 2., false), px(12.));
+        assert_eq!(shadow_inset(px(5.), 1.5, true), px(8. / 1.5));
+        assert_eq!(shadow_inset(px(5.), 1.5, false), px(5.));
+
+        let handle = ResizeHandle::new(Some(px(8.)), px(4.), px(12.))
+            .corner_size(px(24.))
+            .round_to_device_pixels(2.);
+        assert_eq!(handle.outside, px(8.));
+        assert_eq!(handle.inside, px(4.));
+        assert_eq!(handle.corner, px(24.));
+
+        assert!(WindowBorder::new().scale_with_dpi);
+        assert!(!WindowBorder::new().scale_with_dpi(false).scale_with_dpi);
+    }
+
+    #[test]
+    fn test_corner_backdrop() {
+        let background = gpui::white();
+        assert_eq!(
+            CornerBackdrop::Transparent.color(false, background),
+            gpui::transparent_black()
+        );
+        assert_eq!(
+            CornerBackdrop::Solid(gpui::red()).color(true, background),
+            gpui::red()
+        );
+        assert_eq!(
+            CornerBackdrop::Auto.color(true, background),
+            gpui::transparent_black()
+        );
+        assert_eq!(CornerBackdrop::Auto.color(false, background), background);
+    }
+
+    #[test]
+    fn test_window_border_style() {
+        let border = WindowBorder::new().bg(gpui::red()).h(px(80.));
+        for decorations in [
+            Decorations::Server,
+            Decorations::Client {
+                tiling: Tiling::default(),
+            },
+        ] {
+            let mut style = border.style.clone();
+            style.refine(&content_size_style(decorations));
+
+            // The background of the user takes effect, the size of the content is protected.
+            assert_eq!(style.background, Some(gpui::red().into()));
+            assert_ne!(style.size.height, Some(px(80.).into()));
+        }
+    }
+
+    #[test]
+    fn test_clip_content() {
+        let hidden = Some(gpui::Overflow::Hidden);
+        let style =
+            content_clip_style(WindowBorder::new().clip_content, px(0.), &Tiling::default());
+        assert_eq!((style.overflow.x, style.overflow.y), (hidden, hidden));
+
+        // Opted out, the content overflows the frame.
+        let style = content_clip_style(false, px(0.), &Tiling::default());
+        assert_eq!((style.overflow.x, style.overflow.y), (None, None));
+
+        // The rounded corners keep the clip, except on the tiled sides.
+        let tiling = Tiling {
+            top: true,
+            ..Default::default()
+        };
+        let style = content_clip_style(false, px(10.), &tiling);
+        assert_eq!((style.overflow.x, style.overflow.y), (hidden, hidden));
+        assert_eq!(style.corner_radii.top_left, None);
+        assert_eq!(style.corner_radii.top_right, None);
+        assert_eq!(style.corner_radii.bottom_left, Some(px(10.).into()));
+        assert_eq!(style.corner_radii.bottom_right, Some(px(10.).into()));
+    }
+
+    #[test]
+    fn test_keyboard_resize_size() {
+        let resize = |keystroke: &str, constraints: &WindowSizeConstraints, edges: &Edges<bool>| {
+            keyboard_resize_size(
+                &Keystroke::parse(keystroke).unwrap(),
+                size(px(400.), px(300.)),
+                constraints,
+                edges,
+            )
+        };
+        let constraints = WindowSizeConstraints::default();
+        let edges = Edges::all(true);
+
+        assert_eq!(
+            resize("right", &constraints, &edges),
+            Some(size(px(416.), px(300.)))
+        );
+        assert_eq!(
+            resize("shift-left", &constraints, &edges),
+            Some(size(px(399.), px(300.)))
+        );
+        assert_eq!(
+            resize("ctrl-down", &constraints, &edges),
+            Some(size(px(400.), px(364.)))
+        );
+        assert_eq!(
+            resize("up", &constraints, &edges),
+            Some(size(px(400.), px(284.)))
+        );
+        assert_eq!(resize("a", &constraints, &edges), None);
+
+        // The steps are clamped to the constraints, and skip the locked sides.
+        let constraints = WindowSizeConstraints {
+            min_size: None,
+            max_size: Some(size(px(410.), px(300.))),
+        };
+        assert_eq!(
+            resize("right", &constraints, &edges),
+            Some(size(px(410.), px(300.)))
+        );
+        let edges = Edges {
+            bottom: false,
+            ..Edges::all(true)
+        };
+        assert_eq!(
+            resize("down", &constraints, &edges),
+            Some(size(px(400.), px(300.)))
+        );
+    }
 }
@@ -0,0 +1,176 @@
+use std::collections::HashMap;
+
+use gpui::{
+    App, Bounds, Context, Decorations, Global, MouseButton, Pixels, Point, WeakEntity, Window,
+    WindowId, point, px, size,
+};
+
+use super::{window_decorations, window_paddings};
+
+/// The distance to the top edge of the work area where the cursor enters the snap zone.
+const SNAP_EDGE_SIZE: Pixels = px(8.0);
+
+/// Returns true if the cursor `position` is in the snap zone at the top edge of the
+/// `work_area`, both in screen coordinates.
+///
+/// Only the top edge snaps, maximizing the window: GPUI can't move the window, so a half or
+/// quarter snap would only resize the window where it is.
+fn in_snap_zone(position: Point<Pixels>, work_area: Bounds<Pixels>) -> bool {
+    work_area.contains(&position) && position.y - work_area.top() < SNAP_EDGE_SIZE
+}
+
+#[derive(Default)]
+pub(super) struct GlobalSnapStates(pub(super) HashMap<WindowId, WeakEntity<SnapState>>);
+
+impl Global for GlobalSnapStates {}
+
+/// The snap preview of a window move started from the TitleBar drag region.
+#[derive(Default)]
+pub(super) struct SnapState {
+    pub(super) enabled: bool,
+    /// The cursor position in the window and the window origin at the start of the move, None
+    /// if not moving.
+    grab: Option<(Point<Pixels>, Point<Pixels>)>,
+    /// True while the cursor is in the snap zone and the window can be maximized.
+    in_zone: bool,
+}
+
+impl SnapState {
+    fn begin(&mut self, position: Point<Pixels>, window: &Window, cx: &mut Context<Self>) {
+        // The compositor snaps the window itself, e.g. it reports the tiled states.
+        if !self.enabled || compositor_snaps(window) {
+            return;
+        }
+
+        self.grab = Some((position, window.bounds().origin));
+        self.in_zone = false;
+        cx.notify();
+    }
+
+    /// Returns true if a window move is tracked.
+    pub(super) fn is_moving(&self) -> bool {
+        self.grab.is_some()
+    }
+
+    /// Called on every render, the window is refreshed when its bounds change.
+    pub(super) fn observe_bounds(&mut self, window: &Window, cx: &mut Context<Self>) {
+        let Some((grab_position, start_origin)) = self.grab else {
+            return;
+        };
+        if compositor_snaps(window) {
+            self.cancel(cx);
+            return;
+        }
+
+        // The origin is not reported during the move on some platforms, e.g. Wayland.
+        let origin = window.bounds().origin;
+        if origin == start_origin {
+            return;
+        }
+
+        let in_zone = window
+            .display(cx)
+            .is_some_and(|display| in_snap_zone(origin + grab_position, display.visible_bounds()))
+            && crate::WindowSizeConstraints::of(window, cx).can_maximize();
+        if self.in_zone != in_zone {
+            self.in_zone = in_zone;
+            cx.notify();
+        }
+    }
+
+    /// Called on the first mouse event after the move, maximizes the window if released in the
+    /// snap zone.
+    pub(super) fn release(
+        &mut self,
+        pressed_button: Option<MouseButton>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        if !self.is_moving() || pressed_button == Some(MouseButton::Left) {
+            return;
+        }
+
+        let in_zone = self.in_zone;
+        self.cancel(cx);
+        if !in_zone || compositor_snaps(window) || window.is_maximized() {
+            return;
+        }
+
+        if crate::WindowSizeConstraints::of(window, cx).can_maximize() {
+            window.zoom_window();
+        }
+    }
+
+    fn cancel(&mut self, cx: &mut Context<Self>) {
+        self.grab = None;
+        self.in_zone = false;
+        cx.notify();
+    }
+
+    /// Returns the bounds of the preview in the window, the whole window frame, which fills the
+    /// work area once maximized.
+    pub(super) fn preview_bounds(&self, window: &Window) -> Option<Bounds<Pixels>> {
+        if !self.in_zone {
+            return None;
+        }
+
+        let insets = window_paddings(window);
+        let viewport_size = window.viewport_size();
+        Some(Bounds::new(
+            point(insets.left, insets.top),
+            size(
+                viewport_size.width - insets.left - insets.right,
+                viewport_size.height - insets.top - insets.bottom,
+            ),
+        ))
+    }
+}
+
+/// Returns true if the compositor snaps the window natively, the snap preview is not used.
+fn compositor_snaps(window: &Window) -> bool {
+    match window_decorations(window) {
+        Decorations::Server => true,
+        Decorations::Client { tiling } => tiling.is_tiled() || window.is_maximized(),
+    }
+}
+
+/// Start tracking the window move from the TitleBar drag region at the mouse `position`, for the
+/// snap preview of the [`super::WindowBorder`] rendered in the window.
+#[cfg_attr(target_os = "macos", allow(dead_code))]
+pub(crate) fn begin_snap_move(position: Point<Pixels>, window: &mut Window, cx: &mut App) {
+    let window_id = window.window_handle().window_id();
+    let Some(state) = cx
+        .try_global::<GlobalSnapStates>()
+        .and_then(|states| states.0.get(&window_id))
+        .and_then(|state| state.upgrade())
+    else {
+        return;
+    };
+
+    state.update(cx, |state, cx| state.begin(position, window, cx));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bounds(x: f32, y: f32, width: f32, height: f32) -> Bounds<Pixels> {
+        Bounds::new(point(px(x), px(y)), size(px(width), px(height)))
+    }
+
+    #[test]
+    fn test_in_snap_zone() {
+        let work_area = bounds(0., 32., 1920., 1048.);
+        let in_zone = |x: f32, y: f32| in_snap_zone(point(px(x), px(y)), work_area);
+
+        assert!(in_zone(960., 34.));
+        assert!(in_zone(2., 34.));
+        assert!(in_zone(1918., 39.));
+        // The side and bottom edges don't snap, nor the area outside the work area.
+        assert!(!in_zone(2., 500.));
+        assert!(!in_zone(1918., 500.));
+        assert!(!in_zone(960., 1078.));
+        assert!(!in_zone(960., 500.));
+        assert!(!in_zone(960., 10.));
+    }
+}
@@ -0,0 +1,556 @@
+use std::rc::Rc;
+
+use crate::{
+    ActiveTheme, ColorRole, ColorSet, Colorize as _, FocusGroup, FocusGroupElement as _,
+    FocusRingStyle, FocusableExt as _, Icon, IconName, Side, Sizable, Size, WindowSizeConstraints,
+    h_flex,
+};
+use gpui::{
+    App, Axis, ClickEvent, ElementId, FocusHandle, InteractiveElement, IntoElement,
+    MAX_BUTTONS_PER_SIDE, MouseButton, ParentElement, Pixels, RenderOnce, SharedString,
+    StatefulInteractiveElement as _, Styled, Window, WindowButton, WindowButtonLayout,
+    WindowControlArea, div, prelude::FluentBuilder as _, px,
+};
+
+use crate::{
+    title_bar::TITLE_BAR_HEIGHT,
+    tooltip::{ManagedTooltipExt as _, Tooltip, TooltipOptions},
+};
+use rust_i18n::t;
+
+/// The behavior when the close button of the [`WindowControls`] is clicked.
+///
+/// Only used on Linux and Windows, on macOS the native traffic lights handle window close.
+#[derive(Clone, Default)]
+pub enum CloseBehavior {
+    /// Remove the window by `window.remove_window()`, this is the default.
+    #[default]
+    RemoveWindow,
+    /// Minimize the window instead of destroying it, for apps that close to the tray.
+    ///
+    /// The platform close request (e.g. Alt-F4) is intercepted as well when used with
+    /// [`crate::TitleBar`], call `window.activate_window()` to show the window again.
+    Hide,
+    /// Call a custom handler.
+    Callback(Rc<dyn Fn(&ClickEvent, &mut Window, &mut App)>),
+}
+
+impl CloseBehavior {
+    /// Create a [`CloseBehavior::Callback`] with the given handler.
+    pub fn callback(f: impl Fn(&ClickEvent, &mut Window, &mut App) + 'static) -> Self {
+        Self::Callback(Rc::new(f))
+    }
+
+    /// Returns true if the behavior is [`CloseBehavior::Hide`].
+    #[inline]
+    pub fn is_hide(&self) -> bool {
+        matches!(self, Self::Hide)
+    }
+
+    fn close(&self, event: &ClickEvent, window: &mut Window, cx: &mut App) {
+        match self {
+            Self::RemoveWindow => window.remove_window(),
+            Self::Hide => window.minimize_window(),
+            Self::Callback(f) => f(event, window, cx),
+        }
+    }
+}
+
+#[derive(Clone)]
+enum ControlIcon {
+    Minimize,
+    Restore,
+    Maximize,
+    Close { close_behavior: CloseBehavior },
+}
+
+impl ControlIcon {
+    fn id(&self) -> &'static str {
+        match self {
+            Self::Minimize => "minimize",
+            Self::Restore => "restore",
+            Self::Maximize => "maximize",
+            Self::Close { .. } => "close",
+        }
+    }
+
+    fn icon(&self) -> IconName {
+        if cfg!(target_os = "linux") {
+            match self {
+                Self::Minimize => IconName::GenericMinimize,
+                Self::Restore => IconName::GenericRestore,
+                Self::Maximize => IconName::GenericMaximize,
+                Self::Close { .. } => IconName::GenericClose,
+            }
+        } else {
+            match self {
+                Self::Minimize => IconName::WindowMinimize,
+                Self::Restore => IconName::WindowRestore,
+                Self::Maximize => IconName::WindowMaximize,
+                Self::Close { .. } => IconName::WindowClose,
+            }
+        }
+    }
+
+    fn window_control_area(&self) -> WindowControlArea {
+        match self {
+            Self::Minimize => WindowControlArea::Min,
+            Self::Restore | Self::Maximize => WindowControlArea::Max,
+            Self::Close { .. } => WindowControlArea::Close,
+        }
+    }
+
+    fn is_close(&self) -> bool {
+        matches!(self, Self::Close { .. })
+    }
+
+    /// Minimize, maximize/restore or close the window.
+    fn perform(&self, event: &ClickEvent, window: &mut Window, cx: &mut App) {
+        match self {
+            Self::Minimize => window.minimize_window(),
+            Self::Restore | Self::Maximize => {
+                #[cfg(target_os = "windows")]
+                crate::title_bar::toggle_maximize_win32(window);
+                #[cfg(not(target_os = "windows"))]
+                window.zoom_window();
+            }
+            Self::Close { close_behavior } => close_behavior.close(event, window, cx),
+        }
+    }
+
+    fn tooltip(&self) -> SharedString {
+        match self {
+            Self::Minimize => t!("WindowControls.minimize"),
+            Self::Restore => t!("WindowControls.restore"),
+            Self::Maximize => t!("WindowControls.maximize"),
+            Self::Close { .. } => t!("WindowControls.close"),
+        }
+        .to_string()
+        .into()
+    }
+
+    /// The colors of the button, the close button uses the danger base color on hover as the
+    /// Windows caption buttons.
+    ///
+    /// In high contrast, the hover and active states of all buttons use the saturated colors.
+    #[inline]
+    fn colors(&self, cx: &App) -> ColorSet {
+        let contrast = cx.theme().contrast();
+        if self.is_close() {
+            let danger = contrast.colors(ColorRole::Danger);
+            ColorSet {
+                hover: danger.base,
+                ..danger
+            }
+        } else {
+            contrast.colors(ColorRole::Secondary)
+        }
+    }
+}
+
+// The Windows control buttons span the full height of the host element.
+//
+// We don't need implementation the click event for the control buttons on Windows.
+// If user clicked in the bounds, the window event will be triggered.
+#[derive(IntoElement)]
+struct ControlButton {
+    icon: ControlIcon,
+    size: Size,
+    tooltip: Option<TooltipOptions>,
+    /// The focus handle and the tab stop in the [`FocusGroup`] of the buttons.
+    focus: Option<(FocusHandle, bool)>,
+}
+
+impl ControlButton {
+    /// The width of the Windows button.
+    fn width(&self) -> Pixels {
+        match self.size {
+            Size::Size(size) => size,
+            Size::XSmall | Size::Small => px(28.),
+            Size::Medium => TITLE_BAR_HEIGHT,
+            Size::Large => px(46.),
+        }
+    }
+}
+
+impl RenderOnce for ControlButton {
+    fn render(self, window: &mut Window, cx: &mut App) -> impl IntoElement {
+        let is_linux = cfg!(target_os = "linux");
+        let is_macos = cfg!(target_os = "macos");
+        let is_windows = cfg!(target_os = "windows");
+        let colors = self.icon.colors(cx);
+        let contrast = cx.theme().contrast();
+        let high_contrast = contrast.is_high();
+        let border_width = contrast.border_width();
+        // Without the high contrast, there is no border to show the outline.
+        let outline = contrast.outline().unwrap_or(cx.theme().transparent);
+        let fg_muted = cx.theme().muted_foreground;
+        let width = self.width();
+        let size = self.size;
+        let icon = self.icon;
+        // The tooltip is also shown on the keyboard focus.
+        let focus_handle = self.focus.as_ref().map(|(handle, _)| handle.clone());
+
+        div()
+            .id(icon.id())
+            .flex()
+            .flex_shrink_0()
+            .justify_center()
+            .content_center()
+            .items_center()
+            // Windows: tall rectangular buttons spanning the full title bar height
+            .when(!is_linux, |this| {
+                this.w(width)
+                    .h_full()
+                    .text_color(cx.theme().foreground)
+                    .hover(|style| {
+                        style
+                            .bg(colors.hover)
+                            .text_color(colors.foreground)
+                            .border_color(outline)
+                    })
+                    .active(|style| {
+                        style
+                            .bg(colors.active)
+                            .text_color(colors.foreground)
+                            .border_color(outline)
+                    })
+            })
+            // Linux: small rounded circle buttons (20×20px) with uniform muted colors
+            .when(is_linux, |this| {
+                let (bg_muted, fg) = if high_contrast {
+                    (colors.hover, colors.foreground)
+                } else {
+                    (cx.theme().muted, cx.theme().foreground)
+                };
+                this.map(|this| match size {
+                    Size::Size(size) => this.size(size),
+                    Size::XSmall | Size::Small => this.size_4(),
+                    Size::Medium => this.size_5(),
+                    Size::Large => this.size_6(),
+                })
+                .rounded_2xl()
+                .cursor_pointer()
+                .text_color(fg_muted)
+                .hover(|style| style.bg(bg_muted).text_color(fg).border_color(outline))
+                .active(|style| style.bg(bg_muted).text_color(fg).border_color(outline))
+            })
+            // The border is only visible while hovered or pressed, as the outline.
+            .when(high_contrast, |this| {
+                this.border(border_width)
+                    .border_color(cx.theme().transparent)
+            })
+            .when(is_windows, |this| {
+                this.window_control_area(icon.window_control_area())
+            })
+            .when_some(self.focus, |this, (focus_handle, tab_stop)| {
+                let icon = icon.clone();
+                this.relative()
+                    .track_focus(&focus_handle.clone().tab_stop(tab_stop))
+                    .focus_ring_with(
+                        focus_handle.is_focused(window),
+                        FocusRingStyle::new()
+                            .color(cx.theme().ring)
+                            .thickness(px(1.))
+                            .offset(px(-1.)),
+                        window,
+                        cx,
+                    )
+                    .on_key_down(move |event, window, cx| {
+                        if matches!(event.keystroke.key.as_str(), "space" | "enter") {
+                            cx.stop_propagation();
+                            icon.perform(&ClickEvent::default(), window, cx);
+                        }
+                    })
+            })
+            .when_some(self.tooltip, |this, options| {
+                let tooltip = icon.tooltip();
+                this.managed_tooltip_with_focus(options, focus_handle, move |window, cx| {
+                    Tooltip::new(tooltip.clone()).build(window, cx)
+                })
+            })
+            .child(
+                Icon::new(icon.icon())
+                    .when(is_linux, |this| {
+                        this.map(|this| match size {
+                            Size::XSmall | Size::Small => this.size_3(),
+                            Size::Large => this.size_5(),
+                            _ => this.size_4(),
+                        })
+                        // Follow the hover color of the button in high contrast.
+                        .when(!high_contrast, |this| this.text_color(fg_muted))
+                    })
+                    .when(!is_linux, |this| match size {
+                        Size::XSmall | Size::Small => this.xsmall(),
+                        Size::Large => this.with_size(Size::Medium),
+                        _ => this.small(),
+                    }),
+            )
+            .when(!is_macos, |this| {
+                this.on_mouse_down(MouseButton::Left, move |_, window, cx| {
+                    window.prevent_default();
+                    cx.stop_propagation();
+                })
+                .on_click(move |event, window, cx| {
+                    cx.stop_propagation();
+                    icon.perform(event, window, cx);
+                })
+            })
+    }
+}
+
+/// The minimize, maximize/restore and close buttons of a window.
+///
+/// This is used by [`crate::TitleBar`], and can be embedded in any custom window chrome,
+/// e.g. at the end of a tab strip. The host element must provide its own
+/// `WindowControlArea::Drag` region (and drag handling), `WindowControls` only
+/// renders the buttons.
+///
+/// Nothing is rendered on macOS, the native traffic lights are used there.
+#[derive(IntoElement)]
+pub struct WindowControls {
+    id: ElementId,
+    buttons: [Option<WindowButton>; MAX_BUTTONS_PER_SIDE],
+    close_behavior: CloseBehavior,
+    show_minimize: bool,
+    show_maximize: bool,
+    show_close: bool,
+    transparent: bool,
+    tooltip: Option<TooltipOptions>,
+    focusable: bool,
+    size: Size,
+}
+
+impl WindowControls {
+    /// Create a new WindowControls with minimize, maximize and close buttons.
+    pub fn new(id: impl Into<ElementId>) -> Self {
+        Self {
+            id: id.into(),
+            buttons: [
+                Some(WindowButton::Minimize),
+                Some(WindowButton::Maximize),
+                Some(WindowButton::Close),
+            ],
+            close_behavior: CloseBehavior::default(),
+            show_minimize: true,
+            show_maximize: true,
+            show_close: true,
+            transparent: false,
+            tooltip: None,
+            focusable: false,
+            size: Size::default(),
+        }
+    }
+
+    /// Set the buttons to render, in order.
+    pub fn buttons(mut self, buttons: [Option<WindowButton>; MAX_BUTTONS_PER_SIDE]) -> Self {
+        self.buttons = buttons;
+        self
+    }
+
+    /// Use the buttons of the given side of a [`WindowButtonLayout`], e.g. from `cx.button_layout()`.
+    pub fn layout(mut self, layout: &WindowButtonLayout, side: Side) -> Self {
+        self.buttons = match side {
+            Side::Left => layout.left,
+            Side::Right => layout.right,
+        };
+        self
+    }
+
+    /// Set to show the minimize button, default is true.
+    pub fn show_minimize(mut self, show: bool) -> Self {
+        self.show_minimize = show;
+        self
+    }
+
+    /// Set to show the maximize/restore button, default is true.
+    pub fn show_maximize(mut self, show: bool) -> Self {
+        self.show_maximize = show;
+        self
+    }
+
+    /// Set to show the close button, default is true.
+    pub fn show_close(mut self, show: bool) -> Self {
+        self.show_close = show;
+        self
+    }
+
+    /// Add custom for close window event, default is None, then click X button will call `window.remove_window()`.
+    pub fn on_close_window(self, f: impl Fn(&ClickEvent, &mut Window, &mut App) + 'static) -> Self {
+        self.close_behavior(CloseBehavior::callback(f))
+    }
+
+    /// Set the behavior of the close button, default is [`CloseBehavior::RemoveWindow`].
+    pub fn close_behavior(mut self, behavior: CloseBehavior) -> Self {
+        self.close_behavior = behavior;
+        self
+    }
+
+    /// Set to only show a translucent backdrop while hovered, default is false.
+    pub fn transparent(mut self, transparent: bool) -> Self {
+        self.transparent = transparent;
+        self
+    }
+
+    /// Set to show the tooltips of the buttons with the [`TooltipOptions`], default is None.
+    pub fn tooltip(mut self, options: impl Into<Option<TooltipOptions>>) -> Self {
+        self.tooltip = options.into();
+        self
+    }
+
+    /// Set to focus the buttons by keyboard, default is false.
+    ///
+    /// The buttons are a [`FocusGroup`]: `tab` focuses one button, `left` and `right` move the
+    /// focus, and `space` or `enter` clicks the focused button.
+    pub fn focusable(mut self, focusable: bool) -> Self {
+        self.focusable = focusable;
+        self
+    }
+
+    /// Returns true if there is any visible button.
+    pub fn has_buttons(&self) -> bool {
+        self.visible_buttons().next().is_some()
+    }
+
+    fn visible_buttons(&self) -> impl Iterator<Item = WindowButton> + '_ {
+        self.buttons.iter().filter_map(|b| *b).filter(|b| match b {
+            WindowButton::Minimize => self.show_minimize,
+            WindowButton::Maximize => self.show_maximize,
+            WindowButton::Close => self.show_close,
+        })
+    }
+}
+
+impl Sizable for WindowControls {
+    fn with_size(mut self, size: impl Into<Size>) -> Self {
+        self.size = size.into();
+        self
+    }
+}
+
+impl RenderOnce for WindowControls {
+    fn render(self, window: &mut Window, cx: &mut App) -> impl IntoElement {
+        let is_linux = cfg!(target_os = "linux");
+        let is_macos = cfg!(target_os = "macos");
+        let is_maximized = window.is_maximized();
+        // The window can't be maximized beyond the max size of the `WindowBorder`.
+        let can_maximize = is_maximized || WindowSizeConstraints::of(window, cx).can_maximize();
+
+        let focus_group = self.focusable.then(|| {
+            FocusGroup::new(
+                ElementId::Name(format!("window-controls-focus-{:?}", self.id).into()),
+                window,
+                cx,
+            )
+            .orientation(Axis::Horizontal)
+        });
+        // The focus handles of the button slots, kept across renders.
+        let focus_handles = self.focusable.then(|| {
+            window
+                .use_keyed_state(
+                    ElementId::Name(format!("window-controls-focus-handles-{:?}", self.id).into()),
+                    cx,
+                    |_, cx| [(); MAX_BUTTONS_PER_SIDE].map(|_| cx.focus_handle()),
+                )
+                .read(cx)
+                .clone()
+        });
+
+        let buttons: Vec<ControlButton> = self
+            .visible_buttons()
+            .filter(|_| !is_macos)
+            .filter(|button| can_maximize || *button != WindowButton::Maximize)
+            .map(|button| ControlButton {
+                icon: match button {
+                    WindowButton::Minimize => ControlIcon::Minimize,
+                    WindowButton::Maximize => {
+                        if is_maximized {
+                            ControlIcon::Restore
+                        } else {
+                            ControlIcon::Maximize
+                        }
+                    }
+                    WindowButton::Close => ControlIcon::Close {
+                        close_behavior: self.close_behavior.clone(),
+                    },
+                },
+                size: self.size,
+                tooltip: self.tooltip,
+                focus: None,
+            })
+            .collect();
+        let buttons = match (&focus_group, &focus_handles) {
+            (Some(focus_group), Some(focus_handles)) => buttons
+                .into_iter()
+                .zip(focus_handles.iter())
+                .map(|(button, focus_handle)| {
+                    let tab_stop = focus_group.item(focus_handle, window, cx);
+                    ControlButton {
+                        focus: Some((focus_handle.clone(), tab_stop)),
+                        ..button
+                    }
+                })
+                .collect(),
+            _ => buttons,
+        };
+
+        h_flex()
+            .id(self.id)
+            .items_center()
+            .flex_shrink_0()
+            .h_full()
+            // Linux: spaced rounded buttons with padding
+            .when(is_linux, |this| this.gap_2().px_3())
+            .when(self.transparent, |this| {
+                let backdrop = cx.theme().title_bar.opacity(0.6);
+                this.hover(|style| style.bg(backdrop))
+            })
+            .when_some(focus_group, |this, focus_group| {
+                this.focus_group(&focus_group)
+            })
+            .children(buttons)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gpui::MAX_BUTTONS_PER_SIDE;
+
+    #[gpui::test]
+    fn test_window_controls_builder(_cx: &mut gpui::TestAppContext) {
+        let controls = WindowControls::new("controls")
+            .show_minimize(false)
+            .close_behavior(CloseBehavior::Hide)
+            .transparent(true)
+            .tooltip(TooltipOptions::new().side(crate::Placement::Bottom))
+            .focusable(true)
+            .small();
+
+        assert_eq!(controls.id, ElementId::from("controls"));
+        assert!(!controls.show_minimize);
+        assert!(controls.close_behavior.is_hide());
+        assert!(controls.transparent);
+        assert!(controls.focusable);
+        assert_eq!(
+            controls.tooltip.and_then(|options| options.side),
+            Some(crate::Placement::Bottom)
+        );
+        assert_eq!(controls.size, Size::Small);
+        assert_eq!(
+            controls.visible_buttons().collect::<Vec<_>>(),
+            vec![WindowButton::Maximize, WindowButton::Close]
+        );
+
+        let mut layout = WindowButtonLayout {
+            left: [None; MAX_BUTTONS_PER_SIDE],
+            right: [None; MAX_BUTTONS_PER_SIDE],
+        };
+        layout.left[0] = Some(WindowButton::Close);
+        let left = WindowControls::new("left").layout(&layout, Side::Left);
+        assert!(left.has_buttons());
+        let right = WindowControls::new("right").layout(&layout, Side::Right);
+        assert!(!right.has_buttons());
+        assert!(!left.show_close(false).has_buttons());
+    }
+}
@@ -5,8 +5,17 @@ use crate::{
     notification::Notification,
     sheet::Sheet,
 };
-use gpui::{App, ElementId, Entity, Window};
-use std::rc::Rc;
+use gpui::{
+    App, Context, ElementId, Entity, Global, Subscription, WeakFocusHandle, Window, WindowId,
+};
+use std::{collections::HashMap, rc::Rc};
+
+/// The windows deactivated by [`WindowExt::simulate_window_active`], with the focus to restore
+/// on the reactivation.
+#[derive(Default)]
+struct SimulatedInactive(HashMap<WindowId, Option<WeakFocusHandle>>);
+
+impl Global for SimulatedInactive {}
 
 /// Extension trait for [`Window`] to add dialog, sheet .. functionality.
 pub trait WindowExt: Sized {
@@ -98,6 +107,36 @@ pub trait WindowExt: Sized {
 
     /// Ends the in-progress window-level text selection drag (if any).
     fn end_text_selection(&mut self, cx: &mut App);
+
+    /// Return true, if the window is active, like [`Window::is_window_active`] but false while
+    /// the deactivation is simulated, see [`WindowExt::simulate_window_active`].
+    fn window_active(&self, cx: &App) -> bool;
+
+    /// Simulate the window losing or regaining the activation without changing the OS focus,
+    /// e.g. to test the inactive TitleBar.
+    ///
+    /// The deactivation blurs the focused element, which is focused again on the reactivation,
+    /// and notifies the components observing the activation.
+    fn simulate_window_active(&mut self, active: bool, cx: &mut App);
+}
+
+/// Like [`Context::observe_window_activation`], but also called when the activation is
+/// simulated, see [`WindowExt::simulate_window_active`].
+pub(crate) fn observe_window_active<T: 'static>(
+    window: &mut Window,
+    cx: &mut Context<T>,
+    callback: impl Fn(&mut T, &mut Window, &mut Context<T>) + 'static,
+) -> Subscription {
+    let callback = Rc::new(callback);
+    Subscription::join(
+        cx.observe_window_activation(window, {
+            let callback = callback.clone();
+            move |this, window, cx| callback(this, window, cx)
+        }),
+        cx.observe_global_in::<SimulatedInactive>(window, move |this, window, cx| {
+            callback(this, window, cx)
+        }),
+    )
 }
 
 impl WindowExt for Window {
@@ -250,4 +289,36 @@ impl WindowExt for Window {
         };
         root.update(cx, |root, cx| root.end_text_selection(cx));
     }
+
+    fn window_active(&self, cx: &App) -> bool {
+        self.is_window_active()
+            && !cx
+                .try_global::<SimulatedInactive>()
+                .is_some_and(|simulated| {
+                    simulated.0.contains_key(&self.window_handle().window_id())
+                })
+    }
+
+    fn simulate_window_active(&mut self, active: bool, cx: &mut App) {
+        let window_id = self.window_handle().window_id();
+        let simulated = &mut cx.default_global::<SimulatedInactive>().0;
+        if active {
+            let Some(focus) = simulated.remove(&window_id) else {
+                return;
+            };
+            if let Some(focus) = focus.and_then(|focus| focus.upgrade()) {
+                focus.focus(self, cx);
+            }
+        } else {
+            if simulated.contains_key(&window_id) {
+                return;
+            }
+            let focus = self.focused(cx).map(|focus| focus.downgrade());
+            cx.default_global::<SimulatedInactive>()
+                .0
+                .insert(window_id, focus);
+            self.blur();
+        }
+        self.refresh();
+    }
 }